@@ -12,7 +12,8 @@ use acir::{
 };
 use acvm_blackbox_solver::BlackBoxFunctionSolver;
 use brillig_vm::{
-    BranchToFeatureMap, BrilligProfilingSamples, FailureReason, MemoryValue, VM, VMStatus,
+    BranchToFeatureMap, BrilligProfilingSamples, ExecutionLimits, FailureReason, MemoryValue, VM,
+    VMStatus,
 };
 use serde::{Deserialize, Serialize};
 
@@ -41,6 +42,12 @@ pub struct BrilligSolver<'b, F, B: BlackBoxFunctionSolver<F>> {
     pub function_id: BrilligFunctionId,
 }
 
+impl<F: Clone, B: BlackBoxFunctionSolver<F>> Clone for BrilligSolver<'_, F, B> {
+    fn clone(&self) -> Self {
+        Self { vm: self.vm.clone(), acir_index: self.acir_index, function_id: self.function_id }
+    }
+}
+
 impl<'b, B: BlackBoxFunctionSolver<F>, F: AcirField> BrilligSolver<'b, F, B> {
     /// Assigns the zero value to all outputs of a given [brillig call][acir::circuit::opcodes::Opcode::BrilligCall].
     pub(super) fn zero_out_brillig_outputs(
@@ -88,6 +95,11 @@ impl<'b, B: BlackBoxFunctionSolver<F>, F: AcirField> BrilligSolver<'b, F, B> {
         Ok(Self { vm, acir_index, function_id: brillig_function_id })
     }
 
+    /// Makes the solver fail once the given limits on executed Brillig opcodes are exceeded.
+    pub(crate) fn with_execution_limits(&mut self, execution_limits: &'b ExecutionLimits) {
+        self.vm.with_execution_limits(execution_limits);
+    }
+
     /// Get a BrilligVM for executing the provided bytecode
     /// 1. Reduce the input expressions into a known value, or error if they do not reduce to a value.
     /// 2. Instantiate the Brillig VM with the bytecode and the reduced inputs.
@@ -14,7 +14,7 @@ use super::{
 type MemoryIndex = u32;
 
 /// Maintains the state for solving [`MemoryInit`][`acir::circuit::Opcode::MemoryInit`] and [`MemoryOp`][`acir::circuit::Opcode::MemoryOp`] opcodes.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub(crate) struct MemoryOpSolver<F> {
     /// Known values of the memory block, based on the index
     /// This map evolves as we process the opcodes
@@ -15,7 +15,7 @@ use acir::{
     native_types::{Expression, Witness, WitnessMap},
 };
 use acvm_blackbox_solver::BlackBoxResolutionError;
-use brillig_vm::BranchToFeatureMap;
+use brillig_vm::{BranchToFeatureMap, ExecutionLimits};
 
 use self::{
     arithmetic::ExpressionSolver, blackbox::bigint::AcvmBigIntSolver, memory_op::MemoryOpSolver,
@@ -199,7 +199,7 @@ impl<F> From<InvalidInputBitSize> for OpcodeResolutionError<F> {
 
 pub type ProfilingSamples = Vec<ProfilingSample>;
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct ProfilingSample {
     pub call_stack: Vec<OpcodeLocation>,
     pub brillig_function_id: Option<BrilligFunctionId>,
@@ -242,6 +242,9 @@ pub struct ACVM<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> {
 
     profiling_samples: ProfilingSamples,
 
+    /// Indices of the opcodes solved under a true predicate, when coverage is tracked.
+    solved_opcodes: Option<Vec<usize>>,
+
     // Whether we need to trace brillig execution for fuzzing
     brillig_fuzzing_active: bool,
 
@@ -249,6 +252,37 @@ pub struct ACVM<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> {
     brillig_branch_to_feature_map: Option<&'a BranchToFeatureMap>,
 
     brillig_fuzzing_trace: Option<Vec<u32>>,
+
+    /// Limits on the Brillig opcodes executed by the unconstrained calls of the ACVM.
+    execution_limits: Option<&'a ExecutionLimits>,
+}
+
+// Implemented by hand so that cloning an ACVM, for example to take a snapshot of its state,
+// does not require its black box solver to be `Clone`.
+impl<F: AcirField, B: BlackBoxFunctionSolver<F>> Clone for ACVM<'_, F, B> {
+    fn clone(&self) -> Self {
+        Self {
+            status: self.status.clone(),
+            backend: self.backend,
+            block_solvers: self.block_solvers.clone(),
+            bigint_solver: self.bigint_solver.clone(),
+            opcodes: self.opcodes,
+            instruction_pointer: self.instruction_pointer,
+            witness_map: self.witness_map.clone(),
+            brillig_solver: self.brillig_solver.clone(),
+            acir_call_counter: self.acir_call_counter,
+            acir_call_results: self.acir_call_results.clone(),
+            unconstrained_functions: self.unconstrained_functions,
+            assertion_payloads: self.assertion_payloads,
+            profiling_active: self.profiling_active,
+            profiling_samples: self.profiling_samples.clone(),
+            solved_opcodes: self.solved_opcodes.clone(),
+            brillig_fuzzing_active: self.brillig_fuzzing_active,
+            brillig_branch_to_feature_map: self.brillig_branch_to_feature_map,
+            brillig_fuzzing_trace: self.brillig_fuzzing_trace.clone(),
+            execution_limits: self.execution_limits,
+        }
+    }
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
@@ -276,9 +310,11 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
             assertion_payloads,
             profiling_active: false,
             profiling_samples: Vec::new(),
+            solved_opcodes: None,
             brillig_fuzzing_active: false,
             brillig_branch_to_feature_map: None,
             brillig_fuzzing_trace: None,
+            execution_limits: None,
         }
     }
 
@@ -287,6 +323,16 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
         self.profiling_active = profiling_active;
     }
 
+    // Enable tracking of the opcodes which are solved, for code coverage
+    pub fn with_coverage(&mut self, coverage_active: bool) {
+        self.solved_opcodes = coverage_active.then(Vec::new);
+    }
+
+    // Make unconstrained calls fail once the given limits are exceeded
+    pub fn with_execution_limits(&mut self, execution_limits: &'a ExecutionLimits) {
+        self.execution_limits = Some(execution_limits);
+    }
+
     // Enable brillig fuzzing
     pub fn with_brillig_fuzzing(
         &mut self,
@@ -325,6 +371,13 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
         std::mem::take(&mut self.profiling_samples)
     }
 
+    /// Returns the indices of the opcodes solved so far under a true predicate, if coverage is tracked.
+    ///
+    /// Opcodes with a false predicate are skipped by the solver, so they aren't considered executed.
+    pub fn take_solved_opcodes(&mut self) -> Vec<usize> {
+        self.solved_opcodes.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
     /// Finalize the ACVM execution, returning the resulting [`WitnessMap`].
     pub fn finalize(self) -> WitnessMap<F> {
         if self.status != ACVMStatus::Solved {
@@ -454,9 +507,37 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
                 res => res.map(|_| ()),
             },
         };
+        if resolution.is_ok() {
+            self.record_solved_opcode();
+        }
         self.handle_opcode_resolution(resolution)
     }
 
+    /// Records the current opcode as solved if coverage is tracked and its predicate, if any, is true.
+    fn record_solved_opcode(&mut self) {
+        let Some(solved_opcodes) = &mut self.solved_opcodes else {
+            return;
+        };
+        let predicate = match &self.opcodes[self.instruction_pointer] {
+            Opcode::MemoryOp { predicate, .. }
+            | Opcode::BrilligCall { predicate, .. }
+            | Opcode::Call { predicate, .. } => predicate,
+            Opcode::AssertZero(_) | Opcode::BlackBoxFuncCall(_) | Opcode::MemoryInit { .. } => {
+                &None
+            }
+        };
+        let predicate_is_false = is_predicate_false(
+            &self.witness_map,
+            predicate,
+            self.backend.pedantic_solving(),
+            &ErrorLocation::Unresolved,
+        )
+        .unwrap_or(true);
+        if !predicate_is_false {
+            solved_opcodes.push(self.instruction_pointer);
+        }
+    }
+
     /// Returns the status of the ACVM
     /// If the status is an error, it converts the error into [OpcodeResolutionError]
     fn handle_opcode_resolution(
@@ -568,17 +649,23 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
         // there will be a cached `BrilligSolver` to avoid recomputation.
         let mut solver: BrilligSolver<'_, F, B> = match self.brillig_solver.take() {
             Some(solver) => solver,
-            None => BrilligSolver::new_call(
-                &self.witness_map,
-                &self.block_solvers,
-                inputs,
-                &self.unconstrained_functions[id.as_usize()].bytecode,
-                self.backend,
-                self.instruction_pointer,
-                *id,
-                self.profiling_active,
-                self.brillig_branch_to_feature_map,
-            )?,
+            None => {
+                let mut solver = BrilligSolver::new_call(
+                    &self.witness_map,
+                    &self.block_solvers,
+                    inputs,
+                    &self.unconstrained_functions[id.as_usize()].bytecode,
+                    self.backend,
+                    self.instruction_pointer,
+                    *id,
+                    self.profiling_active,
+                    self.brillig_branch_to_feature_map,
+                )?;
+                if let Some(execution_limits) = self.execution_limits {
+                    solver.with_execution_limits(execution_limits);
+                }
+                solver
+            }
         };
 
         // If we're fuzzing, we need to get the fuzzing trace on an error
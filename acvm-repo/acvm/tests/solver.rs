@@ -17,6 +17,7 @@ use acir::{
 use acvm::pwg::{ACVM, ACVMStatus, ErrorLocation, ForeignCallWaitInfo, OpcodeResolutionError};
 use acvm_blackbox_solver::{BigIntSolver, StubbedBlackBoxSolver};
 use bn254_blackbox_solver::{Bn254BlackBoxSolver, POSEIDON2_CONFIG, field_from_hex};
+use brillig_vm::ExecutionLimits;
 use brillig_vm::brillig::HeapValueType;
 
 use num_bigint::BigUint;
@@ -608,6 +609,74 @@ fn brillig_oracle_predicate() {
     acvm.finalize();
 }
 
+#[test]
+fn coverage_skips_opcodes_with_false_predicate() {
+    let solver = StubbedBlackBoxSolver::default();
+    let w_x = Witness(1);
+
+    let opcodes = vec![
+        Opcode::AssertZero(Expression {
+            mul_terms: vec![],
+            linear_combinations: vec![(FieldElement::one(), w_x)],
+            q_c: -FieldElement::from(2u128),
+        }),
+        Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![],
+            outputs: vec![],
+            predicate: Some(Expression::default()),
+        },
+        Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![],
+            outputs: vec![],
+            predicate: Some(Expression::one()),
+        },
+    ];
+    let zero_usize = MemoryAddress::direct(0);
+    let unconstrained_functions = vec![BrilligBytecode {
+        bytecode: vec![
+            BrilligOpcode::Const {
+                destination: zero_usize,
+                bit_size: BitSize::Integer(IntegerBitSize::U32),
+                value: FieldElement::from(0u64),
+            },
+            BrilligOpcode::Stop {
+                return_data: HeapVector { pointer: zero_usize, size: zero_usize },
+            },
+        ],
+    }];
+
+    let witness_assignments = BTreeMap::from([(w_x, FieldElement::from(2u128))]).into();
+    let mut acvm = ACVM::new(&solver, &opcodes, witness_assignments, &unconstrained_functions, &[]);
+    acvm.with_coverage(true);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved, "should be fully solved");
+    assert_eq!(acvm.take_solved_opcodes(), vec![0, 2]);
+}
+
+#[test]
+fn brillig_call_fails_when_exceeding_execution_limits() {
+    let solver = StubbedBlackBoxSolver::default();
+    let opcodes: Vec<Opcode<FieldElement>> = vec![Opcode::BrilligCall {
+        id: BrilligFunctionId(0),
+        inputs: vec![],
+        outputs: vec![],
+        predicate: None,
+    }];
+    // An infinite loop
+    let unconstrained_functions =
+        vec![BrilligBytecode { bytecode: vec![BrilligOpcode::Jump { location: 0 }] }];
+
+    let execution_limits = ExecutionLimits::new(Some(100));
+    let mut acvm = ACVM::new(&solver, &opcodes, WitnessMap::new(), &unconstrained_functions, &[]);
+    acvm.with_execution_limits(&execution_limits);
+    assert!(matches!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::BrilligFunctionFailed { .. })
+    ));
+    assert!(execution_limits.max_opcodes_exceeded());
+}
+
 #[test]
 fn unsatisfied_opcode_resolved() {
     let solver = StubbedBlackBoxSolver::default();
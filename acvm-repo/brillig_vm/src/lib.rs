@@ -21,6 +21,7 @@ use black_box::{BrilligBigIntSolver, evaluate_black_box};
 
 // Re-export `brillig`.
 pub use acir::brillig;
+pub use limits::ExecutionLimits;
 use memory::MemoryTypeError;
 pub use memory::{MEMORY_ADDRESSING_BIT_SIZE, Memory, MemoryValue};
 
@@ -28,6 +29,7 @@ use num_bigint::BigUint;
 mod arithmetic;
 mod black_box;
 mod cast;
+mod limits;
 mod memory;
 
 /// The error call stack contains the opcode indexes of the call stack at the time of failure, plus the index of the opcode that failed.
@@ -121,7 +123,7 @@ pub struct BrilligProfilingSample {
     pub call_stack: Vec<usize>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq)]
 /// VM encapsulates the state of the Brillig VM during execution.
 pub struct VM<'a, F, B: BlackBoxFunctionSolver<F>> {
     /// Calldata to the brillig function
@@ -158,6 +160,33 @@ pub struct VM<'a, F, B: BlackBoxFunctionSolver<F>> {
 
     // Branch to feature map for fuzzing
     branch_to_feature_map: BranchToFeatureMap,
+
+    // Limits on the opcodes executed by the VM, if any.
+    execution_limits: Option<&'a ExecutionLimits>,
+}
+
+// Implemented by hand so that cloning a VM does not require its black box solver to be `Clone`.
+impl<F: Clone, B: BlackBoxFunctionSolver<F>> Clone for VM<'_, F, B> {
+    fn clone(&self) -> Self {
+        Self {
+            calldata: self.calldata.clone(),
+            program_counter: self.program_counter,
+            foreign_call_counter: self.foreign_call_counter,
+            foreign_call_results: self.foreign_call_results.clone(),
+            bytecode: self.bytecode,
+            status: self.status.clone(),
+            memory: self.memory.clone(),
+            call_stack: self.call_stack.clone(),
+            black_box_solver: self.black_box_solver,
+            bigint_solver: self.bigint_solver.clone(),
+            profiling_active: self.profiling_active,
+            profiling_samples: self.profiling_samples.clone(),
+            fuzzing_active: self.fuzzing_active,
+            fuzzer_trace: self.fuzzer_trace.clone(),
+            branch_to_feature_map: self.branch_to_feature_map.clone(),
+            execution_limits: self.execution_limits,
+        }
+    }
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
@@ -194,9 +223,15 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
             fuzzing_active,
             fuzzer_trace,
             branch_to_feature_map,
+            execution_limits: None,
         }
     }
 
+    /// Makes the VM fail once the given limits are exceeded.
+    pub fn with_execution_limits(&mut self, execution_limits: &'a ExecutionLimits) {
+        self.execution_limits = Some(execution_limits);
+    }
+
     pub fn is_profiling_active(&self) -> bool {
         self.profiling_active
     }
@@ -300,6 +335,12 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
             self.profiling_samples.push(BrilligProfilingSample { call_stack });
         }
 
+        if let Some(execution_limits) = self.execution_limits {
+            if let Err(message) = execution_limits.record_opcode() {
+                return self.fail(message);
+            }
+        }
+
         self.process_opcode_internal()
     }
 
@@ -1080,6 +1121,33 @@ mod tests {
         assert_eq!(output_value.to_field(), FieldElement::from(27u128));
     }
 
+    #[test]
+    fn fails_when_exceeding_execution_limits() {
+        let opcodes = [Opcode::<FieldElement>::Jump { location: 0 }];
+        let solver = StubbedBlackBoxSolver::default();
+        let limits = ExecutionLimits::new(Some(10));
+        let mut vm = VM::new(vec![], &opcodes, &solver, false, None);
+        vm.with_execution_limits(&limits);
+
+        let status = vm.process_opcodes();
+        let message = "Exceeded the limit of 10 executed Brillig opcodes".to_string();
+        assert_eq!(
+            status,
+            VMStatus::Failure {
+                reason: FailureReason::RuntimeError { message },
+                call_stack: vec![0]
+            }
+        );
+        assert!(limits.max_opcodes_exceeded());
+
+        let limits = ExecutionLimits::new(None);
+        limits.interrupt();
+        let mut vm = VM::new(vec![], &opcodes, &solver, false, None);
+        vm.with_execution_limits(&limits);
+        assert!(matches!(vm.process_opcodes(), VMStatus::Failure { .. }));
+        assert_eq!(limits.executed_opcodes(), 0);
+    }
+
     #[test]
     fn jmpif_opcode() {
         let mut calldata: Vec<FieldElement> = vec![];
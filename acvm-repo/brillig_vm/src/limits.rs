@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Limits on the Brillig opcodes executed by the VMs sharing them.
+///
+/// The limits are checked before each opcode, so a VM stuck in an infinite loop
+/// still stops once they are exceeded.
+/// They can be shared between threads, which allows a timer to [interrupt][Self::interrupt]
+/// the VMs of a program while it is being executed.
+#[derive(Debug, Default)]
+pub struct ExecutionLimits {
+    /// The maximum number of opcodes the VMs may execute in total.
+    max_opcodes: Option<usize>,
+    executed_opcodes: AtomicUsize,
+    interrupted: AtomicBool,
+}
+
+impl ExecutionLimits {
+    pub fn new(max_opcodes: Option<usize>) -> Self {
+        Self { max_opcodes, ..Default::default() }
+    }
+
+    /// Makes the VMs using these limits fail before executing their next opcode.
+    pub fn interrupt(&self) {
+        self.interrupted.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::Relaxed)
+    }
+
+    pub fn executed_opcodes(&self) -> usize {
+        self.executed_opcodes.load(Ordering::Relaxed)
+    }
+
+    pub fn max_opcodes(&self) -> Option<usize> {
+        self.max_opcodes
+    }
+
+    /// Returns true if the VMs tried to execute more opcodes than allowed.
+    pub fn max_opcodes_exceeded(&self) -> bool {
+        self.max_opcodes.is_some_and(|max_opcodes| self.executed_opcodes() > max_opcodes)
+    }
+
+    /// Counts an opcode about to be executed, returning the failure message
+    /// if it must not be executed.
+    pub(crate) fn record_opcode(&self) -> Result<(), String> {
+        if self.is_interrupted() {
+            return Err("Execution was interrupted".to_string());
+        }
+        let executed_opcodes = self.executed_opcodes.fetch_add(1, Ordering::Relaxed) + 1;
+        match self.max_opcodes {
+            Some(max_opcodes) if executed_opcodes > max_opcodes => {
+                Err(format!("Exceeded the limit of {max_opcodes} executed Brillig opcodes"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -8,6 +8,7 @@ use noirc_errors::debug_info::DebugInfo;
 use noirc_evaluator::errors::SsaReport;
 
 use super::debug::DebugFile;
+use crate::CompileProfile;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CompiledContractOutputs {
@@ -29,6 +30,8 @@ pub struct CompiledContract {
 
     pub file_map: BTreeMap<FileId, DebugFile>,
     pub warnings: Vec<SsaReport>,
+    /// The compilation profile the contract was compiled with.
+    pub profile: Option<CompileProfile>,
 }
 
 /// Each function in the contract will be compiled
@@ -26,11 +26,13 @@ use noirc_frontend::node_interner::{FuncId, GlobalId, TypeId};
 use noirc_frontend::token::SecondaryAttributeKind;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 use tracing::info;
 
 mod abi_gen;
 mod contract;
 mod debug;
+mod profile;
 mod program;
 mod stdlib;
 
@@ -40,6 +42,7 @@ pub use abi_gen::gen_abi;
 pub use contract::{CompiledContract, CompiledContractOutputs, ContractFunction};
 pub use debug::DebugFile;
 pub use noirc_frontend::graph::{CrateId, CrateName};
+pub use profile::{BUILTIN_PROFILE_NAMES, CompileProfile, DEFAULT_PROFILE_NAME};
 pub use program::CompiledProgram;
 
 const STD_CRATE_NAME: &str = "std";
@@ -174,8 +177,9 @@ pub struct CompileOptions {
     /// Setting to decide on an inlining strategy for Brillig functions.
     /// A more aggressive inliner should generate larger programs but more optimized
     /// A less aggressive inliner should generate smaller programs
-    #[arg(long, hide = true, allow_hyphen_values = true, default_value_t = i64::MAX)]
-    pub inliner_aggressiveness: i64,
+    /// When left empty, the most aggressive setting is used.
+    #[arg(long, hide = true, allow_hyphen_values = true)]
+    pub inliner_aggressiveness: Option<i64>,
 
     /// Setting the maximum acceptable increase in Brillig bytecode size due to
     /// unrolling small loops. When left empty, any change is accepted as long
@@ -216,6 +220,14 @@ pub struct CompileOptions {
     /// Used internally to avoid comptime println from producing output
     #[arg(long, hide = true)]
     pub disable_comptime_printing: bool,
+
+    /// The compilation profile applied to these options, which is recorded in the artifacts.
+    #[arg(skip)]
+    pub profile: Option<CompileProfile>,
+
+    /// Comptime code still being evaluated at this instant fails with an error.
+    #[arg(skip)]
+    pub comptime_deadline: Option<Instant>,
 }
 
 impl CompileOptions {
@@ -245,7 +257,9 @@ impl CompileOptions {
                 .enable_brillig_constraints_check_lookback,
             skip_brillig_constraints_check: !self.silence_warnings
                 && self.skip_brillig_constraints_check,
-            inliner_aggressiveness: self.inliner_aggressiveness,
+            inliner_aggressiveness: self
+                .inliner_aggressiveness
+                .unwrap_or(DEFAULT_INLINER_AGGRESSIVENESS),
             max_bytecode_increase_percent: self.max_bytecode_increase_percent,
             skip_passes: self.skip_ssa_pass.clone(),
         }
@@ -275,6 +289,7 @@ impl CompileOptions {
             pedantic_solving: self.pedantic_solving,
             enabled_unstable_features: &self.unstable_features,
             disable_required_unstable_features: self.no_unstable_features,
+            comptime_deadline: self.comptime_deadline,
         }
     }
 }
@@ -734,6 +749,7 @@ fn compile_contract_inner(
             file_map,
             noir_version: NOIR_ARTIFACT_VERSION_STRING.to_string(),
             warnings,
+            profile: options.profile.clone(),
         })
     } else {
         Err(errors)
@@ -746,6 +762,9 @@ fn compile_contract_inner(
 /// Thus, we set it separately here rather than trying to alter the default derivation of the type.
 pub const DEFAULT_EXPRESSION_WIDTH: ExpressionWidth = ExpressionWidth::Bounded { width: 4 };
 
+/// The inliner aggressiveness used when none is given.
+pub const DEFAULT_INLINER_AGGRESSIVENESS: i64 = i64::MAX;
+
 /// Compile the current crate using `main_function` as the entrypoint.
 ///
 /// This function assumes [`check_crate`] is called beforehand.
@@ -800,7 +819,10 @@ pub fn compile_no_check(
     let hash = fxhash::hash64(&program);
 
     if let Some(cached_program) = cached_program {
-        if !force_compile && cached_program.hash == hash {
+        if !force_compile
+            && cached_program.hash == hash
+            && cached_program.profile == options.profile
+        {
             info!("Program matches existing artifact, returning early");
             return Ok(cached_program);
         }
@@ -837,6 +859,7 @@ pub fn compile_no_check(
         warnings,
         names,
         brillig_names,
+        profile: options.profile.clone(),
     })
 }
 
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{CompileOptions, parse_expression_width};
+
+/// Name of the profile used when none is selected.
+pub const DEFAULT_PROFILE_NAME: &str = "dev";
+
+/// Profiles which can be selected without being declared in `Nargo.toml`.
+pub const BUILTIN_PROFILE_NAMES: [&str; 2] = [DEFAULT_PROFILE_NAME, "release"];
+
+/// A named set of compilation settings, declared in a `[profile.<name>]` table of `Nargo.toml`.
+///
+/// Settings which are left unset don't change the [CompileOptions] given on the command line.
+/// The profile is stored in the compiled artifacts, to tell which settings produced them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CompileProfile {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expression_width: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounded_codegen: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inliner_aggressiveness: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytecode_increase_percent: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_underconstrained_check: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_brillig_constraints_check: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_brillig_constraints_check_lookback: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_brillig_debug_assertions: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count_array_copies: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub force_brillig: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pedantic_solving: Option<bool>,
+}
+
+impl CompileProfile {
+    /// Fills in the settings which are not set with the ones from `base`,
+    /// e.g. the profile this one inherits from.
+    pub fn inherit(self, base: &CompileProfile) -> CompileProfile {
+        CompileProfile {
+            name: self.name,
+            expression_width: self.expression_width.or(base.expression_width),
+            bounded_codegen: self.bounded_codegen.or(base.bounded_codegen),
+            inliner_aggressiveness: self.inliner_aggressiveness.or(base.inliner_aggressiveness),
+            max_bytecode_increase_percent: self
+                .max_bytecode_increase_percent
+                .or(base.max_bytecode_increase_percent),
+            skip_underconstrained_check: self
+                .skip_underconstrained_check
+                .or(base.skip_underconstrained_check),
+            skip_brillig_constraints_check: self
+                .skip_brillig_constraints_check
+                .or(base.skip_brillig_constraints_check),
+            enable_brillig_constraints_check_lookback: self
+                .enable_brillig_constraints_check_lookback
+                .or(base.enable_brillig_constraints_check_lookback),
+            enable_brillig_debug_assertions: self
+                .enable_brillig_debug_assertions
+                .or(base.enable_brillig_debug_assertions),
+            count_array_copies: self.count_array_copies.or(base.count_array_copies),
+            force_brillig: self.force_brillig.or(base.force_brillig),
+            pedantic_solving: self.pedantic_solving.or(base.pedantic_solving),
+        }
+    }
+
+    /// Checks that the settings have valid values.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(width) = self.expression_width {
+            parse_expression_width(&width.to_string())
+                .map_err(|err| format!("invalid `expression_width`: {err}"))?;
+        }
+        Ok(())
+    }
+
+    /// Applies the profile to the options given on the command line.
+    ///
+    /// Options which were set on the command line take precedence: flags can only be turned on,
+    /// and values are only taken from the profile if they were left at their default.
+    pub fn apply(&self, options: &CompileOptions) -> CompileOptions {
+        let mut options = options.clone();
+
+        if options.expression_width.is_none() {
+            options.expression_width = self.expression_width.map(|width| {
+                parse_expression_width(&width.to_string()).expect("profile should be validated")
+            });
+        }
+        if options.inliner_aggressiveness.is_none() {
+            options.inliner_aggressiveness = self.inliner_aggressiveness;
+        }
+        if options.max_bytecode_increase_percent.is_none() {
+            options.max_bytecode_increase_percent = self.max_bytecode_increase_percent;
+        }
+
+        let flags = [
+            (&mut options.bounded_codegen, self.bounded_codegen),
+            (&mut options.skip_underconstrained_check, self.skip_underconstrained_check),
+            (&mut options.skip_brillig_constraints_check, self.skip_brillig_constraints_check),
+            (
+                &mut options.enable_brillig_constraints_check_lookback,
+                self.enable_brillig_constraints_check_lookback,
+            ),
+            (&mut options.enable_brillig_debug_assertions, self.enable_brillig_debug_assertions),
+            (&mut options.count_array_copies, self.count_array_copies),
+            (&mut options.force_brillig, self.force_brillig),
+            (&mut options.pedantic_solving, self.pedantic_solving),
+        ];
+        for (option, setting) in flags {
+            *option = *option || setting.unwrap_or_default();
+        }
+
+        options.profile = Some(self.clone());
+        options
+    }
+}
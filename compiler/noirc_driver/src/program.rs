@@ -8,6 +8,7 @@ use noirc_evaluator::errors::SsaReport;
 use serde::{Deserialize, Serialize};
 
 use super::debug::DebugFile;
+use crate::CompileProfile;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Hash)]
 pub struct CompiledProgram {
@@ -31,4 +32,6 @@ pub struct CompiledProgram {
     pub names: Vec<String>,
     /// Names of the unconstrained functions in the program.
     pub brillig_names: Vec<String>,
+    /// The compilation profile the program was compiled with.
+    pub profile: Option<CompileProfile>,
 }
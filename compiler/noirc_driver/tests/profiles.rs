@@ -0,0 +1,46 @@
+use noirc_driver::{CompileOptions, CompileProfile};
+
+#[test]
+fn command_line_options_take_precedence_over_profile() {
+    let base = CompileProfile {
+        name: "release".to_string(),
+        inliner_aggressiveness: Some(0),
+        skip_underconstrained_check: Some(true),
+        ..Default::default()
+    };
+    let profile = CompileProfile {
+        name: "ci".to_string(),
+        inliner_aggressiveness: Some(5),
+        max_bytecode_increase_percent: Some(10),
+        ..Default::default()
+    }
+    .inherit(&base);
+    assert_eq!(profile.skip_underconstrained_check, Some(true));
+    assert_eq!(profile.inliner_aggressiveness, Some(5));
+
+    let options = CompileOptions {
+        max_bytecode_increase_percent: Some(20),
+        force_brillig: true,
+        ..Default::default()
+    };
+    let options = profile.apply(&options);
+    assert_eq!(options.inliner_aggressiveness, Some(5));
+    assert_eq!(options.max_bytecode_increase_percent, Some(20));
+    assert!(options.skip_underconstrained_check);
+    assert!(options.force_brillig);
+    assert_eq!(options.profile.map(|profile| profile.name), Some("ci".to_string()));
+}
+
+#[test]
+fn explicit_inliner_aggressiveness_takes_precedence_over_profile() {
+    let profile = CompileProfile {
+        name: "release".to_string(),
+        inliner_aggressiveness: Some(5),
+        ..Default::default()
+    };
+    for aggressiveness in [0, i64::MAX] {
+        let options =
+            CompileOptions { inliner_aggressiveness: Some(aggressiveness), ..Default::default() };
+        assert_eq!(profile.apply(&options).inliner_aggressiveness, Some(aggressiveness));
+    }
+}
@@ -11,7 +11,7 @@ use crate::elaborator::PrimitiveType;
 use crate::node_interner::{ExprId, InternedExpressionKind, InternedStatementKind, QuotedTypeId};
 use crate::shared::Visibility;
 use crate::signed_field::SignedField;
-use crate::token::{Attributes, FmtStrFragment, IntegerTypeSuffix, Token, Tokens};
+use crate::token::{Attributes, FmtStrFragment, FuzzStrategy, IntegerTypeSuffix, Token, Tokens};
 use crate::{Kind, Type};
 use acvm::FieldElement;
 use iter_extended::vecmap;
@@ -483,6 +483,8 @@ pub struct Param {
     pub visibility: Visibility,
     pub pattern: Pattern,
     pub typ: UnresolvedType,
+    /// The `#[range(..)]` or `#[one_of(..)]` attribute of a test parameter
+    pub fuzz_strategy: Option<FuzzStrategy>,
     pub location: Location,
 }
 
@@ -844,6 +846,7 @@ impl FunctionDefinition {
                 visibility: Visibility::Private,
                 pattern: Pattern::Identifier(ident.clone()),
                 typ: unresolved_type.clone(),
+                fuzz_strategy: None,
                 location: ident.location().merge(unresolved_type.location),
             })
             .collect();
@@ -865,14 +868,18 @@ impl FunctionDefinition {
     }
 
     pub fn signature(&self) -> String {
-        let parameters =
-            vecmap(&self.parameters, |Param { visibility, pattern, typ, location: _ }| {
+        let parameters = vecmap(
+            &self.parameters,
+            |Param { visibility, pattern, typ, fuzz_strategy, location: _ }| {
+                let strategy = fuzz_strategy.as_ref().map(|strategy| format!("{strategy} "));
+                let strategy = strategy.unwrap_or_default();
                 if *visibility == Visibility::Public {
-                    format!("{pattern}: {visibility} {typ}")
+                    format!("{strategy}{pattern}: {visibility} {typ}")
                 } else {
-                    format!("{pattern}: {typ}")
+                    format!("{strategy}{pattern}: {typ}")
                 }
-            });
+            },
+        );
 
         let where_clause = vecmap(&self.where_clause, ToString::to_string);
         let where_clause_str = if !where_clause.is_empty() {
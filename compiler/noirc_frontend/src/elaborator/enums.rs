@@ -243,6 +243,7 @@ impl Elaborator<'_> {
             kind: FunctionKind::Normal,
             parameters,
             parameter_idents: Vec::new(),
+            fuzz_strategies: Vec::new(),
             return_type: crate::ast::FunctionReturnType::Ty(self_type_unresolved),
             return_visibility: Visibility::Private,
             typ: function_type,
@@ -1,8 +1,11 @@
+use acvm::{AcirField, FieldElement};
+
 use crate::{
     Type,
-    ast::{Ident, NoirFunction},
+    ast::{Ident, NoirFunction, Pattern},
     graph::CrateId,
     hir::{
+        def_map::ModuleData,
         resolution::errors::{PubPosition, ResolverError},
         type_check::TypeCheckError,
     },
@@ -15,7 +18,7 @@ use crate::{
         DefinitionId, DefinitionKind, ExprId, FuncId, FunctionModifiers, NodeInterner,
     },
     shared::{Signedness, Visibility},
-    token::FunctionAttributeKind,
+    token::{FunctionAttributeKind, FuzzStrategy, FuzzStrategyKind},
 };
 
 use noirc_errors::Location;
@@ -65,9 +68,10 @@ pub(super) fn inlining_attributes(
         FunctionAttributeKind::Foreign(_)
         | FunctionAttributeKind::Builtin(_)
         | FunctionAttributeKind::Oracle(_)
-        | FunctionAttributeKind::Test(_)
+        | FunctionAttributeKind::Test(..)
         | FunctionAttributeKind::InlineAlways
-        | FunctionAttributeKind::FuzzingHarness(_) => None,
+        | FunctionAttributeKind::FuzzingHarness(_)
+        | FunctionAttributeKind::Bench => None,
     }
 }
 
@@ -107,6 +111,138 @@ pub(super) fn oracle_not_marked_unconstrained(
     }
 }
 
+/// Each fixture of a test (`#[test(fixtures(a, b))]`) must name both a parameter of the test
+/// and a `#[fixture]` function in scope taking no parameters and returning a value of its type.
+pub(super) fn invalid_test_fixture(
+    func: &FuncMeta,
+    modifiers: &FunctionModifiers,
+    module: &ModuleData,
+    interner: &NodeInterner,
+) -> Option<ResolverError> {
+    let attribute = modifiers.attributes.function()?;
+    let FunctionAttributeKind::Test(_, options) = &attribute.kind else {
+        return None;
+    };
+
+    let location = attribute.location;
+    let error = |name: &String, reason| {
+        Some(ResolverError::InvalidTestFixture { name: name.clone(), reason, location })
+    };
+    for (index, name) in options.fixtures.iter().enumerate() {
+        if options.fixtures[..index].contains(name) {
+            return error(name, "it is listed more than once");
+        }
+
+        let parameter = func.parameters.iter().find(|(pattern, _, _)| {
+            pattern.identifier().is_some_and(|ident| interner.definition_name(ident.id) == name)
+        });
+        let Some((_, parameter_type, _)) = parameter else {
+            return error(name, "the test has no parameter with this name");
+        };
+
+        let Some(fixture) = module.find_func_with_name(&name.clone().into()) else {
+            return error(name, "there is no function with this name in scope");
+        };
+        if !interner.function_attributes(&fixture).is_fixture() {
+            return error(name, "the function with this name is not marked with `#[fixture]`");
+        }
+
+        let fixture_meta = interner.function_meta(&fixture);
+        if !fixture_meta.parameters.is_empty() {
+            return error(name, "fixtures must not take any parameters");
+        }
+        if fixture_meta.return_type() != parameter_type {
+            return error(name, "the fixture doesn't return a value of the parameter's type");
+        }
+    }
+
+    // A test using fixtures isn't fuzzed, so nothing would give a value to its other parameters
+    let first_fixture = options.fixtures.first()?;
+    let has_other_parameters = func.parameters.iter().any(|(pattern, _, _)| {
+        !pattern.identifier().is_some_and(|ident| {
+            options.fixtures.iter().any(|fixture| fixture == interner.definition_name(ident.id))
+        })
+    });
+    if has_other_parameters {
+        error(
+            first_fixture,
+            "all the parameters of a test using fixtures must be given by fixtures",
+        )
+    } else {
+        None
+    }
+}
+
+/// A `#[range(..)]` or `#[one_of(..)]` strategy may only be given to a named parameter of a test
+/// whose type is made of fields or integers, and all the values it describes must fit that type.
+pub(super) fn invalid_fuzz_strategy(
+    is_test: bool,
+    pattern: &Pattern,
+    typ: &Type,
+    strategy: &FuzzStrategy,
+) -> Option<ResolverError> {
+    let error = |reason| {
+        Some(ResolverError::InvalidFuzzStrategy {
+            strategy: strategy.to_string(),
+            reason,
+            location: strategy.location,
+        })
+    };
+    if !is_test {
+        return error("fuzzing strategies are only allowed on the parameters of tests");
+    }
+    if !matches!(pattern, Pattern::Identifier(_)) {
+        return error("fuzzing strategies are only allowed on named parameters");
+    }
+
+    // A strategy on an array applies to each of its elements
+    let mut typ = typ.follow_bindings();
+    while let Type::Array(_, element) = typ {
+        typ = *element;
+    }
+    let fits = |value: FieldElement| match &typ {
+        Type::Integer(Signedness::Unsigned, bit_size) => value.num_bits() <= u32::from(*bit_size),
+        Type::Integer(Signedness::Signed, bit_size) => value.num_bits() < u32::from(*bit_size),
+        _ => true,
+    };
+    if !matches!(typ, Type::FieldElement | Type::Integer(..)) {
+        return error("the parameter must be a field, an integer or an array of them");
+    }
+
+    match &strategy.kind {
+        FuzzStrategyKind::Range { min, max } => {
+            let range_fits = match &typ {
+                Type::Integer(Signedness::Unsigned, _) => {
+                    *min >= 0 && fits(FieldElement::from(max.unsigned_abs()))
+                }
+                Type::Integer(Signedness::Signed, bit_size) => {
+                    // Values are between -bound and bound - 1
+                    let bit_size = u32::from(*bit_size);
+                    bit_size >= 128 || {
+                        let bound = 1i128 << (bit_size - 1);
+                        -bound <= *min && *max < bound
+                    }
+                }
+                _ => true,
+            };
+            if min > max {
+                error("the minimum is greater than the maximum")
+            } else if !range_fits {
+                error("the range doesn't fit in the parameter's type")
+            } else {
+                None
+            }
+        }
+        FuzzStrategyKind::OneOf(values) => {
+            if values.iter().all(|value| fits(*value)) {
+                None
+            } else {
+                error("a value doesn't fit in the parameter's type")
+            }
+        }
+    }
+}
+
 /// Oracle functions may not be called by constrained functions directly.
 ///
 /// In order for a constrained function to call an oracle it must first call through an unconstrained function.
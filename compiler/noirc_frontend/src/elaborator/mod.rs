@@ -2,6 +2,7 @@ use std::{
     cell::RefCell,
     collections::{BTreeMap, BTreeSet},
     rc::Rc,
+    time::Instant,
 };
 
 use crate::{
@@ -402,6 +403,11 @@ impl<'context> Elaborator<'context> {
         self.options.pedantic_solving
     }
 
+    /// Whether comptime code must stop being evaluated because its deadline was reached.
+    pub(crate) fn comptime_deadline_reached(&self) -> bool {
+        self.options.comptime_deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
     /// Runs `f` and if it modifies `self.generics`, `self.generics` is truncated
     /// back to the previous length.
     fn recover_generics<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
@@ -975,9 +981,12 @@ impl<'context> Elaborator<'context> {
         let mut parameters = Vec::new();
         let mut parameter_types = Vec::new();
         let mut parameter_idents = Vec::new();
+        let mut fuzz_strategies = Vec::new();
         let wildcard_allowed = false;
 
-        for Param { visibility, pattern, typ, location: _ } in func.parameters().iter().cloned() {
+        for Param { visibility, pattern, typ, fuzz_strategy, location: _ } in
+            func.parameters().iter().cloned()
+        {
             self.run_lint(|_| {
                 lints::unnecessary_pub_argument(func, visibility, is_pub_allowed).map(Into::into)
             });
@@ -1002,6 +1011,17 @@ impl<'context> Elaborator<'context> {
                 self.mark_type_as_used(&typ);
             }
 
+            if let Some(strategy) = fuzz_strategy {
+                let is_test = func.attributes().is_test_function();
+                if let Some(error) =
+                    lints::invalid_fuzz_strategy(is_test, &pattern, &typ, &strategy)
+                {
+                    self.push_err(error);
+                } else if let Pattern::Identifier(ident) = &pattern {
+                    fuzz_strategies.push((ident.to_string(), strategy.kind));
+                }
+            }
+
             let pattern = self.elaborate_pattern_and_store_ids(
                 pattern,
                 typ.clone(),
@@ -1063,6 +1083,7 @@ impl<'context> Elaborator<'context> {
             enum_variant_index: None,
             parameters: parameters.into(),
             parameter_idents,
+            fuzz_strategies,
             return_type: func.def.return_type.clone(),
             return_visibility: func.def.return_visibility,
             has_body: !func.def.body.is_empty(),
@@ -1147,6 +1168,11 @@ impl<'context> Elaborator<'context> {
             lints::unnecessary_pub_return(func, modifiers, pub_allowed).map(Into::into)
         });
         self.run_lint(|_| lints::oracle_not_marked_unconstrained(func, modifiers).map(Into::into));
+        self.run_lint(|elaborator| {
+            let module = elaborator.get_module(elaborator.module_id());
+            lints::invalid_test_fixture(func, modifiers, module, elaborator.interner)
+                .map(Into::into)
+        });
         self.run_lint(|elaborator| {
             lints::low_level_function_outside_stdlib(modifiers, elaborator.crate_id).map(Into::into)
         });
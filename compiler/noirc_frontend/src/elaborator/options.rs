@@ -1,4 +1,5 @@
 use std::str::FromStr;
+use std::time::Instant;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum UnstableFeature {
@@ -44,6 +45,9 @@ pub struct GenericOptions<'a, T> {
 
     /// Deny crates from requiring unstable features.
     pub disable_required_unstable_features: bool,
+
+    /// Stop evaluating comptime code with an error once this instant is reached
+    pub comptime_deadline: Option<Instant>,
 }
 
 /// Options from nargo_cli that need to be passed down to the elaborator
@@ -61,6 +65,7 @@ impl<T> GenericOptions<'_, T> {
             pedantic_solving: true,
             enabled_unstable_features: &[UnstableFeature::Enums],
             disable_required_unstable_features: true,
+            comptime_deadline: None,
         }
     }
 }
@@ -264,6 +264,9 @@ pub enum InterpreterError {
     LoopHaltedForUiResponsiveness {
         location: Location,
     },
+    ComptimeDeadlineReached {
+        location: Location,
+    },
 
     // These cases are not errors, they are just used to prevent us from running more code
     // until the loop can be resumed properly. These cases will never be displayed to users.
@@ -341,7 +344,8 @@ impl InterpreterError {
             | InterpreterError::UnknownArrayLength { location, .. }
             | InterpreterError::CannotInterpretFormatStringWithErrors { location }
             | InterpreterError::GlobalsDependencyCycle { location }
-            | InterpreterError::LoopHaltedForUiResponsiveness { location } => *location,
+            | InterpreterError::LoopHaltedForUiResponsiveness { location }
+            | InterpreterError::ComptimeDeadlineReached { location } => *location,
 
             InterpreterError::FailedToParseMacro { error, .. } => error.location(),
             InterpreterError::NoMatchingImplFound { error } => error.location,
@@ -732,6 +736,10 @@ impl<'a> From<&'a InterpreterError> for CustomDiagnostic {
                     "This error doesn't happen in normal executions of `nargo`".to_string();
                 CustomDiagnostic::simple_warning(msg, secondary, *location)
             }
+            InterpreterError::ComptimeDeadlineReached { location } => {
+                let msg = "Comptime evaluation was stopped because it took too long".to_string();
+                CustomDiagnostic::simple_error(msg, String::new(), *location)
+            }
         }
     }
 }
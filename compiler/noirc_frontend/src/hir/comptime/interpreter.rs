@@ -98,6 +98,10 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
         mut instantiation_bindings: TypeBindings,
         location: Location,
     ) -> IResult<Value> {
+        if self.elaborator.comptime_deadline_reached() {
+            return Err(InterpreterError::ComptimeDeadlineReached { location });
+        }
+
         let trait_method = self.elaborator.interner.get_trait_item_id(function);
 
         // To match the monomorphizer, we need to call follow_bindings on each of
@@ -1382,6 +1386,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
             if must_break {
                 break;
             }
+
+            if self.elaborator.comptime_deadline_reached() {
+                let location = self.elaborator.interner.expr_location(&block);
+                result = Err(InterpreterError::ComptimeDeadlineReached { location });
+                break;
+            }
         }
 
         self.in_loop = was_in_loop;
@@ -1411,6 +1421,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 result = Err(InterpreterError::LoopHaltedForUiResponsiveness { location });
                 break;
             }
+
+            if self.elaborator.comptime_deadline_reached() {
+                let location = self.elaborator.interner.expr_location(&expr);
+                result = Err(InterpreterError::ComptimeDeadlineReached { location });
+                break;
+            }
         }
 
         self.in_loop = was_in_loop;
@@ -1451,6 +1467,12 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
                 result = Err(InterpreterError::LoopHaltedForUiResponsiveness { location });
                 break;
             }
+
+            if self.elaborator.comptime_deadline_reached() {
+                let location = self.elaborator.interner.expr_location(&block);
+                result = Err(InterpreterError::ComptimeDeadlineReached { location });
+                break;
+            }
         }
 
         self.in_loop = was_in_loop;
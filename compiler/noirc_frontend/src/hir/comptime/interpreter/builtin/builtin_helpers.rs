@@ -606,6 +606,7 @@ fn secondary_attribute_name(
         }
         SecondaryAttributeKind::Export => Some("export".to_string()),
         SecondaryAttributeKind::Field(_) => Some("field".to_string()),
+        SecondaryAttributeKind::Fixture => Some("fixture".to_string()),
         SecondaryAttributeKind::Tag(contents) => {
             let mut lexer = Lexer::new_with_dummy_file(contents);
             let token = lexer.next()?.ok()?;
@@ -616,6 +617,7 @@ fn secondary_attribute_name(
         SecondaryAttributeKind::Varargs => Some("varargs".to_string()),
         SecondaryAttributeKind::UseCallersScope => Some("use_callers_scope".to_string()),
         SecondaryAttributeKind::Allow(_) => Some("allow".to_string()),
+        SecondaryAttributeKind::Cfg(_) => Some("cfg".to_string()),
     }
 }
 
@@ -1,7 +1,7 @@
 use super::dc_mod::collect_defs;
 use super::errors::{DefCollectorErrorKind, DuplicateType};
 use crate::elaborator::Elaborator;
-use crate::graph::CrateId;
+use crate::graph::{CrateId, CrateName};
 use crate::hir::comptime::{ComptimeError, InterpreterError};
 use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleId};
 use crate::hir::resolution::errors::ResolverError;
@@ -12,7 +12,7 @@ use crate::usage_tracker::UnusedItem;
 use crate::{Generics, Type};
 
 use crate::hir::Context;
-use crate::hir::resolution::import::{ImportDirective, resolve_import};
+use crate::hir::resolution::import::{ImportDirective, PathResolutionError, resolve_import};
 
 use crate::ast::{Expression, NoirEnumeration};
 use crate::node_interner::{
@@ -22,8 +22,8 @@ use crate::node_interner::{
 
 use crate::ast::{
     ExpressionKind, Ident, ItemVisibility, LetStatement, Literal, NoirFunction, NoirStruct,
-    NoirTrait, NoirTypeAlias, Path, PathSegment, UnresolvedGenerics, UnresolvedTraitConstraint,
-    UnresolvedType, UnsupportedNumericGenericType,
+    NoirTrait, NoirTypeAlias, Path, PathKind, PathSegment, UnresolvedGenerics,
+    UnresolvedTraitConstraint, UnresolvedType, UnsupportedNumericGenericType,
 };
 
 use crate::elaborator::FrontendOptions;
@@ -380,6 +380,10 @@ impl DefCollector {
             inject_prelude(crate_id, context, submodule, &mut def_collector.imports);
         }
 
+        // Errors caused by an unlinked dev-dependency are only known once the crate is elaborated.
+        let first_import_error = errors.len();
+        let mut ignored_imports = Vec::new();
+
         // Resolve unresolved imports collected from the crate, one by one.
         for collected_import in std::mem::take(&mut def_collector.imports) {
             let local_module_id = collected_import.module_id;
@@ -477,8 +481,17 @@ impl DefCollector {
                     }
                 }
                 Err(error) => {
-                    let error = DefCollectorErrorKind::PathResolutionError(error);
-                    errors.push(error.into());
+                    match unlinked_dev_dependency(context, crate_id, &collected_import) {
+                        // Only test functions can use this import, and they are skipped as well.
+                        // Any other use of it is reported once the crate is elaborated.
+                        Some(dependency) => {
+                            ignored_imports.push((collected_import.name(), dependency));
+                        }
+                        None => {
+                            let error = DefCollectorErrorKind::PathResolutionError(error);
+                            errors.push(error.into());
+                        }
+                    }
                 }
             }
         }
@@ -497,6 +510,7 @@ impl DefCollector {
             pedantic_solving: options.pedantic_solving,
             enabled_unstable_features: options.enabled_unstable_features,
             disable_required_unstable_features: options.disable_required_unstable_features,
+            comptime_deadline: options.comptime_deadline,
         };
 
         let mut more_errors =
@@ -504,6 +518,14 @@ impl DefCollector {
 
         errors.append(&mut more_errors);
 
+        if let Some(dev_dependencies) = context.unlinked_dev_dependencies.get(&crate_id) {
+            report_dev_dependency_uses(
+                &mut errors[first_import_error..],
+                dev_dependencies,
+                &ignored_imports,
+            );
+        }
+
         Self::check_unused_items(context, crate_id, &mut errors);
 
         errors
@@ -602,6 +624,61 @@ fn inject_prelude(
     }
 }
 
+/// Returns the dev-dependency of the crate an import refers to, if it was not linked.
+fn unlinked_dev_dependency(
+    context: &Context,
+    crate_id: CrateId,
+    import: &ImportDirective,
+) -> Option<CrateName> {
+    let dev_dependencies = context.unlinked_dev_dependencies.get(&crate_id)?;
+    if !matches!(import.path.kind, PathKind::Plain | PathKind::Dep) {
+        return None;
+    }
+    let segment = import.path.segments.first()?;
+    dev_dependencies.iter().find(|name| name.to_string() == segment.ident.as_str()).cloned()
+}
+
+/// Replaces the errors about names which could not be resolved with a targeted error when the name
+/// is an unlinked dev-dependency, or an ignored import of one: since test functions are skipped when
+/// dev-dependencies aren't linked, the name is used outside of tests.
+fn report_dev_dependency_uses(
+    errors: &mut [CompilationError],
+    dev_dependencies: &[CrateName],
+    ignored_imports: &[(Ident, CrateName)],
+) {
+    for error in errors {
+        let (name, location) = match error {
+            CompilationError::ResolverError(ResolverError::VariableNotDeclared {
+                name,
+                location,
+            }) => (name.as_str(), *location),
+            CompilationError::ResolverError(ResolverError::PathResolutionError(
+                PathResolutionError::Unresolved(ident),
+            ))
+            | CompilationError::DefinitionError(DefCollectorErrorKind::PathResolutionError(
+                PathResolutionError::Unresolved(ident),
+            )) => (ident.as_str(), ident.location()),
+            _ => continue,
+        };
+
+        let import = ignored_imports.iter().find(|(import, _)| import.as_str() == name);
+        let (dependency, import_location) = match import {
+            Some((import, dependency)) => (dependency, Some(import.location())),
+            None => match dev_dependencies.iter().find(|dependency| dependency.to_string() == name)
+            {
+                Some(dependency) => (dependency, None),
+                None => continue,
+            },
+        };
+        *error =
+            CompilationError::DefinitionError(DefCollectorErrorKind::DevDependencyOutsideTests {
+                dependency: dependency.to_string(),
+                location,
+                import_location,
+            });
+    }
+}
+
 /// Separate the globals Vec into two. The first element in the tuple will be the
 /// literal globals, except for arrays, and the second will be all other globals.
 /// We exclude array literals as they can contain complex types
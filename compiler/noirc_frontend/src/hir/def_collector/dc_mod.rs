@@ -1,4 +1,5 @@
 use core::str;
+use std::collections::BTreeSet;
 use std::path::Path;
 use std::rc::Rc;
 use std::vec;
@@ -19,7 +20,7 @@ use crate::ast::{
 use crate::elaborator::PrimitiveType;
 use crate::hir::resolution::errors::ResolverError;
 use crate::node_interner::{DefinitionKind, ModuleAttributes, NodeInterner, ReferenceId, TypeId};
-use crate::token::{SecondaryAttribute, TestScope};
+use crate::token::{SecondaryAttribute, SecondaryAttributeKind, TestScope};
 use crate::usage_tracker::{UnusedItem, UsageTracker};
 use crate::{Generics, Kind, ResolvedGeneric, Type, TypeVariable};
 use crate::{
@@ -54,7 +55,7 @@ struct ModCollector<'a> {
 /// This performs the entirety of the definition collection phase of the name resolution pass.
 pub fn collect_defs(
     def_collector: &mut DefCollector,
-    ast: SortedModule,
+    mut ast: SortedModule,
     file_id: FileId,
     module_id: LocalModuleId,
     crate_id: CrateId,
@@ -63,6 +64,9 @@ pub fn collect_defs(
     let mut collector = ModCollector { def_collector, file_id, module_id };
     let mut errors: Vec<CompilationError> = vec![];
 
+    let testing = !context.unlinked_dev_dependencies.contains_key(&crate_id);
+    remove_disabled_items(&mut ast, context.enabled_features.get(&crate_id), testing);
+
     // First resolve the module declarations
     for decl in ast.module_decls {
         errors.extend(
@@ -117,6 +121,45 @@ pub fn collect_defs(
     errors
 }
 
+/// Removes the items marked with `#[cfg(...)]` whose condition doesn't hold for the features enabled
+/// for the crate and whether its tests are compiled, along with everything in the module if the module itself is marked that way.
+fn remove_disabled_items(
+    module: &mut SortedModule,
+    features: Option<&BTreeSet<String>>,
+    testing: bool,
+) {
+    let is_feature_enabled =
+        |feature: &str| features.is_some_and(|features| features.contains(feature));
+    let is_enabled = |attributes: &[SecondaryAttribute]| {
+        attributes.iter().all(|attribute| match &attribute.kind {
+            SecondaryAttributeKind::Cfg(predicate) => predicate.holds(&is_feature_enabled, testing),
+            _ => true,
+        })
+    };
+
+    if !is_enabled(&module.inner_attributes) {
+        *module = SortedModule::default();
+        return;
+    }
+
+    module.functions.retain(|function| is_enabled(&function.item.def.attributes.secondary));
+    module.structs.retain(|noir_struct| is_enabled(&noir_struct.item.attributes));
+    module.enums.retain(|noir_enum| is_enabled(&noir_enum.item.attributes));
+    module.traits.retain(|noir_trait| is_enabled(&noir_trait.item.attributes));
+    module.globals.retain(|(global, _)| is_enabled(&global.item.attributes));
+    module.module_decls.retain(|decl| is_enabled(&decl.item.outer_attributes));
+    module.submodules.retain(|submodule| is_enabled(&submodule.item.outer_attributes));
+    for type_impl in &mut module.impls {
+        type_impl.methods.retain(|(method, _)| is_enabled(&method.item.def.attributes.secondary));
+    }
+    for trait_impl in &mut module.trait_impls {
+        trait_impl.items.retain(|item| match &item.item.kind {
+            TraitImplItemKind::Function(function) => is_enabled(&function.def.attributes.secondary),
+            TraitImplItemKind::Constant(..) | TraitImplItemKind::Type { .. } => true,
+        });
+    }
+}
+
 impl ModCollector<'_> {
     fn collect_attributes(
         &mut self,
@@ -277,8 +320,19 @@ impl ModCollector<'_> {
         let mut errors = vec![];
 
         let module = ModuleId { krate, local_id: self.module_id };
+        let skip_tests = context.unlinked_dev_dependencies.contains_key(&krate);
 
         for function in functions {
+            let attributes = &function.item.def.attributes;
+            if skip_tests
+                && (attributes.is_test_function()
+                    || attributes.as_fuzzing_harness().is_some()
+                    || attributes.is_bench_function())
+            {
+                // These may use dev-dependencies, which are only linked when building tests.
+                continue;
+            }
+
             let Some(func_id) = collect_function(
                 &mut context.def_interner,
                 &mut self.def_collector.def_map,
@@ -1018,12 +1072,15 @@ pub fn collect_function(
     let is_test = test_attribute.is_some();
     let fuzz_attribute = function.def.attributes.as_fuzzing_harness();
     let is_fuzzing_harness = fuzz_attribute.is_some();
+    let is_bench = function.def.attributes.is_bench_function();
     let is_entry_point_function = if module_data.is_contract {
         function.attributes().is_contract_entry_point()
     } else {
         function.name() == MAIN_FUNCTION
     };
     let has_export = function.def.attributes.has_export();
+    // Fixtures are only referenced by name from the tests using them
+    let is_fixture = function.def.attributes.is_fixture();
     let has_allow_dead_code = function.def.attributes.has_allow("dead_code");
 
     let name = function.name_ident().clone();
@@ -1046,8 +1103,10 @@ pub fn collect_function(
 
     if !is_test
         && !is_fuzzing_harness
+        && !is_bench
         && !is_entry_point_function
         && !has_export
+        && !is_fixture
         && !has_allow_dead_code
     {
         let item = UnusedItem::Function(func_id);
@@ -1056,7 +1115,7 @@ pub fn collect_function(
 
     interner.set_doc_comments(ReferenceId::Function(func_id), doc_comments);
 
-    if let Some((test_scope, location)) = test_attribute {
+    if let Some((test_scope, _, location)) = test_attribute {
         if function.def.parameters.is_empty()
             && matches!(test_scope, TestScope::OnlyFailWith { .. })
         {
@@ -1072,6 +1131,12 @@ pub fn collect_function(
         }
     }
 
+    if is_bench && !function.def.parameters.is_empty() {
+        let location = function.name_ident().location();
+        let error = DefCollectorErrorKind::BenchWithParameters { location };
+        errors.push(error.into());
+    }
+
     // Add function to scope/ns of the module
     let result = module_data.declare_function(name, visibility, func_id);
     if let Err((first_def, second_def)) = result {
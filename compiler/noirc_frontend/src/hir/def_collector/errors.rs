@@ -75,8 +75,16 @@ pub enum DefCollectorErrorKind {
     TestOnlyFailWithWithoutParameters { location: Location },
     #[error("The `#[fuzz]` attribute may only be used on functions with parameters")]
     FuzzingHarnessWithoutParameters { location: Location },
+    #[error("The `#[bench]` attribute may only be used on functions without parameters")]
+    BenchWithParameters { location: Location },
     #[error("`{name}` entry-point function is not allowed to have generic parameters")]
     EntryPointWithGenerics { name: String, location: Location },
+    #[error("Dev-dependency `{dependency}` used outside tests")]
+    DevDependencyOutsideTests {
+        dependency: String,
+        location: Location,
+        import_location: Option<Location>,
+    },
 }
 
 impl DefCollectorErrorKind {
@@ -111,7 +119,9 @@ impl DefCollectorErrorKind {
             | DefCollectorErrorKind::ForeignImpl { location, .. }
             | DefCollectorErrorKind::TestOnlyFailWithWithoutParameters { location }
             | DefCollectorErrorKind::FuzzingHarnessWithoutParameters { location }
-            | DefCollectorErrorKind::EntryPointWithGenerics { location, .. } => *location,
+            | DefCollectorErrorKind::BenchWithParameters { location }
+            | DefCollectorErrorKind::EntryPointWithGenerics { location, .. }
+            | DefCollectorErrorKind::DevDependencyOutsideTests { location, .. } => *location,
             DefCollectorErrorKind::NotATrait { not_a_trait_name: path }
             | DefCollectorErrorKind::TraitNotFound { trait_path: path } => path.location,
         }
@@ -294,11 +304,28 @@ impl<'a> From<&'a DefCollectorErrorKind> for Diagnostic {
                 String::new(),
                 *location,
             ),
+            DefCollectorErrorKind::BenchWithParameters { location } => Diagnostic::simple_error(
+                "The `#[bench]` attribute may only be used on functions without parameters".into(),
+                String::new(),
+                *location,
+            ),
             DefCollectorErrorKind::EntryPointWithGenerics { name, location } => Diagnostic::simple_error(
                 format!("`{name}` entry-point function is not allowed to have generic parameters"),
                 String::new(),
                 *location,
             ),
+            DefCollectorErrorKind::DevDependencyOutsideTests { dependency, location, import_location } => {
+                let mut diag = Diagnostic::simple_error(
+                    format!("Dev-dependency `{dependency}` used outside tests"),
+                    "Dev-dependencies are only linked when building tests".to_string(),
+                    *location,
+                );
+                if let Some(import_location) = import_location {
+                    diag.add_secondary(format!("`{dependency}` is imported here"), *import_location);
+                }
+                diag.add_note("Mark test helpers with `#[cfg(test)]` to leave them out when tests aren't built".to_string());
+                diag
+            }
         }
     }
 }
@@ -206,13 +206,35 @@ impl CrateDefMap {
         self.modules.iter().flat_map(|(_, module)| {
             module.value_definitions().filter_map(|id| {
                 if let Some(func_id) = id.as_function() {
-                    let has_arguments = !interner.function_meta(&func_id).parameters.is_empty();
+                    let meta = interner.function_meta(&func_id);
                     let attributes = interner.function_attributes(&func_id);
                     match attributes.function().map(|attr| &attr.kind) {
-                        Some(FunctionAttributeKind::Test(scope)) => {
-                            let location = interner.function_meta(&func_id).name.location;
-                            let scope = scope.clone();
-                            Some(TestFunction { id: func_id, scope, location, has_arguments })
+                        Some(FunctionAttributeKind::Test(scope, options)) => {
+                            // Parameters given by fixtures don't turn the test into a fuzz test
+                            let has_arguments = meta.parameters.iter().any(|(pattern, _, _)| {
+                                !pattern.identifier().is_some_and(|ident| {
+                                    let name = interner.definition_name(ident.id);
+                                    options.fixtures.iter().any(|fixture| fixture == name)
+                                })
+                            });
+                            let fixtures = options
+                                .fixtures
+                                .iter()
+                                .filter_map(|name| {
+                                    let function =
+                                        module.find_func_with_name(&name.clone().into())?;
+                                    Some(TestFixture { parameter: name.clone(), function })
+                                })
+                                .collect();
+                            Some(TestFunction {
+                                id: func_id,
+                                scope: scope.clone(),
+                                location: meta.name.location,
+                                has_arguments,
+                                timeout: options.timeout,
+                                max_opcodes: options.max_opcodes,
+                                fixtures,
+                            })
                         }
                         _ => None,
                     }
@@ -247,6 +269,25 @@ impl CrateDefMap {
         })
     }
 
+    /// Go through all modules in this crate, and find all functions in
+    /// each module with the `#[bench]` attribute
+    pub fn get_all_bench_functions<'a>(
+        &'a self,
+        interner: &'a NodeInterner,
+    ) -> impl Iterator<Item = BenchFunction> + 'a {
+        self.modules.iter().flat_map(|(_, module)| {
+            module.value_definitions().filter_map(|id| {
+                let func_id = id.as_function()?;
+                if interner.function_attributes(&func_id).is_bench_function() {
+                    let location = interner.function_meta(&func_id).name.location;
+                    Some(BenchFunction { id: func_id, location })
+                } else {
+                    None
+                }
+            })
+        })
+    }
+
     /// Go through all modules in this crate, and find all functions in
     /// each module with the `#[export]` attribute
     pub fn get_all_exported_functions<'a>(
@@ -383,6 +424,18 @@ pub struct TestFunction {
     pub scope: TestScope,
     pub location: Location,
     pub has_arguments: bool,
+    /// The time limit of the test in seconds, given with `#[test(timeout = N)]`
+    pub timeout: Option<u64>,
+    /// The maximum number of ACIR opcodes of the test program, given with `#[test(max_opcodes = N)]`
+    pub max_opcodes: Option<usize>,
+    /// The parameters of the test given by fixtures, listed with `#[test(fixtures(a, b))]`
+    pub fixtures: Vec<TestFixture>,
+}
+
+/// A parameter of a test whose value is returned by a `#[fixture]` function of the same name.
+pub struct TestFixture {
+    pub parameter: String,
+    pub function: FuncId,
 }
 
 impl TestFunction {
@@ -407,6 +460,11 @@ impl TestFunction {
     }
 }
 
+pub struct BenchFunction {
+    pub id: FuncId,
+    pub location: Location,
+}
+
 pub struct FuzzingHarness {
     pub id: FuncId,
     pub scope: FuzzingScope,
@@ -8,7 +8,7 @@ pub mod type_check;
 use crate::ast::UnresolvedGenerics;
 use crate::debug::DebugInstrumenter;
 use crate::elaborator::UnstableFeature;
-use crate::graph::{CrateGraph, CrateId};
+use crate::graph::{CrateGraph, CrateId, CrateName};
 use crate::hir::def_map::DefMaps;
 use crate::hir_def::function::FuncMeta;
 use crate::node_interner::{FuncId, NodeInterner, TypeId};
@@ -16,13 +16,13 @@ use crate::parser::ParserError;
 use crate::usage_tracker::UsageTracker;
 use crate::{Generics, Kind, ParsedModule, ResolvedGeneric, TypeVariable};
 use def_collector::dc_crate::CompilationError;
-use def_map::{CrateDefMap, FuzzingHarness, fully_qualified_module_path};
+use def_map::{BenchFunction, CrateDefMap, FuzzingHarness, fully_qualified_module_path};
 use fm::{FileId, FileManager};
 use iter_extended::vecmap;
 use noirc_errors::Location;
 use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 use std::rc::Rc;
 
@@ -61,6 +61,15 @@ pub struct Context<'file_manager, 'parsed_files> {
 
     /// Any unstable features required by the current package or its dependencies.
     pub required_unstable_features: BTreeMap<CrateId, Vec<UnstableFeature>>,
+
+    /// Names of the dev-dependencies of each crate which were not linked because we aren't building tests.
+    /// Test functions and `#[cfg(test)]` items of these crates are skipped, and imports from these
+    /// dependencies are ignored.
+    pub unlinked_dev_dependencies: BTreeMap<CrateId, Vec<CrateName>>,
+
+    /// Package features enabled for each crate. Items marked with `#[cfg(feature = "...")]`
+    /// for any other feature are left out.
+    pub enabled_features: BTreeMap<CrateId, BTreeSet<String>>,
 }
 
 #[derive(Debug)]
@@ -84,6 +93,8 @@ impl Context<'_, '_> {
             package_build_path: PathBuf::default(),
             interpreter_output: Some(Rc::new(RefCell::new(std::io::stdout()))),
             required_unstable_features: BTreeMap::new(),
+            unlinked_dev_dependencies: BTreeMap::new(),
+            enabled_features: BTreeMap::new(),
         }
     }
 
@@ -103,6 +114,8 @@ impl Context<'_, '_> {
             package_build_path: PathBuf::default(),
             interpreter_output: Some(Rc::new(RefCell::new(std::io::stdout()))),
             required_unstable_features: BTreeMap::new(),
+            unlinked_dev_dependencies: BTreeMap::new(),
+            enabled_features: BTreeMap::new(),
         }
     }
 
@@ -215,6 +228,37 @@ impl Context<'_, '_> {
             .collect()
     }
 
+    /// Returns a list of all functions in the current crate marked with `#[bench]`
+    /// whose names contain the given pattern string. An empty pattern string
+    /// will return all functions marked with `#[bench]`.
+    pub fn get_all_bench_functions_in_crate_matching(
+        &self,
+        crate_id: &CrateId,
+        pattern: &FunctionNameMatch,
+    ) -> Vec<(String, BenchFunction)> {
+        let interner = &self.def_interner;
+        let def_map = self.def_map(crate_id).expect("The local crate should be analyzed already");
+
+        def_map
+            .get_all_bench_functions(interner)
+            .filter_map(|bench_function| {
+                let fully_qualified_name =
+                    self.fully_qualified_function_name(crate_id, &bench_function.id);
+                match &pattern {
+                    FunctionNameMatch::Anything => Some((fully_qualified_name, bench_function)),
+                    FunctionNameMatch::Exact(patterns) => patterns
+                        .iter()
+                        .any(|pattern| &fully_qualified_name == pattern)
+                        .then_some((fully_qualified_name, bench_function)),
+                    FunctionNameMatch::Contains(patterns) => patterns
+                        .iter()
+                        .any(|pattern| fully_qualified_name.contains(pattern))
+                        .then_some((fully_qualified_name, bench_function)),
+                }
+            })
+            .collect()
+    }
+
     pub fn get_all_exported_functions_in_crate(&self, crate_id: &CrateId) -> Vec<(String, FuncId)> {
         let interner = &self.def_interner;
         let def_map = self.def_map(crate_id).expect("The local crate should be analyzed already");
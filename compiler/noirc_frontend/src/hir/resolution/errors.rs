@@ -74,6 +74,10 @@ pub enum ResolverError {
     OracleMarkedAsConstrained { ident: Ident, location: Location },
     #[error("Oracle functions cannot be called directly from constrained functions")]
     UnconstrainedOracleReturnToConstrained { location: Location },
+    #[error("Invalid test fixture `{name}`: {reason}")]
+    InvalidTestFixture { name: String, reason: &'static str, location: Location },
+    #[error("Invalid fuzzing strategy `{strategy}`: {reason}")]
+    InvalidFuzzStrategy { strategy: String, reason: &'static str, location: Location },
     #[error("Dependency cycle found, '{item}' recursively depends on itself: {cycle} ")]
     DependencyCycle { location: Location, item: String, cycle: String },
     #[error("break/continue are only allowed in unconstrained functions")]
@@ -243,6 +247,8 @@ impl ResolverError {
             | ResolverError::FoldAttributeOnUnconstrained { location, .. }
             | ResolverError::OracleMarkedAsConstrained { location, .. }
             | ResolverError::LowLevelFunctionOutsideOfStdlib { location }
+            | ResolverError::InvalidTestFixture { location, .. }
+            | ResolverError::InvalidFuzzStrategy { location, .. }
             | ResolverError::UnreachableStatement { location, .. }
             | ResolverError::AssociatedItemConstraintsNotAllowedInGenerics { location }
             | ResolverError::AmbiguousAssociatedType { location, .. }
@@ -459,6 +465,10 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                 "This oracle call must be wrapped in a call to another unconstrained function before being returned to a constrained runtime".into(),
                 *location,
             ),
+            ResolverError::InvalidTestFixture { location, .. }
+            | ResolverError::InvalidFuzzStrategy { location, .. } => {
+                Diagnostic::simple_error(error.to_string(), String::new(), *location)
+            },
             ResolverError::DependencyCycle { location, item, cycle } => {
                 Diagnostic::simple_error(
                     "Dependency cycle found".into(),
@@ -10,6 +10,7 @@ use crate::graph::CrateId;
 use crate::hir::def_map::LocalModuleId;
 use crate::node_interner::{ExprId, NodeInterner, TraitId, TraitImplId, TypeId};
 use crate::shared::Visibility;
+use crate::token::FuzzStrategyKind;
 
 use crate::{ResolvedGeneric, Type};
 
@@ -106,6 +107,10 @@ pub struct FuncMeta {
     /// Note that this includes separate entries for each identifier in e.g. tuple patterns.
     pub parameter_idents: Vec<HirIdent>,
 
+    /// The `#[range(..)]` and `#[one_of(..)]` strategies the fuzzer uses to generate
+    /// values for the parameters of a test, by parameter name.
+    pub fuzz_strategies: Vec<(String, FuzzStrategyKind)>,
+
     pub return_type: FunctionReturnType,
 
     pub return_visibility: Visibility,
@@ -110,6 +110,15 @@ impl HirPattern {
         }
     }
 
+    /// Returns the identifier bound by this pattern, if it's a possibly mutable identifier.
+    pub fn identifier(&self) -> Option<&HirIdent> {
+        match self {
+            HirPattern::Identifier(ident) => Some(ident),
+            HirPattern::Mutable(pattern, _) => pattern.identifier(),
+            HirPattern::Tuple(..) | HirPattern::Struct(..) => None,
+        }
+    }
+
     pub fn span(&self) -> Span {
         match self {
             HirPattern::Identifier(ident) => ident.location.span,
@@ -809,6 +809,19 @@ impl fmt::Display for TestScope {
     }
 }
 
+/// TestOptions holds the annotations of a test function which don't affect whether it should pass or fail
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Default, PartialOrd, Ord)]
+pub struct TestOptions {
+    /// The number of seconds the test may run for before being reported as timed out.
+    /// This overrides the timeout given to `nargo test`.
+    pub timeout: Option<u64>,
+    /// The maximum number of ACIR opcodes of the program compiled for the test.
+    pub max_opcodes: Option<usize>,
+    /// The parameters of the test whose value is the one returned by the `#[fixture]`
+    /// function of the same name, rather than one generated by the fuzzer.
+    pub fixtures: Vec<String>,
+}
+
 /// FuzzingScope is used to specify additional annotations for fuzzing harnesses
 #[derive(PartialEq, Eq, Hash, Debug, Clone, PartialOrd, Ord)]
 pub enum FuzzingScope {
@@ -839,6 +852,40 @@ impl fmt::Display for FuzzingScope {
     }
 }
 
+/// An attribute on a parameter of a test, constraining the values the fuzzer generates for it
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct FuzzStrategy {
+    pub kind: FuzzStrategyKind,
+    pub location: Location,
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub enum FuzzStrategyKind {
+    /// Values between `min` and `max`, both included: `#[range(min, max)]`.
+    /// The bounds can be negative, for signed integers and fields.
+    Range { min: i128, max: i128 },
+    /// One of the given values: `#[one_of([a, b, c])]`
+    OneOf(Vec<FieldElement>),
+}
+
+impl fmt::Display for FuzzStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl fmt::Display for FuzzStrategyKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FuzzStrategyKind::Range { min, max } => write!(f, "#[range({min}, {max})]"),
+            FuzzStrategyKind::OneOf(values) => {
+                let values: Vec<_> = values.iter().map(|value| value.to_string()).collect();
+                write!(f, "#[one_of([{}])]", values.join(", "))
+            }
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Debug, Clone)]
 // Attributes are special language markers in the target language
 // An example of one is `#[SHA256]` . Currently only Foreign attributes are supported
@@ -876,10 +923,10 @@ impl Attributes {
         self.as_test_function().is_some()
     }
 
-    pub fn as_test_function(&self) -> Option<(&TestScope, Location)> {
+    pub fn as_test_function(&self) -> Option<(&TestScope, &TestOptions, Location)> {
         self.function().and_then(|attr| {
-            if let FunctionAttributeKind::Test(scope) = &attr.kind {
-                Some((scope, attr.location))
+            if let FunctionAttributeKind::Test(scope, options) = &attr.kind {
+                Some((scope, options, attr.location))
             } else {
                 None
             }
@@ -900,6 +947,11 @@ impl Attributes {
         })
     }
 
+    /// True if the function is marked with a `#[bench]` attribute.
+    pub fn is_bench_function(&self) -> bool {
+        self.function().is_some_and(|attr| attr.kind == FunctionAttributeKind::Bench)
+    }
+
     /// True if these attributes mean the given function is an entry point function if it was
     /// defined within a contract. Note that this does not check if the function is actually part
     /// of a contract.
@@ -907,6 +959,7 @@ impl Attributes {
         !self.has_contract_library_method()
             && !self.is_test_function()
             && !self.is_fuzzing_harness()
+            && !self.is_bench_function()
     }
 
     /// Returns note if a deprecated secondary attribute is found
@@ -947,6 +1000,11 @@ impl Attributes {
         self.has_secondary_attr(&SecondaryAttributeKind::Export)
     }
 
+    /// True if the function is marked with a `#[fixture]` attribute.
+    pub fn is_fixture(&self) -> bool {
+        self.has_secondary_attr(&SecondaryAttributeKind::Fixture)
+    }
+
     pub fn has_allow(&self, name: &'static str) -> bool {
         self.secondary.iter().any(|attr| attr.kind.is_allow(name))
     }
@@ -990,11 +1048,12 @@ pub enum FunctionAttributeKind {
     Foreign(String),
     Builtin(String),
     Oracle(String),
-    Test(TestScope),
+    Test(TestScope, TestOptions),
     Fold,
     NoPredicates,
     InlineAlways,
     FuzzingHarness(FuzzingScope),
+    Bench,
 }
 
 impl FunctionAttributeKind {
@@ -1050,11 +1109,12 @@ impl FunctionAttributeKind {
             FunctionAttributeKind::Foreign(_) => "foreign",
             FunctionAttributeKind::Builtin(_) => "builtin",
             FunctionAttributeKind::Oracle(_) => "oracle",
-            FunctionAttributeKind::Test(_) => "test",
+            FunctionAttributeKind::Test(..) => "test",
             FunctionAttributeKind::Fold => "fold",
             FunctionAttributeKind::NoPredicates => "no_predicates",
             FunctionAttributeKind::InlineAlways => "inline_always",
             FunctionAttributeKind::FuzzingHarness(_) => "fuzz",
+            FunctionAttributeKind::Bench => "bench",
         }
     }
 }
@@ -1068,7 +1128,35 @@ impl fmt::Display for FunctionAttribute {
 impl fmt::Display for FunctionAttributeKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            FunctionAttributeKind::Test(scope) => write!(f, "#[test{scope}]"),
+            FunctionAttributeKind::Test(scope, options) => {
+                let mut arguments = Vec::new();
+                match scope {
+                    TestScope::None => (),
+                    TestScope::ShouldFailWith { reason: None } => {
+                        arguments.push("should_fail".to_string());
+                    }
+                    TestScope::ShouldFailWith { reason: Some(reason) } => {
+                        arguments.push(format!("should_fail_with = {reason:?}"));
+                    }
+                    TestScope::OnlyFailWith { reason } => {
+                        arguments.push(format!("only_fail_with = {reason:?}"));
+                    }
+                }
+                if let Some(timeout) = options.timeout {
+                    arguments.push(format!("timeout = {timeout}"));
+                }
+                if let Some(max_opcodes) = options.max_opcodes {
+                    arguments.push(format!("max_opcodes = {max_opcodes}"));
+                }
+                if !options.fixtures.is_empty() {
+                    arguments.push(format!("fixtures({})", options.fixtures.join(", ")));
+                }
+                if arguments.is_empty() {
+                    write!(f, "#[test]")
+                } else {
+                    write!(f, "#[test({})]", arguments.join(", "))
+                }
+            }
             FunctionAttributeKind::Foreign(k) => write!(f, "#[foreign({k})]"),
             FunctionAttributeKind::Builtin(k) => write!(f, "#[builtin({k})]"),
             FunctionAttributeKind::Oracle(k) => write!(f, "#[oracle({k})]"),
@@ -1076,6 +1164,7 @@ impl fmt::Display for FunctionAttributeKind {
             FunctionAttributeKind::NoPredicates => write!(f, "#[no_predicates]"),
             FunctionAttributeKind::InlineAlways => write!(f, "#[inline_always]"),
             FunctionAttributeKind::FuzzingHarness(scope) => write!(f, "#[fuzz{scope}]"),
+            FunctionAttributeKind::Bench => write!(f, "#[bench]"),
         }
     }
 }
@@ -1099,6 +1188,10 @@ pub enum SecondaryAttributeKind {
     Export,
     Field(String),
 
+    /// A function whose return value is injected into the test parameters of the same name:
+    /// `#[fixture]`
+    Fixture,
+
     /// A custom tag attribute: `#['foo]`
     Tag(String),
 
@@ -1117,6 +1210,42 @@ pub enum SecondaryAttributeKind {
 
     /// Allow chosen warnings to happen so they are silenced.
     Allow(String),
+
+    /// Only compile the item if the condition holds for the package features:
+    /// `#[cfg(feature = "name")]`, `#[cfg(test)]` or `#[cfg(not(feature = "name"))]`
+    Cfg(CfgPredicate),
+}
+
+/// The condition of a `#[cfg(...)]` attribute
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum CfgPredicate {
+    /// Holds if the package feature is enabled: `feature = "name"`
+    Feature(String),
+    /// Holds if the package's tests are compiled: `test`
+    Test,
+    /// Holds if the inner condition doesn't: `not(feature = "name")`
+    Not(Box<CfgPredicate>),
+}
+
+impl CfgPredicate {
+    /// Whether the condition holds, given which features are enabled and whether tests are compiled.
+    pub fn holds(&self, is_enabled: &impl Fn(&str) -> bool, testing: bool) -> bool {
+        match self {
+            CfgPredicate::Feature(feature) => is_enabled(feature),
+            CfgPredicate::Test => testing,
+            CfgPredicate::Not(predicate) => !predicate.holds(is_enabled, testing),
+        }
+    }
+}
+
+impl Display for CfgPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CfgPredicate::Feature(feature) => write!(f, "feature = {feature:?}"),
+            CfgPredicate::Test => write!(f, "test"),
+            CfgPredicate::Not(predicate) => write!(f, "not({predicate})"),
+        }
+    }
 }
 
 impl SecondaryAttributeKind {
@@ -1142,10 +1271,12 @@ impl SecondaryAttributeKind {
             SecondaryAttributeKind::ContractLibraryMethod => "contract_library_method".to_string(),
             SecondaryAttributeKind::Export => "export".to_string(),
             SecondaryAttributeKind::Field(k) => format!("field({k})"),
+            SecondaryAttributeKind::Fixture => "fixture".to_string(),
             SecondaryAttributeKind::Abi(k) => format!("abi({k})"),
             SecondaryAttributeKind::Varargs => "varargs".to_string(),
             SecondaryAttributeKind::UseCallersScope => "use_callers_scope".to_string(),
             SecondaryAttributeKind::Allow(k) => format!("allow({k})"),
+            SecondaryAttributeKind::Cfg(predicate) => format!("cfg({predicate})"),
         }
     }
 }
@@ -44,6 +44,8 @@ pub enum ParserErrorReason {
     DocCommentDoesNotDocumentAnything,
     #[error("Documentation comments cannot be applied to function parameters")]
     DocCommentCannotBeAppliedToFunctionParameters,
+    #[error("Expected `#[range(min, max)]` or `#[one_of([values])]` on a function parameter")]
+    MalformedFuzzStrategyAttribute,
 
     #[error("Missing type for function parameter")]
     MissingTypeForFunctionParameter,
@@ -80,6 +82,8 @@ pub enum ParserErrorReason {
     MultipleFunctionAttributesFound,
     #[error("A function attribute cannot be placed on a struct or enum")]
     NoFunctionAttributesAllowedOnType,
+    #[error("A cfg attribute cannot be placed on a `use`, `impl` or `type` item")]
+    CfgAttributeNotAllowed,
     #[error("{0}")]
     Lexer(LexerErrorKind),
     #[error("Associated types are not allowed in paths")]
@@ -298,6 +302,11 @@ impl<'a> From<&'a ParserError> for Diagnostic {
                             .to_string();
                     Diagnostic::simple_error(primary, secondary, error.location)
                 }
+                ParserErrorReason::CfgAttributeNotAllowed => Diagnostic::simple_error(
+                    reason.to_string(),
+                    "Place it on the functions inside, or move the item into a module with the attribute".to_string(),
+                    error.location,
+                ),
                 ParserErrorReason::MissingTypeForAssociatedConstant => Diagnostic::simple_error(
                     "Missing type for associated constant".to_string(),
                     "Provide a type for the associated constant: `: u32`".to_string(),
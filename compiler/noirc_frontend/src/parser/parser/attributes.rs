@@ -1,3 +1,4 @@
+use acvm::AcirField;
 use noirc_errors::Location;
 
 use crate::ast::{Expression, ExpressionKind, Ident, Literal, Path};
@@ -5,8 +6,9 @@ use crate::lexer::errors::LexerErrorKind;
 use crate::parser::ParserErrorReason;
 use crate::parser::labels::ParsingRuleLabel;
 use crate::token::{
-    Attribute, FunctionAttribute, FunctionAttributeKind, FuzzingScope, MetaAttribute,
-    MetaAttributeName, SecondaryAttribute, SecondaryAttributeKind, TestScope, Token,
+    Attribute, CfgPredicate, FunctionAttribute, FunctionAttributeKind, FuzzStrategy,
+    FuzzStrategyKind, FuzzingScope, MetaAttribute, MetaAttributeName, SecondaryAttribute,
+    SecondaryAttributeKind, TestOptions, TestScope, Token,
 };
 
 use super::Parser;
@@ -64,6 +66,7 @@ impl Parser<'_> {
     /// SecondaryAttribute
     ///     = 'abi' '(' AttributeValue ')'
     ///     | 'allow' '(' AttributeValue ')'
+    ///     | 'cfg' '(' 'feature' '=' string ')'
     ///     | 'deprecated'
     ///     | 'deprecated' '(' string ')'
     ///     | 'contract_library_method'
@@ -90,6 +93,86 @@ impl Parser<'_> {
         Some((attribute, self.location_since(start_location)))
     }
 
+    /// FuzzStrategyAttribute
+    ///     = '#[' 'range' '(' '-'? integer ',' '-'? integer ')' ']'
+    ///     | '#[' 'one_of' '(' '[' integer (',' integer)* ','? ']' ')' ']'
+    pub(super) fn parse_fuzz_strategy_attribute(&mut self) -> Option<FuzzStrategy> {
+        let start_location = self.current_token_location;
+        if !matches!(self.token.token(), Token::AttributeStart { is_inner: false, is_tag: false }) {
+            return None;
+        }
+        self.bump();
+
+        // 1 because of the starting `#[`
+        let mut open_brackets = 1;
+        let kind = self.parse_fuzz_strategy_kind(&mut open_brackets);
+        if kind.is_some() && self.eat_right_bracket() {
+            let location = self.location_since(start_location);
+            return kind.map(|kind| FuzzStrategy { kind, location });
+        }
+
+        // Skip the rest of the malformed attribute without reporting any other error
+        while !self.at_eof() && open_brackets > 0 {
+            if self.at(Token::LeftBracket) {
+                open_brackets += 1;
+            } else if self.at(Token::RightBracket) {
+                open_brackets -= 1;
+            }
+            self.bump();
+        }
+        self.push_error(
+            ParserErrorReason::MalformedFuzzStrategyAttribute,
+            self.location_since(start_location),
+        );
+        None
+    }
+
+    fn parse_fuzz_strategy_kind(&mut self, open_brackets: &mut usize) -> Option<FuzzStrategyKind> {
+        let ident = self.eat_ident()?;
+        if !self.eat_left_paren() {
+            return None;
+        }
+
+        let kind = match ident.as_str() {
+            "range" => {
+                let min = self.parse_fuzz_strategy_bound()?;
+                if !self.eat_comma() {
+                    return None;
+                }
+                let max = self.parse_fuzz_strategy_bound()?;
+                FuzzStrategyKind::Range { min, max }
+            }
+            "one_of" => {
+                if !self.eat_left_bracket() {
+                    return None;
+                }
+                *open_brackets += 1;
+                let mut values = Vec::new();
+                while let Some((value, _)) = self.eat_int() {
+                    values.push(value);
+                    if !self.eat_comma() {
+                        break;
+                    }
+                }
+                if values.is_empty() || !self.eat_right_bracket() {
+                    return None;
+                }
+                *open_brackets -= 1;
+                FuzzStrategyKind::OneOf(values)
+            }
+            _ => return None,
+        };
+
+        if self.eat_right_paren() { Some(kind) } else { None }
+    }
+
+    /// An integer which fits in an `i128`, optionally preceded by a minus sign
+    fn parse_fuzz_strategy_bound(&mut self) -> Option<i128> {
+        let negative = self.eat(Token::Minus);
+        let value = self.eat_int()?.0.try_into_u128()?;
+        if negative { 0i128.checked_sub_unsigned(value) } else { i128::try_from(value).ok() }
+    }
+
     pub(super) fn validate_secondary_attributes(
         &mut self,
         attributes: Vec<(Attribute, Location)>,
@@ -151,6 +234,9 @@ impl Parser<'_> {
                     // The fuzz attribute is a secondary attribute that has `a = b` in its syntax
                     // (`only_fail_with = "..."``) or (`should_fail_with = "..."``) so we parse it differently.
                     self.parse_fuzz_attribute(start_location)
+                } else if ident.as_str() == "cfg" {
+                    // The cfg attribute also has `a = b` in its syntax (`feature = "..."`)
+                    self.parse_cfg_attribute(start_location)
                 } else {
                     // Every other attribute has the form `name(arg1, arg2, .., argN)`
                     self.parse_ident_attribute_other_than_test_and_fuzz(ident, start_location)
@@ -202,6 +288,12 @@ impl Parser<'_> {
                 let attr = SecondaryAttribute { kind, location };
                 Attribute::Secondary(attr)
             }),
+            "bench" => {
+                let kind = FunctionAttributeKind::Bench;
+                let attr = FunctionAttribute { kind, location };
+                let attr = Attribute::Function(attr);
+                self.parse_no_args_attribute(ident, arguments, attr)
+            }
             "builtin" => {
                 self.parse_single_name_attribute(ident, arguments, start_location, |name| {
                     let kind = FunctionAttributeKind::Builtin(name);
@@ -231,6 +323,12 @@ impl Parser<'_> {
                 let attr = SecondaryAttribute { kind, location };
                 Attribute::Secondary(attr)
             }),
+            "fixture" => {
+                let kind = SecondaryAttributeKind::Fixture;
+                let attr = SecondaryAttribute { kind, location };
+                let attr = Attribute::Secondary(attr);
+                self.parse_no_args_attribute(ident, arguments, attr)
+            }
             "fold" => {
                 let kind = FunctionAttributeKind::Fold;
                 let attr = FunctionAttribute { kind, location };
@@ -321,58 +419,107 @@ impl Parser<'_> {
     }
 
     fn parse_test_attribute(&mut self, start_location: Location) -> Attribute {
-        let scope = if self.eat_left_paren() {
-            let scope = if let Some(ident) = self.eat_ident() {
-                match ident.as_str() {
-                    "should_fail" => Some(TestScope::ShouldFailWith { reason: None }),
-                    "should_fail_with" => {
-                        self.eat_or_error(Token::Assign);
-                        if let Some(reason) = self.eat_str() {
-                            Some(TestScope::ShouldFailWith { reason: Some(reason) })
-                        } else {
-                            Some(TestScope::ShouldFailWith { reason: None })
-                        }
-                    }
-                    "only_fail_with" => {
-                        self.eat_or_error(Token::Assign);
-                        if let Some(reason) = self.eat_str() {
-                            Some(TestScope::OnlyFailWith { reason })
-                        } else {
-                            self.expected_string();
-                            None
-                        }
-                    }
-                    _ => None,
+        let mut scope = TestScope::None;
+        let mut options = TestOptions::default();
+        let mut malformed = false;
+        if self.eat_left_paren() {
+            loop {
+                if !self.parse_test_attribute_argument(&mut scope, &mut options) {
+                    malformed = true;
+                    break;
                 }
-            } else {
-                None
-            };
-            self.eat_or_error(Token::RightParen);
-            scope
-        } else {
-            Some(TestScope::None)
-        };
+                if !self.eat_comma() || self.at(Token::RightParen) {
+                    break;
+                }
+            }
+            if !malformed {
+                self.eat_or_error(Token::RightParen);
+            }
+        }
 
         self.skip_until_right_bracket();
 
-        let scope = if let Some(scope) = scope {
-            scope
-        } else {
+        if malformed {
             self.errors.push(
                 LexerErrorKind::MalformedTestAttribute {
                     location: self.location_since(start_location),
                 }
                 .into(),
             );
-            TestScope::None
-        };
+        }
 
         let location = self.location_since(start_location);
-        let kind = FunctionAttributeKind::Test(scope);
+        let kind = FunctionAttributeKind::Test(scope, options);
         let attr = FunctionAttribute { kind, location };
         Attribute::Function(attr)
     }
 
+    /// Parses one of the comma-separated arguments of `#[test(...)]`, returning false
+    /// if it's unknown, malformed or was already given.
+    fn parse_test_attribute_argument(
+        &mut self,
+        scope: &mut TestScope,
+        options: &mut TestOptions,
+    ) -> bool {
+        let Some(ident) = self.eat_ident() else {
+            return false;
+        };
+
+        let has_scope = *scope != TestScope::None;
+        match ident.as_str() {
+            "should_fail" if !has_scope => {
+                *scope = TestScope::ShouldFailWith { reason: None };
+                true
+            }
+            "should_fail_with" if !has_scope => {
+                self.eat_or_error(Token::Assign);
+                *scope = TestScope::ShouldFailWith { reason: self.eat_str() };
+                true
+            }
+            "only_fail_with" if !has_scope => {
+                self.eat_or_error(Token::Assign);
+                if let Some(reason) = self.eat_str() {
+                    *scope = TestScope::OnlyFailWith { reason };
+                    true
+                } else {
+                    self.expected_string();
+                    false
+                }
+            }
+            "timeout" if options.timeout.is_none() => {
+                self.eat_or_error(Token::Assign);
+                let timeout = self.eat_int().and_then(|(value, _)| value.try_to_u64());
+                options.timeout = timeout;
+                timeout.is_some()
+            }
+            "max_opcodes" if options.max_opcodes.is_none() => {
+                self.eat_or_error(Token::Assign);
+                let max_opcodes = self
+                    .eat_int()
+                    .and_then(|(value, _)| value.try_to_u64())
+                    .and_then(|value| usize::try_from(value).ok());
+                options.max_opcodes = max_opcodes;
+                max_opcodes.is_some()
+            }
+            "fixtures" if options.fixtures.is_empty() => {
+                if !self.eat_left_paren() {
+                    return false;
+                }
+                loop {
+                    let Some(fixture) = self.eat_ident() else {
+                        return false;
+                    };
+                    options.fixtures.push(fixture.to_string());
+                    if !self.eat_comma() || self.at(Token::RightParen) {
+                        break;
+                    }
+                }
+                self.eat_right_paren()
+            }
+            _ => false,
+        }
+    }
+
     fn parse_fuzz_attribute(&mut self, start_location: Location) -> Attribute {
         let scope = if self.eat_left_paren() {
             let scope = if let Some(ident) = self.eat_ident() {
@@ -421,6 +568,50 @@ impl Parser<'_> {
         Attribute::Function(attr)
     }
 
+    fn parse_cfg_attribute(&mut self, start_location: Location) -> Attribute {
+        let predicate = if self.eat_left_paren() {
+            let predicate = self.parse_cfg_predicate();
+            self.eat_or_error(Token::RightParen);
+            predicate
+        } else {
+            None
+        };
+
+        self.skip_until_right_bracket();
+
+        let location = self.location_since(start_location);
+        let predicate = predicate.unwrap_or_else(|| {
+            self.errors.push(LexerErrorKind::MalformedCfgAttribute { location }.into());
+            CfgPredicate::Feature(String::new())
+        });
+        let kind = SecondaryAttributeKind::Cfg(predicate);
+        let attr = SecondaryAttribute { kind, location };
+        Attribute::Secondary(attr)
+    }
+
+    /// CfgPredicate = 'feature' '=' string
+    ///              | 'test'
+    ///              | 'not' '(' CfgPredicate ')'
+    fn parse_cfg_predicate(&mut self) -> Option<CfgPredicate> {
+        let ident = self.eat_ident()?;
+        match ident.as_str() {
+            "feature" => {
+                self.eat_or_error(Token::Assign);
+                self.eat_str().map(CfgPredicate::Feature)
+            }
+            "test" => Some(CfgPredicate::Test),
+            "not" => {
+                if !self.eat_left_paren() {
+                    return None;
+                }
+                let predicate = self.parse_cfg_predicate();
+                self.eat_or_error(Token::RightParen);
+                predicate.map(|predicate| CfgPredicate::Not(Box::new(predicate)))
+            }
+            _ => None,
+        }
+    }
+
     fn parse_single_name_attribute<F>(
         &mut self,
         ident: &Ident,
@@ -508,7 +699,10 @@ impl Parser<'_> {
 mod tests {
     use crate::{
         parser::{Parser, parser::tests::expect_no_errors},
-        token::{Attribute, FunctionAttributeKind, SecondaryAttributeKind, TestScope},
+        token::{
+            Attribute, CfgPredicate, FunctionAttributeKind, SecondaryAttributeKind, TestOptions,
+            TestScope,
+        },
     };
 
     fn parse_inner_secondary_attribute_no_errors(src: &str, expected: SecondaryAttributeKind) {
@@ -611,6 +805,43 @@ mod tests {
         parse_secondary_attribute_no_errors(src, expected);
     }
 
+    #[test]
+    fn parses_attribute_cfg() {
+        let src = "#[cfg(feature = \"poseidon\")]";
+        let expected = SecondaryAttributeKind::Cfg(CfgPredicate::Feature("poseidon".to_string()));
+        parse_secondary_attribute_no_errors(src, expected);
+    }
+
+    #[test]
+    fn parses_attribute_cfg_not() {
+        let src = "#[cfg(not(not(feature = \"poseidon\")))]";
+        let feature = CfgPredicate::Feature("poseidon".to_string());
+        let predicate = CfgPredicate::Not(Box::new(CfgPredicate::Not(Box::new(feature))));
+        parse_secondary_attribute_no_errors(src, SecondaryAttributeKind::Cfg(predicate));
+    }
+
+    #[test]
+    fn parses_attribute_cfg_test() {
+        let src = "#[cfg(not(test))]";
+        let predicate = CfgPredicate::Not(Box::new(CfgPredicate::Test));
+        parse_secondary_attribute_no_errors(src, SecondaryAttributeKind::Cfg(predicate));
+    }
+
+    #[test]
+    fn errors_on_malformed_cfg_attribute() {
+        let src = "#[cfg(poseidon)]";
+        let mut parser = Parser::for_str_with_dummy_file(src);
+        let (attribute, _span) = parser.parse_attribute().unwrap();
+        assert_eq!(parser.errors.len(), 1);
+        let Attribute::Secondary(attribute) = attribute else {
+            panic!("Expected secondary attribute");
+        };
+        assert_eq!(
+            attribute.kind,
+            SecondaryAttributeKind::Cfg(CfgPredicate::Feature(String::new()))
+        );
+    }
+
     #[test]
     fn parses_attribute_foreign() {
         let src = "#[foreign(foo)]";
@@ -639,6 +870,13 @@ mod tests {
         parse_function_attribute_no_errors(src, expected);
     }
 
+    #[test]
+    fn parses_attribute_bench() {
+        let src = "#[bench]";
+        let expected = FunctionAttributeKind::Bench;
+        parse_function_attribute_no_errors(src, expected);
+    }
+
     #[test]
     fn parses_attribute_no_predicates() {
         let src = "#[no_predicates]";
@@ -660,6 +898,13 @@ mod tests {
         parse_secondary_attribute_no_errors(src, expected);
     }
 
+    #[test]
+    fn parses_attribute_fixture() {
+        let src = "#[fixture]";
+        let expected = SecondaryAttributeKind::Fixture;
+        parse_secondary_attribute_no_errors(src, expected);
+    }
+
     #[test]
     fn parses_attribute_field_with_integer() {
         let src = "#[field(23)]";
@@ -677,14 +922,17 @@ mod tests {
     #[test]
     fn parses_attribute_test_no_scope() {
         let src = "#[test]";
-        let expected = FunctionAttributeKind::Test(TestScope::None);
+        let expected = FunctionAttributeKind::Test(TestScope::None, TestOptions::default());
         parse_function_attribute_no_errors(src, expected);
     }
 
     #[test]
     fn parses_attribute_test_should_fail() {
         let src = "#[test(should_fail)]";
-        let expected = FunctionAttributeKind::Test(TestScope::ShouldFailWith { reason: None });
+        let expected = FunctionAttributeKind::Test(
+            TestScope::ShouldFailWith { reason: None },
+            TestOptions::default(),
+        );
         parse_function_attribute_no_errors(src, expected);
     }
 
@@ -692,7 +940,10 @@ mod tests {
     fn parses_attribute_test_should_fail_with() {
         let src = "#[test(should_fail_with = \"reason\")]";
         let reason = Some("reason".to_string());
-        let expected = FunctionAttributeKind::Test(TestScope::ShouldFailWith { reason });
+        let expected = FunctionAttributeKind::Test(
+            TestScope::ShouldFailWith { reason },
+            TestOptions::default(),
+        );
         parse_function_attribute_no_errors(src, expected);
     }
 
@@ -700,10 +951,63 @@ mod tests {
     fn parses_attribute_test_only_fail_with() {
         let src = "#[test(only_fail_with = \"reason\")]";
         let reason = "reason".to_string();
-        let expected = FunctionAttributeKind::Test(TestScope::OnlyFailWith { reason });
+        let expected =
+            FunctionAttributeKind::Test(TestScope::OnlyFailWith { reason }, TestOptions::default());
+        parse_function_attribute_no_errors(src, expected);
+    }
+
+    #[test]
+    fn parses_attribute_test_timeout() {
+        let src = "#[test(timeout = 10)]";
+        let options = TestOptions { timeout: Some(10), ..Default::default() };
+        let expected = FunctionAttributeKind::Test(TestScope::None, options);
+        parse_function_attribute_no_errors(src, expected);
+    }
+
+    #[test]
+    fn parses_attribute_test_should_fail_with_timeout() {
+        let src = "#[test(should_fail_with = \"reason\", timeout = 10)]";
+        let reason = Some("reason".to_string());
+        let options = TestOptions { timeout: Some(10), ..Default::default() };
+        let expected = FunctionAttributeKind::Test(TestScope::ShouldFailWith { reason }, options);
+        parse_function_attribute_no_errors(src, expected);
+    }
+
+    #[test]
+    fn parses_attribute_test_max_opcodes() {
+        let src = "#[test(timeout = 10, max_opcodes = 1200)]";
+        let options =
+            TestOptions { timeout: Some(10), max_opcodes: Some(1200), ..Default::default() };
+        let expected = FunctionAttributeKind::Test(TestScope::None, options);
         parse_function_attribute_no_errors(src, expected);
     }
 
+    #[test]
+    fn parses_attribute_test_fixtures() {
+        let src = "#[test(should_fail, fixtures(a, b))]";
+        let fixtures = vec!["a".to_string(), "b".to_string()];
+        let options = TestOptions { fixtures, ..Default::default() };
+        let expected =
+            FunctionAttributeKind::Test(TestScope::ShouldFailWith { reason: None }, options);
+        parse_function_attribute_no_errors(src, expected);
+    }
+
+    #[test]
+    fn errors_on_empty_test_attribute_fixtures() {
+        let src = "#[test(fixtures())]";
+        let mut parser = Parser::for_str_with_dummy_file(src);
+        let _ = parser.parse_attribute();
+        assert_eq!(parser.errors.len(), 1);
+    }
+
+    #[test]
+    fn errors_on_duplicate_test_attribute_timeout() {
+        let src = "#[test(timeout = 10, timeout = 20)]";
+        let mut parser = Parser::for_str_with_dummy_file(src);
+        let _ = parser.parse_attribute();
+        assert_eq!(parser.errors.len(), 1);
+    }
+
     #[test]
     fn parses_meta_attribute_single_identifier_no_arguments() {
         let src = "#[foo]";
@@ -782,7 +1086,7 @@ mod tests {
         let Attribute::Function(attr) = attr else {
             panic!("Expected function attribute");
         };
-        assert!(matches!(attr.kind, FunctionAttributeKind::Test(TestScope::None)));
+        assert!(matches!(attr.kind, FunctionAttributeKind::Test(TestScope::None, _)));
 
         let (attr, _) = attributes.remove(0);
         let Attribute::Secondary(attr) = attr else {
@@ -197,6 +197,7 @@ impl Parser<'_> {
             self.error_on_outer_doc_comments_on_parameter();
 
             let start_location = self.current_token_location;
+            let fuzz_strategy = self.parse_fuzz_strategy_attribute();
 
             let pattern_or_self = if allow_self {
                 self.parse_pattern_or_self()
@@ -217,7 +218,11 @@ impl Parser<'_> {
             };
 
             return Some(match pattern_or_self {
-                PatternOrSelf::Pattern(pattern) => self.pattern_param(pattern, start_location),
+                PatternOrSelf::Pattern(pattern) => {
+                    let mut param = self.pattern_param(pattern, start_location);
+                    param.fuzz_strategy = fuzz_strategy;
+                    param
+                }
                 PatternOrSelf::SelfPattern(self_pattern) => self.self_pattern_param(self_pattern),
             });
         }
@@ -241,7 +246,8 @@ impl Parser<'_> {
             )
         };
 
-        Param { visibility, pattern, typ, location: self.location_since(start_location) }
+        let location = self.location_since(start_location);
+        Param { visibility, pattern, typ, fuzz_strategy: None, location }
     }
 
     fn self_pattern_param(&mut self, self_pattern: SelfPattern) -> Param {
@@ -264,6 +270,7 @@ impl Parser<'_> {
             visibility: Visibility::Private,
             pattern,
             typ: self_type,
+            fuzz_strategy: None,
             location: self.location_since(ident_location),
         }
     }
@@ -359,6 +366,7 @@ mod tests {
             },
         },
         shared::Visibility,
+        token::FuzzStrategyKind,
     };
 
     fn parse_function_no_error(src: &str) -> NoirFunction {
@@ -437,6 +445,48 @@ mod tests {
         assert_eq!(param.visibility, Visibility::CallData(42));
     }
 
+    #[test]
+    fn parse_function_with_argument_fuzz_strategies() {
+        let src = "fn foo(#[range(0, 100)] x: u32, #[one_of([1, 2, 3,])] y: Field, z: bool) {}";
+        let mut noir_function = parse_function_no_error(src);
+        assert_eq!(noir_function.def.parameters.len(), 3);
+
+        let param = noir_function.def.parameters.remove(0);
+        assert_eq!("x", param.pattern.to_string());
+        let strategy = param.fuzz_strategy.unwrap();
+        assert_eq!(strategy.kind, FuzzStrategyKind::Range { min: 0, max: 100 });
+        assert_eq!(strategy.to_string(), "#[range(0, 100)]");
+
+        let param = noir_function.def.parameters.remove(0);
+        assert_eq!("y", param.pattern.to_string());
+        let values = vec![1_u128.into(), 2_u128.into(), 3_u128.into()];
+        assert_eq!(param.fuzz_strategy.unwrap().kind, FuzzStrategyKind::OneOf(values));
+
+        let param = noir_function.def.parameters.remove(0);
+        assert!(param.fuzz_strategy.is_none());
+    }
+
+    #[test]
+    fn parse_function_with_negative_range_fuzz_strategy() {
+        let src = "fn foo(#[range(-10, 10)] x: i8) {}";
+        let mut noir_function = parse_function_no_error(src);
+        let strategy = noir_function.def.parameters.remove(0).fuzz_strategy.unwrap();
+        assert_eq!(strategy.kind, FuzzStrategyKind::Range { min: -10, max: 10 });
+        assert_eq!(strategy.to_string(), "#[range(-10, 10)]");
+    }
+
+    #[test]
+    fn errors_on_malformed_fuzz_strategy() {
+        let src = "
+        fn foo(#[range(0)] x: u32) {}
+               ^^^^^^^^^^^
+        ";
+        let (src, span) = get_source_with_error_span(src);
+        let (_, errors) = parse_program_with_dummy_file(&src);
+        let reason = get_single_error_reason(&errors, span);
+        assert!(matches!(reason, ParserErrorReason::MalformedFuzzStrategyAttribute));
+    }
+
     #[test]
     fn parse_function_return_type() {
         let src = "fn foo() -> Field {}";
@@ -3,7 +3,7 @@ use noirc_errors::Location;
 
 use crate::{
     parser::{Item, ItemKind, ParserErrorReason, labels::ParsingRuleLabel},
-    token::{Attribute, Keyword, Token},
+    token::{Attribute, Keyword, SecondaryAttribute, SecondaryAttributeKind, Token},
 };
 
 use super::{Parser, impls::Impl, parse_many::without_separator};
@@ -145,6 +145,7 @@ impl<'a> Parser<'a> {
 
         if self.eat_keyword(Keyword::Use) {
             self.comptime_mutable_and_unconstrained_not_applicable(modifiers);
+            self.cfg_attributes_not_allowed(&attributes);
 
             let use_tree = self.parse_use_tree();
             return vec![ItemKind::Import(use_tree, modifiers.visibility)];
@@ -189,6 +190,7 @@ impl<'a> Parser<'a> {
 
         if self.eat_keyword(Keyword::Impl) {
             self.comptime_mutable_and_unconstrained_not_applicable(modifiers);
+            self.cfg_attributes_not_allowed(&attributes);
 
             return vec![match self.parse_impl() {
                 Impl::Impl(type_impl) => ItemKind::Impl(type_impl),
@@ -224,6 +226,7 @@ impl<'a> Parser<'a> {
 
         if self.eat_keyword(Keyword::Type) {
             self.comptime_mutable_and_unconstrained_not_applicable(modifiers);
+            self.cfg_attributes_not_allowed(&attributes);
 
             return vec![ItemKind::TypeAlias(
                 self.parse_type_alias(modifiers.visibility, start_location),
@@ -259,6 +262,19 @@ impl<'a> Parser<'a> {
         vec![]
     }
 
+    /// `use`, `impl` and `type` items don't keep their attributes, so they can't be compiled conditionally.
+    fn cfg_attributes_not_allowed(&mut self, attributes: &[(Attribute, Location)]) {
+        for (attribute, location) in attributes {
+            if let Attribute::Secondary(SecondaryAttribute {
+                kind: SecondaryAttributeKind::Cfg(_),
+                ..
+            }) = attribute
+            {
+                self.push_error(ParserErrorReason::CfgAttributeNotAllowed, *location);
+            }
+        }
+    }
+
     fn eat_mod_or_contract(&mut self) -> Option<bool> {
         if self.eat_keyword(Keyword::Mod) {
             Some(false)
@@ -277,8 +293,10 @@ mod tests {
     use crate::{
         parse_program_with_dummy_file,
         parser::{
-            ItemKind, Parser,
-            parser::tests::{get_single_error, get_source_with_error_span},
+            ItemKind, Parser, ParserErrorReason,
+            parser::tests::{
+                get_single_error, get_single_error_reason, get_source_with_error_span,
+            },
         },
     };
 
@@ -351,6 +369,20 @@ mod tests {
         assert_eq!(attributes[1].to_string(), "#[two]");
     }
 
+    #[test]
+    fn errors_on_cfg_attribute_on_impl() {
+        let src = "
+        #[cfg(feature = \"foo\")]
+        ^^^^^^^^^^^^^^^^^^^^^^^
+        impl Foo {}
+        ";
+        let (src, span) = get_source_with_error_span(src);
+        let (module, errors) = parse_program_with_dummy_file(&src);
+        assert_eq!(module.items.len(), 1);
+        let reason = get_single_error_reason(&errors, span);
+        assert!(matches!(reason, ParserErrorReason::CfgAttributeNotAllowed));
+    }
+
     #[test]
     fn error_recovery_for_missing_fn_between_visibility_and_name() {
         let src = "
@@ -17,6 +17,7 @@ mod visibility;
 // what we should do is have test cases which are passed to a test harness
 // A test harness will allow for more expressive and readable tests
 use std::collections::HashMap;
+use std::time::Instant;
 
 use ::function_name::named;
 
@@ -3556,6 +3557,150 @@ fn disallows_test_attribute_on_trait_impl_method() {
     check_errors!(src);
 }
 
+#[named]
+#[test]
+fn test_fixtures_are_injected_into_test_parameters() {
+    let src = "
+        #[fixture]
+        fn owner() -> Field {
+            1
+        }
+
+        #[test(fixtures(owner))]
+        fn test_owner(owner: Field) {
+            assert_eq(owner, 1);
+        }
+
+        fn main() { }
+    ";
+    assert_no_errors!(src);
+}
+
+#[named]
+#[test]
+fn errors_on_invalid_test_fixtures() {
+    let src = "
+        fn owner() -> Field {
+            1
+        }
+
+        #[fixture]
+        fn amount() -> u32 {
+            1
+        }
+
+        #[test(fixtures(owner))]
+        ^^^^^^^^^^^^^^^^^^^^^^^^ Invalid test fixture `owner`: the function with this name is not marked with `#[fixture]`
+        fn test_owner(owner: Field) {
+            assert(owner != 0);
+        }
+
+        #[test(fixtures(amount))]
+        ^^^^^^^^^^^^^^^^^^^^^^^^^ Invalid test fixture `amount`: the fixture doesn't return a value of the parameter's type
+        fn test_amount(amount: Field) {
+            assert(amount != 0);
+        }
+
+        #[test(fixtures(missing))]
+        ^^^^^^^^^^^^^^^^^^^^^^^^^^ Invalid test fixture `missing`: the test has no parameter with this name
+        fn test_missing(owner: Field) {
+            assert(owner != 0);
+        }
+
+        fn main() {
+            let _ = owner();
+        }
+    ";
+    check_errors!(src);
+}
+
+#[named]
+#[test]
+fn errors_on_bench_functions_with_parameters() {
+    let src = "
+        #[bench]
+        fn bench_sum() {
+            let _ = 1 + 2;
+        }
+
+        #[bench]
+        fn bench_sum_of(x: Field) {
+           ^^^^^^^^^^^^ The `#[bench]` attribute may only be used on functions without parameters
+            let _ = x + 2;
+        }
+
+        fn main() { }
+    ";
+    check_errors!(src);
+}
+
+#[named]
+#[test]
+fn fuzz_strategies_on_test_parameters() {
+    let src = "
+        #[test]
+        fn test_strategies(#[range(1, 100)] x: u32, #[one_of([2, 4, 8])] ys: [Field; 3]) {
+            assert(x != 0);
+            assert(ys[0] != 0);
+        }
+
+        #[test]
+        fn test_negative_ranges(#[range(-10, 10)] x: i8, #[range(-128, 127)] y: i8, #[range(-5, -1)] z: Field) {
+            assert(x != y);
+            assert(z != 0);
+        }
+
+        fn main() { }
+    ";
+    assert_no_errors!(src);
+}
+
+#[named]
+#[test]
+fn errors_on_invalid_fuzz_strategies() {
+    let src = "
+        fn not_a_test(#[range(0, 10)] x: u32) -> u32 {
+                      ^^^^^^^^^^^^^^^ Invalid fuzzing strategy `#[range(0, 10)]`: fuzzing strategies are only allowed on the parameters of tests
+            x
+        }
+
+        #[test]
+        fn test_reversed_range(#[range(10, 0)] x: u32) {
+                               ^^^^^^^^^^^^^^^ Invalid fuzzing strategy `#[range(10, 0)]`: the minimum is greater than the maximum
+            assert(x != 0);
+        }
+
+        #[test]
+        fn test_range_too_large(#[range(0, 256)] x: u8) {
+                                ^^^^^^^^^^^^^^^^ Invalid fuzzing strategy `#[range(0, 256)]`: the range doesn't fit in the parameter's type
+            assert(x != 0);
+        }
+
+        #[test]
+        fn test_negative_unsigned(#[range(-10, 10)] x: u8) {
+                                  ^^^^^^^^^^^^^^^^^ Invalid fuzzing strategy `#[range(-10, 10)]`: the range doesn't fit in the parameter's type
+            assert(x != 0);
+        }
+
+        #[test]
+        fn test_range_too_negative(#[range(-129, 0)] x: i8) {
+                                   ^^^^^^^^^^^^^^^^^ Invalid fuzzing strategy `#[range(-129, 0)]`: the range doesn't fit in the parameter's type
+            assert(x != 0);
+        }
+
+        #[test]
+        fn test_boolean(#[one_of([0, 1])] x: bool) {
+                        ^^^^^^^^^^^^^^^^^ Invalid fuzzing strategy `#[one_of([0, 1])]`: the parameter must be a field, an integer or an array of them
+            assert(x);
+        }
+
+        fn main() {
+            let _ = not_a_test(1);
+        }
+    ";
+    check_errors!(src);
+}
+
 #[named]
 #[test]
 fn disallows_export_attribute_on_impl_method() {
@@ -4810,3 +4955,61 @@ fn cannot_assign_to_nested_struct() {
     "#;
     check_errors!(src);
 }
+
+#[named]
+#[test]
+fn cfg_attribute_leaves_out_items_of_disabled_features() {
+    let src = r#"
+    #[cfg(feature = "foo")]
+    fn foo() {}
+
+    #[cfg(feature = "foo")]
+    mod bar {
+        pub fn bar() {}
+    }
+
+    fn main() {
+        foo();
+        ^^^ cannot find `foo` in this scope
+    }
+    "#;
+    check_errors!(src);
+}
+
+#[named]
+#[test]
+fn cfg_test_attribute_keeps_items_when_tests_are_compiled() {
+    let src = r#"
+    #[cfg(test)]
+    fn helper() {}
+
+    #[cfg(not(test))]
+    fn without_tests() {}
+
+    fn main() {
+        helper();
+        without_tests();
+        ^^^^^^^^^^^^^ cannot find `without_tests` in this scope
+    }
+    "#;
+    check_errors!(src);
+}
+
+#[test]
+fn comptime_evaluation_stops_once_its_deadline_is_reached() {
+    let src = r#"
+    comptime fn spin() {}
+
+    fn main() {
+        comptime {
+            spin();
+            ^^^^^^ Comptime evaluation was stopped because it took too long
+        }
+    }
+    "#;
+    let options = FrontendOptions {
+        comptime_deadline: Some(Instant::now()),
+        ..FrontendOptions::test_default()
+    };
+    check_errors_with_options(src, None, false, false, options);
+}
@@ -1,6 +1,12 @@
-use crate::foreign_calls::DebugForeignCallExecutor;
-use acvm::acir::brillig::BitSize;
-use acvm::acir::circuit::brillig::{BrilligBytecode, BrilligFunctionId};
+use crate::expressions::{Expression, ExpressionError, Value};
+use crate::foreign_calls::{DebugForeignCall, DebugForeignCallExecutor, debug_var_id};
+use crate::functions::ProgramFunctions;
+use crate::variable_storage::{
+    Member, ValueStorage, VariableStorage, assigned_member, brillig_call_input_witnesses,
+    foreign_call_input_cells,
+};
+use acvm::acir::brillig::{BitSize, ForeignCallParam, ForeignCallResult, Opcode as BrilligOpcode};
+use acvm::acir::circuit::brillig::{BrilligBytecode, BrilligFunctionId, BrilligInputs};
 use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
 use acvm::acir::native_types::{Witness, WitnessMap, WitnessStack};
 use acvm::brillig_vm::MemoryValue;
@@ -8,22 +14,25 @@ use acvm::pwg::{
     ACVM, ACVMStatus, AcirCallWaitInfo, BrilligSolver, BrilligSolverStatus, ForeignCallWaitInfo,
     OpcodeNotSolvable, StepResult,
 };
-use acvm::{BlackBoxFunctionSolver, FieldElement};
+use acvm::{AcirField, BlackBoxFunctionSolver, FieldElement};
 
 use codespan_reporting::files::{Files, SimpleFile};
 use fm::FileId;
 use nargo::NargoError;
 use nargo::errors::{ExecutionError, Location, ResolvedOpcodeLocation, execution_error_from};
-use noirc_artifacts::debug::{DebugArtifact, StackFrame};
+use nargo::foreign_calls::ForeignCallError;
+use noirc_artifacts::debug::{DebugArtifact, DebugVars, StackFrame};
 use noirc_driver::{CompiledProgram, DebugFile};
 
 use noirc_errors::call_stack::CallStackId;
-use noirc_errors::debug_info::DebugInfo;
-use noirc_printable_type::{PrintableType, PrintableValue};
+use noirc_errors::debug_info::{DebugInfo, DebugVarId};
+use noirc_printable_type::{PrintableType, PrintableValue, PrintableValueDisplay};
 use thiserror::Error;
 
 use std::collections::BTreeMap;
-use std::collections::HashSet;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::ops::ControlFlow;
 use std::path::PathBuf;
 
 /// A Noir program is composed by
@@ -232,11 +241,153 @@ impl std::str::FromStr for DebugLocation {
     }
 }
 
+#[derive(Error, Debug)]
+pub(super) enum SetVariableError {
+    #[error(transparent)]
+    Expression(#[from] ExpressionError),
+    #[error("Cannot tell where `{0}` is stored")]
+    UnknownStorage(String),
+    #[error("`{0}` is stored in a function that is not being executed")]
+    NotExecuting(String),
+    #[error("`{0}` is computed from other witnesses, so it cannot be set")]
+    NotAWitness(String),
+    #[error("The value does not fit in the memory cell of `{0}`")]
+    InvalidBitSize(String),
+    #[error("`{0}` is stored in an array shared with other values, so it cannot be set")]
+    SharedArray(String),
+}
+
+#[derive(Error, Debug)]
+pub enum HitConditionFromStrError {
+    #[error("Invalid hit condition: {0}")]
+    InvalidHitCondition(String),
+}
+
+/// How many times a breakpoint needs to be hit before execution stops on it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum HitCondition {
+    Equal(u64),
+    Greater(u64),
+    GreaterOrEqual(u64),
+    Less(u64),
+    LessOrEqual(u64),
+    /// Every `n` hits
+    Multiple(u64),
+}
+
+impl HitCondition {
+    fn is_met(&self, hits: u64) -> bool {
+        match *self {
+            HitCondition::Equal(count) => hits == count,
+            HitCondition::Greater(count) => hits > count,
+            HitCondition::GreaterOrEqual(count) => hits >= count,
+            HitCondition::Less(count) => hits < count,
+            HitCondition::LessOrEqual(count) => hits <= count,
+            HitCondition::Multiple(count) => hits % count == 0,
+        }
+    }
+}
+
+impl std::str::FromStr for HitCondition {
+    type Err = HitConditionFromStrError;
+
+    /// Parses a hit count optionally preceded by one of `==`, `>`, `>=`, `<`, `<=` or `%`.
+    /// A bare count `n` is the same as `>=n`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let prefixes: [(&str, fn(u64) -> HitCondition); 6] = [
+            ("==", HitCondition::Equal),
+            (">=", HitCondition::GreaterOrEqual),
+            ("<=", HitCondition::LessOrEqual),
+            (">", HitCondition::Greater),
+            ("<", HitCondition::Less),
+            ("%", HitCondition::Multiple),
+        ];
+        let (count, make_condition) = prefixes
+            .into_iter()
+            .find_map(|(prefix, make_condition)| {
+                s.strip_prefix(prefix).map(|count| (count, make_condition))
+            })
+            .unwrap_or((s, HitCondition::GreaterOrEqual));
+        match count.trim().parse::<u64>() {
+            Ok(0) if s.starts_with('%') => {
+                Err(HitConditionFromStrError::InvalidHitCondition(s.to_string()))
+            }
+            Ok(count) => Ok(make_condition(count)),
+            Err(_) => Err(HitConditionFromStrError::InvalidHitCondition(s.to_string())),
+        }
+    }
+}
+
+/// The conditions under which execution stops on a breakpoint. Hits are only counted
+/// while `condition` holds.
+#[derive(Clone, Debug, Default)]
+pub(super) struct BreakpointCondition {
+    pub(super) condition: Option<Expression>,
+    pub(super) hit_condition: Option<HitCondition>,
+}
+
+struct Breakpoint {
+    condition: BreakpointCondition,
+    hits: u64,
+}
+
+/// Something whose value is watched, stopping execution whenever it changes
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(super) enum Watchpoint {
+    /// A variable of the function whose stack frame is at the given depth, named for display
+    Variable {
+        name: String,
+        var_id: DebugVarId,
+        frame: usize,
+    },
+    Witness {
+        circuit_id: u32,
+        witness: Witness,
+    },
+}
+
+impl std::fmt::Display for Watchpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Watchpoint::Variable { name, .. } => write!(f, "{name}"),
+            Watchpoint::Witness { circuit_id: 0, witness } => {
+                write!(f, "witness _{}", witness.witness_index())
+            }
+            Watchpoint::Witness { circuit_id, witness } => {
+                write!(f, "witness _{} of circuit {circuit_id}", witness.witness_index())
+            }
+        }
+    }
+}
+
+/// A change in the value of a watchpoint. A value of `None` means unassigned.
+#[derive(Debug)]
+pub(super) struct WatchpointHit {
+    pub(super) watchpoint: Watchpoint,
+    pub(super) old_value: Option<String>,
+    pub(super) new_value: Option<String>,
+}
+
+impl std::fmt::Display for WatchpointHit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let describe = |value: &Option<String>| value.as_deref().unwrap_or("unassigned").to_owned();
+        write!(
+            f,
+            "{} changed from {} to {}",
+            self.watchpoint,
+            describe(&self.old_value),
+            describe(&self.new_value)
+        )
+    }
+}
+
 #[derive(Debug)]
 pub(super) enum DebugCommandResult {
     Done,
     Ok,
     BreakpointReached(DebugLocation),
+    WatchpointTriggered(WatchpointHit),
     Error(NargoError<FieldElement>),
 }
 
@@ -268,6 +419,38 @@ pub struct ExecutionFrame<'a, B: BlackBoxFunctionSolver<FieldElement>> {
     acvm: ACVM<'a, FieldElement, B>,
 }
 
+impl<B: BlackBoxFunctionSolver<FieldElement>> Clone for ExecutionFrame<'_, B> {
+    fn clone(&self) -> Self {
+        Self { circuit_id: self.circuit_id, acvm: self.acvm.clone() }
+    }
+}
+
+/// Initial number of steps between the snapshots of the execution state. Going back in time
+/// restores the latest snapshot before the target step and replays execution from there.
+const SNAPSHOT_INTERVAL: usize = 1000;
+
+/// Maximum number of snapshots taken periodically. Once there are more, every other one is
+/// dropped and the interval between them doubles, so that long executions use bounded memory.
+const MAX_PERIODIC_SNAPSHOTS: usize = 64;
+
+/// The execution state of a `DebugContext` after some number of steps
+struct Snapshot<'a, B: BlackBoxFunctionSolver<FieldElement>> {
+    acvm: ACVM<'a, FieldElement, B>,
+    current_circuit_id: u32,
+    brillig_solver: Option<BrilligSolver<'a, FieldElement, B>>,
+    witness_stack: WitnessStack<FieldElement>,
+    acvm_stack: Vec<ExecutionFrame<'a, B>>,
+    debug_vars: DebugVars<FieldElement>,
+    variable_storage: Vec<HashMap<String, VariableStorage>>,
+    next_foreign_call: usize,
+    /// The hit counts of the breakpoints set when the snapshot was taken
+    breakpoint_hits: HashMap<DebugLocation, u64>,
+    /// Whether the snapshot was taken after a number of steps, and can be dropped because
+    /// replaying execution rebuilds it. The initial state and the states modified by the user
+    /// can't be rebuilt that way.
+    periodic: bool,
+}
+
 #[derive(Debug)]
 pub enum DebugExecutionResult {
     Solved(WitnessStack<FieldElement>),
@@ -309,14 +492,35 @@ pub(super) struct DebugContext<'a, B: BlackBoxFunctionSolver<FieldElement>> {
     foreign_call_executor: Box<dyn DebugForeignCallExecutor + 'a>,
 
     debug_artifact: &'a DebugArtifact,
-    breakpoints: HashSet<DebugLocation>,
+    breakpoints: HashMap<DebugLocation, Breakpoint>,
+    breakpoint_condition_error: Option<ExpressionError>,
+    /// Watched variables, by stack frame depth and id, along with their names
+    variable_watchpoints: BTreeMap<(usize, DebugVarId), String>,
+    /// Watched witnesses of each circuit, along with the value they had when last checked
+    witness_watchpoints: BTreeMap<(u32, Witness), Option<FieldElement>>,
+    /// Where the variables of each function in the call stack are stored, by name
+    variable_storage: Vec<HashMap<String, VariableStorage>>,
     source_to_locations: BTreeMap<FileId, Vec<(usize, DebugLocation)>>,
 
     circuits: &'a [Circuit<FieldElement>],
     unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
+    /// The unconstrained functions that expressions can call
+    functions: ProgramFunctions<'a, B>,
 
     acir_opcode_addresses: AddressMap,
     initial_witness: WitnessMap<FieldElement>,
+
+    /// Number of times `step_into_opcode` was called since the start of the execution
+    step_count: usize,
+    snapshots: BTreeMap<usize, Snapshot<'a, B>>,
+    /// Number of steps between the periodic snapshots
+    snapshot_interval: usize,
+    /// Results of the foreign calls made so far, other than the debug instrumentation ones,
+    /// which are replayed instead of calling them again when going back in time
+    foreign_call_results: Vec<ForeignCallResult<FieldElement>>,
+    next_foreign_call: usize,
+    /// Set while replaying execution to rebuild a previous state
+    replaying: bool,
 }
 
 fn initialize_acvm<'a, B: BlackBoxFunctionSolver<FieldElement>>(
@@ -345,11 +549,12 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         initial_witness: WitnessMap<FieldElement>,
         foreign_call_executor: Box<dyn DebugForeignCallExecutor + 'a>,
         unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
+        brillig_names: &'a [String],
     ) -> Self {
         let source_to_opcodes = build_source_to_opcode_debug_mappings(debug_artifact);
         let current_circuit_id: u32 = 0;
         let acir_opcode_addresses = AddressMap::new(circuits, unconstrained_functions);
-        Self {
+        let mut context = Self {
             current_circuit_id,
             brillig_solver: None,
             witness_stack: WitnessStack::default(),
@@ -357,10 +562,20 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
             backend: blackbox_solver,
             foreign_call_executor,
             debug_artifact,
-            breakpoints: HashSet::new(),
+            breakpoints: HashMap::new(),
+            breakpoint_condition_error: None,
+            variable_watchpoints: BTreeMap::new(),
+            witness_watchpoints: BTreeMap::new(),
+            variable_storage: Vec::new(),
             source_to_locations: source_to_opcodes,
             circuits,
             unconstrained_functions,
+            functions: ProgramFunctions::new(
+                blackbox_solver,
+                circuits,
+                unconstrained_functions,
+                brillig_names,
+            ),
             acir_opcode_addresses,
             initial_witness: initial_witness.clone(), // we keep it so the context can restart itself
             acvm: initialize_acvm(
@@ -369,7 +584,15 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
                 initial_witness,
                 unconstrained_functions,
             ),
-        }
+            step_count: 0,
+            snapshots: BTreeMap::new(),
+            snapshot_interval: SNAPSHOT_INTERVAL,
+            foreign_call_results: Vec::new(),
+            next_foreign_call: 0,
+            replaying: false,
+        };
+        context.take_snapshot(false);
+        context
     }
 
     pub(super) fn get_opcodes(&self) -> &[Opcode<FieldElement>] {
@@ -380,6 +603,10 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         &self.circuits[circuit_id as usize].opcodes
     }
 
+    pub(super) fn get_current_circuit_id(&self) -> u32 {
+        self.current_circuit_id
+    }
+
     pub(super) fn get_witness_map(&self) -> &WitnessMap<FieldElement> {
         self.acvm.witness_map()
     }
@@ -389,7 +616,9 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         witness: Witness,
         value: FieldElement,
     ) -> Option<FieldElement> {
-        self.acvm.overwrite_witness(witness, value)
+        let old_value = self.acvm.overwrite_witness(witness, value);
+        self.discard_future();
+        old_value
     }
 
     pub(super) fn get_current_debug_location(&self) -> Option<DebugLocation> {
@@ -653,7 +882,25 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         &mut self,
         foreign_call: ForeignCallWaitInfo<FieldElement>,
     ) -> DebugCommandResult {
-        let foreign_call_result = self.foreign_call_executor.execute(&foreign_call);
+        let assigns_variable = matches!(
+            DebugForeignCall::lookup(&foreign_call.function),
+            Some(
+                DebugForeignCall::VarAssign
+                    | DebugForeignCall::MemberAssign(_)
+                    | DebugForeignCall::DerefAssign
+            )
+        );
+        let watched_values = if assigns_variable { self.watched_variable_values() } else { vec![] };
+        if let Some(debug_call) = DebugForeignCall::lookup(&foreign_call.function) {
+            self.track_variable_storage(debug_call, &foreign_call);
+        }
+        let foreign_call_result = if DebugForeignCall::lookup(&foreign_call.function).is_some() {
+            // Debug instrumentation calls are executed again when replaying, to rebuild the
+            // variables of the program
+            self.foreign_call_executor.execute(&foreign_call)
+        } else {
+            self.execute_recorded_foreign_call(&foreign_call)
+        };
 
         match foreign_call_result {
             Ok(foreign_call_result) => {
@@ -663,6 +910,11 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
                 } else {
                     self.acvm.resolve_pending_foreign_call(foreign_call_result);
                 }
+                if assigns_variable {
+                    if let Some(hit) = self.variable_watchpoint_hit(watched_values) {
+                        return DebugCommandResult::WatchpointTriggered(hit);
+                    }
+                }
                 // TODO: should we retry executing the opcode somehow in this
                 // case? Otherwise, executing a foreign call takes two debugging
                 // steps.
@@ -672,6 +924,210 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         }
     }
 
+    /// Keeps track of where the variables of the program are stored, as debug instrumentation
+    /// reports their assignments
+    fn track_variable_storage(
+        &mut self,
+        debug_call: DebugForeignCall,
+        foreign_call: &ForeignCallWaitInfo<FieldElement>,
+    ) {
+        let var_id = match (&debug_call, foreign_call.inputs.first()) {
+            (DebugForeignCall::FnEnter, _) => {
+                self.variable_storage.push(HashMap::new());
+                return;
+            }
+            (DebugForeignCall::FnExit, _) => {
+                self.variable_storage.pop();
+                return;
+            }
+            (_, Some(ForeignCallParam::Single(var_id))) => *var_id,
+            _ => return,
+        };
+        let Some(debug_info) = self.debug_artifact.debug_symbols.first() else {
+            return;
+        };
+        let Some(variable) = debug_info.variables.get(&debug_var_id(&var_id)) else {
+            return;
+        };
+        let name = variable.name.clone();
+        let inputs = &foreign_call.inputs;
+        let storage = match debug_call {
+            DebugForeignCall::VarAssign => {
+                self.value_storage(var_id, foreign_call, inputs.len() - 1).map(|storage| {
+                    let values = inputs[1..].iter().flat_map(|input| input.fields());
+                    VariableStorage {
+                        var_id: debug_var_id(&var_id),
+                        values: values.collect(),
+                        storage,
+                    }
+                })
+            }
+            DebugForeignCall::MemberAssign(arity) => {
+                let Some(value_inputs) = inputs.len().checked_sub(arity as usize + 1) else {
+                    return;
+                };
+                let indices: Option<Vec<usize>> = inputs[1 + value_inputs..]
+                    .iter()
+                    .map(|input| match input {
+                        ForeignCallParam::Single(index) => {
+                            usize::try_from(index.try_into_u128()?).ok()
+                        }
+                        ForeignCallParam::Array(_) => None,
+                    })
+                    .collect();
+                let member = debug_info
+                    .types
+                    .get(&variable.debug_type_id)
+                    .zip(indices)
+                    .and_then(|(typ, indices)| assigned_member(typ, &indices));
+                member.and_then(|member| {
+                    self.member_storage(&name, var_id, foreign_call, value_inputs, member)
+                })
+            }
+            // Assignments through references leave the value of the reference itself as is
+            DebugForeignCall::DerefAssign => return,
+            _ => None,
+        };
+        let Some(frame) = self.variable_storage.last_mut() else {
+            return;
+        };
+        match storage {
+            Some(variable) => {
+                frame.insert(name, variable);
+            }
+            None => {
+                frame.remove(&name);
+            }
+        }
+    }
+
+    /// Updates the storage of a variable after a `__debug_member_assign` foreign call assigns
+    /// one of its fields or elements, or returns `None` if it can no longer be told
+    fn member_storage(
+        &self,
+        name: &str,
+        var_id: FieldElement,
+        foreign_call: &ForeignCallWaitInfo<FieldElement>,
+        value_inputs: usize,
+        member: Member,
+    ) -> Option<VariableStorage> {
+        let mut variable = self.variable_storage.last()?.get(name)?.clone();
+        let values: Vec<FieldElement> =
+            foreign_call.inputs[1..=value_inputs].iter().flat_map(|input| input.fields()).collect();
+        let range = member.offset..member.offset + values.len();
+        if range.end > variable.values.len() {
+            return None;
+        }
+        match (&mut variable.storage, self.value_storage(var_id, foreign_call, value_inputs)) {
+            // Unconstrained code writes array elements into the array, unless it is shared and
+            // copied to other cells first
+            (ValueStorage::BrilligMemory { addresses, .. }, _) if member.in_array => {
+                let memory = self.brillig_solver.as_ref()?.get_memory();
+                let written =
+                    addresses[range.clone()].iter().zip(&values).all(|(address, value)| {
+                        memory.get(*address).map(MemoryValue::to_field) == Some(*value)
+                    });
+                if !written {
+                    return None;
+                }
+            }
+            (
+                ValueStorage::BrilligMemory { addresses, reference_counts, .. },
+                Some(ValueStorage::BrilligMemory {
+                    addresses: value_addresses,
+                    reference_counts: value_reference_counts,
+                    ..
+                }),
+            ) => {
+                addresses.splice(range.clone(), value_addresses);
+                reference_counts.extend(value_reference_counts);
+            }
+            (ValueStorage::Witnesses { witnesses, .. }, value_storage) => {
+                // The value is computed into new witnesses, which may not be known
+                let value_witnesses = match value_storage {
+                    Some(ValueStorage::Witnesses { witnesses, .. }) => witnesses,
+                    _ => vec![None; values.len()],
+                };
+                witnesses.splice(range.clone(), value_witnesses);
+            }
+            _ => return None,
+        }
+        variable.values.splice(range, values);
+        Some(variable)
+    }
+
+    /// Finds the witnesses or Brillig memory cells holding the value passed to a debug
+    /// instrumentation foreign call, made of the given number of inputs after the variable id
+    fn value_storage(
+        &self,
+        var_id: FieldElement,
+        foreign_call: &ForeignCallWaitInfo<FieldElement>,
+        value_inputs: usize,
+    ) -> Option<ValueStorage> {
+        let field_count = |inputs: &[ForeignCallParam<FieldElement>]| -> usize {
+            inputs.iter().map(|input| input.fields().len()).sum()
+        };
+        let value_count = field_count(foreign_call.inputs.get(1..=value_inputs)?);
+        let acir_index = self.acvm.instruction_pointer();
+        let Opcode::BrilligCall { inputs, .. } = self.get_opcodes().get(acir_index)? else {
+            return None;
+        };
+        // Constrained code passes the variable id and the other inputs of the foreign call to
+        // a Brillig function that makes it
+        match inputs.split_first() {
+            Some((BrilligInputs::Single(id), other_inputs))
+                if id.is_const() && id.q_c == var_id =>
+            {
+                let mut witnesses = brillig_call_input_witnesses(other_inputs)?;
+                if witnesses.len() != field_count(&foreign_call.inputs[1..]) {
+                    return None;
+                }
+                witnesses.truncate(value_count);
+                return Some(ValueStorage::Witnesses {
+                    circuit_id: self.current_circuit_id,
+                    witnesses,
+                });
+            }
+            _ => (),
+        }
+        let solver = self.brillig_solver.as_ref()?;
+        let bytecode = &self.unconstrained_functions[solver.function_id.as_usize()].bytecode;
+        let BrilligOpcode::ForeignCall { inputs, input_value_types, .. } =
+            bytecode.get(solver.program_counter())?
+        else {
+            return None;
+        };
+        let cells = foreign_call_input_cells(
+            solver.get_memory(),
+            inputs.get(1..=value_inputs)?,
+            input_value_types.get(1..=value_inputs)?,
+        )?;
+        (cells.addresses.len() == value_count).then_some(ValueStorage::BrilligMemory {
+            circuit_id: self.current_circuit_id,
+            acir_index,
+            addresses: cells.addresses,
+            reference_counts: cells.reference_counts,
+        })
+    }
+
+    /// Executes a foreign call, unless execution already went past it and its result was
+    /// recorded
+    fn execute_recorded_foreign_call(
+        &mut self,
+        foreign_call: &ForeignCallWaitInfo<FieldElement>,
+    ) -> Result<ForeignCallResult<FieldElement>, ForeignCallError> {
+        let result = match self.foreign_call_results.get(self.next_foreign_call) {
+            Some(result) => result.clone(),
+            None => {
+                let result = self.foreign_call_executor.execute(foreign_call)?;
+                self.foreign_call_results.push(result.clone());
+                result
+            }
+        };
+        self.next_foreign_call += 1;
+        Ok(result)
+    }
+
     fn handle_acir_call(
         &mut self,
         call_info: AcirCallWaitInfo<FieldElement>,
@@ -724,19 +1180,29 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         }
         self.acvm.resolve_pending_acir_call(call_resolved_outputs);
 
-        DebugCommandResult::Ok
+        match self.witness_watchpoint_hit() {
+            Some(hit) => DebugCommandResult::WatchpointTriggered(hit),
+            None => DebugCommandResult::Ok,
+        }
     }
 
     fn handle_acvm_status(&mut self, status: ACVMStatus<FieldElement>) -> DebugCommandResult {
         match status {
             ACVMStatus::Solved => {
+                // The last opcode of a circuit can assign watched witnesses too, in which case
+                // the circuit is finished by the next step
+                if let Some(hit) = self.witness_watchpoint_hit() {
+                    return DebugCommandResult::WatchpointTriggered(hit);
+                }
                 if self.acvm_stack.is_empty() {
                     return DebugCommandResult::Done;
                 }
                 self.handle_acir_call_finished()
             }
             ACVMStatus::InProgress => {
-                if self.breakpoint_reached() {
+                if let Some(hit) = self.witness_watchpoint_hit() {
+                    DebugCommandResult::WatchpointTriggered(hit)
+                } else if self.breakpoint_reached() {
                     DebugCommandResult::BreakpointReached(
                         self.get_current_debug_location()
                             .expect("Breakpoint reached but we have no location"),
@@ -753,18 +1219,28 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         }
     }
 
+    /// Steps into the next opcode. All execution goes through this function, which counts
+    /// the steps and takes the snapshots used to go back in time.
     pub(super) fn step_into_opcode(&mut self) -> DebugCommandResult {
-        if self.brillig_solver.is_some() {
-            return self.step_brillig_opcode();
-        }
-
-        match self.acvm.step_into_brillig() {
-            StepResult::IntoBrillig(solver) => {
-                self.brillig_solver = Some(solver);
-                self.step_brillig_opcode()
+        let result = if self.brillig_solver.is_some() {
+            self.step_brillig_opcode()
+        } else if self.is_solved() {
+            // Execution stopped at a watchpoint after solving the last opcode of a circuit
+            self.handle_acvm_status(ACVMStatus::Solved)
+        } else {
+            match self.acvm.step_into_brillig() {
+                StepResult::IntoBrillig(solver) => {
+                    self.brillig_solver = Some(solver);
+                    self.step_brillig_opcode()
+                }
+                StepResult::Status(status) => self.handle_acvm_status(status),
             }
-            StepResult::Status(status) => self.handle_acvm_status(status),
+        };
+        self.step_count += 1;
+        if self.step_count % self.snapshot_interval == 0 {
+            self.take_snapshot(true);
         }
+        result
     }
 
     fn get_current_acir_index(&self) -> Option<usize> {
@@ -818,8 +1294,8 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         if self.is_executing_brillig() {
             self.step_out_of_brillig_opcode()
         } else {
-            let status = self.acvm.solve_opcode();
-            self.handle_acvm_status(status)
+            // Not being a Brillig call, stepping into the opcode solves it
+            self.step_into_opcode()
         }
     }
 
@@ -879,6 +1355,213 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         }
     }
 
+    /// Returns whether execution is at its start, so that it cannot go back
+    pub(super) fn is_at_start(&self) -> bool {
+        self.step_count == 0
+    }
+
+    /// Steps debugging execution back by one opcode, undoing the last `step_into_opcode`
+    pub(super) fn step_back(&mut self) -> DebugCommandResult {
+        if let Some(step) = self.step_count.checked_sub(1) {
+            self.replay_to(step);
+        }
+        DebugCommandResult::Ok
+    }
+
+    /// Steps debugging execution back to the start of the previous source location at the
+    /// same (or less) call stack depth, the reverse of `next_over`
+    pub(super) fn reverse_next(&mut self) -> DebugCommandResult {
+        let start_location = self.get_current_source_location();
+        let start_depth = start_location.as_ref().map(|_| self.get_source_call_stack().len());
+        // The step, source location and call stack depth of the previous source location
+        let mut previous: Option<(usize, Vec<Location>, usize)> = None;
+        let step = self.find_previous_step(
+            |context, _| {
+                (context.get_current_source_location(), context.get_source_call_stack().len())
+            },
+            |step, (location, depth)| {
+                let Some(location) = location else {
+                    return ControlFlow::Continue(());
+                };
+                match &mut previous {
+                    None => {
+                        if Some(&location) != start_location.as_ref()
+                            && start_depth.is_none_or(|start_depth| depth <= start_depth)
+                        {
+                            previous = Some((step, location, depth));
+                        }
+                        ControlFlow::Continue(())
+                    }
+                    // Keep going back to where the previous source location starts, skipping
+                    // over the functions it calls
+                    Some((previous_step, previous_location, previous_depth)) => {
+                        if depth > *previous_depth {
+                            ControlFlow::Continue(())
+                        } else if depth == *previous_depth && location == *previous_location {
+                            *previous_step = step;
+                            ControlFlow::Continue(())
+                        } else {
+                            ControlFlow::Break(*previous_step)
+                        }
+                    }
+                }
+            },
+        );
+        let step = step.or(previous.map(|(step, ..)| step)).unwrap_or(0);
+        self.replay_to(step);
+        DebugCommandResult::Ok
+    }
+
+    /// Runs execution backwards until the last breakpoint or watchpoint reached before the
+    /// current step, or until the start if there is none. Hit counts are ignored.
+    pub(super) fn reverse_continue(&mut self) -> DebugCommandResult {
+        let mut stop = None;
+        let step = self.find_previous_step(
+            |context, result| match result {
+                DebugCommandResult::BreakpointReached(_)
+                | DebugCommandResult::WatchpointTriggered(_) => {
+                    Some((result, context.take_breakpoint_condition_error()))
+                }
+                _ => None,
+            },
+            |step, state| match state {
+                Some(state) => {
+                    stop = Some(state);
+                    ControlFlow::Break(step)
+                }
+                None => ControlFlow::Continue(()),
+            },
+        );
+        self.replay_to(step.unwrap_or(0));
+        match stop {
+            Some((result, error)) => {
+                self.breakpoint_condition_error = error;
+                result
+            }
+            None => DebugCommandResult::Ok,
+        }
+    }
+
+    /// Replays execution backwards, one snapshot interval at a time, visiting the states
+    /// before the current one from the latest to the earliest until `visit` breaks with the
+    /// step to go back to. `observe` extracts what `visit` needs from each state, given the
+    /// result of the step that led to it. Execution is left at an arbitrary step, to be set
+    /// with `replay_to`.
+    fn find_previous_step<T>(
+        &mut self,
+        mut observe: impl FnMut(&mut Self, DebugCommandResult) -> T,
+        mut visit: impl FnMut(usize, T) -> ControlFlow<usize>,
+    ) -> Option<usize> {
+        let mut last_step = self.step_count.checked_sub(1)?;
+        self.replaying = true;
+        let found = loop {
+            if last_step == 0 {
+                self.restore_snapshot(0);
+                let state = observe(self, DebugCommandResult::Ok);
+                break visit(0, state).break_value();
+            }
+            let first_step = self.restore_snapshot(last_step - 1);
+            let mut states = Vec::with_capacity(last_step - first_step);
+            while self.step_count < last_step {
+                let result = self.step_into_opcode();
+                states.push(observe(self, result));
+            }
+            let found = (first_step + 1..=last_step)
+                .zip(states)
+                .rev()
+                .find_map(|(step, state)| visit(step, state).break_value());
+            if found.is_some() {
+                break found;
+            }
+            last_step = first_step;
+        };
+        self.replaying = false;
+        found
+    }
+
+    /// Brings execution back to the state it had after the given number of steps
+    fn replay_to(&mut self, step: usize) {
+        self.restore_snapshot(step);
+        self.replaying = true;
+        while self.step_count < step {
+            self.step_into_opcode();
+        }
+        self.replaying = false;
+        self.breakpoint_condition_error = None;
+        self.refresh_witness_watchpoints();
+    }
+
+    fn take_snapshot(&mut self, periodic: bool) {
+        if self.snapshots.contains_key(&self.step_count) {
+            return;
+        }
+        let snapshot = Snapshot {
+            acvm: self.acvm.clone(),
+            current_circuit_id: self.current_circuit_id,
+            brillig_solver: self.brillig_solver.clone(),
+            witness_stack: self.witness_stack.clone(),
+            acvm_stack: self.acvm_stack.clone(),
+            debug_vars: self.foreign_call_executor.save_debug_vars(),
+            variable_storage: self.variable_storage.clone(),
+            next_foreign_call: self.next_foreign_call,
+            breakpoint_hits: self
+                .breakpoints
+                .iter()
+                .map(|(location, breakpoint)| (*location, breakpoint.hits))
+                .collect(),
+            periodic,
+        };
+        self.snapshots.insert(self.step_count, snapshot);
+        if periodic {
+            self.thin_out_snapshots();
+        }
+    }
+
+    /// Drops every other periodic snapshot and doubles the interval between them once there
+    /// are more than `MAX_PERIODIC_SNAPSHOTS`. Going back in time then replays more steps.
+    fn thin_out_snapshots(&mut self) {
+        let periodic_snapshots = self.snapshots.values().filter(|snapshot| snapshot.periodic);
+        if periodic_snapshots.count() <= MAX_PERIODIC_SNAPSHOTS {
+            return;
+        }
+        self.snapshot_interval *= 2;
+        let interval = self.snapshot_interval;
+        self.snapshots.retain(|&step, snapshot| !snapshot.periodic || step % interval == 0);
+    }
+
+    /// Restores the latest snapshot taken at or before the given step, returning its step
+    fn restore_snapshot(&mut self, max_step: usize) -> usize {
+        let (&step, snapshot) = self
+            .snapshots
+            .range(..=max_step)
+            .next_back()
+            .expect("There is always a snapshot of the initial state");
+        self.acvm = snapshot.acvm.clone();
+        self.current_circuit_id = snapshot.current_circuit_id;
+        self.brillig_solver = snapshot.brillig_solver.clone();
+        self.witness_stack = snapshot.witness_stack.clone();
+        self.acvm_stack = snapshot.acvm_stack.clone();
+        self.foreign_call_executor.restore_debug_vars(snapshot.debug_vars.clone());
+        self.variable_storage = snapshot.variable_storage.clone();
+        self.next_foreign_call = snapshot.next_foreign_call;
+        // Breakpoints set after the snapshot was taken count their hits from there on
+        for (location, breakpoint) in &mut self.breakpoints {
+            breakpoint.hits = snapshot.breakpoint_hits.get(location).copied().unwrap_or_default();
+        }
+        self.step_count = step;
+        self.refresh_witness_watchpoints();
+        step
+    }
+
+    /// Forgets the execution after the current step, which is no longer valid once the
+    /// state is modified, and takes a snapshot of the modified state
+    fn discard_future(&mut self) {
+        let step_count = self.step_count;
+        self.snapshots.retain(|&step, _| step < step_count);
+        self.foreign_call_results.truncate(self.next_foreign_call);
+        self.take_snapshot(false);
+    }
+
     pub(super) fn get_brillig_memory(&self) -> Option<&[MemoryValue<FieldElement>]> {
         self.brillig_solver.as_ref().map(|solver| solver.get_memory())
     }
@@ -895,6 +1578,7 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
                 MemoryValue::new_checked(value, bit_size)
                     .expect("Invalid value for the given bit size"),
             );
+            self.discard_future();
         }
     }
 
@@ -906,12 +1590,116 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
         self.foreign_call_executor.current_stack_frame()
     }
 
-    fn breakpoint_reached(&self) -> bool {
-        if let Some(location) = self.get_current_debug_location() {
-            self.breakpoints.contains(&location)
-        } else {
-            false
+    /// Evaluates an expression over the variables of the current stack frame
+    pub(super) fn evaluate(&self, expression: &Expression) -> Result<Value, ExpressionError> {
+        let variables = self.current_stack_frame().map(|frame| frame.variables).unwrap_or_default();
+        expression.evaluate(&variables, &self.functions)
+    }
+
+    /// Assigns a value to a variable of the current function, or to a field or element of one,
+    /// by modifying the witness or Brillig memory cell that stores it
+    pub(super) fn set_variable(
+        &mut self,
+        target: &Expression,
+        value: &Expression,
+    ) -> Result<(), SetVariableError> {
+        let (place, field) = {
+            let variables =
+                self.current_stack_frame().map(|frame| frame.variables).unwrap_or_default();
+            let place = target.place(&variables, &self.functions)?;
+            let field = value.evaluate(&variables, &self.functions)?.to_field_as(&place.typ)?;
+            (place, field)
+        };
+        let Some(mut variable) = self
+            .variable_storage
+            .last()
+            .and_then(|frame| frame.get(&place.variable))
+            .filter(|variable| place.offset < variable.values.len())
+            .cloned()
+        else {
+            return Err(SetVariableError::UnknownStorage(place.variable));
+        };
+        match &variable.storage {
+            ValueStorage::Witnesses { circuit_id, witnesses } => {
+                if *circuit_id != self.current_circuit_id {
+                    return Err(SetVariableError::NotExecuting(place.variable));
+                }
+                let Some(witness) = witnesses[place.offset] else {
+                    return Err(SetVariableError::NotAWitness(place.variable));
+                };
+                self.acvm.overwrite_witness(witness, field);
+            }
+            ValueStorage::BrilligMemory { circuit_id, acir_index, addresses, reference_counts } => {
+                let executing = *circuit_id == self.current_circuit_id
+                    && *acir_index == self.acvm.instruction_pointer();
+                let Some(solver) = self.brillig_solver.as_mut().filter(|_| executing) else {
+                    return Err(SetVariableError::NotExecuting(place.variable));
+                };
+                // Arrays are copied on write only while other values refer to them, so writing
+                // into a shared one would change those values too
+                let shared = reference_counts.iter().any(|address| {
+                    solver.get_memory().get(*address).map(MemoryValue::to_field)
+                        != Some(FieldElement::one())
+                });
+                if shared {
+                    return Err(SetVariableError::SharedArray(place.variable));
+                }
+                let address = addresses[place.offset];
+                let bit_size = solver.get_memory()[address].bit_size();
+                let Some(memory_value) = MemoryValue::new_checked(field, bit_size) else {
+                    return Err(SetVariableError::InvalidBitSize(place.variable));
+                };
+                solver.write_memory_at(address, memory_value);
+            }
+        }
+
+        // Show the new value until the program assigns the variable again
+        variable.values[place.offset] = field;
+        self.foreign_call_executor.assign_var(variable.var_id, &variable.values);
+        if let Some(frame) = self.variable_storage.last_mut() {
+            frame.insert(place.variable, variable);
+        }
+        self.discard_future();
+        Ok(())
+    }
+
+    fn breakpoint_reached(&mut self) -> bool {
+        let Some(location) = self.get_current_debug_location() else {
+            return false;
+        };
+        let Some(breakpoint) = self.breakpoints.get_mut(&location) else {
+            return false;
+        };
+        if let Some(condition) = &breakpoint.condition.condition {
+            let variables = self
+                .foreign_call_executor
+                .current_stack_frame()
+                .map(|frame| frame.variables)
+                .unwrap_or_default();
+            match condition.evaluate_condition(&variables, &self.functions) {
+                Ok(true) => (),
+                Ok(false) => return false,
+                Err(error) => {
+                    // Stop anyway, so that the user can inspect the state and fix the condition
+                    self.breakpoint_condition_error = Some(error);
+                    return true;
+                }
+            }
         }
+        // Hits are counted again when replaying, starting from the counts of the snapshot
+        breakpoint.hits += 1;
+        if self.replaying {
+            // Going back in time stops at breakpoints regardless of their hit counts
+            return true;
+        }
+        let hits = breakpoint.hits;
+        breakpoint.condition.hit_condition.is_none_or(|hit_condition| hit_condition.is_met(hits))
+    }
+
+    /// Returns the error raised by the condition of the last breakpoint reached, if it could
+    /// not be evaluated
+    pub(super) fn take_breakpoint_condition_error(&mut self) -> Option<ExpressionError> {
+        self.breakpoint_condition_error.take()
     }
 
     pub(super) fn is_valid_debug_location(&self, location: &DebugLocation) -> bool {
@@ -938,54 +1726,198 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> DebugContext<'a, B> {
     }
 
     pub(super) fn is_breakpoint_set(&self, location: &DebugLocation) -> bool {
-        self.breakpoints.contains(location)
+        self.breakpoints.contains_key(location)
     }
 
-    pub(super) fn add_breakpoint(&mut self, location: DebugLocation) -> bool {
-        self.breakpoints.insert(location)
+    /// Adds a breakpoint which only stops execution when its condition is met. Returns false
+    /// if there is already a breakpoint at the location.
+    pub(super) fn add_breakpoint(
+        &mut self,
+        location: DebugLocation,
+        condition: BreakpointCondition,
+    ) -> bool {
+        match self.breakpoints.entry(location) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(Breakpoint { condition, hits: 0 });
+                true
+            }
+        }
     }
 
     pub(super) fn delete_breakpoint(&mut self, location: &DebugLocation) -> bool {
-        self.breakpoints.remove(location)
+        self.breakpoints.remove(location).is_some()
     }
 
     pub(super) fn clear_breakpoints(&mut self) {
         self.breakpoints.clear();
     }
 
-    pub(super) fn is_solved(&self) -> bool {
-        matches!(self.acvm.get_status(), ACVMStatus::Solved)
+    /// Returns a watchpoint on the variable with the given name in the current function, if
+    /// it has a value
+    pub(super) fn variable_watchpoint(&self, name: &str) -> Option<Watchpoint> {
+        let (var_id, frame) = self.foreign_call_executor.find_variable(name)?;
+        Some(Watchpoint::Variable { name: name.to_string(), var_id, frame })
     }
 
-    pub fn finalize(mut self) -> WitnessStack<FieldElement> {
-        let last_witness_map = self.acvm.finalize();
-        self.witness_stack.push(0, last_witness_map);
-        self.witness_stack
+    /// Returns the watchpoint on a variable with the given name, preferring the variable of
+    /// the current function over those of the deepest stack frames
+    pub(super) fn watched_variable(&self, name: &str) -> Option<Watchpoint> {
+        let current =
+            self.foreign_call_executor.find_variable(name).map(|(var_id, frame)| (frame, var_id));
+        let (frame, var_id) =
+            current.filter(|key| self.variable_watchpoints.contains_key(key)).or_else(|| {
+                let mut watched = self.variable_watchpoints.iter().rev();
+                watched.find(|(_, watched_name)| *watched_name == name).map(|(key, _)| *key)
+            })?;
+        Some(Watchpoint::Variable { name: name.to_string(), var_id, frame })
     }
 
-    pub(super) fn restart(&mut self) {
-        // restart everything that's progress related
-        // by assigning the initial values
-        self.current_circuit_id = 0;
-        self.brillig_solver = None;
-        self.witness_stack = WitnessStack::default();
-        self.acvm_stack = vec![];
-        self.foreign_call_executor.restart(self.debug_artifact);
-        self.acvm = initialize_acvm(
-            self.backend,
-            self.circuits,
-            self.initial_witness.clone(),
-            self.unconstrained_functions,
-        );
+    /// Adds a watchpoint. Returns false if it was already set.
+    pub(super) fn add_watchpoint(&mut self, watchpoint: Watchpoint) -> bool {
+        match watchpoint {
+            Watchpoint::Variable { name, var_id, frame } => {
+                self.variable_watchpoints.insert((frame, var_id), name).is_none()
+            }
+            Watchpoint::Witness { circuit_id, witness } => {
+                if self.witness_watchpoints.contains_key(&(circuit_id, witness)) {
+                    return false;
+                }
+                let value = if circuit_id == self.current_circuit_id {
+                    self.acvm.witness_map().get(&witness).copied()
+                } else {
+                    None
+                };
+                self.witness_watchpoints.insert((circuit_id, witness), value);
+                true
+            }
+        }
     }
-}
-
-fn is_debug_file_in_debug_crate(debug_file: &DebugFile) -> bool {
-    debug_file.path.starts_with("__debug/")
-}
 
-/// Builds a map from FileId to an ordered vector of tuples with line
-/// numbers and opcode locations corresponding to those line numbers
+    pub(super) fn delete_watchpoint(&mut self, watchpoint: &Watchpoint) -> bool {
+        match watchpoint {
+            Watchpoint::Variable { var_id, frame, .. } => {
+                self.variable_watchpoints.remove(&(*frame, *var_id)).is_some()
+            }
+            Watchpoint::Witness { circuit_id, witness } => {
+                self.witness_watchpoints.remove(&(*circuit_id, *witness)).is_some()
+            }
+        }
+    }
+
+    pub(super) fn clear_watchpoints(&mut self) {
+        self.variable_watchpoints.clear();
+        self.witness_watchpoints.clear();
+    }
+
+    /// Renders the values of the watched variables, in the order of `variable_watchpoints`
+    fn watched_variable_values(&self) -> Vec<Option<String>> {
+        self.variable_watchpoints
+            .keys()
+            .map(|(frame, var_id)| {
+                let (value, typ) = self.foreign_call_executor.get_variable(*var_id, *frame)?;
+                let display = PrintableValueDisplay::Plain(value.clone(), typ.clone());
+                Some(display.to_string())
+            })
+            .collect()
+    }
+
+    /// Returns the first watched variable whose value differs from the given previous values
+    fn variable_watchpoint_hit(&self, old_values: Vec<Option<String>>) -> Option<WatchpointHit> {
+        self.variable_watchpoints
+            .iter()
+            .zip(old_values)
+            .zip(self.watched_variable_values())
+            .find(|((_, old_value), new_value)| old_value != new_value)
+            .map(|((((frame, var_id), name), old_value), new_value)| WatchpointHit {
+                watchpoint: Watchpoint::Variable {
+                    name: name.clone(),
+                    var_id: *var_id,
+                    frame: *frame,
+                },
+                old_value,
+                new_value,
+            })
+    }
+
+    /// Sets the last known values of the watched witnesses to their current values, without
+    /// triggering them
+    fn refresh_witness_watchpoints(&mut self) {
+        let witness_map = self.acvm.witness_map();
+        for ((circuit_id, witness), value) in &mut self.witness_watchpoints {
+            *value = if *circuit_id == self.current_circuit_id {
+                witness_map.get(witness).copied()
+            } else {
+                None
+            };
+        }
+    }
+
+    /// Updates the values of the watched witnesses of the current circuit, returning the
+    /// first one that changed since it was last checked
+    fn witness_watchpoint_hit(&mut self) -> Option<WatchpointHit> {
+        let witness_map = self.acvm.witness_map();
+        let mut hit = None;
+        for ((circuit_id, witness), last_value) in &mut self.witness_watchpoints {
+            if *circuit_id != self.current_circuit_id {
+                continue;
+            }
+            let value = witness_map.get(witness).copied();
+            if value != *last_value && hit.is_none() {
+                hit = Some(WatchpointHit {
+                    watchpoint: Watchpoint::Witness { circuit_id: *circuit_id, witness: *witness },
+                    old_value: last_value.map(|value| value.to_string()),
+                    new_value: value.map(|value| value.to_string()),
+                });
+            }
+            *last_value = value;
+        }
+        hit
+    }
+
+    pub(super) fn is_solved(&self) -> bool {
+        matches!(self.acvm.get_status(), ACVMStatus::Solved)
+    }
+
+    pub fn finalize(mut self) -> WitnessStack<FieldElement> {
+        let last_witness_map = self.acvm.finalize();
+        self.witness_stack.push(0, last_witness_map);
+        self.witness_stack
+    }
+
+    pub(super) fn restart(&mut self) {
+        // restart everything that's progress related
+        // by assigning the initial values
+        self.current_circuit_id = 0;
+        self.brillig_solver = None;
+        self.witness_stack = WitnessStack::default();
+        self.acvm_stack = vec![];
+        self.breakpoints.values_mut().for_each(|breakpoint| breakpoint.hits = 0);
+        self.breakpoint_condition_error = None;
+        self.foreign_call_executor.restart(self.debug_artifact);
+        self.variable_storage.clear();
+        self.acvm = initialize_acvm(
+            self.backend,
+            self.circuits,
+            self.initial_witness.clone(),
+            self.unconstrained_functions,
+        );
+        self.step_count = 0;
+        self.snapshots.clear();
+        self.snapshot_interval = SNAPSHOT_INTERVAL;
+        self.foreign_call_results.clear();
+        self.next_foreign_call = 0;
+        self.take_snapshot(false);
+        self.refresh_witness_watchpoints();
+    }
+}
+
+fn is_debug_file_in_debug_crate(debug_file: &DebugFile) -> bool {
+    debug_file.path.starts_with("__debug/")
+}
+
+/// Builds a map from FileId to an ordered vector of tuples with line
+/// numbers and opcode locations corresponding to those line numbers
 fn build_source_to_opcode_debug_mappings(
     debug_artifact: &DebugArtifact,
 ) -> BTreeMap<FileId, Vec<(usize, DebugLocation)>> {
@@ -1086,7 +2018,7 @@ mod tests {
     use acvm::{
         acir::{
             AcirField,
-            brillig::{HeapVector, IntegerBitSize},
+            brillig::{HeapArray, HeapVector, IntegerBitSize},
             circuit::{
                 brillig::{BrilligFunctionId, BrilligInputs, BrilligOutputs},
                 opcodes::{AcirFunctionId, BlockId, BlockType},
@@ -1095,9 +2027,95 @@ mod tests {
         },
         blackbox_solver::StubbedBlackBoxSolver,
         brillig_vm::brillig::{
-            BinaryFieldOp, HeapValueType, MemoryAddress, Opcode as BrilligOpcode, ValueOrArray,
+            BinaryFieldOp, BinaryIntOp, HeapValueType, MemoryAddress, Opcode as BrilligOpcode,
+            ValueOrArray,
         },
     };
+    use noirc_errors::debug_info::{
+        DebugFnId, DebugFunction, DebugTypeId, DebugVarId, DebugVariable,
+    };
+
+    /// The inputs of a program run by the tests, from which debugging contexts are created
+    struct TestProgram {
+        solver: StubbedBlackBoxSolver,
+        circuits: Vec<Circuit<FieldElement>>,
+        unconstrained_functions: Vec<BrilligBytecode<FieldElement>>,
+        brillig_names: Vec<String>,
+        debug_artifact: DebugArtifact,
+        initial_witness: WitnessMap<FieldElement>,
+    }
+
+    impl TestProgram {
+        /// A program made of a single circuit, without debug symbols
+        fn new(
+            opcodes: Vec<Opcode<FieldElement>>,
+            unconstrained_functions: Vec<BrilligBytecode<FieldElement>>,
+            initial_witness: WitnessMap<FieldElement>,
+        ) -> Self {
+            Self {
+                solver: StubbedBlackBoxSolver::default(),
+                circuits: vec![Circuit { opcodes, ..Circuit::default() }],
+                unconstrained_functions,
+                brillig_names: vec![],
+                debug_artifact: DebugArtifact { debug_symbols: vec![], file_map: BTreeMap::new() },
+                initial_witness,
+            }
+        }
+
+        /// z = x, then x - y = 0, with x = y = 1
+        fn assign_then_assert_equal() -> Self {
+            let fe_1 = FieldElement::one();
+            let (w_x, w_y, w_z) = (Witness(1), Witness(2), Witness(3));
+            let opcodes = vec![
+                Opcode::AssertZero(Expression {
+                    linear_combinations: vec![(fe_1, w_x), (-fe_1, w_z)],
+                    ..Expression::default()
+                }),
+                Opcode::AssertZero(Expression {
+                    linear_combinations: vec![(fe_1, w_x), (-fe_1, w_y)],
+                    ..Expression::default()
+                }),
+            ];
+            let initial_witness = BTreeMap::from([(w_x, fe_1), (w_y, fe_1)]).into();
+            Self::new(opcodes, vec![], initial_witness)
+        }
+
+        fn context(&self) -> DebugContext<'_, StubbedBlackBoxSolver> {
+            let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+                std::io::stdout(),
+                None,
+                &self.debug_artifact,
+                None,
+                String::new(),
+            ));
+            DebugContext::new(
+                &self.solver,
+                &self.circuits,
+                &self.debug_artifact,
+                self.initial_witness.clone(),
+                foreign_call_executor,
+                &self.unconstrained_functions,
+                &self.brillig_names,
+            )
+        }
+    }
+
+    /// A variable of type `DebugTypeId(0)`
+    fn variable(name: &str) -> DebugVariable {
+        DebugVariable { name: String::from(name), debug_type_id: DebugTypeId(0) }
+    }
+
+    fn function(name: &str) -> DebugFunction {
+        DebugFunction { name: String::from(name), arg_names: vec![] }
+    }
+
+    fn acir_location(acir_index: usize) -> DebugLocation {
+        DebugLocation {
+            circuit_id: 0,
+            opcode_location: OpcodeLocation::Acir(acir_index),
+            brillig_function_id: None,
+        }
+    }
 
     #[test]
     fn test_resolve_foreign_calls_stepping_into_brillig() {
@@ -1171,6 +2189,7 @@ mod tests {
             initial_witness,
             foreign_call_executor,
             brillig_functions,
+            &[],
         );
 
         assert_eq!(
@@ -1247,6 +2266,160 @@ mod tests {
         let result = context.step_into_opcode();
         assert!(matches!(result, DebugCommandResult::Done));
         assert_eq!(context.get_current_debug_location(), None);
+
+        // go back to before the foreign call was resolved
+        for _ in 0..3 {
+            context.step_back();
+        }
+        assert_eq!(
+            context.get_current_debug_location(),
+            Some(DebugLocation {
+                circuit_id: 0,
+                opcode_location: OpcodeLocation::Brillig { acir_index: 0, brillig_index: 3 },
+                brillig_function_id: Some(BrilligFunctionId(0)),
+            })
+        );
+
+        // stepping forward again replays the recorded result of the foreign call
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+        assert_eq!(context.foreign_call_results.len(), 1);
+    }
+
+    #[test]
+    fn test_reverse_execution() {
+        let fe_1 = FieldElement::one();
+        let (w_y, w_z) = (Witness(2), Witness(3));
+        let program = TestProgram::assign_then_assert_equal();
+        let mut context = program.context();
+
+        assert!(context.is_at_start());
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+
+        // stepping back undoes the assignment of z
+        context.step_back();
+        assert_eq!(context.get_current_debug_location(), Some(acir_location(1)));
+        assert_eq!(context.get_witness_map().get(&w_z), Some(&fe_1));
+        context.step_back();
+        assert_eq!(context.get_current_debug_location(), Some(acir_location(0)));
+        assert_eq!(context.get_witness_map().get(&w_z), None);
+        assert!(context.is_at_start());
+
+        // going back without breakpoints reaches the start
+        context.cont();
+        let result = context.reverse_continue();
+        assert!(matches!(result, DebugCommandResult::Ok));
+        assert!(context.is_at_start());
+
+        // going back stops at the last breakpoint reached
+        context.cont();
+        assert!(context.add_breakpoint(acir_location(1), BreakpointCondition::default()));
+        let result = context.reverse_continue();
+        assert!(matches!(result, DebugCommandResult::BreakpointReached(_)));
+        assert_eq!(context.get_current_debug_location(), Some(acir_location(1)));
+
+        // overwriting a witness discards the execution that followed
+        context.overwrite_witness(w_y, FieldElement::from(2u128));
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Error(_)));
+        context.step_back();
+        assert_eq!(context.get_witness_map().get(&w_y), Some(&FieldElement::from(2u128)));
+    }
+
+    #[test]
+    fn test_snapshots_are_thinned_out() {
+        let program = TestProgram::assign_then_assert_equal();
+        let mut context = program.context();
+
+        for snapshot in 1..=MAX_PERIODIC_SNAPSHOTS + 1 {
+            context.step_count = snapshot * SNAPSHOT_INTERVAL;
+            context.take_snapshot(true);
+        }
+
+        // The initial snapshot is kept, along with every other periodic one
+        let interval = 2 * SNAPSHOT_INTERVAL;
+        assert_eq!(context.snapshot_interval, interval);
+        assert_eq!(context.snapshots.len(), 1 + (MAX_PERIODIC_SNAPSHOTS + 1) / 2);
+        assert!(context.snapshots.keys().all(|step| step % interval == 0));
+    }
+
+    #[test]
+    fn test_breakpoint_hits_when_going_back() {
+        let memory = MemoryAddress::direct;
+        let constant = |address, value: u128| BrilligOpcode::Const {
+            destination: memory(address),
+            bit_size: BitSize::Integer(IntegerBitSize::U32),
+            value: FieldElement::from(value),
+        };
+
+        // for i in 0..3 {}
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                constant(0, 0),
+                constant(1, 1),
+                constant(2, 3),
+                constant(4, 0),
+                BrilligOpcode::BinaryIntOp {
+                    destination: memory(0),
+                    op: BinaryIntOp::Add,
+                    bit_size: IntegerBitSize::U32,
+                    lhs: memory(0),
+                    rhs: memory(1),
+                },
+                BrilligOpcode::BinaryIntOp {
+                    destination: memory(3),
+                    op: BinaryIntOp::LessThan,
+                    bit_size: IntegerBitSize::U32,
+                    lhs: memory(0),
+                    rhs: memory(2),
+                },
+                BrilligOpcode::JumpIf { condition: memory(3), location: 4 },
+                BrilligOpcode::Stop {
+                    return_data: HeapVector { pointer: memory(0), size: memory(4) },
+                },
+            ],
+        };
+        let opcodes = vec![Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![],
+            outputs: vec![],
+            predicate: None,
+        }];
+        let program = TestProgram::new(opcodes, vec![brillig_bytecode], WitnessMap::default());
+        let mut context = program.context();
+
+        // the loop body is reached three times, and the breakpoint stops at the second one
+        let loop_body = DebugLocation {
+            circuit_id: 0,
+            opcode_location: OpcodeLocation::Brillig { acir_index: 0, brillig_index: 4 },
+            brillig_function_id: Some(BrilligFunctionId(0)),
+        };
+        let condition =
+            BreakpointCondition { condition: None, hit_condition: Some(HitCondition::Equal(2)) };
+        assert!(context.add_breakpoint(loop_body, condition));
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::BreakpointReached(_)));
+        let step = context.step_count;
+
+        // going back before the second hit and continuing stops at it again
+        context.step_back();
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::BreakpointReached(_)));
+        assert_eq!(context.step_count, step);
+
+        // going back to the first hit ignores the hit count, and continuing stops at the
+        // second hit again
+        let result = context.reverse_continue();
+        assert!(matches!(result, DebugCommandResult::BreakpointReached(_)));
+        assert!(context.step_count < step);
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::BreakpointReached(_)));
+        assert_eq!(context.step_count, step);
+
+        // the third hit doesn't meet the hit count
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
     }
 
     #[test]
@@ -1344,6 +2517,7 @@ mod tests {
             initial_witness,
             foreign_call_executor,
             brillig_functions,
+            &[],
         );
 
         // set breakpoint
@@ -1352,7 +2526,7 @@ mod tests {
             opcode_location: OpcodeLocation::Brillig { acir_index: 0, brillig_index: 1 },
             brillig_function_id: Some(BrilligFunctionId(0)),
         };
-        assert!(context.add_breakpoint(breakpoint_location));
+        assert!(context.add_breakpoint(breakpoint_location, BreakpointCondition::default()));
 
         // execute the first ACIR opcode (Brillig block) -> should reach the breakpoint instead
         let result = context.step_acir_opcode();
@@ -1377,6 +2551,575 @@ mod tests {
         assert_eq!(context.get_current_debug_location(), None);
     }
 
+    #[test]
+    fn test_conditional_breakpoints() {
+        let program = TestProgram::assign_then_assert_equal();
+        let mut context = program.context();
+
+        let breakpoint_location = acir_location(1);
+        // runs the program from the start with a single breakpoint
+        let set_breakpoint = |context: &mut DebugContext<StubbedBlackBoxSolver>, condition| {
+            context.restart();
+            context.clear_breakpoints();
+            assert!(context.add_breakpoint(breakpoint_location, condition));
+            context.cont()
+        };
+
+        // a condition that does not hold never stops execution
+        let condition =
+            BreakpointCondition { condition: Some("1 == 2".parse().unwrap()), hit_condition: None };
+        let result = set_breakpoint(&mut context, condition);
+        assert!(matches!(result, DebugCommandResult::Done));
+
+        // the hit count is met the first time the breakpoint is reached
+        let hit_condition = Some(HitCondition::Equal(1));
+        let condition = BreakpointCondition { condition: None, hit_condition };
+        let result = set_breakpoint(&mut context, condition);
+        assert!(matches!(result, DebugCommandResult::BreakpointReached(_)));
+        assert_eq!(context.get_current_debug_location(), Some(breakpoint_location));
+
+        // the breakpoint is only reached once, so the hit count is never met
+        let hit_condition = Some(HitCondition::Greater(1));
+        let condition = BreakpointCondition { condition: None, hit_condition };
+        let result = set_breakpoint(&mut context, condition);
+        assert!(matches!(result, DebugCommandResult::Done));
+
+        // a condition that cannot be evaluated stops execution and reports the error
+        let condition =
+            BreakpointCondition { condition: Some("x == 1".parse().unwrap()), hit_condition: None };
+        let result = set_breakpoint(&mut context, condition);
+        assert!(matches!(result, DebugCommandResult::BreakpointReached(_)));
+        assert_eq!(
+            context.take_breakpoint_condition_error(),
+            Some(ExpressionError::UnknownVariable(String::from("x")))
+        );
+    }
+
+    #[test]
+    fn test_witness_watchpoints() {
+        let fe_1 = FieldElement::one();
+        let (w_y, w_z, w_w) = (Witness(2), Witness(3), Witness(4));
+
+        // z = x, then x - y = 0, then w = y
+        let mut program = TestProgram::assign_then_assert_equal();
+        program.circuits[0].opcodes.push(Opcode::AssertZero(Expression {
+            linear_combinations: vec![(fe_1, w_y), (-fe_1, w_w)],
+            ..Expression::default()
+        }));
+        let mut context = program.context();
+
+        let watchpoint = Watchpoint::Witness { circuit_id: 0, witness: w_z };
+        assert!(context.add_watchpoint(watchpoint.clone()));
+        assert!(!context.add_watchpoint(watchpoint.clone()));
+
+        // execution stops right after the first opcode assigns z
+        let result = context.cont();
+        let DebugCommandResult::WatchpointTriggered(hit) = result else {
+            panic!("Expected the watchpoint to be triggered, got {result:?}");
+        };
+        assert_eq!(hit.watchpoint, watchpoint);
+        assert_eq!(hit.old_value, None);
+        assert!(hit.new_value.is_some());
+        assert_eq!(context.get_current_debug_location(), Some(acir_location(1)));
+
+        // w is assigned by the last opcode, which solves the circuit
+        let last_watchpoint = Watchpoint::Witness { circuit_id: 0, witness: w_w };
+        assert!(context.add_watchpoint(last_watchpoint.clone()));
+        let result = context.cont();
+        let DebugCommandResult::WatchpointTriggered(hit) = result else {
+            panic!("Expected the watchpoint to be triggered, got {result:?}");
+        };
+        assert_eq!(hit.watchpoint, last_watchpoint);
+        assert!(context.is_solved());
+
+        // z and w are not assigned again
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+
+        // the watchpoints are triggered again after restarting
+        context.restart();
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::WatchpointTriggered(_)));
+
+        assert!(context.delete_watchpoint(&watchpoint));
+        assert!(context.delete_watchpoint(&last_watchpoint));
+        context.restart();
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+    }
+
+    #[test]
+    fn test_variable_watchpoints() {
+        let memory_address = |address| ValueOrArray::MemoryAddress(MemoryAddress::direct(address));
+        let constant = |address, value: u128| BrilligOpcode::Const {
+            destination: MemoryAddress::direct(address),
+            bit_size: BitSize::Field,
+            value: FieldElement::from(value),
+        };
+        let foreign_call = |function: &str, inputs: Vec<usize>| BrilligOpcode::ForeignCall {
+            function: function.into(),
+            destinations: vec![],
+            destination_value_types: vec![],
+            input_value_types: vec![HeapValueType::field(); inputs.len()],
+            inputs: inputs.into_iter().map(memory_address).collect(),
+        };
+
+        // main() { let mut x = 5; helper(); x = 6; }, with helper() { let x = 7; }
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                constant(1, 0),
+                constant(2, 5),
+                constant(3, 1),
+                constant(4, 7),
+                constant(5, 6),
+                foreign_call("__debug_fn_enter", vec![1]),
+                foreign_call("__debug_var_assign", vec![1, 2]),
+                foreign_call("__debug_fn_enter", vec![3]),
+                foreign_call("__debug_var_assign", vec![3, 4]),
+                foreign_call("__debug_fn_exit", vec![3]),
+                foreign_call("__debug_var_assign", vec![1, 5]),
+                BrilligOpcode::Stop {
+                    return_data: HeapVector {
+                        pointer: MemoryAddress::direct(1),
+                        size: MemoryAddress::direct(1),
+                    },
+                },
+            ],
+        };
+        let opcodes = vec![Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![],
+            outputs: vec![],
+            predicate: None,
+        }];
+        let mut program = TestProgram::new(opcodes, vec![brillig_bytecode], WitnessMap::default());
+        let debug_info = DebugInfo {
+            variables: BTreeMap::from([
+                (DebugVarId(0), variable("x")),
+                (DebugVarId(1), variable("x")),
+            ]),
+            types: BTreeMap::from([(DebugTypeId(0), PrintableType::Field)]),
+            functions: BTreeMap::from([
+                (DebugFnId(0), function("main")),
+                (DebugFnId(1), function("helper")),
+            ]),
+            ..DebugInfo::default()
+        };
+        program.debug_artifact.debug_symbols = vec![debug_info];
+        let mut context = program.context();
+
+        // step until x is assigned in main
+        let watchpoint = loop {
+            if let Some(watchpoint) = context.variable_watchpoint("x") {
+                break watchpoint;
+            }
+            let result = context.step_into_opcode();
+            assert!(matches!(result, DebugCommandResult::Ok), "Unexpected result {result:?}");
+        };
+        assert_eq!(
+            watchpoint,
+            Watchpoint::Variable { name: String::from("x"), var_id: DebugVarId(0), frame: 0 }
+        );
+        assert!(context.add_watchpoint(watchpoint.clone()));
+
+        // the x of helper doesn't trigger the watchpoint, while the x of main does
+        let result = context.cont();
+        let DebugCommandResult::WatchpointTriggered(hit) = result else {
+            panic!("Expected the watchpoint to be triggered, got {result:?}");
+        };
+        assert_eq!(hit.watchpoint, watchpoint);
+        assert_eq!(hit.old_value.as_deref(), Some("0x05"));
+        assert_eq!(hit.new_value.as_deref(), Some("0x06"));
+        assert_eq!(context.watched_variable("x"), Some(watchpoint.clone()));
+
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+        assert!(context.delete_watchpoint(&watchpoint));
+        assert_eq!(context.watched_variable("x"), None);
+    }
+
+    #[test]
+    fn test_set_variable() {
+        let memory_address = |address| ValueOrArray::MemoryAddress(MemoryAddress::direct(address));
+
+        // main() { let x = 5; }
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::direct(1),
+                    bit_size: BitSize::Integer(IntegerBitSize::U32),
+                    value: FieldElement::zero(),
+                },
+                BrilligOpcode::ForeignCall {
+                    function: "__debug_fn_enter".into(),
+                    destinations: vec![],
+                    destination_value_types: vec![],
+                    inputs: vec![memory_address(1)],
+                    input_value_types: vec![HeapValueType::field()],
+                },
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::direct(2),
+                    bit_size: BitSize::Field,
+                    value: FieldElement::from(5u128),
+                },
+                BrilligOpcode::ForeignCall {
+                    function: "__debug_var_assign".into(),
+                    destinations: vec![],
+                    destination_value_types: vec![],
+                    inputs: vec![memory_address(1), memory_address(2)],
+                    input_value_types: vec![HeapValueType::field(), HeapValueType::field()],
+                },
+                BrilligOpcode::Stop {
+                    return_data: HeapVector {
+                        pointer: MemoryAddress::direct(1),
+                        size: MemoryAddress::direct(1),
+                    },
+                },
+            ],
+        };
+        let opcodes = vec![Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![],
+            outputs: vec![],
+            predicate: None,
+        }];
+        let mut program = TestProgram::new(opcodes, vec![brillig_bytecode], WitnessMap::default());
+        let debug_info = DebugInfo {
+            variables: BTreeMap::from([(
+                DebugVarId(0),
+                DebugVariable { name: String::from("x"), debug_type_id: DebugTypeId(0) },
+            )]),
+            types: BTreeMap::from([(DebugTypeId(0), PrintableType::Field)]),
+            functions: BTreeMap::from([(
+                DebugFnId(0),
+                DebugFunction { name: String::from("main"), arg_names: vec![] },
+            )]),
+            ..DebugInfo::default()
+        };
+        program.debug_artifact.debug_symbols = vec![debug_info];
+        let mut context = program.context();
+
+        // step until x is assigned, which tells where it is stored
+        let x = "x".parse().unwrap();
+        while context.evaluate(&x).is_err() {
+            let result = context.step_into_opcode();
+            assert!(matches!(result, DebugCommandResult::Ok), "Unexpected result {result:?}");
+        }
+        assert_eq!(context.evaluate(&x), Ok(Value::Field(FieldElement::from(5u128))));
+
+        let value = "x * 2 + 1".parse().unwrap();
+        context.set_variable(&x, &value).unwrap();
+        assert_eq!(context.evaluate(&x), Ok(Value::Field(FieldElement::from(11u128))));
+        let memory = context.get_brillig_memory().unwrap();
+        assert_eq!(memory[2].to_field(), FieldElement::from(11u128));
+
+        let result = context.set_variable(&x, &"true".parse().unwrap());
+        assert!(matches!(
+            result,
+            Err(SetVariableError::Expression(ExpressionError::TypeMismatch { .. }))
+        ));
+
+        // x is no longer stored anywhere once the Brillig function returns
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+        let result = context.set_variable(&x, &value);
+        assert!(matches!(result, Err(SetVariableError::NotExecuting(_))));
+    }
+
+    #[test]
+    fn test_set_variable_members_in_witnesses() {
+        let memory_address = |address| ValueOrArray::MemoryAddress(MemoryAddress::direct(address));
+        let u32_constant = |address, value: usize| BrilligOpcode::Const {
+            destination: MemoryAddress::direct(address),
+            bit_size: BitSize::Integer(IntegerBitSize::U32),
+            value: FieldElement::from(value),
+        };
+        // Brillig functions making a debug foreign call with their calldata
+        let debug_call = |function: &str, input_count: usize| BrilligBytecode {
+            bytecode: vec![
+                u32_constant(0, input_count),
+                u32_constant(1, 0),
+                BrilligOpcode::CalldataCopy {
+                    destination_address: MemoryAddress::direct(2),
+                    size_address: MemoryAddress::direct(0),
+                    offset_address: MemoryAddress::direct(1),
+                },
+                BrilligOpcode::ForeignCall {
+                    function: function.into(),
+                    destinations: vec![],
+                    destination_value_types: vec![],
+                    inputs: (2..2 + input_count).map(memory_address).collect(),
+                    input_value_types: vec![HeapValueType::field(); input_count],
+                },
+                BrilligOpcode::Stop {
+                    return_data: HeapVector {
+                        pointer: MemoryAddress::direct(1),
+                        size: MemoryAddress::direct(1),
+                    },
+                },
+            ],
+        };
+        let brillig_call = |id, inputs| Opcode::BrilligCall {
+            id: BrilligFunctionId(id),
+            inputs,
+            outputs: vec![],
+            predicate: None,
+        };
+        let constant = |value: u128| BrilligInputs::Single(Expression::from_field(value.into()));
+        let witness = |witness: Witness| BrilligInputs::Single(Expression::from(witness));
+
+        // main(a, b, c) { let mut x = (a, b); x.1 = c; let d = x.1; }
+        let fe_1 = FieldElement::one();
+        let (w_a, w_b, w_c, w_d) = (Witness(1), Witness(2), Witness(3), Witness(4));
+        let opcodes = vec![
+            brillig_call(0, vec![constant(0)]),
+            brillig_call(1, vec![constant(0), witness(w_a), witness(w_b)]),
+            brillig_call(2, vec![constant(0), witness(w_c), constant(1)]),
+            Opcode::AssertZero(Expression {
+                linear_combinations: vec![(fe_1, w_c), (-fe_1, w_d)],
+                ..Expression::default()
+            }),
+        ];
+        let unconstrained_functions = vec![
+            debug_call("__debug_fn_enter", 1),
+            debug_call("__debug_var_assign", 3),
+            debug_call("__debug_member_assign_1", 3),
+        ];
+        let initial_witness = BTreeMap::from([
+            (w_a, FieldElement::from(1u128)),
+            (w_b, FieldElement::from(2u128)),
+            (w_c, FieldElement::from(3u128)),
+        ])
+        .into();
+        let mut program = TestProgram::new(opcodes, unconstrained_functions, initial_witness);
+        let debug_info = DebugInfo {
+            variables: BTreeMap::from([(DebugVarId(0), variable("x"))]),
+            types: BTreeMap::from([(
+                DebugTypeId(0),
+                PrintableType::Tuple { types: vec![PrintableType::Field, PrintableType::Field] },
+            )]),
+            functions: BTreeMap::from([(DebugFnId(0), function("main"))]),
+            ..DebugInfo::default()
+        };
+        program.debug_artifact.debug_symbols = vec![debug_info];
+        let mut context = program.context();
+
+        // step until the second element of x is assigned, which then is stored in c
+        let x_0 = "x.0".parse().unwrap();
+        let x_1 = "x.1".parse().unwrap();
+        while context.evaluate(&x_1) != Ok(Value::Field(FieldElement::from(3u128))) {
+            let result = context.step_into_opcode();
+            assert!(matches!(result, DebugCommandResult::Ok), "Unexpected result {result:?}");
+        }
+
+        context.set_variable(&x_0, &"5".parse().unwrap()).unwrap();
+        context.set_variable(&x_1, &"7".parse().unwrap()).unwrap();
+        assert_eq!(context.get_witness_map().get(&w_a), Some(&FieldElement::from(5u128)));
+        assert_eq!(context.get_witness_map().get(&w_b), Some(&FieldElement::from(2u128)));
+        assert_eq!(context.get_witness_map().get(&w_c), Some(&FieldElement::from(7u128)));
+
+        let result = context.cont();
+        assert!(matches!(result, DebugCommandResult::Done));
+        assert_eq!(context.get_witness_map().get(&w_d), Some(&FieldElement::from(7u128)));
+    }
+
+    #[test]
+    fn test_set_variable_array_elements() {
+        let memory_address = |address| ValueOrArray::MemoryAddress(MemoryAddress::direct(address));
+        let constant = |address, bit_size, value: u128| BrilligOpcode::Const {
+            destination: MemoryAddress::direct(address),
+            bit_size,
+            value: FieldElement::from(value),
+        };
+        let u32_size = BitSize::Integer(IntegerBitSize::U32);
+
+        // main() { let mut x = [5, 6]; x[1] = 9; let y = x; }, with x stored after its
+        // reference count at 10
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                constant(1, u32_size, 0),
+                BrilligOpcode::ForeignCall {
+                    function: "__debug_fn_enter".into(),
+                    destinations: vec![],
+                    destination_value_types: vec![],
+                    inputs: vec![memory_address(1)],
+                    input_value_types: vec![HeapValueType::field()],
+                },
+                constant(10, u32_size, 1),
+                constant(11, BitSize::Field, 5),
+                constant(12, BitSize::Field, 6),
+                constant(2, u32_size, 11),
+                BrilligOpcode::ForeignCall {
+                    function: "__debug_var_assign".into(),
+                    destinations: vec![],
+                    destination_value_types: vec![],
+                    inputs: vec![
+                        memory_address(1),
+                        ValueOrArray::HeapArray(HeapArray {
+                            pointer: MemoryAddress::direct(2),
+                            size: 2,
+                        }),
+                    ],
+                    input_value_types: vec![
+                        HeapValueType::field(),
+                        HeapValueType::Array { value_types: vec![HeapValueType::field()], size: 2 },
+                    ],
+                },
+                constant(12, BitSize::Field, 9),
+                constant(3, BitSize::Field, 9),
+                constant(4, u32_size, 1),
+                BrilligOpcode::ForeignCall {
+                    function: "__debug_member_assign_1".into(),
+                    destinations: vec![],
+                    destination_value_types: vec![],
+                    inputs: vec![memory_address(1), memory_address(3), memory_address(4)],
+                    input_value_types: vec![HeapValueType::field(); 3],
+                },
+                constant(10, u32_size, 2),
+                BrilligOpcode::Stop {
+                    return_data: HeapVector {
+                        pointer: MemoryAddress::direct(1),
+                        size: MemoryAddress::direct(1),
+                    },
+                },
+            ],
+        };
+        let opcodes = vec![Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![],
+            outputs: vec![],
+            predicate: None,
+        }];
+        let mut program = TestProgram::new(opcodes, vec![brillig_bytecode], WitnessMap::default());
+        let debug_info = DebugInfo {
+            variables: BTreeMap::from([(DebugVarId(0), variable("x"))]),
+            types: BTreeMap::from([(
+                DebugTypeId(0),
+                PrintableType::Array { length: 2, typ: Box::new(PrintableType::Field) },
+            )]),
+            functions: BTreeMap::from([(DebugFnId(0), function("main"))]),
+            ..DebugInfo::default()
+        };
+        program.debug_artifact.debug_symbols = vec![debug_info];
+        let mut context = program.context();
+
+        // step until the element is assigned, which is written into the array
+        let x_1 = "x[1]".parse().unwrap();
+        while context.evaluate(&x_1) != Ok(Value::Field(FieldElement::from(9u128))) {
+            let result = context.step_into_opcode();
+            assert!(matches!(result, DebugCommandResult::Ok), "Unexpected result {result:?}");
+        }
+
+        context.set_variable(&x_1, &"4".parse().unwrap()).unwrap();
+        assert_eq!(context.evaluate(&x_1), Ok(Value::Field(FieldElement::from(4u128))));
+        let memory = context.get_brillig_memory().unwrap();
+        assert_eq!(memory[12].to_field(), FieldElement::from(4u128));
+
+        // once y shares the array, writing into it would change y too
+        let result = context.step_into_opcode();
+        assert!(matches!(result, DebugCommandResult::Ok), "Unexpected result {result:?}");
+        let result = context.set_variable(&"x[0]".parse().unwrap(), &"1".parse().unwrap());
+        assert!(matches!(result, Err(SetVariableError::SharedArray(_))));
+        let memory = context.get_brillig_memory().unwrap();
+        assert_eq!(memory[11].to_field(), FieldElement::from(5u128));
+    }
+
+    #[test]
+    fn test_function_calls() {
+        let memory_address = |address| ValueOrArray::MemoryAddress(MemoryAddress::direct(address));
+
+        // unconstrained fn double(x: Field) -> Field { x + x }
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::direct(1),
+                    bit_size: BitSize::Integer(IntegerBitSize::U32),
+                    value: FieldElement::one(),
+                },
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::direct(2),
+                    bit_size: BitSize::Integer(IntegerBitSize::U32),
+                    value: FieldElement::zero(),
+                },
+                BrilligOpcode::CalldataCopy {
+                    destination_address: MemoryAddress::direct(3),
+                    size_address: MemoryAddress::direct(1),
+                    offset_address: MemoryAddress::direct(2),
+                },
+                // Debug instrumentation is skipped when calling the function
+                BrilligOpcode::ForeignCall {
+                    function: "__debug_fn_enter".into(),
+                    destinations: vec![],
+                    destination_value_types: vec![],
+                    inputs: vec![memory_address(2)],
+                    input_value_types: vec![HeapValueType::field()],
+                },
+                BrilligOpcode::BinaryFieldOp {
+                    destination: MemoryAddress::direct(3),
+                    op: BinaryFieldOp::Add,
+                    lhs: MemoryAddress::direct(3),
+                    rhs: MemoryAddress::direct(3),
+                },
+                BrilligOpcode::Const {
+                    destination: MemoryAddress::direct(4),
+                    bit_size: BitSize::Integer(IntegerBitSize::U32),
+                    value: FieldElement::from(3u128),
+                },
+                BrilligOpcode::Stop {
+                    return_data: HeapVector {
+                        pointer: MemoryAddress::direct(4),
+                        size: MemoryAddress::direct(1),
+                    },
+                },
+            ],
+        };
+        let (w_x, w_y) = (Witness(1), Witness(2));
+        let opcodes = vec![Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![BrilligInputs::Single(Expression {
+                linear_combinations: vec![(FieldElement::one(), w_x)],
+                ..Expression::default()
+            })],
+            outputs: vec![BrilligOutputs::Simple(w_y)],
+            predicate: None,
+        }];
+        let initial_witness = BTreeMap::from([(w_x, FieldElement::from(21u128))]).into();
+        let mut program = TestProgram::new(opcodes, vec![brillig_bytecode], initial_witness);
+        program.brillig_names = vec![String::from("double")];
+        let context = program.context();
+
+        let evaluate = |source: &str| context.evaluate(&source.parse().unwrap());
+        assert_eq!(evaluate("double(21)"), Ok(Value::Field(FieldElement::from(42u128))));
+        assert_eq!(evaluate("double(double(3)) == 12"), Ok(Value::Bool(true)));
+        assert_eq!(
+            evaluate("double(1, 2)"),
+            Err(ExpressionError::ArgumentCount {
+                function: String::from("double"),
+                expected: 1,
+                found: 2,
+            })
+        );
+        assert_eq!(
+            evaluate("triple(1)"),
+            Err(ExpressionError::UnknownFunction(String::from("triple")))
+        );
+
+        // Calls run apart from the program, which has not started executing
+        assert_eq!(context.get_current_debug_location(), Some(acir_location(0)));
+        assert_eq!(context.get_witness_map().get(&w_y), None);
+    }
+
+    #[test]
+    fn test_hit_condition_from_str() {
+        assert_eq!("3".parse::<HitCondition>().ok(), Some(HitCondition::GreaterOrEqual(3)));
+        assert_eq!("== 3".parse::<HitCondition>().ok(), Some(HitCondition::Equal(3)));
+        assert_eq!(">=3".parse::<HitCondition>().ok(), Some(HitCondition::GreaterOrEqual(3)));
+        assert_eq!("<3".parse::<HitCondition>().ok(), Some(HitCondition::Less(3)));
+        assert_eq!("%2".parse::<HitCondition>().ok(), Some(HitCondition::Multiple(2)));
+        assert!("%0".parse::<HitCondition>().is_err());
+        assert!("x".parse::<HitCondition>().is_err());
+    }
+
     #[test]
     fn test_address_debug_location_mapping() {
         let solver = StubbedBlackBoxSolver::default();
@@ -1437,6 +3180,7 @@ mod tests {
                 String::new(),
             )),
             brillig_functions,
+            &[],
         );
 
         let locations =
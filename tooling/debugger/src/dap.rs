@@ -1,13 +1,15 @@
 use std::collections::BTreeMap;
 use std::io::{Read, Write};
 
+use acvm::acir::native_types::Witness;
 use acvm::{BlackBoxFunctionSolver, FieldElement};
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use nargo::NargoError;
 
 use crate::DebugProject;
-use crate::context::{DebugCommandResult, DebugLocation, RunParams};
-use crate::context::{DebugContext, DebugExecutionResult};
+use crate::context::{BreakpointCondition, DebugCommandResult, DebugLocation, RunParams};
+use crate::context::{DebugContext, DebugExecutionResult, HitCondition, Watchpoint};
+use crate::expressions::Expression;
 use crate::foreign_calls::DefaultDebugForeignCallExecutor;
 
 use dap::errors::ServerError;
@@ -15,9 +17,10 @@ use dap::events::StoppedEventBody;
 use dap::prelude::Event;
 use dap::requests::{Command, Request, SetBreakpointsArguments};
 use dap::responses::{
-    ContinueResponse, DisassembleResponse, ResponseBody, ScopesResponse, SetBreakpointsResponse,
-    SetExceptionBreakpointsResponse, SetInstructionBreakpointsResponse, StackTraceResponse,
-    ThreadsResponse, VariablesResponse,
+    ContinueResponse, DataBreakpointInfoResponse, DisassembleResponse, EvaluateResponse,
+    ResponseBody, ScopesResponse, SetBreakpointsResponse, SetDataBreakpointsResponse,
+    SetExceptionBreakpointsResponse, SetInstructionBreakpointsResponse, SetVariableResponse,
+    StackTraceResponse, ThreadsResponse, VariablesResponse,
 };
 use dap::server::Server;
 use dap::types::{
@@ -25,6 +28,7 @@ use dap::types::{
     StoppedEventReason, Thread, Variable,
 };
 use noirc_artifacts::debug::DebugArtifact;
+use noirc_errors::debug_info::DebugVarId;
 
 use fm::FileId;
 
@@ -36,8 +40,9 @@ pub struct DapSession<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElem
     debug_artifact: &'a DebugArtifact,
     running: bool,
     next_breakpoint_id: BreakpointId,
-    instruction_breakpoints: Vec<(DebugLocation, BreakpointId)>,
-    source_breakpoints: BTreeMap<FileId, Vec<(DebugLocation, BreakpointId)>>,
+    instruction_breakpoints: Vec<(DebugLocation, BreakpointId, BreakpointCondition)>,
+    source_breakpoints: BTreeMap<FileId, Vec<(DebugLocation, BreakpointId, BreakpointCondition)>>,
+    data_breakpoints: Vec<(Watchpoint, BreakpointId)>,
     last_result: DebugCommandResult,
 }
 
@@ -78,6 +83,7 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                 project.package_name.clone(),
             )),
             &project.compiled_program.program.unconstrained_functions,
+            &project.compiled_program.brillig_names,
         );
         Self {
             server,
@@ -87,6 +93,7 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
             next_breakpoint_id: 1,
             instruction_breakpoints: vec![],
             source_breakpoints: BTreeMap::new(),
+            data_breakpoints: vec![],
             last_result: DebugCommandResult::Ok,
         }
     }
@@ -141,6 +148,12 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                 Command::SetInstructionBreakpoints(_) => {
                     self.handle_set_instruction_breakpoints(req)?;
                 }
+                Command::DataBreakpointInfo(_) => {
+                    self.handle_data_breakpoint_info(req)?;
+                }
+                Command::SetDataBreakpoints(_) => {
+                    self.handle_set_data_breakpoints(req)?;
+                }
                 Command::Threads => {
                     self.server.respond(req.success(ResponseBody::Threads(ThreadsResponse {
                         threads: vec![Thread { id: 0, name: "main".to_string() }],
@@ -179,12 +192,29 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                 Command::Continue(_) => {
                     self.handle_continue(req)?;
                 }
+                Command::StepBack(ref args) => {
+                    let granularity =
+                        args.granularity.as_ref().unwrap_or(&SteppingGranularity::Statement);
+                    match granularity {
+                        SteppingGranularity::Instruction => self.handle_step_back(req)?,
+                        _ => self.handle_reverse_next(req)?,
+                    }
+                }
+                Command::ReverseContinue(_) => {
+                    self.handle_reverse_continue(req)?;
+                }
                 Command::Scopes(_) => {
                     self.handle_scopes(req)?;
                 }
                 Command::Variables(ref _args) => {
                     self.handle_variables(req)?;
                 }
+                Command::Evaluate(_) => {
+                    self.handle_evaluate(req)?;
+                }
+                Command::SetVariable(_) => {
+                    self.handle_set_variable(req)?;
+                }
                 _ => {
                     eprintln!("ERROR: unhandled command: {:?}", req.command);
                 }
@@ -326,15 +356,36 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         self.handle_execution_result(result)
     }
 
+    fn handle_step_back(&mut self, req: Request) -> Result<(), ServerError> {
+        let result = self.context.step_back();
+        eprintln!("INFO: stepped back by instruction with result {result:?}");
+        self.server.respond(req.ack()?)?;
+        self.handle_execution_result(result)
+    }
+
+    fn handle_reverse_next(&mut self, req: Request) -> Result<(), ServerError> {
+        let result = self.context.reverse_next();
+        eprintln!("INFO: stepped back by statement with result {result:?}");
+        self.server.respond(req.ack()?)?;
+        self.handle_execution_result(result)
+    }
+
+    fn handle_reverse_continue(&mut self, req: Request) -> Result<(), ServerError> {
+        let result = self.context.reverse_continue();
+        eprintln!("INFO: reverse continue with result {result:?}");
+        self.server.respond(req.ack()?)?;
+        self.handle_execution_result(result)
+    }
+
     fn find_breakpoints_at_location(&self, debug_location: &DebugLocation) -> Vec<i64> {
         let mut result = vec![];
-        for (location, id) in &self.instruction_breakpoints {
+        for (location, id, _) in &self.instruction_breakpoints {
             if debug_location == location {
                 result.push(*id);
             }
         }
         for breakpoints in self.source_breakpoints.values() {
-            for (location, id) in breakpoints {
+            for (location, id, _) in breakpoints {
                 if debug_location == location {
                     result.push(*id);
                 }
@@ -362,12 +413,32 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
             }
             DebugCommandResult::BreakpointReached(location) => {
                 let breakpoint_ids = self.find_breakpoints_at_location(location);
+                let condition_error = self.context.take_breakpoint_condition_error();
                 self.server.send_event(Event::Stopped(StoppedEventBody {
                     reason: StoppedEventReason::Breakpoint,
                     description: Some(String::from("Paused at breakpoint")),
                     thread_id: Some(0),
                     preserve_focus_hint: Some(false),
-                    text: None,
+                    text: condition_error.map(|error| {
+                        format!("Could not evaluate the breakpoint condition: {error}")
+                    }),
+                    all_threads_stopped: Some(false),
+                    hit_breakpoint_ids: Some(breakpoint_ids),
+                }))?;
+            }
+            DebugCommandResult::WatchpointTriggered(hit) => {
+                let breakpoint_ids = self
+                    .data_breakpoints
+                    .iter()
+                    .filter(|(watchpoint, _)| *watchpoint == hit.watchpoint)
+                    .map(|(_, id)| *id)
+                    .collect();
+                self.server.send_event(Event::Stopped(StoppedEventBody {
+                    reason: StoppedEventReason::Breakpoint,
+                    description: Some(String::from("Paused at data breakpoint")),
+                    thread_id: Some(0),
+                    preserve_focus_hint: Some(false),
+                    text: Some(hit.to_string()),
                     all_threads_stopped: Some(false),
                     hit_breakpoint_ids: Some(breakpoint_ids),
                 }))?;
@@ -385,12 +456,12 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
 
     fn reinstall_breakpoints(&mut self) {
         self.context.clear_breakpoints();
-        for (location, _) in &self.instruction_breakpoints {
-            self.context.add_breakpoint(*location);
+        for (location, _, condition) in &self.instruction_breakpoints {
+            self.context.add_breakpoint(*location, condition.clone());
         }
         for breakpoints in self.source_breakpoints.values() {
-            for (location, _) in breakpoints {
-                self.context.add_breakpoint(*location);
+            for (location, _, condition) in breakpoints {
+                self.context.add_breakpoint(*location, condition.clone());
             }
         }
     }
@@ -401,7 +472,7 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         };
 
         // compute breakpoints to set and return
-        let mut breakpoints_to_set: Vec<(DebugLocation, i64)> = vec![];
+        let mut breakpoints_to_set: Vec<(DebugLocation, i64, BreakpointCondition)> = vec![];
         let breakpoints: Vec<Breakpoint> = args
             .breakpoints
             .iter()
@@ -426,8 +497,21 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                         ..Breakpoint::default()
                     };
                 };
+                let condition = match parse_breakpoint_condition(
+                    &breakpoint.condition,
+                    &breakpoint.hit_condition,
+                ) {
+                    Ok(condition) => condition,
+                    Err(message) => {
+                        return Breakpoint {
+                            verified: false,
+                            message: Some(message),
+                            ..Breakpoint::default()
+                        };
+                    }
+                };
                 let id = self.get_next_breakpoint_id();
-                breakpoints_to_set.push((location, id));
+                breakpoints_to_set.push((location, id, condition));
                 Breakpoint {
                     id: Some(id),
                     verified: true,
@@ -469,7 +553,7 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         let Some(breakpoints) = &args.breakpoints else {
             return vec![];
         };
-        let mut breakpoints_to_set: Vec<(DebugLocation, i64)> = vec![];
+        let mut breakpoints_to_set: Vec<(DebugLocation, i64, BreakpointCondition)> = vec![];
         let breakpoints = breakpoints
             .iter()
             .map(|breakpoint| {
@@ -494,10 +578,23 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
                         ..Breakpoint::default()
                     };
                 }
+                let condition = match parse_breakpoint_condition(
+                    &breakpoint.condition,
+                    &breakpoint.hit_condition,
+                ) {
+                    Ok(condition) => condition,
+                    Err(message) => {
+                        return Breakpoint {
+                            verified: false,
+                            message: Some(message),
+                            ..Breakpoint::default()
+                        };
+                    }
+                };
                 let breakpoint_address = self.context.debug_location_to_address(&location);
                 let instruction_reference = format!("{breakpoint_address}");
                 let breakpoint_id = self.get_next_breakpoint_id();
-                breakpoints_to_set.push((location, breakpoint_id));
+                breakpoints_to_set.push((location, breakpoint_id, condition));
                 Breakpoint {
                     id: Some(breakpoint_id),
                     verified: true,
@@ -527,6 +624,86 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         Ok(())
     }
 
+    fn handle_data_breakpoint_info(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::DataBreakpointInfo(ref args) = req.command else {
+            unreachable!("handle_data_breakpoint_info called on a different request");
+        };
+        let scope = args.variables_reference.map(ScopeReferences::from);
+        let watchpoint = match scope {
+            Some(ScopeReferences::Locals) => self.context.variable_watchpoint(&args.name),
+            Some(ScopeReferences::WitnessMap) => {
+                args.name.strip_prefix('_').and_then(|index| index.parse().ok()).map(|index| {
+                    Watchpoint::Witness {
+                        circuit_id: self.context.get_current_circuit_id(),
+                        witness: Witness(index),
+                    }
+                })
+            }
+            _ => None,
+        };
+        let response = match watchpoint {
+            Some(watchpoint) => DataBreakpointInfoResponse {
+                data_id: Some(watchpoint_data_id(&watchpoint)),
+                description: format!("Pause when {watchpoint} changes"),
+                access_types: None,
+                can_persist: Some(false),
+            },
+            None => DataBreakpointInfoResponse {
+                data_id: None,
+                description: String::from("Only local variables and witnesses can be watched"),
+                access_types: None,
+                can_persist: None,
+            },
+        };
+        self.server.respond(req.success(ResponseBody::DataBreakpointInfo(response)))?;
+        Ok(())
+    }
+
+    fn handle_set_data_breakpoints(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::SetDataBreakpoints(ref args) = req.command else {
+            unreachable!("handle_set_data_breakpoints called on a different request");
+        };
+        self.context.clear_watchpoints();
+        let mut data_breakpoints = vec![];
+        let breakpoints: Vec<Breakpoint> = args
+            .breakpoints
+            .iter()
+            .map(|breakpoint| {
+                let has_condition = [&breakpoint.condition, &breakpoint.hit_condition]
+                    .into_iter()
+                    .any(|condition| {
+                        condition.as_ref().is_some_and(|value| !value.trim().is_empty())
+                    });
+                if has_condition {
+                    return Breakpoint {
+                        verified: false,
+                        message: Some(String::from(
+                            "Conditions are not supported on data breakpoints",
+                        )),
+                        ..Breakpoint::default()
+                    };
+                }
+                let Some(watchpoint) = parse_watchpoint_data_id(&breakpoint.data_id) else {
+                    return Breakpoint {
+                        verified: false,
+                        message: Some(String::from("Invalid data breakpoint")),
+                        ..Breakpoint::default()
+                    };
+                };
+                self.context.add_watchpoint(watchpoint.clone());
+                let id = self.get_next_breakpoint_id();
+                data_breakpoints.push((watchpoint, id));
+                Breakpoint { id: Some(id), verified: true, ..Breakpoint::default() }
+            })
+            .collect();
+        self.data_breakpoints = data_breakpoints;
+
+        self.server.respond(req.success(ResponseBody::SetDataBreakpoints(
+            SetDataBreakpointsResponse { breakpoints },
+        )))?;
+        Ok(())
+    }
+
     fn handle_scopes(&mut self, req: Request) -> Result<(), ServerError> {
         self.server.respond(req.success(ResponseBody::Scopes(ScopesResponse {
             scopes: vec![
@@ -598,6 +775,71 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         Ok(())
     }
 
+    /// Evaluates expressions from the watch panel, hovers and the debug console. They are
+    /// always evaluated over the variables of the current function, whatever frame is selected.
+    fn handle_evaluate(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::Evaluate(ref args) = req.command else {
+            unreachable!("handle_evaluate called on a different request");
+        };
+        let result = args
+            .expression
+            .parse::<Expression>()
+            .and_then(|expression| self.context.evaluate(&expression));
+        let response = match result {
+            Ok(value) => req.success(ResponseBody::Evaluate(EvaluateResponse {
+                result: value.to_string(),
+                type_field: Some(value.type_name()),
+                variables_reference: 0,
+                ..EvaluateResponse::default()
+            })),
+            Err(error) => req.error(&error.to_string()),
+        };
+        self.server.respond(response)?;
+        Ok(())
+    }
+
+    fn handle_set_variable(&mut self, req: Request) -> Result<(), ServerError> {
+        let Command::SetVariable(ref args) = req.command else {
+            unreachable!("handle_set_variable called on a different request");
+        };
+        let result = match ScopeReferences::from(args.variables_reference) {
+            ScopeReferences::Locals => self.set_local_variable(&args.name, &args.value),
+            ScopeReferences::WitnessMap => self.set_witness(&args.name, &args.value),
+            _ => Err(String::from("Only local variables and witnesses can be set")),
+        };
+        let response = match result {
+            Ok(value) => req.success(ResponseBody::SetVariable(SetVariableResponse {
+                value,
+                ..SetVariableResponse::default()
+            })),
+            Err(error) => req.error(&error),
+        };
+        self.server.respond(response)?;
+        Ok(())
+    }
+
+    /// Sets a local variable, returning its new value as shown in the Locals scope
+    fn set_local_variable(&mut self, name: &str, value: &str) -> Result<String, String> {
+        let target = Expression::Variable(name.to_string());
+        let value = value.parse::<Expression>().map_err(|error| error.to_string())?;
+        self.context.set_variable(&target, &value).map_err(|error| error.to_string())?;
+        let variable =
+            self.build_local_variables().into_iter().find(|variable| variable.name == name);
+        Ok(variable.map(|variable| variable.value).unwrap_or_default())
+    }
+
+    /// Sets a witness of the Witness Map scope, returning its new value as shown there
+    fn set_witness(&mut self, name: &str, value: &str) -> Result<String, String> {
+        let Some(index) = name.strip_prefix('_').and_then(|index| index.parse::<u32>().ok()) else {
+            return Err(format!("Invalid witness {name}"));
+        };
+        let Some(value) = FieldElement::try_from_str(value) else {
+            return Err(format!("Invalid witness value: {value}"));
+        };
+        self.context.overwrite_witness(Witness(index), value);
+        Ok(format!("{value:?}"))
+    }
+
     pub fn last_error(self) -> Option<NargoError<FieldElement>> {
         match self.last_result {
             DebugCommandResult::Error(error) => Some(error),
@@ -606,6 +848,52 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
     }
 }
 
+/// Parses the `condition` and `hitCondition` of a breakpoint request, ignoring empty ones
+fn parse_breakpoint_condition(
+    condition: &Option<String>,
+    hit_condition: &Option<String>,
+) -> Result<BreakpointCondition, String> {
+    let non_empty = |value: &Option<String>| {
+        value.as_deref().map(str::trim).filter(|value| !value.is_empty()).map(str::to_owned)
+    };
+    let condition = non_empty(condition)
+        .map(|condition| condition.parse::<Expression>())
+        .transpose()
+        .map_err(|error| format!("Invalid condition: {error}"))?;
+    let hit_condition = non_empty(hit_condition)
+        .map(|hit_condition| hit_condition.parse::<HitCondition>())
+        .transpose()
+        .map_err(|error| error.to_string())?;
+    Ok(BreakpointCondition { condition, hit_condition })
+}
+
+/// Identifies a watchpoint in `dataBreakpointInfo` and `setDataBreakpoints` requests
+fn watchpoint_data_id(watchpoint: &Watchpoint) -> String {
+    match watchpoint {
+        Watchpoint::Variable { name, var_id, frame } => {
+            format!("variable:{frame}:{}:{name}", var_id.0)
+        }
+        Watchpoint::Witness { circuit_id, witness } => {
+            format!("witness:{circuit_id}:{}", witness.witness_index())
+        }
+    }
+}
+
+fn parse_watchpoint_data_id(data_id: &str) -> Option<Watchpoint> {
+    if let Some(variable) = data_id.strip_prefix("variable:") {
+        let mut parts = variable.splitn(3, ':');
+        let frame = parts.next()?.parse().ok()?;
+        let var_id = DebugVarId(parts.next()?.parse().ok()?);
+        let name = parts.next()?.to_string();
+        return Some(Watchpoint::Variable { name, var_id, frame });
+    }
+    let (circuit_id, index) = data_id.strip_prefix("witness:")?.split_once(':')?;
+    Some(Watchpoint::Witness {
+        circuit_id: circuit_id.parse().ok()?,
+        witness: Witness(index.parse().ok()?),
+    })
+}
+
 pub fn run_session<R: Read, W: Write>(
     server: &mut Server<R, W>,
     project: DebugProject,
@@ -0,0 +1,1258 @@
+//! Expressions over the variables in scope, as used in breakpoint conditions and evaluated by
+//! the `eval` command of the REPL and the `evaluate` request of the DAP server. Variables and
+//! their fields and elements can also be resolved as places to assign values to.
+//!
+//! The supported syntax is a subset of Noir's: integer and boolean literals, variables, struct
+//! and tuple member accesses, array and slice indexing, function calls, the unary operators `!`
+//! and `-`, and the arithmetic, comparison and bitwise binary operators. `&&` and `||` are also
+//! accepted on booleans, and short-circuit. Operands are checked against the `PrintableType`s of
+//! the variables they come from, so that `x + y` fails if `x` is a `u8` and `y` is a `Field`.
+//! Integer literals take the type of the other operand, and those too large for an `i128` can
+//! only be `Field`s.
+
+use std::fmt;
+use std::str::FromStr;
+
+use acvm::{AcirField, FieldElement};
+use noirc_printable_type::{PrintableType, PrintableValue, PrintableValueDisplay};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub(crate) enum ExpressionError {
+    #[error("Unexpected character `{0}`")]
+    UnexpectedCharacter(char),
+    #[error("Invalid integer literal `{0}`")]
+    InvalidInteger(String),
+    #[error("Expected {expected}, found {found}")]
+    UnexpectedToken { expected: &'static str, found: String },
+    #[error("Unknown variable `{0}`")]
+    UnknownVariable(String),
+    #[error("Type {typ} has no field `{field}`")]
+    UnknownField { typ: String, field: String },
+    #[error("Type {0} cannot be indexed")]
+    NotIndexable(String),
+    #[error("Cannot index with a value of type {0}")]
+    InvalidIndex(String),
+    #[error("Index {index} is out of bounds for length {length}")]
+    IndexOutOfBounds { index: String, length: usize },
+    #[error("Cannot apply `{op}` to {typ}")]
+    InvalidUnaryOperand { op: UnaryOp, typ: String },
+    #[error("Cannot apply `{op}` to {lhs} and {rhs}")]
+    InvalidOperands { op: BinaryOp, lhs: String, rhs: String },
+    #[error("Literal {value} does not fit in {typ}")]
+    LiteralOutOfRange { value: String, typ: String },
+    #[error("Arithmetic overflow in {0}")]
+    Overflow(String),
+    #[error("Division by zero")]
+    DivisionByZero,
+    #[error("Expected a condition of type bool, found {0}")]
+    NotABoolean(String),
+    #[error("Unknown function `{0}`")]
+    UnknownFunction(String),
+    #[error("Function `{function}` takes {expected} field elements as arguments, found {found}")]
+    ArgumentCount { function: String, expected: usize, found: usize },
+    #[error("Values of type {0} cannot be passed to functions")]
+    UnsupportedArgument(String),
+    #[error("Call to `{function}` failed: {message}")]
+    CallFailed { function: String, message: String },
+    #[error("Only variables and their fields and elements can be assigned")]
+    NotAssignable,
+    #[error("Values of type {0} cannot be assigned")]
+    UnassignableType(String),
+    #[error("Expected a value of type {expected}, found {found}")]
+    TypeMismatch { expected: String, found: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnaryOp {
+    Not,
+    Negate,
+}
+
+impl fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnaryOp::Not => write!(f, "!"),
+            UnaryOp::Negate => write!(f, "-"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    BitAnd,
+    BitOr,
+    Xor,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Subtract => "-",
+            BinaryOp::Multiply => "*",
+            BinaryOp::Divide => "/",
+            BinaryOp::Modulo => "%",
+            BinaryOp::Equal => "==",
+            BinaryOp::NotEqual => "!=",
+            BinaryOp::Less => "<",
+            BinaryOp::LessOrEqual => "<=",
+            BinaryOp::Greater => ">",
+            BinaryOp::GreaterOrEqual => ">=",
+            BinaryOp::BitAnd => "&",
+            BinaryOp::BitOr => "|",
+            BinaryOp::Xor => "^",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+        }
+    }
+
+    /// Returns the result of this operator if it is an ordering comparison
+    fn compare<T: Ord>(self, lhs: T, rhs: T) -> Option<bool> {
+        match self {
+            BinaryOp::Less => Some(lhs < rhs),
+            BinaryOp::LessOrEqual => Some(lhs <= rhs),
+            BinaryOp::Greater => Some(lhs > rhs),
+            BinaryOp::GreaterOrEqual => Some(lhs >= rhs),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// Binary operators grouped by precedence, from the loosest to the tightest binding.
+/// As in Noir, comparisons bind tighter than the bitwise operators.
+const PRECEDENCE: [&[BinaryOp]; 8] = [
+    &[BinaryOp::Or],
+    &[BinaryOp::And],
+    &[BinaryOp::BitOr],
+    &[BinaryOp::Xor],
+    &[BinaryOp::BitAnd],
+    &[
+        BinaryOp::Equal,
+        BinaryOp::NotEqual,
+        BinaryOp::LessOrEqual,
+        BinaryOp::GreaterOrEqual,
+        BinaryOp::Less,
+        BinaryOp::Greater,
+    ],
+    &[BinaryOp::Add, BinaryOp::Subtract],
+    &[BinaryOp::Multiply, BinaryOp::Divide, BinaryOp::Modulo],
+];
+
+/// The functions that expressions can call
+pub(crate) trait Functions {
+    /// Calls the function with the given name on the field elements of its arguments, laid out
+    /// one after the other
+    fn call(&self, name: &str, arguments: Vec<FieldElement>) -> Result<Value, ExpressionError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expression {
+    Integer(i128),
+    /// An integer literal too large for an `i128`, which can only be a `Field`
+    FieldLiteral(FieldElement),
+    Bool(bool),
+    Variable(String),
+    /// A struct field or tuple element access, such as `x.field` or `x.0`
+    Member(Box<Expression>, String),
+    Index(Box<Expression>, Box<Expression>),
+    Unary(UnaryOp, Box<Expression>),
+    Binary(Box<Expression>, BinaryOp, Box<Expression>),
+    Call(String, Vec<Expression>),
+}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { tokens: tokenize(source)?, position: 0 };
+        let expression = parser.parse_binary(0)?;
+        match parser.next() {
+            None => Ok(expression),
+            token => Err(unexpected("the end of the expression", token)),
+        }
+    }
+}
+
+impl Expression {
+    /// Evaluates this expression as a condition, which must be of type `bool`
+    pub(crate) fn evaluate_condition(
+        &self,
+        variables: &[(&str, &PrintableValue<FieldElement>, &PrintableType)],
+        functions: &dyn Functions,
+    ) -> Result<bool, ExpressionError> {
+        match self.evaluate(variables, functions)? {
+            Value::Bool(value) => Ok(value),
+            value => Err(ExpressionError::NotABoolean(value.type_name())),
+        }
+    }
+
+    /// Evaluates this expression to a value of any type
+    pub(crate) fn evaluate(
+        &self,
+        variables: &[(&str, &PrintableValue<FieldElement>, &PrintableType)],
+        functions: &dyn Functions,
+    ) -> Result<Value, ExpressionError> {
+        match self {
+            Expression::Integer(value) => Ok(Value::Literal(*value)),
+            Expression::FieldLiteral(value) => Ok(Value::FieldLiteral(*value)),
+            Expression::Bool(value) => Ok(Value::Bool(*value)),
+            Expression::Variable(name) => {
+                // Later variables shadow earlier ones with the same name
+                let (_, value, typ) = variables
+                    .iter()
+                    .rev()
+                    .find(|(variable, ..)| *variable == name.as_str())
+                    .ok_or_else(|| ExpressionError::UnknownVariable(name.clone()))?;
+                Ok(Value::from_printable(value, typ))
+            }
+            Expression::Member(expression, member) => {
+                expression.evaluate(variables, functions)?.member(member)
+            }
+            Expression::Index(expression, index) => {
+                let collection = expression.evaluate(variables, functions)?;
+                collection.index(index.evaluate(variables, functions)?)
+            }
+            Expression::Unary(op, expression) => {
+                expression.evaluate(variables, functions)?.unary(*op)
+            }
+            Expression::Binary(lhs, op, rhs) => {
+                let lhs = lhs.evaluate(variables, functions)?;
+                match (op, &lhs) {
+                    (BinaryOp::And, Value::Bool(false)) => Ok(Value::Bool(false)),
+                    (BinaryOp::Or, Value::Bool(true)) => Ok(Value::Bool(true)),
+                    _ => lhs.binary(*op, rhs.evaluate(variables, functions)?),
+                }
+            }
+            Expression::Call(name, arguments) => {
+                let mut fields = Vec::new();
+                for argument in arguments {
+                    fields.extend(argument.evaluate(variables, functions)?.to_fields()?);
+                }
+                functions.call(name, fields)
+            }
+        }
+    }
+
+    /// Resolves this expression as a place that can be assigned a value, such as `x`, `p.x`
+    /// or `a[i + 1]`. Indexes can be any expression.
+    pub(crate) fn place(
+        &self,
+        variables: &[(&str, &PrintableValue<FieldElement>, &PrintableType)],
+        functions: &dyn Functions,
+    ) -> Result<Place, ExpressionError> {
+        match self {
+            Expression::Variable(name) => {
+                let (_, _, typ) = variables
+                    .iter()
+                    .rev()
+                    .find(|(variable, ..)| *variable == name.as_str())
+                    .ok_or_else(|| ExpressionError::UnknownVariable(name.clone()))?;
+                Ok(Place { variable: name.clone(), offset: 0, typ: (*typ).clone() })
+            }
+            Expression::Member(expression, member) => {
+                let place = expression.place(variables, functions)?;
+                let (position, types): (_, Vec<&PrintableType>) = match &place.typ {
+                    PrintableType::Struct { fields, .. } => (
+                        fields.iter().position(|(name, _)| name == member),
+                        fields.iter().map(|(_, typ)| typ).collect(),
+                    ),
+                    PrintableType::Tuple { types } => (
+                        member.parse::<usize>().ok().filter(|index| *index < types.len()),
+                        types.iter().collect(),
+                    ),
+                    _ => (None, vec![]),
+                };
+                let Some(position) = position else {
+                    return Err(ExpressionError::UnknownField {
+                        typ: type_name(&place.typ),
+                        field: member.clone(),
+                    });
+                };
+                let offset = types[..position]
+                    .iter()
+                    .map(|typ| flattened_size(typ))
+                    .sum::<Option<usize>>()
+                    .ok_or_else(|| ExpressionError::UnassignableType(type_name(&place.typ)))?;
+                Ok(Place {
+                    variable: place.variable,
+                    offset: place.offset + offset,
+                    typ: types[position].clone(),
+                })
+            }
+            Expression::Index(expression, index) => {
+                let place = expression.place(variables, functions)?;
+                let (length, typ) = match &place.typ {
+                    PrintableType::Array { length, typ } => (*length as usize, typ),
+                    // The position of the elements of a slice depends on its length
+                    PrintableType::Slice { .. } => {
+                        return Err(ExpressionError::UnassignableType(type_name(&place.typ)));
+                    }
+                    _ => return Err(ExpressionError::NotIndexable(type_name(&place.typ))),
+                };
+                let index = index.evaluate(variables, functions)?;
+                let Some(position) = index.position()?.filter(|position| *position < length) else {
+                    return Err(ExpressionError::IndexOutOfBounds {
+                        index: index.to_string(),
+                        length,
+                    });
+                };
+                let element_size = flattened_size(typ)
+                    .ok_or_else(|| ExpressionError::UnassignableType(type_name(typ)))?;
+                Ok(Place {
+                    variable: place.variable,
+                    offset: place.offset + position * element_size,
+                    typ: (**typ).clone(),
+                })
+            }
+            _ => Err(ExpressionError::NotAssignable),
+        }
+    }
+}
+
+/// A variable, or a field or element of one, that can be assigned a value
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Place {
+    pub(crate) variable: String,
+    /// The position of the place among the field elements of the variable, which are laid out
+    /// as they are passed to foreign calls
+    pub(crate) offset: usize,
+    pub(crate) typ: PrintableType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Integer(i128),
+    FieldLiteral(FieldElement),
+    Identifier(String),
+    Symbol(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Integer(value) => write!(f, "{value}"),
+            Token::FieldLiteral(value) => write!(f, "{value}"),
+            Token::Identifier(name) => write!(f, "{name}"),
+            Token::Symbol(symbol) => write!(f, "{symbol}"),
+        }
+    }
+}
+
+/// Symbols are matched in order, so those that are a prefix of another one come last
+const SYMBOLS: [&str; 23] = [
+    "==", "!=", "<=", ">=", "&&", "||", "(", ")", "[", "]", ".", ",", "!", "-", "+", "*", "/", "%",
+    "<", ">", "&", "|", "^",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ExpressionError> {
+    let is_identifier_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut tokens = Vec::new();
+    let mut rest = source.trim_start();
+    while let Some(char) = rest.chars().next() {
+        let length = if char.is_ascii_digit() {
+            let length = rest.find(|c: char| !is_identifier_char(c)).unwrap_or(rest.len());
+            tokens.push(parse_integer(&rest[..length])?);
+            length
+        } else if is_identifier_char(char) {
+            let length = rest.find(|c: char| !is_identifier_char(c)).unwrap_or(rest.len());
+            tokens.push(Token::Identifier(rest[..length].to_string()));
+            length
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            tokens.push(Token::Symbol(*symbol));
+            symbol.len()
+        } else {
+            return Err(ExpressionError::UnexpectedCharacter(char));
+        };
+        rest = rest[length..].trim_start();
+    }
+    Ok(tokens)
+}
+
+/// Parses a decimal or hexadecimal integer literal, as a field element if it does not fit in an
+/// `i128`
+fn parse_integer(literal: &str) -> Result<Token, ExpressionError> {
+    let invalid = || ExpressionError::InvalidInteger(literal.to_string());
+    let digits = literal.replace('_', "").to_ascii_lowercase();
+    let (digits, radix) = match digits.strip_prefix("0x") {
+        Some(hex_digits) => (hex_digits, 16),
+        None => (digits.as_str(), 10),
+    };
+    if let Ok(value) = i128::from_str_radix(digits, radix) {
+        return Ok(Token::Integer(value));
+    }
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() || !digits.chars().all(|char| char.is_digit(radix)) {
+        return Err(invalid());
+    }
+    // As in Noir, literals that do not fit in a field element are rejected instead of reduced
+    let modulus = FieldElement::modulus().to_str_radix(radix);
+    if (digits.len(), digits) >= (modulus.len(), modulus.as_str()) {
+        return Err(invalid());
+    }
+    let value = if radix == 16 {
+        FieldElement::from_hex(digits)
+    } else {
+        FieldElement::try_from_str(digits)
+    };
+    value.map(Token::FieldLiteral).ok_or_else(invalid)
+}
+
+fn unexpected(expected: &'static str, token: Option<Token>) -> ExpressionError {
+    let found = match token {
+        Some(token) => format!("`{token}`"),
+        None => String::from("the end of the expression"),
+    };
+    ExpressionError::UnexpectedToken { expected, found }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek_symbol(&self) -> Option<&'static str> {
+        match self.tokens.get(self.position) {
+            Some(Token::Symbol(symbol)) => Some(*symbol),
+            _ => None,
+        }
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        let found = self.peek_symbol() == Some(symbol);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, symbol: &'static str) -> Result<(), ExpressionError> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            let token = self.next();
+            Err(unexpected(symbol, token))
+        }
+    }
+
+    /// Parses a chain of binary operators at the given precedence level or tighter
+    fn parse_binary(&mut self, level: usize) -> Result<Expression, ExpressionError> {
+        let Some(operators) = PRECEDENCE.get(level) else {
+            return self.parse_unary();
+        };
+        let mut lhs = self.parse_binary(level + 1)?;
+        while let Some(op) =
+            operators.iter().find(|op| self.peek_symbol() == Some(op.symbol())).copied()
+        {
+            self.position += 1;
+            let rhs = self.parse_binary(level + 1)?;
+            lhs = Expression::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, ExpressionError> {
+        let op = if self.eat("!") {
+            UnaryOp::Not
+        } else if self.eat("-") {
+            UnaryOp::Negate
+        } else {
+            return self.parse_postfix();
+        };
+        Ok(Expression::Unary(op, Box::new(self.parse_unary()?)))
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expression, ExpressionError> {
+        let mut expression = self.parse_primary()?;
+        loop {
+            if self.eat(".") {
+                let member = match self.next() {
+                    Some(Token::Identifier(name)) => name,
+                    Some(Token::Integer(index)) => index.to_string(),
+                    token => return Err(unexpected("a field name", token)),
+                };
+                expression = Expression::Member(Box::new(expression), member);
+            } else if self.eat("[") {
+                let index = self.parse_binary(0)?;
+                self.expect("]")?;
+                expression = Expression::Index(Box::new(expression), Box::new(index));
+            } else if let (Expression::Variable(name), Some("(")) =
+                (&expression, self.peek_symbol())
+            {
+                self.position += 1;
+                let mut arguments = Vec::new();
+                while !self.eat(")") {
+                    arguments.push(self.parse_binary(0)?);
+                    if !self.eat(",") {
+                        self.expect(")")?;
+                        break;
+                    }
+                }
+                expression = Expression::Call(name.clone(), arguments);
+            } else {
+                return Ok(expression);
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, ExpressionError> {
+        match self.next() {
+            Some(Token::Integer(value)) => Ok(Expression::Integer(value)),
+            Some(Token::FieldLiteral(value)) => Ok(Expression::FieldLiteral(value)),
+            Some(Token::Identifier(name)) => Ok(match name.as_str() {
+                "true" => Expression::Bool(true),
+                "false" => Expression::Bool(false),
+                _ => Expression::Variable(name),
+            }),
+            Some(Token::Symbol("(")) => {
+                let expression = self.parse_binary(0)?;
+                self.expect(")")?;
+                Ok(expression)
+            }
+            token => Err(unexpected("an expression", token)),
+        }
+    }
+}
+
+/// The result of evaluating an expression
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Bool(bool),
+    Field(FieldElement),
+    Unsigned {
+        value: u128,
+        width: u32,
+    },
+    Signed {
+        value: i128,
+        width: u32,
+    },
+    /// An integer literal, which takes the type of the value it is combined with
+    Literal(i128),
+    /// An integer literal too large for an `i128`, which can only be a `Field`
+    FieldLiteral(FieldElement),
+    /// A value of any other type, which only supports equality, member accesses and indexing
+    Compound(PrintableValue<FieldElement>, PrintableType),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::Field(value) | Value::FieldLiteral(value) => write!(f, "{value}"),
+            Value::Unsigned { value, .. } => write!(f, "{value}"),
+            Value::Signed { value, .. } | Value::Literal(value) => write!(f, "{value}"),
+            Value::Compound(value, typ) => {
+                write!(f, "{}", PrintableValueDisplay::Plain(value.clone(), typ.clone()))
+            }
+        }
+    }
+}
+
+impl Value {
+    pub(crate) fn from_printable(
+        value: &PrintableValue<FieldElement>,
+        typ: &PrintableType,
+    ) -> Value {
+        match (value, typ) {
+            // References are decoded as the value they point to
+            (_, PrintableType::Reference { typ, .. }) => Value::from_printable(value, typ),
+            (PrintableValue::Field(field), PrintableType::Field) => Value::Field(*field),
+            (PrintableValue::Field(field), PrintableType::Boolean) => Value::Bool(field.is_one()),
+            (PrintableValue::Field(field), PrintableType::UnsignedInteger { width }) => {
+                Value::Unsigned { value: field.to_u128(), width: *width }
+            }
+            (PrintableValue::Field(field), PrintableType::SignedInteger { width }) => {
+                // Signed integers are stored in two's complement relative to their width
+                let shift = 128 - width;
+                let value = ((field.to_u128() << shift) as i128) >> shift;
+                Value::Signed { value, width: *width }
+            }
+            _ => Value::Compound(value.clone(), typ.clone()),
+        }
+    }
+
+    pub(crate) fn type_name(&self) -> String {
+        match self {
+            Value::Bool(_) => String::from("bool"),
+            Value::Field(_) => String::from("Field"),
+            Value::Unsigned { width, .. } => format!("u{width}"),
+            Value::Signed { width, .. } => format!("i{width}"),
+            Value::Literal(_) | Value::FieldLiteral(_) => String::from("an integer literal"),
+            Value::Compound(_, typ) => type_name(typ),
+        }
+    }
+
+    fn same_type(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Bool(_), Value::Bool(_))
+            | (Value::Field(_), Value::Field(_))
+            | (Value::Literal(_), Value::Literal(_))
+            | (Value::FieldLiteral(_), Value::FieldLiteral(_)) => true,
+            (Value::Unsigned { width, .. }, Value::Unsigned { width: other_width, .. })
+            | (Value::Signed { width, .. }, Value::Signed { width: other_width, .. }) => {
+                width == other_width
+            }
+            (Value::Compound(_, typ), Value::Compound(_, other_typ)) => typ == other_typ,
+            _ => false,
+        }
+    }
+
+    fn unsigned(value: Option<u128>, width: u32) -> Result<Value, ExpressionError> {
+        match value {
+            Some(value) if width >= 128 || value >> width == 0 => {
+                Ok(Value::Unsigned { value, width })
+            }
+            _ => Err(ExpressionError::Overflow(format!("u{width}"))),
+        }
+    }
+
+    fn signed(value: Option<i128>, width: u32) -> Result<Value, ExpressionError> {
+        match value {
+            Some(value) if width >= 128 || (value >> (width - 1)) == (value >> 127) => {
+                Ok(Value::Signed { value, width })
+            }
+            _ => Err(ExpressionError::Overflow(format!("i{width}"))),
+        }
+    }
+
+    fn literal(value: Option<i128>) -> Result<Value, ExpressionError> {
+        value.map(Value::Literal).ok_or_else(|| ExpressionError::Overflow(String::from("i128")))
+    }
+
+    /// Gives an integer literal the type of `other`, if it is numeric. Other values, and
+    /// literals combined with values of other types, are kept as they are.
+    fn typed_as(self, other: &Value) -> Result<Value, ExpressionError> {
+        let converted = match (&self, other) {
+            (Value::Literal(value), Value::Field(_) | Value::FieldLiteral(_)) => {
+                Some(Value::Field(FieldElement::from(*value)))
+            }
+            (Value::Literal(value), Value::Unsigned { width, .. }) => {
+                Value::unsigned(u128::try_from(*value).ok(), *width).ok()
+            }
+            (Value::Literal(value), Value::Signed { width, .. }) => {
+                Value::signed(Some(*value), *width).ok()
+            }
+            (
+                Value::FieldLiteral(value),
+                Value::Field(_) | Value::Literal(_) | Value::FieldLiteral(_),
+            ) => Some(Value::Field(*value)),
+            (Value::FieldLiteral(_), Value::Unsigned { .. } | Value::Signed { .. }) => None,
+            _ => return Ok(self),
+        };
+        converted.ok_or_else(|| ExpressionError::LiteralOutOfRange {
+            value: self.to_string(),
+            typ: other.type_name(),
+        })
+    }
+
+    fn member(self, member: &str) -> Result<Value, ExpressionError> {
+        let field = match &self {
+            Value::Compound(
+                PrintableValue::Struct(field_values),
+                PrintableType::Struct { fields, .. },
+            ) => fields
+                .iter()
+                .find(|(name, _)| name == member)
+                .and_then(|(name, typ)| Some((field_values.get(name)?, typ))),
+            Value::Compound(
+                PrintableValue::Vec { array_elements, .. },
+                PrintableType::Tuple { types },
+            ) => member
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| Some((array_elements.get(index)?, types.get(index)?))),
+            _ => None,
+        };
+        match field {
+            Some((value, typ)) => Ok(Value::from_printable(value, typ)),
+            None => Err(ExpressionError::UnknownField {
+                typ: self.type_name(),
+                field: member.to_string(),
+            }),
+        }
+    }
+
+    /// Converts this value to the field element that represents it in a place of the given
+    /// type, which must be a primitive one
+    pub(crate) fn to_field_as(&self, typ: &PrintableType) -> Result<FieldElement, ExpressionError> {
+        let target = match typ {
+            PrintableType::Field => Value::Field(FieldElement::zero()),
+            PrintableType::Boolean => Value::Bool(false),
+            PrintableType::UnsignedInteger { width } => Value::Unsigned { value: 0, width: *width },
+            PrintableType::SignedInteger { width } => Value::Signed { value: 0, width: *width },
+            _ => return Err(ExpressionError::UnassignableType(type_name(typ))),
+        };
+        let value = self.clone().typed_as(&target)?;
+        if !value.same_type(&target) {
+            return Err(ExpressionError::TypeMismatch {
+                expected: target.type_name(),
+                found: self.type_name(),
+            });
+        }
+        Ok(value.primitive_field().expect("Only primitive values have the type of a place"))
+    }
+
+    /// The field element that represents this value, if it is a primitive one. Integer literals
+    /// are taken as `Field`s.
+    fn primitive_field(&self) -> Option<FieldElement> {
+        match self {
+            Value::Bool(value) => {
+                Some(if *value { FieldElement::one() } else { FieldElement::zero() })
+            }
+            Value::Field(value) | Value::FieldLiteral(value) => Some(*value),
+            Value::Unsigned { value, .. } => Some(FieldElement::from(*value)),
+            Value::Signed { value, width } => {
+                // Signed integers are stored in two's complement relative to their width
+                Some(FieldElement::from(*value as u128 & (u128::MAX >> (128 - width))))
+            }
+            Value::Literal(value) => Some(FieldElement::from(*value)),
+            Value::Compound(..) => None,
+        }
+    }
+
+    /// The field elements that make up this value, as laid out when passed to functions
+    fn to_fields(&self) -> Result<Vec<FieldElement>, ExpressionError> {
+        let mut elements = Vec::new();
+        let flattened = match self {
+            Value::Compound(value, typ) => flatten(value, typ, &mut elements),
+            value => value.primitive_field().map(|field| elements.push(field)),
+        };
+        match flattened {
+            Some(()) => Ok(elements),
+            None => Err(ExpressionError::UnsupportedArgument(self.type_name())),
+        }
+    }
+
+    /// The position that indexing with this value refers to, if it is a valid one
+    fn position(&self) -> Result<Option<usize>, ExpressionError> {
+        let position = match self {
+            Value::Unsigned { value, .. } => Some(*value),
+            Value::Field(value) | Value::FieldLiteral(value) => value.try_into_u128(),
+            Value::Literal(value) | Value::Signed { value, .. } => u128::try_from(*value).ok(),
+            Value::Bool(_) | Value::Compound(..) => {
+                return Err(ExpressionError::InvalidIndex(self.type_name()));
+            }
+        };
+        Ok(position.and_then(|position| usize::try_from(position).ok()))
+    }
+
+    fn index(self, index: Value) -> Result<Value, ExpressionError> {
+        let position = index.position()?;
+        let Value::Compound(
+            PrintableValue::Vec { array_elements, .. },
+            PrintableType::Array { typ, .. } | PrintableType::Slice { typ },
+        ) = &self
+        else {
+            return Err(ExpressionError::NotIndexable(self.type_name()));
+        };
+        let element = position.and_then(|position| array_elements.get(position));
+        match element {
+            Some(element) => Ok(Value::from_printable(element, typ)),
+            None => Err(ExpressionError::IndexOutOfBounds {
+                index: index.to_string(),
+                length: array_elements.len(),
+            }),
+        }
+    }
+
+    fn unary(self, op: UnaryOp) -> Result<Value, ExpressionError> {
+        match (op, &self) {
+            (UnaryOp::Not, Value::Bool(value)) => Ok(Value::Bool(!value)),
+            (UnaryOp::Not, Value::Unsigned { value, width }) => {
+                let mask = if *width >= 128 { u128::MAX } else { (1 << width) - 1 };
+                Ok(Value::Unsigned { value: !value & mask, width: *width })
+            }
+            (UnaryOp::Not, Value::Signed { value, width }) => {
+                Ok(Value::Signed { value: !value, width: *width })
+            }
+            (UnaryOp::Negate, Value::Field(value)) => Ok(Value::Field(-*value)),
+            (UnaryOp::Negate, Value::FieldLiteral(value)) => Ok(Value::FieldLiteral(-*value)),
+            (UnaryOp::Negate, Value::Signed { value, width }) => {
+                Value::signed(value.checked_neg(), *width)
+            }
+            (UnaryOp::Negate, Value::Literal(value)) => Value::literal(value.checked_neg()),
+            _ => Err(ExpressionError::InvalidUnaryOperand { op, typ: self.type_name() }),
+        }
+    }
+
+    fn binary(self, op: BinaryOp, rhs: Value) -> Result<Value, ExpressionError> {
+        let rhs = rhs.typed_as(&self)?;
+        let lhs = self.typed_as(&rhs)?;
+        match (op, &lhs, &rhs) {
+            (BinaryOp::Equal | BinaryOp::NotEqual, ..) if lhs.same_type(&rhs) => {
+                Ok(Value::Bool((lhs == rhs) == (op == BinaryOp::Equal)))
+            }
+            (
+                BinaryOp::BitAnd | BinaryOp::BitOr | BinaryOp::Xor | BinaryOp::And | BinaryOp::Or,
+                Value::Bool(lhs),
+                Value::Bool(rhs),
+            ) => Ok(Value::Bool(match op {
+                BinaryOp::BitAnd | BinaryOp::And => lhs & rhs,
+                BinaryOp::BitOr | BinaryOp::Or => lhs | rhs,
+                _ => lhs ^ rhs,
+            })),
+            (
+                BinaryOp::Add | BinaryOp::Subtract | BinaryOp::Multiply | BinaryOp::Divide,
+                Value::Field(lhs),
+                Value::Field(rhs),
+            ) => match op {
+                BinaryOp::Add => Ok(Value::Field(*lhs + *rhs)),
+                BinaryOp::Subtract => Ok(Value::Field(*lhs - *rhs)),
+                BinaryOp::Multiply => Ok(Value::Field(*lhs * *rhs)),
+                _ if rhs.is_zero() => Err(ExpressionError::DivisionByZero),
+                _ => Ok(Value::Field(*lhs / *rhs)),
+            },
+            (BinaryOp::Equal | BinaryOp::NotEqual | BinaryOp::And | BinaryOp::Or, ..) => {
+                Err(invalid_operands(op, &lhs, &rhs))
+            }
+            (
+                _,
+                Value::Unsigned { value: lhs, width },
+                Value::Unsigned { value: rhs, width: rhs_width },
+            ) if width == rhs_width => unsigned_binary(op, *lhs, *rhs, *width),
+            (
+                _,
+                Value::Signed { value: lhs, width },
+                Value::Signed { value: rhs, width: rhs_width },
+            ) if width == rhs_width => {
+                signed_binary(op, *lhs, *rhs, |value| Value::signed(value, *width))
+            }
+            (_, Value::Literal(lhs), Value::Literal(rhs)) => {
+                signed_binary(op, *lhs, *rhs, Value::literal)
+            }
+            _ => Err(invalid_operands(op, &lhs, &rhs)),
+        }
+    }
+}
+
+fn invalid_operands(op: BinaryOp, lhs: &Value, rhs: &Value) -> ExpressionError {
+    ExpressionError::InvalidOperands { op, lhs: lhs.type_name(), rhs: rhs.type_name() }
+}
+
+/// Applies an arithmetic, comparison or bitwise operator to two unsigned integers
+fn unsigned_binary(
+    op: BinaryOp,
+    lhs: u128,
+    rhs: u128,
+    width: u32,
+) -> Result<Value, ExpressionError> {
+    if let Some(result) = op.compare(lhs, rhs) {
+        return Ok(Value::Bool(result));
+    }
+    if matches!(op, BinaryOp::Divide | BinaryOp::Modulo) && rhs == 0 {
+        return Err(ExpressionError::DivisionByZero);
+    }
+    let value = match op {
+        BinaryOp::Add => lhs.checked_add(rhs),
+        BinaryOp::Subtract => lhs.checked_sub(rhs),
+        BinaryOp::Multiply => lhs.checked_mul(rhs),
+        BinaryOp::Divide => lhs.checked_div(rhs),
+        BinaryOp::Modulo => lhs.checked_rem(rhs),
+        BinaryOp::BitAnd => Some(lhs & rhs),
+        BinaryOp::BitOr => Some(lhs | rhs),
+        BinaryOp::Xor => Some(lhs ^ rhs),
+        _ => unreachable!("`{op}` is not an integer operator"),
+    };
+    Value::unsigned(value, width)
+}
+
+/// Applies an arithmetic, comparison or bitwise operator to two signed integers, building the
+/// result with `make_value` unless it is a comparison
+fn signed_binary(
+    op: BinaryOp,
+    lhs: i128,
+    rhs: i128,
+    make_value: impl Fn(Option<i128>) -> Result<Value, ExpressionError>,
+) -> Result<Value, ExpressionError> {
+    if let Some(result) = op.compare(lhs, rhs) {
+        return Ok(Value::Bool(result));
+    }
+    if matches!(op, BinaryOp::Divide | BinaryOp::Modulo) && rhs == 0 {
+        return Err(ExpressionError::DivisionByZero);
+    }
+    let value = match op {
+        BinaryOp::Add => lhs.checked_add(rhs),
+        BinaryOp::Subtract => lhs.checked_sub(rhs),
+        BinaryOp::Multiply => lhs.checked_mul(rhs),
+        BinaryOp::Divide => lhs.checked_div(rhs),
+        BinaryOp::Modulo => lhs.checked_rem(rhs),
+        BinaryOp::BitAnd => Some(lhs & rhs),
+        BinaryOp::BitOr => Some(lhs | rhs),
+        BinaryOp::Xor => Some(lhs ^ rhs),
+        _ => unreachable!("`{op}` is not an integer operator"),
+    };
+    make_value(value)
+}
+
+/// The number of field elements that make up a value of the given type, as laid out when
+/// passed to foreign calls, or `None` if it depends on the value
+pub(crate) fn flattened_size(typ: &PrintableType) -> Option<usize> {
+    match typ {
+        PrintableType::Field
+        | PrintableType::SignedInteger { .. }
+        | PrintableType::UnsignedInteger { .. }
+        | PrintableType::Boolean => Some(1),
+        PrintableType::Array { length, typ } => {
+            flattened_size(typ).map(|size| size * *length as usize)
+        }
+        PrintableType::Tuple { types } => types.iter().map(flattened_size).sum(),
+        PrintableType::Struct { fields, .. } => {
+            fields.iter().map(|(_, typ)| flattened_size(typ)).sum()
+        }
+        PrintableType::String { length } => Some(*length as usize),
+        PrintableType::Function { env, .. } => flattened_size(env).map(|size| size + 1),
+        PrintableType::Reference { typ, .. } => flattened_size(typ),
+        PrintableType::Unit => Some(0),
+        // Slices and format strings start with their length, and enums with their variant
+        PrintableType::Slice { .. }
+        | PrintableType::FmtString { .. }
+        | PrintableType::Enum { .. } => None,
+    }
+}
+
+/// Appends the field elements that make up a value of the given type, as laid out when passed
+/// to functions, or returns `None` if values of that type cannot be passed
+fn flatten(
+    value: &PrintableValue<FieldElement>,
+    typ: &PrintableType,
+    elements: &mut Vec<FieldElement>,
+) -> Option<()> {
+    match (value, typ) {
+        (
+            PrintableValue::Field(field),
+            PrintableType::Field
+            | PrintableType::Boolean
+            | PrintableType::UnsignedInteger { .. }
+            | PrintableType::SignedInteger { .. },
+        ) => elements.push(*field),
+        (PrintableValue::Vec { array_elements, .. }, PrintableType::Array { typ, .. }) => {
+            for element in array_elements {
+                flatten(element, typ, elements)?;
+            }
+        }
+        (PrintableValue::Vec { array_elements, .. }, PrintableType::Tuple { types }) => {
+            for (element, typ) in array_elements.iter().zip(types) {
+                flatten(element, typ, elements)?;
+            }
+        }
+        (PrintableValue::Struct(field_values), PrintableType::Struct { fields, .. }) => {
+            for (name, typ) in fields {
+                flatten(field_values.get(name)?, typ, elements)?;
+            }
+        }
+        (PrintableValue::String(string), PrintableType::String { .. }) => {
+            elements.extend(string.bytes().map(|byte| FieldElement::from(u128::from(byte))));
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Renders a type the way it is written in Noir
+fn type_name(typ: &PrintableType) -> String {
+    match typ {
+        PrintableType::Field => String::from("Field"),
+        PrintableType::Array { length, typ } => format!("[{}; {length}]", type_name(typ)),
+        PrintableType::Slice { typ } => format!("[{}]", type_name(typ)),
+        PrintableType::Tuple { types } => {
+            let types: Vec<_> = types.iter().map(type_name).collect();
+            format!("({})", types.join(", "))
+        }
+        PrintableType::SignedInteger { width } => format!("i{width}"),
+        PrintableType::UnsignedInteger { width } => format!("u{width}"),
+        PrintableType::Boolean => String::from("bool"),
+        PrintableType::Struct { name, .. } | PrintableType::Enum { name, .. } => name.clone(),
+        PrintableType::String { length } => format!("str<{length}>"),
+        PrintableType::FmtString { length, .. } => format!("fmtstr<{length}, _>"),
+        PrintableType::Function { .. } => String::from("function"),
+        PrintableType::Reference { typ, mutable } => {
+            format!("&{}{}", if *mutable { "mut " } else { "" }, type_name(typ))
+        }
+        PrintableType::Unit => String::from("()"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::FieldElement;
+    use noirc_printable_type::{PrintableType, PrintableValue};
+
+    use super::{BinaryOp, Expression, ExpressionError, Functions, UnaryOp, Value, type_name};
+
+    fn field(value: i128) -> PrintableValue<FieldElement> {
+        PrintableValue::Field(FieldElement::from(value))
+    }
+
+    /// Provides a `sum` function, which adds up the field elements of its arguments
+    struct Sum;
+
+    impl Functions for Sum {
+        fn call(&self, name: &str, arguments: Vec<FieldElement>) -> Result<Value, ExpressionError> {
+            match name {
+                "sum" => Ok(Value::Field(
+                    arguments
+                        .into_iter()
+                        .fold(FieldElement::from(0_u128), |sum, argument| sum + argument),
+                )),
+                _ => Err(ExpressionError::UnknownFunction(name.to_string())),
+            }
+        }
+    }
+
+    fn evaluate_with<T>(
+        source: &str,
+        evaluate: impl FnOnce(
+            &Expression,
+            &[(&str, &PrintableValue<FieldElement>, &PrintableType)],
+            &dyn Functions,
+        ) -> Result<T, ExpressionError>,
+    ) -> Result<T, ExpressionError> {
+        let point_type = PrintableType::Struct {
+            name: String::from("Point"),
+            fields: vec![
+                (String::from("x"), PrintableType::UnsignedInteger { width: 8 }),
+                (String::from("y"), PrintableType::SignedInteger { width: 8 }),
+            ],
+        };
+        let point = PrintableValue::Struct(BTreeMap::from([
+            (String::from("x"), field(3)),
+            // -2 as an i8 in two's complement
+            (String::from("y"), field(254)),
+        ]));
+        let points_type = PrintableType::Array { length: 2, typ: Box::new(point_type.clone()) };
+        let points =
+            PrintableValue::Vec { array_elements: vec![point.clone(), point], is_slice: false };
+        let index_type = PrintableType::UnsignedInteger { width: 32 };
+        let index = field(1);
+        let field_type = PrintableType::Field;
+        let value = field(10);
+        let bool_type = PrintableType::Boolean;
+        let flag = field(1);
+
+        let variables = [
+            ("points", &points, &points_type),
+            ("i", &index, &index_type),
+            ("f", &value, &field_type),
+            ("flag", &flag, &bool_type),
+        ];
+        evaluate(&source.parse()?, &variables, &Sum)
+    }
+
+    fn evaluate(source: &str) -> Result<bool, ExpressionError> {
+        evaluate_with(source, Expression::evaluate_condition)
+    }
+
+    /// Evaluates an expression of any type, displayed along with its type
+    fn evaluate_value(source: &str) -> Result<String, ExpressionError> {
+        evaluate_with(source, |expression, variables, functions| {
+            let value = expression.evaluate(variables, functions)?;
+            Ok(format!("{value}: {}", value.type_name()))
+        })
+    }
+
+    #[test]
+    fn parses_with_precedence() {
+        let expression: Expression = "a + b * 2 == c & !d".parse().unwrap();
+        let variable = |name: &str| Box::new(Expression::Variable(name.to_string()));
+        let sum = Expression::Binary(
+            variable("a"),
+            BinaryOp::Add,
+            Box::new(Expression::Binary(
+                variable("b"),
+                BinaryOp::Multiply,
+                Box::new(Expression::Integer(2)),
+            )),
+        );
+        let comparison = Expression::Binary(Box::new(sum), BinaryOp::Equal, variable("c"));
+        let negation = Expression::Unary(UnaryOp::Not, variable("d"));
+        assert_eq!(
+            expression,
+            Expression::Binary(Box::new(comparison), BinaryOp::BitAnd, Box::new(negation))
+        );
+
+        assert!(matches!(
+            "(a + ".parse::<Expression>(),
+            Err(ExpressionError::UnexpectedToken { expected: "an expression", .. })
+        ));
+        assert_eq!("a # b".parse::<Expression>(), Err(ExpressionError::UnexpectedCharacter('#')));
+    }
+
+    #[test]
+    fn evaluates_conditions_over_variables() {
+        assert_eq!(evaluate("points[i].x == 3"), Ok(true));
+        assert_eq!(evaluate("points[0].y == -2 && points[1].y < 0"), Ok(true));
+        assert_eq!(evaluate("points[i - 1].x * 2 + 1 != 7"), Ok(false));
+        assert_eq!(evaluate("f / 5 == 2 & flag"), Ok(true));
+        assert_eq!(evaluate("i >= 2 || points[i].x > 2"), Ok(true));
+        // `&&` short-circuits, so the out of bounds access is not evaluated
+        assert_eq!(evaluate("i > 5 && points[i + 5].x == 0"), Ok(false));
+    }
+
+    #[test]
+    fn reports_type_errors() {
+        assert_eq!(
+            evaluate("points[i].x + i == 0"),
+            Err(ExpressionError::InvalidOperands {
+                op: BinaryOp::Add,
+                lhs: String::from("u8"),
+                rhs: String::from("u32"),
+            })
+        );
+        assert_eq!(
+            evaluate("points[i].x == 256"),
+            Err(ExpressionError::LiteralOutOfRange {
+                value: String::from("256"),
+                typ: String::from("u8"),
+            })
+        );
+        assert_eq!(
+            evaluate("points[i].x * 100 == 0"),
+            Err(ExpressionError::Overflow(String::from("u8")))
+        );
+        assert_eq!(
+            evaluate("points[2].x == 0"),
+            Err(ExpressionError::IndexOutOfBounds { index: String::from("2"), length: 2 })
+        );
+        assert_eq!(
+            evaluate("points[0].z == 0"),
+            Err(ExpressionError::UnknownField {
+                typ: String::from("Point"),
+                field: String::from("z"),
+            })
+        );
+        assert_eq!(evaluate("j == 0"), Err(ExpressionError::UnknownVariable(String::from("j"))));
+        assert_eq!(evaluate("i"), Err(ExpressionError::NotABoolean(String::from("u32"))));
+    }
+
+    #[test]
+    fn evaluates_values_of_any_type() {
+        assert_eq!(evaluate_value("points[i].x * 2"), Ok(String::from("6: u8")));
+        assert_eq!(evaluate_value("points[0].y - 1"), Ok(String::from("-3: i8")));
+        assert_eq!(evaluate_value("f + 1"), Ok(String::from("11: Field")));
+        assert_eq!(evaluate_value("!flag"), Ok(String::from("false: bool")));
+        assert_eq!(evaluate_value("points[1]"), Ok(String::from("Point { x: 3, y: -2 }: Point")));
+    }
+
+    #[test]
+    fn calls_functions_with_flattened_arguments() {
+        assert_eq!(
+            "sum(a, b[0])".parse::<Expression>(),
+            Ok(Expression::Call(
+                String::from("sum"),
+                vec![
+                    Expression::Variable(String::from("a")),
+                    Expression::Index(
+                        Box::new(Expression::Variable(String::from("b"))),
+                        Box::new(Expression::Integer(0)),
+                    ),
+                ],
+            ))
+        );
+        // The second point is made of 3 and -2 as an i8 in two's complement
+        assert_eq!(evaluate_value("sum(points[1], f, 1)"), Ok(String::from("268: Field")));
+        assert_eq!(evaluate_value("sum() + 1"), Ok(String::from("1: Field")));
+        assert_eq!(evaluate("sum(i, flag) == 2"), Ok(true));
+        assert_eq!(
+            evaluate_value("hash(f)"),
+            Err(ExpressionError::UnknownFunction(String::from("hash")))
+        );
+        assert!(matches!(
+            "points[0](1)".parse::<Expression>(),
+            Err(ExpressionError::UnexpectedToken { expected: "the end of the expression", .. })
+        ));
+    }
+
+    #[test]
+    fn parses_literals_too_large_for_an_i128_as_fields() {
+        // The largest field element, which is -1
+        let largest = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+        assert_eq!(evaluate_value(&format!("f + {largest}")), Ok(String::from("9: Field")));
+        assert_eq!(evaluate(&format!("-{largest} == 1")), Ok(true));
+
+        let two_to_the_128 = "340_282_366_920_938_463_463_374_607_431_768_211_456";
+        assert_eq!(evaluate(&format!("f * {two_to_the_128} / {two_to_the_128} == 10")), Ok(true));
+        assert_eq!(
+            evaluate(&format!("points[i].x == {two_to_the_128}")),
+            Err(ExpressionError::LiteralOutOfRange {
+                value: String::from("340282366920938463463374607431768211456"),
+                typ: String::from("u8"),
+            })
+        );
+
+        let modulus = "0x30644E72E131A029B85045B68181585D2833E84879B9709143E1F593F0000001";
+        assert_eq!(
+            modulus.parse::<Expression>(),
+            Err(ExpressionError::InvalidInteger(modulus.to_string()))
+        );
+    }
+
+    #[test]
+    fn resolves_places_to_assign() {
+        let place = |source: &str| {
+            evaluate_with(source, |expression, variables, functions| {
+                let place = expression.place(variables, functions)?;
+                Ok((place.variable, place.offset, type_name(&place.typ)))
+            })
+        };
+        assert_eq!(place("f"), Ok((String::from("f"), 0, String::from("Field"))));
+        assert_eq!(place("points[1]"), Ok((String::from("points"), 2, String::from("Point"))));
+        assert_eq!(place("points[i].y"), Ok((String::from("points"), 3, String::from("i8"))));
+        assert_eq!(
+            place("points[i + 1].x"),
+            Err(ExpressionError::IndexOutOfBounds { index: String::from("2"), length: 2 })
+        );
+        assert_eq!(place("f + 1"), Err(ExpressionError::NotAssignable));
+
+        let signed_type = PrintableType::SignedInteger { width: 8 };
+        let to_field_as = |source: &str, typ: &PrintableType| {
+            evaluate_with(source, |expression, variables, functions| {
+                expression.evaluate(variables, functions)?.to_field_as(typ)
+            })
+        };
+        // -3 as an i8 in two's complement
+        assert_eq!(to_field_as("points[0].y - 1", &signed_type), Ok(FieldElement::from(253_u128)));
+        assert_eq!(
+            to_field_as("300", &PrintableType::UnsignedInteger { width: 8 }),
+            Err(ExpressionError::LiteralOutOfRange {
+                value: String::from("300"),
+                typ: String::from("u8"),
+            })
+        );
+        assert_eq!(
+            to_field_as("flag", &PrintableType::Field),
+            Err(ExpressionError::TypeMismatch {
+                expected: String::from("Field"),
+                found: String::from("bool"),
+            })
+        );
+    }
+}
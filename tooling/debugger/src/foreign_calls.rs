@@ -10,6 +10,7 @@ use nargo::foreign_calls::{
 };
 use noirc_artifacts::debug::{DebugArtifact, DebugVars, StackFrame};
 use noirc_errors::debug_info::{DebugFnId, DebugVarId};
+use noirc_printable_type::{PrintableType, PrintableValue};
 
 pub(crate) enum DebugForeignCall {
     VarAssign,
@@ -42,7 +43,21 @@ impl DebugForeignCall {
 pub trait DebugForeignCallExecutor: ForeignCallExecutor<FieldElement> {
     fn get_variables(&self) -> Vec<StackFrame<FieldElement>>;
     fn current_stack_frame(&self) -> Option<StackFrame<FieldElement>>;
+    /// Returns the id of the variable with the given name in the current function, along with
+    /// the depth of its stack frame
+    fn find_variable(&self, name: &str) -> Option<(DebugVarId, usize)>;
+    /// Returns the value and type of a variable of the stack frame at the given depth
+    fn get_variable(
+        &self,
+        var_id: DebugVarId,
+        frame: usize,
+    ) -> Option<(&PrintableValue<FieldElement>, &PrintableType)>;
     fn restart(&mut self, artifact: &DebugArtifact);
+    /// Returns a copy of the variables tracked so far, to be restored with `restore_debug_vars`
+    fn save_debug_vars(&self) -> DebugVars<FieldElement>;
+    fn restore_debug_vars(&mut self, debug_vars: DebugVars<FieldElement>);
+    /// Replaces the value of a variable of the current function with the given field elements
+    fn assign_var(&mut self, var_id: DebugVarId, values: &[FieldElement]);
 }
 
 #[derive(Default)]
@@ -110,13 +125,37 @@ impl DebugForeignCallExecutor for DefaultDebugForeignCallExecutor {
         self.debug_vars.current_stack_frame()
     }
 
+    fn find_variable(&self, name: &str) -> Option<(DebugVarId, usize)> {
+        self.debug_vars.find_var(name)
+    }
+
+    fn get_variable(
+        &self,
+        var_id: DebugVarId,
+        frame: usize,
+    ) -> Option<(&PrintableValue<FieldElement>, &PrintableType)> {
+        self.debug_vars.get_var(var_id, frame)
+    }
+
     fn restart(&mut self, artifact: &DebugArtifact) {
         self.debug_vars = DebugVars::default();
         self.load_artifact(artifact);
     }
+
+    fn save_debug_vars(&self) -> DebugVars<FieldElement> {
+        self.debug_vars.clone()
+    }
+
+    fn restore_debug_vars(&mut self, debug_vars: DebugVars<FieldElement>) {
+        self.debug_vars = debug_vars;
+    }
+
+    fn assign_var(&mut self, var_id: DebugVarId, values: &[FieldElement]) {
+        self.debug_vars.assign_var(var_id, values);
+    }
 }
 
-fn debug_var_id(value: &FieldElement) -> DebugVarId {
+pub(crate) fn debug_var_id(value: &FieldElement) -> DebugVarId {
     DebugVarId(value.to_u128() as u32)
 }
 
@@ -216,7 +255,32 @@ where
     fn current_stack_frame(&self) -> Option<StackFrame<FieldElement>> {
         self.handler().current_stack_frame()
     }
+
+    fn find_variable(&self, name: &str) -> Option<(DebugVarId, usize)> {
+        self.handler().find_variable(name)
+    }
+
+    fn get_variable(
+        &self,
+        var_id: DebugVarId,
+        frame: usize,
+    ) -> Option<(&PrintableValue<FieldElement>, &PrintableType)> {
+        self.handler().get_variable(var_id, frame)
+    }
+
     fn restart(&mut self, artifact: &DebugArtifact) {
         self.handler.restart(artifact);
     }
+
+    fn save_debug_vars(&self) -> DebugVars<FieldElement> {
+        self.handler().save_debug_vars()
+    }
+
+    fn restore_debug_vars(&mut self, debug_vars: DebugVars<FieldElement>) {
+        self.handler.restore_debug_vars(debug_vars);
+    }
+
+    fn assign_var(&mut self, var_id: DebugVarId, values: &[FieldElement]) {
+        self.handler.assign_var(var_id, values);
+    }
 }
@@ -0,0 +1,202 @@
+//! Calls to the unconstrained functions of the program from expressions, such as `is_valid(x)`.
+//!
+//! The unconstrained functions called from constrained code are compiled to Brillig functions
+//! of their own, which can be called by name. A call runs the function in a new Brillig VM,
+//! with the field elements of its arguments as calldata, so that the state of the program is
+//! left untouched. Compiled functions do not record their types, so calls return a `Field`, an
+//! array of `Field`s, or a tuple of those when the function has several outputs.
+
+use acvm::acir::brillig::ForeignCallResult;
+use acvm::acir::circuit::brillig::{BrilligBytecode, BrilligInputs, BrilligOutputs};
+use acvm::acir::circuit::{Circuit, Opcode};
+use acvm::brillig_vm::{ExecutionLimits, FailureReason, MemoryValue, VM, VMStatus};
+use acvm::{BlackBoxFunctionSolver, FieldElement};
+use noirc_printable_type::{PrintableType, PrintableValue};
+
+use crate::expressions::{ExpressionError, Functions, Value};
+use crate::foreign_calls::DebugForeignCall;
+
+/// The maximum number of Brillig opcodes that a call can execute, so that evaluating an
+/// expression cannot hang the debugger
+const MAX_CALL_OPCODES: usize = 10_000_000;
+
+/// How a Brillig function is called from constrained code
+struct Signature {
+    /// The number of field elements of its inputs
+    input_size: usize,
+    /// The length of each of its outputs, or `None` for those that are a single field element
+    outputs: Vec<Option<usize>>,
+}
+
+pub(crate) struct ProgramFunctions<'a, B: BlackBoxFunctionSolver<FieldElement>> {
+    backend: &'a B,
+    circuits: &'a [Circuit<FieldElement>],
+    unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
+    /// The names of the unconstrained functions, by Brillig function id
+    brillig_names: &'a [String],
+}
+
+impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ProgramFunctions<'a, B> {
+    pub(crate) fn new(
+        backend: &'a B,
+        circuits: &'a [Circuit<FieldElement>],
+        unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
+        brillig_names: &'a [String],
+    ) -> Self {
+        Self { backend, circuits, unconstrained_functions, brillig_names }
+    }
+
+    /// Finds a call to the Brillig function with the given id to learn its signature
+    fn signature(&self, function_id: usize) -> Option<Signature> {
+        self.circuits.iter().find_map(|circuit| {
+            circuit.opcodes.iter().find_map(|opcode| {
+                let Opcode::BrilligCall { id, inputs, outputs, .. } = opcode else {
+                    return None;
+                };
+                if id.as_usize() != function_id {
+                    return None;
+                }
+                let input_size = inputs
+                    .iter()
+                    .map(|input| match input {
+                        BrilligInputs::Single(_) => Some(1),
+                        BrilligInputs::Array(expressions) => Some(expressions.len()),
+                        BrilligInputs::MemoryArray(block_id) => {
+                            circuit.opcodes.iter().find_map(|opcode| match opcode {
+                                Opcode::MemoryInit { block_id: init_id, init, .. }
+                                    if init_id == block_id =>
+                                {
+                                    Some(init.len())
+                                }
+                                _ => None,
+                            })
+                        }
+                    })
+                    .sum::<Option<usize>>()?;
+                let outputs = outputs
+                    .iter()
+                    .map(|output| match output {
+                        BrilligOutputs::Simple(_) => None,
+                        BrilligOutputs::Array(witnesses) => Some(witnesses.len()),
+                    })
+                    .collect();
+                Some(Signature { input_size, outputs })
+            })
+        })
+    }
+
+    /// Runs the Brillig function with the given id, returning the field elements of its outputs
+    fn run(
+        &self,
+        name: &str,
+        function_id: usize,
+        arguments: Vec<FieldElement>,
+    ) -> Result<Vec<FieldElement>, ExpressionError> {
+        let call_failed =
+            |message: String| ExpressionError::CallFailed { function: name.to_string(), message };
+        let limits = ExecutionLimits::new(Some(MAX_CALL_OPCODES));
+        let bytecode = &self.unconstrained_functions[function_id].bytecode;
+        let mut vm = VM::new(arguments, bytecode, self.backend, false, None);
+        vm.with_execution_limits(&limits);
+        loop {
+            match vm.process_opcodes() {
+                VMStatus::Finished { return_data_offset, return_data_size } => {
+                    let memory = &vm.get_memory()[return_data_offset..];
+                    return Ok(memory[..return_data_size]
+                        .iter()
+                        .map(MemoryValue::to_field)
+                        .collect());
+                }
+                VMStatus::ForeignCallWait { function, .. } => {
+                    // Debug instrumentation calls only report on the variables of the program,
+                    // while other foreign calls could have side effects
+                    if DebugForeignCall::lookup(&function).is_none() {
+                        return Err(call_failed(format!("it calls foreign function `{function}`")));
+                    }
+                    vm.resolve_foreign_call(ForeignCallResult::default());
+                }
+                VMStatus::Failure { reason: FailureReason::RuntimeError { message }, .. } => {
+                    return Err(call_failed(message));
+                }
+                VMStatus::Failure { reason: FailureReason::Trap { .. }, .. } => {
+                    return Err(call_failed(String::from("an assertion failed")));
+                }
+                VMStatus::InProgress => unreachable!("Brillig execution stopped while in progress"),
+            }
+        }
+    }
+}
+
+impl<B: BlackBoxFunctionSolver<FieldElement>> Functions for ProgramFunctions<'_, B> {
+    fn call(&self, name: &str, arguments: Vec<FieldElement>) -> Result<Value, ExpressionError> {
+        let signatures: Vec<_> = self
+            .brillig_names
+            .iter()
+            .enumerate()
+            .filter(|(_, function_name)| *function_name == name)
+            .filter_map(|(function_id, _)| Some((function_id, self.signature(function_id)?)))
+            .collect();
+        let Some((_, first_signature)) = signatures.first() else {
+            return Err(ExpressionError::UnknownFunction(name.to_string()));
+        };
+        // A function is compiled once for each combination of generic arguments it is called
+        // with, so the one to call is told apart by the size of its arguments
+        let Some((function_id, signature)) =
+            signatures.iter().find(|(_, signature)| signature.input_size == arguments.len())
+        else {
+            return Err(ExpressionError::ArgumentCount {
+                function: name.to_string(),
+                expected: first_signature.input_size,
+                found: arguments.len(),
+            });
+        };
+
+        let results = self.run(name, *function_id, arguments)?;
+        let output_size: usize = signature.outputs.iter().map(|length| length.unwrap_or(1)).sum();
+        if results.len() != output_size {
+            return Err(ExpressionError::CallFailed {
+                function: name.to_string(),
+                message: format!("expected {output_size} results, found {}", results.len()),
+            });
+        }
+        let mut results = results.into_iter();
+        let mut outputs: Vec<_> = signature
+            .outputs
+            .iter()
+            .map(|length| match length {
+                None => (
+                    PrintableValue::Field(
+                        results.next().expect("The number of results was checked"),
+                    ),
+                    PrintableType::Field,
+                ),
+                Some(length) => (
+                    PrintableValue::Vec {
+                        array_elements: results
+                            .by_ref()
+                            .take(*length)
+                            .map(PrintableValue::Field)
+                            .collect(),
+                        is_slice: false,
+                    },
+                    PrintableType::Array {
+                        length: *length as u32,
+                        typ: Box::new(PrintableType::Field),
+                    },
+                ),
+            })
+            .collect();
+        let (value, typ) = match outputs.len() {
+            0 => (PrintableValue::Other, PrintableType::Unit),
+            1 => outputs.remove(0),
+            _ => {
+                let (values, types) = outputs.into_iter().unzip();
+                (
+                    PrintableValue::Vec { array_elements: values, is_slice: false },
+                    PrintableType::Tuple { types },
+                )
+            }
+        };
+        Ok(Value::from_printable(&value, &typ))
+    }
+}
@@ -5,9 +5,12 @@
 mod context;
 mod dap;
 pub mod errors;
+mod expressions;
 mod foreign_calls;
+mod functions;
 mod repl;
 mod source_code_printer;
+mod variable_storage;
 
 use std::io::{Read, Write};
 
@@ -1,14 +1,15 @@
 use crate::DebugProject;
 use crate::context::{
-    DebugCommandResult, DebugContext, DebugExecutionResult, DebugLocation, DebugStackFrame,
-    RunParams,
+    BreakpointCondition, DebugCommandResult, DebugContext, DebugExecutionResult, DebugLocation,
+    DebugStackFrame, HitCondition, RunParams, Watchpoint,
 };
+use crate::expressions::Expression;
 use noirc_driver::CompiledProgram;
 
 use crate::foreign_calls::DefaultDebugForeignCallExecutor;
 use noirc_artifacts::debug::DebugArtifact;
 
-use easy_repl::{CommandStatus, Repl, command};
+use easy_repl::{Command, CommandStatus, Repl, command};
 use std::cell::RefCell;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread;
@@ -38,8 +39,12 @@ type Context<'a> = DebugContext<'a, Bn254BlackBoxSolver>;
 #[derive(Debug, Clone)]
 pub(super) enum DebugCommandAPI {
     AddBreakpoint(DebugLocation),
-    AddBreakpointAtLine(i64),
+    AddBreakpointAtLine(i64, BreakpointCondition),
     DeleteBreakpoint(DebugLocation),
+    WatchVariable(String),
+    WatchWitness(u32),
+    UnwatchVariable(String),
+    UnwatchWitness(u32),
     Restart,
     StepAcirOpcode,
     StepIntoOpcode,
@@ -47,9 +52,14 @@ pub(super) enum DebugCommandAPI {
     NextOver,
     NextOut,
     Cont,
+    StepBack,
+    ReverseNext,
+    ReverseContinue,
     UpdateWitness(u32, String),
     WriteBrilligMemory(usize, String, u32),
     ShowVariables,
+    Evaluate(Expression),
+    SetVariable(Expression, Expression),
     ShowWitnessMap,
     ShowWitness(u32),
     ShowBrilligMemory,
@@ -71,6 +81,7 @@ pub struct AsyncReplDebugger<'a> {
     debug_artifact: &'a DebugArtifact,
     initial_witness: WitnessMap<FieldElement>,
     unconstrained_functions: Vec<BrilligBytecode<FieldElement>>,
+    brillig_names: Vec<String>,
     command_receiver: Receiver<DebugCommandAPI>,
     status_sender: Sender<DebuggerStatus>,
     last_result: DebugCommandResult,
@@ -97,6 +108,7 @@ impl<'a> AsyncReplDebugger<'a> {
             debug_artifact,
             last_result,
             unconstrained_functions: compiled_program.program.unconstrained_functions.clone(),
+            brillig_names: compiled_program.brillig_names.clone(),
             raw_source_printing,
             initial_witness,
             pedantic_solving,
@@ -114,6 +126,7 @@ impl<'a> AsyncReplDebugger<'a> {
         let blackbox_solver = &Bn254BlackBoxSolver(self.pedantic_solving);
         let circuits = &self.circuits.clone();
         let unconstrained_functions = &self.unconstrained_functions.clone();
+        let brillig_names = &self.brillig_names.clone();
         let mut context = DebugContext::new(
             blackbox_solver,
             circuits,
@@ -121,6 +134,7 @@ impl<'a> AsyncReplDebugger<'a> {
             self.initial_witness.clone(),
             foreign_call_executor,
             unconstrained_functions,
+            brillig_names,
         );
 
         if context.get_current_debug_location().is_none() {
@@ -135,11 +149,35 @@ impl<'a> AsyncReplDebugger<'a> {
                 self.send_status(DebuggerStatus::Busy);
                 match received {
                     DebugCommandAPI::AddBreakpoint(debug_location) => {
-                        Self::add_breakpoint_at(&mut context, debug_location);
+                        Self::add_breakpoint_at(
+                            &mut context,
+                            debug_location,
+                            BreakpointCondition::default(),
+                        );
                     }
                     DebugCommandAPI::DeleteBreakpoint(debug_location) => {
                         Self::delete_breakpoint_at(&mut context, debug_location);
                     }
+                    DebugCommandAPI::WatchVariable(name) => {
+                        match context.variable_watchpoint(&name) {
+                            Some(watchpoint) => Self::add_watchpoint(&mut context, watchpoint),
+                            None => println!("Unknown variable {name}"),
+                        }
+                    }
+                    DebugCommandAPI::WatchWitness(index) => {
+                        let watchpoint = Self::witness_watchpoint(&context, index);
+                        Self::add_watchpoint(&mut context, watchpoint);
+                    }
+                    DebugCommandAPI::UnwatchVariable(name) => {
+                        match context.watched_variable(&name) {
+                            Some(watchpoint) => Self::delete_watchpoint(&mut context, watchpoint),
+                            None => println!("Not watching {name}"),
+                        }
+                    }
+                    DebugCommandAPI::UnwatchWitness(index) => {
+                        let watchpoint = Self::witness_watchpoint(&context, index);
+                        Self::delete_watchpoint(&mut context, watchpoint);
+                    }
                     DebugCommandAPI::Restart => {
                         self.restart_session(&mut context);
                     }
@@ -168,12 +206,30 @@ impl<'a> AsyncReplDebugger<'a> {
                         println!("(Continuing execution...)");
                         context.cont()
                     }),
-                    DebugCommandAPI::AddBreakpointAtLine(line_number) => {
-                        Self::add_breakpoint_at_line(&mut context, line_number);
+                    DebugCommandAPI::StepBack => {
+                        self.handle_reverse_step(&mut context, |context| context.step_back());
+                    }
+                    DebugCommandAPI::ReverseNext => {
+                        self.handle_reverse_step(&mut context, |context| context.reverse_next());
+                    }
+                    DebugCommandAPI::ReverseContinue => {
+                        self.handle_reverse_step(&mut context, |context| {
+                            println!("(Continuing execution backwards...)");
+                            context.reverse_continue()
+                        });
+                    }
+                    DebugCommandAPI::AddBreakpointAtLine(line_number, condition) => {
+                        Self::add_breakpoint_at_line(&mut context, line_number, condition);
                     }
                     DebugCommandAPI::ShowVariables => {
                         Self::show_variables(&mut context);
                     }
+                    DebugCommandAPI::Evaluate(expression) => {
+                        Self::evaluate(&context, &expression);
+                    }
+                    DebugCommandAPI::SetVariable(target, value) => {
+                        Self::set_variable(&mut context, &target, &value);
+                    }
                     DebugCommandAPI::ShowWitnessMap => {
                         Self::show_witness_map(&mut context);
                     }
@@ -368,22 +424,30 @@ impl<'a> AsyncReplDebugger<'a> {
         }
     }
 
-    fn add_breakpoint_at(context: &mut Context<'_>, location: DebugLocation) {
+    fn add_breakpoint_at(
+        context: &mut Context<'_>,
+        location: DebugLocation,
+        condition: BreakpointCondition,
+    ) {
         if !context.is_valid_debug_location(&location) {
             println!("Invalid location {location}");
-        } else if context.add_breakpoint(location) {
+        } else if context.add_breakpoint(location, condition) {
             println!("Added breakpoint at {location}");
         } else {
             println!("Breakpoint at {location} already set");
         }
     }
 
-    fn add_breakpoint_at_line(context: &mut Context<'_>, line_number: i64) {
+    fn add_breakpoint_at_line(
+        context: &mut Context<'_>,
+        line_number: i64,
+        condition: BreakpointCondition,
+    ) {
         let best_location = context.find_opcode_at_current_file_line(line_number);
         match best_location {
             Some(location) => {
                 println!("Added breakpoint at line {line_number}");
-                Self::add_breakpoint_at(context, location);
+                Self::add_breakpoint_at(context, location, condition);
             }
             None => println!("No opcode at line {line_number}"),
         }
@@ -397,6 +461,30 @@ impl<'a> AsyncReplDebugger<'a> {
         }
     }
 
+    /// Watches a witness of the circuit being executed
+    fn witness_watchpoint(context: &Context<'_>, index: u32) -> Watchpoint {
+        Watchpoint::Witness {
+            circuit_id: context.get_current_circuit_id(),
+            witness: Witness(index),
+        }
+    }
+
+    fn add_watchpoint(context: &mut Context<'_>, watchpoint: Watchpoint) {
+        if context.add_watchpoint(watchpoint.clone()) {
+            println!("Watching {watchpoint}");
+        } else {
+            println!("Already watching {watchpoint}");
+        }
+    }
+
+    fn delete_watchpoint(context: &mut Context<'_>, watchpoint: Watchpoint) {
+        if context.delete_watchpoint(&watchpoint) {
+            println!("Stopped watching {watchpoint}");
+        } else {
+            println!("Not watching {watchpoint}");
+        }
+    }
+
     fn handle_result(&mut self, result: DebugCommandResult) {
         self.last_result = result;
         match &self.last_result {
@@ -407,6 +495,9 @@ impl<'a> AsyncReplDebugger<'a> {
             DebugCommandResult::BreakpointReached(location) => {
                 println!("Stopped at breakpoint in opcode {location}");
             }
+            DebugCommandResult::WatchpointTriggered(hit) => {
+                println!("Stopped at watchpoint: {hit}");
+            }
             DebugCommandResult::Error(error) => {
                 println!("ERROR: {error}");
             }
@@ -418,7 +509,9 @@ impl<'a> AsyncReplDebugger<'a> {
         F: Fn(&mut Context) -> DebugCommandResult,
     {
         let should_execute = match self.last_result {
-            DebugCommandResult::Ok | DebugCommandResult::BreakpointReached(..) => true,
+            DebugCommandResult::Ok
+            | DebugCommandResult::BreakpointReached(..)
+            | DebugCommandResult::WatchpointTriggered(..) => true,
             DebugCommandResult::Done => {
                 println!("Execution finished");
                 false
@@ -431,8 +524,29 @@ impl<'a> AsyncReplDebugger<'a> {
         };
         if should_execute {
             let result = step(context);
-            self.show_current_vm_status(context);
-            self.handle_result(result);
+            self.show_step_result(context, result);
+        }
+    }
+
+    /// Unlike stepping forward, going back in time is possible after execution finished or
+    /// failed
+    fn handle_reverse_step<F>(&mut self, context: &mut Context<'_>, step: F)
+    where
+        F: Fn(&mut Context) -> DebugCommandResult,
+    {
+        if context.is_at_start() {
+            println!("Already at the start of the execution");
+            return;
+        }
+        let result = step(context);
+        self.show_step_result(context, result);
+    }
+
+    fn show_step_result(&mut self, context: &mut Context<'_>, result: DebugCommandResult) {
+        self.show_current_vm_status(context);
+        self.handle_result(result);
+        if let Some(error) = context.take_breakpoint_condition_error() {
+            println!("Could not evaluate the breakpoint condition: {error}");
         }
     }
 
@@ -443,6 +557,21 @@ impl<'a> AsyncReplDebugger<'a> {
         self.show_current_vm_status(context);
     }
 
+    fn evaluate(context: &Context<'_>, expression: &Expression) {
+        match context.evaluate(expression) {
+            Ok(value) => println!("{value}"),
+            Err(error) => println!("Could not evaluate the expression: {error}"),
+        }
+    }
+
+    fn set_variable(context: &mut Context<'_>, target: &Expression, value: &Expression) {
+        if let Err(error) = context.set_variable(target, value) {
+            println!("Could not set the variable: {error}");
+            return;
+        }
+        Self::evaluate(context, target);
+    }
+
     fn show_witness_map(context: &mut Context<'_>) {
         let witness_map = context.get_witness_map();
         // NOTE: we need to clone() here to get the iterator
@@ -592,11 +721,20 @@ impl DebugController {
     pub fn next_out(&self) {
         self.call_debugger(DebugCommandAPI::NextOut);
     }
+    pub fn step_back(&self) {
+        self.call_debugger(DebugCommandAPI::StepBack);
+    }
+    pub fn reverse_next(&self) {
+        self.call_debugger(DebugCommandAPI::ReverseNext);
+    }
+    pub fn reverse_continue(&self) {
+        self.call_debugger(DebugCommandAPI::ReverseContinue);
+    }
     pub fn restart_session(&self) {
         self.call_debugger(DebugCommandAPI::Restart);
     }
-    pub fn add_breakpoint_at_line(&self, line_number: i64) {
-        self.call_debugger(DebugCommandAPI::AddBreakpointAtLine(line_number));
+    pub fn add_breakpoint_at_line(&self, line_number: i64, condition: BreakpointCondition) {
+        self.call_debugger(DebugCommandAPI::AddBreakpointAtLine(line_number, condition));
     }
     pub fn add_breakpoint_at(&self, location: DebugLocation) {
         self.call_debugger(DebugCommandAPI::AddBreakpoint(location));
@@ -604,6 +742,18 @@ impl DebugController {
     pub fn delete_breakpoint_at(&self, location: DebugLocation) {
         self.call_debugger(DebugCommandAPI::DeleteBreakpoint(location));
     }
+    pub fn watch_variable(&self, name: String) {
+        self.call_debugger(DebugCommandAPI::WatchVariable(name));
+    }
+    pub fn watch_witness(&self, index: u32) {
+        self.call_debugger(DebugCommandAPI::WatchWitness(index));
+    }
+    pub fn unwatch_variable(&self, name: String) {
+        self.call_debugger(DebugCommandAPI::UnwatchVariable(name));
+    }
+    pub fn unwatch_witness(&self, index: u32) {
+        self.call_debugger(DebugCommandAPI::UnwatchWitness(index));
+    }
     pub fn update_witness(&self, index: u32, value: String) {
         self.call_debugger(DebugCommandAPI::UpdateWitness(index, value));
     }
@@ -613,6 +763,12 @@ impl DebugController {
     pub fn show_vars(&self) {
         self.call_debugger(DebugCommandAPI::ShowVariables);
     }
+    pub fn evaluate(&self, expression: Expression) {
+        self.call_debugger(DebugCommandAPI::Evaluate(expression));
+    }
+    pub fn set_variable(&self, target: Expression, value: Expression) {
+        self.call_debugger(DebugCommandAPI::SetVariable(target, value));
+    }
     pub fn show_opcodes(&self) {
         self.call_debugger(DebugCommandAPI::ShowOpcodes);
     }
@@ -636,6 +792,51 @@ impl DebugController {
     }
 }
 
+/// Parses the conditions of a breakpoint, given as `[hits <count>] [if <condition>]`
+fn parse_breakpoint_condition(args: &[&str]) -> Result<BreakpointCondition, String> {
+    let mut condition = BreakpointCondition::default();
+    let mut args = args;
+    if let ["hits", hit_condition, rest @ ..] = args {
+        let hit_condition =
+            hit_condition.parse::<HitCondition>().map_err(|error| format!("{error}"))?;
+        condition.hit_condition = Some(hit_condition);
+        args = rest;
+    }
+    match args {
+        [] => Ok(condition),
+        ["if", expression @ ..] if !expression.is_empty() => {
+            let expression = expression.join(" ");
+            let expression = expression
+                .parse::<Expression>()
+                .map_err(|error| format!("Invalid condition: {error}"))?;
+            condition.condition = Some(expression);
+            Ok(condition)
+        }
+        _ => Err(String::from("Expected: break <line> [hits <count>] [if <condition>]")),
+    }
+}
+
+/// Parses an assignment given as `<variable> = <value>`
+fn parse_assignment(source: &str) -> Result<(Expression, Expression), String> {
+    // The `=` of the assignment, rather than one that is part of a comparison
+    let bytes = source.as_bytes();
+    let position = (0..bytes.len()).find(|&index| {
+        bytes[index] == b'='
+            && bytes.get(index + 1) != Some(&b'=')
+            && (index == 0 || !matches!(bytes[index - 1], b'=' | b'!' | b'<' | b'>'))
+    });
+    let Some(position) = position else {
+        return Err(String::from("Expected: set <variable> = <value>"));
+    };
+    let target = source[..position]
+        .parse::<Expression>()
+        .map_err(|error| format!("Invalid variable: {error}"))?;
+    let value = source[position + 1..]
+        .parse::<Expression>()
+        .map_err(|error| format!("Invalid value: {error}"))?;
+    Ok((target, value))
+}
+
 pub fn run(project: DebugProject, run_params: RunParams) -> DebugExecutionResult {
     let debug_artifact = DebugArtifact {
         debug_symbols: project.compiled_program.debug.clone(),
@@ -732,6 +933,36 @@ pub fn run(project: DebugProject, run_params: RunParams) -> DebugExecutionResult
                 }
             },
         )
+        .add(
+            "back",
+            command! {
+                "step back to the previous opcode",
+                () => || {
+                    ref_context.borrow_mut().step_back();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "reverse-next",
+            command! {
+                "step back to the start of the previous source location without diving into function calls",
+                () => || {
+                    ref_context.borrow_mut().reverse_next();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "reverse-continue",
+            command! {
+                "run backwards until the previous breakpoint or watchpoint, or the start of the program",
+                () => || {
+                    ref_context.borrow_mut().reverse_continue();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
         .add(
             "restart",
             command! {
@@ -757,7 +988,9 @@ pub fn run(project: DebugProject, run_params: RunParams) -> DebugExecutionResult
             command! {
                 "add a breakpoint at a line of the current file",
                 (line_number: i64) => |line_number| {
-                    ref_context.borrow_mut().add_breakpoint_at_line(line_number);
+                    ref_context
+                        .borrow_mut()
+                        .add_breakpoint_at_line(line_number, BreakpointCondition::default());
                     Ok(CommandStatus::Done)
                 }
             },
@@ -772,6 +1005,34 @@ pub fn run(project: DebugProject, run_params: RunParams) -> DebugExecutionResult
                 }
             },
         )
+        .add(
+            "break",
+            // Built by hand since conditions span a variable number of arguments. It comes last so
+            // that the overloads above get to parse their arguments first
+            Command {
+                description: String::from(
+                    "add a breakpoint at a line of the current file that stops only when its conditions hold: break <line> [hits <count>] [if <condition>]",
+                ),
+                args_info: vec![
+                    String::from("line_number:i64"),
+                    String::from("conditions:String"),
+                ],
+                handler: Box::new(move |args: &[&str]| {
+                    let Some((line_number, conditions)) = args.split_first() else {
+                        println!("Missing line number");
+                        return Ok(CommandStatus::Done);
+                    };
+                    match (line_number.parse::<i64>(), parse_breakpoint_condition(conditions)) {
+                        (Ok(line_number), Ok(condition)) => {
+                            ref_context.borrow_mut().add_breakpoint_at_line(line_number, condition);
+                        }
+                        (Err(_), _) => println!("Invalid line number {line_number}"),
+                        (_, Err(error)) => println!("{error}"),
+                    }
+                    Ok(CommandStatus::Done)
+                }),
+            },
+        )
         .add(
             "delete",
             command! {
@@ -782,6 +1043,54 @@ pub fn run(project: DebugProject, run_params: RunParams) -> DebugExecutionResult
                 }
             },
         )
+        .add(
+            "watch",
+            command! {
+                "stop execution when a variable of the current function changes",
+                (name: String) => |name| {
+                    ref_context.borrow_mut().watch_variable(name);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "watch",
+            command! {
+                "stop execution when a witness of the current circuit changes: watch witness <index>",
+                (kind: String, index: u32) => |kind, index| {
+                    if kind == "witness" {
+                        ref_context.borrow_mut().watch_witness(index);
+                    } else {
+                        println!("Expected: watch witness <index>");
+                    }
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "unwatch",
+            command! {
+                "stop watching a variable",
+                (name: String) => |name| {
+                    ref_context.borrow_mut().unwatch_variable(name);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "unwatch",
+            command! {
+                "stop watching a witness of the current circuit: unwatch witness <index>",
+                (kind: String, index: u32) => |kind, index| {
+                    if kind == "witness" {
+                        ref_context.borrow_mut().unwatch_witness(index);
+                    } else {
+                        println!("Expected: unwatch witness <index>");
+                    }
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
         .add(
             "witness",
             command! {
@@ -852,6 +1161,40 @@ pub fn run(project: DebugProject, run_params: RunParams) -> DebugExecutionResult
                 }
             },
         )
+        .add(
+            "set",
+            // Built by hand since assignments span a variable number of arguments
+            Command {
+                description: String::from(
+                    "assign a value to a variable of the current function, or to one of its fields or elements: set <variable> = <value>",
+                ),
+                args_info: vec![String::from("assignment:String")],
+                handler: Box::new(move |args: &[&str]| {
+                    match parse_assignment(&args.join(" ")) {
+                        Ok((target, value)) => ref_context.borrow_mut().set_variable(target, value),
+                        Err(error) => println!("{error}"),
+                    }
+                    Ok(CommandStatus::Done)
+                }),
+            },
+        )
+        .add(
+            "eval",
+            // Built by hand since expressions span a variable number of arguments
+            Command {
+                description: String::from(
+                    "evaluate an expression over the variables of the current function",
+                ),
+                args_info: vec![String::from("expression:String")],
+                handler: Box::new(move |args: &[&str]| {
+                    match args.join(" ").parse::<Expression>() {
+                        Ok(expression) => ref_context.borrow().evaluate(expression),
+                        Err(error) => println!("Invalid expression: {error}"),
+                    }
+                    Ok(CommandStatus::Done)
+                }),
+            },
+        )
         .build()
         .expect("Failed to initialize debugger repl");
 
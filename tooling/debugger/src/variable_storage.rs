@@ -0,0 +1,262 @@
+//! Tracks where the values of the variables of the program are stored, so that they can be
+//! modified by name.
+//!
+//! Debug instrumentation reports every assignment of a whole value to a variable through a
+//! `__debug_var_assign` foreign call. In unconstrained code its inputs are read from the Brillig
+//! memory cells holding the value. In constrained code the foreign call is made by a Brillig
+//! function called with the value, so the inputs of that call point at the witnesses holding it.
+//!
+//! Assignments to a field or element of a variable are reported by `__debug_member_assign`
+//! foreign calls, along with the indices leading to it. Constrained code, and unconstrained code
+//! assigning a struct field or tuple element, computes the new value into other witnesses or
+//! registers, which then store that part of the variable. Array elements are instead written
+//! in place, unless the array is shared with other values, in which case it is copied to cells
+//! that cannot be found from the foreign call.
+
+use acvm::acir::brillig::{HeapArray, HeapValueType, HeapVector, MemoryAddress, ValueOrArray};
+use acvm::acir::circuit::brillig::BrilligInputs;
+use acvm::acir::native_types::Witness;
+use acvm::brillig_vm::MemoryValue;
+use acvm::{AcirField, FieldElement};
+use noirc_errors::debug_info::DebugVarId;
+use noirc_printable_type::PrintableType;
+
+use crate::expressions::flattened_size;
+
+/// Where the field elements that make up the value of a variable are stored
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ValueStorage {
+    /// Witnesses of a circuit. Field elements given by an expression other than a single
+    /// witness cannot be modified.
+    Witnesses { circuit_id: u32, witnesses: Vec<Option<Witness>> },
+    /// Cells of the memory of the Brillig function called by an ACIR opcode, which are only
+    /// valid while that call executes
+    BrilligMemory {
+        circuit_id: u32,
+        acir_index: usize,
+        addresses: Vec<usize>,
+        /// The cells holding the reference counts of the arrays and vectors that the value is
+        /// stored in. Writing into one that is shared would change the other values sharing it.
+        reference_counts: Vec<usize>,
+    },
+}
+
+/// The storage of a variable, along with the field elements it was last assigned
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct VariableStorage {
+    pub(crate) var_id: DebugVarId,
+    pub(crate) values: Vec<FieldElement>,
+    pub(crate) storage: ValueStorage,
+}
+
+/// Returns the witnesses holding the inputs of a Brillig call, in the order of the field
+/// elements it receives, or `None` if some inputs are read from memory blocks
+pub(crate) fn brillig_call_input_witnesses(
+    inputs: &[BrilligInputs<FieldElement>],
+) -> Option<Vec<Option<Witness>>> {
+    let mut witnesses = Vec::new();
+    for input in inputs {
+        match input {
+            BrilligInputs::Single(expression) => witnesses.push(expression.to_witness()),
+            BrilligInputs::Array(expressions) => {
+                witnesses.extend(expressions.iter().map(|expression| expression.to_witness()));
+            }
+            BrilligInputs::MemoryArray(_) => return None,
+        }
+    }
+    Some(witnesses)
+}
+
+/// The memory cells that a Brillig foreign call reads its inputs from
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct InputCells {
+    /// The addresses of the inputs, in the order of the field elements the call receives
+    pub(crate) addresses: Vec<usize>,
+    /// The addresses of the reference counts of the arrays and vectors holding the inputs
+    pub(crate) reference_counts: Vec<usize>,
+}
+
+/// Returns the memory cells that a Brillig foreign call reads its inputs from
+pub(crate) fn foreign_call_input_cells(
+    memory: &[MemoryValue<FieldElement>],
+    inputs: &[ValueOrArray],
+    value_types: &[HeapValueType],
+) -> Option<InputCells> {
+    let memory = MemoryReader(memory);
+    let mut cells = InputCells::default();
+    for (input, value_type) in inputs.iter().zip(value_types) {
+        match (input, value_type) {
+            (ValueOrArray::MemoryAddress(address), HeapValueType::Simple(_)) => {
+                cells.addresses.push(memory.resolve(*address)?);
+            }
+            (
+                ValueOrArray::HeapArray(HeapArray { pointer, size }),
+                HeapValueType::Array { value_types, .. },
+            ) => {
+                // Foreign calls receive a pointer to the items, which follow the reference count
+                let start = memory.read(memory.resolve(*pointer)?)?;
+                cells.reference_counts.push(start.checked_sub(1)?);
+                memory.slice_cells(start, *size, value_types, &mut cells)?;
+            }
+            (
+                ValueOrArray::HeapVector(HeapVector { pointer, size }),
+                HeapValueType::Vector { value_types },
+            ) => {
+                // The items of a vector follow its reference count, size and capacity
+                let start = memory.read(memory.resolve(*pointer)?)?;
+                cells.reference_counts.push(start.checked_sub(3)?);
+                let size = memory.read(memory.resolve(*size)?)?;
+                memory.slice_cells(start, size, value_types, &mut cells)?;
+            }
+            _ => return None,
+        }
+    }
+    Some(cells)
+}
+
+/// The part of a variable assigned by a `__debug_member_assign` foreign call
+#[derive(Debug, PartialEq)]
+pub(crate) struct Member {
+    /// The position of the member among the field elements of the variable
+    pub(crate) offset: usize,
+    /// Whether the member is an array element, or part of one, which unconstrained code writes
+    /// in place instead of computing into new registers
+    pub(crate) in_array: bool,
+}
+
+/// Follows the indices of a `__debug_member_assign` foreign call through a value of the given
+/// type, which are field positions for structs and tuples, and element indices for arrays
+pub(crate) fn assigned_member(typ: &PrintableType, indices: &[usize]) -> Option<Member> {
+    let mut member = Member { offset: 0, in_array: false };
+    let mut typ = typ;
+    for index in indices {
+        let field_types: Vec<&PrintableType> = match typ {
+            PrintableType::Array { length, typ: element_type } if *index < *length as usize => {
+                member.in_array = true;
+                member.offset += index * flattened_size(element_type)?;
+                typ = &**element_type;
+                continue;
+            }
+            PrintableType::Struct { fields, .. } => fields.iter().map(|(_, typ)| typ).collect(),
+            PrintableType::Tuple { types } => types.iter().collect(),
+            _ => return None,
+        };
+        typ = *field_types.get(*index)?;
+        member.offset +=
+            field_types[..*index].iter().copied().map(flattened_size).sum::<Option<usize>>()?;
+    }
+    Some(member)
+}
+
+/// Follows pointers in the memory of the Brillig VM the same way it does when reading the
+/// inputs of a foreign call
+struct MemoryReader<'a>(&'a [MemoryValue<FieldElement>]);
+
+impl MemoryReader<'_> {
+    fn read(&self, address: usize) -> Option<usize> {
+        let value = self.0.get(address)?.to_field().try_into_u128()?;
+        usize::try_from(value).ok()
+    }
+
+    fn resolve(&self, address: MemoryAddress) -> Option<usize> {
+        match address {
+            MemoryAddress::Direct(address) => Some(address),
+            // The stack pointer is stored at address 0
+            MemoryAddress::Relative(offset) => Some(self.read(0)? + offset),
+        }
+    }
+
+    fn slice_cells(
+        &self,
+        start: usize,
+        size: usize,
+        value_types: &[HeapValueType],
+        cells: &mut InputCells,
+    ) -> Option<()> {
+        if HeapValueType::all_simple(value_types) {
+            cells.addresses.extend(start..start + size);
+            return Some(());
+        }
+        for (address, value_type) in (start..start + size).zip(value_types.iter().cycle()) {
+            match value_type {
+                HeapValueType::Simple(_) => cells.addresses.push(address),
+                // Nested arrays start with their reference count
+                HeapValueType::Array { value_types, size } => {
+                    let array = self.read(address)?;
+                    cells.reference_counts.push(array);
+                    self.slice_cells(array + 1, *size, value_types, cells)?;
+                }
+                // Nested vectors start with their reference count and their size
+                HeapValueType::Vector { value_types } => {
+                    let vector = self.read(address)?;
+                    cells.reference_counts.push(vector);
+                    let size = self.read(vector + 1)?;
+                    self.slice_cells(vector + 2, size, value_types, cells)?;
+                }
+            }
+        }
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::FieldElement;
+    use acvm::acir::brillig::{BitSize, HeapArray, HeapValueType, MemoryAddress, ValueOrArray};
+    use acvm::brillig_vm::MemoryValue;
+    use noirc_printable_type::PrintableType;
+
+    use super::{InputCells, Member, assigned_member, foreign_call_input_cells};
+
+    #[test]
+    fn follows_pointers_to_nested_arrays() {
+        let field = || HeapValueType::Simple(BitSize::Field);
+        // The stack pointer, a field, and a pointer to the items of an array of two arrays of
+        // two fields, followed by the reference count and items of that array and then the
+        // inner arrays, each starting with its reference count
+        let memory: Vec<MemoryValue<FieldElement>> =
+            [1_usize, 7, 4, 1, 7, 10, 0, 1, 20, 21, 1, 22, 23]
+                .into_iter()
+                .map(MemoryValue::from)
+                .collect();
+        let inputs = [
+            ValueOrArray::MemoryAddress(MemoryAddress::relative(0)),
+            ValueOrArray::HeapArray(HeapArray { pointer: MemoryAddress::direct(2), size: 2 }),
+        ];
+        let value_types = [
+            field(),
+            HeapValueType::Array {
+                value_types: vec![HeapValueType::Array { value_types: vec![field()], size: 2 }],
+                size: 2,
+            },
+        ];
+
+        // The outer array holds pointers to addresses 7 and 10, so that its elements are read
+        // from 8, 9, 11 and 12.
+        let cells = foreign_call_input_cells(&memory, &inputs, &value_types);
+        assert_eq!(
+            cells,
+            Some(InputCells { addresses: vec![1, 8, 9, 11, 12], reference_counts: vec![3, 7, 10] })
+        );
+    }
+
+    #[test]
+    fn finds_nested_members() {
+        let field = PrintableType::Field;
+        let point = PrintableType::Struct {
+            name: String::from("Point"),
+            fields: vec![(String::from("x"), field.clone()), (String::from("y"), field.clone())],
+        };
+        // (Field, [Point; 3])
+        let typ = PrintableType::Tuple {
+            types: vec![field, PrintableType::Array { length: 3, typ: Box::new(point) }],
+        };
+
+        // t.0
+        assert_eq!(assigned_member(&typ, &[0]), Some(Member { offset: 0, in_array: false }));
+        // t.1[2].y
+        assert_eq!(assigned_member(&typ, &[1, 2, 1]), Some(Member { offset: 6, in_array: true }));
+        // t.1[3] is out of bounds
+        assert_eq!(assigned_member(&typ, &[1, 3]), None);
+    }
+}
@@ -5,6 +5,7 @@
 use core::panic;
 use std::{
     cmp::max,
+    collections::BTreeMap,
     path::{Path, PathBuf},
     time::{Duration, Instant},
 };
@@ -26,9 +27,11 @@ mod corpus;
 mod coverage;
 mod dictionary;
 mod mutation;
+mod shrink;
 mod types;
 
 use corpus::{Corpus, DEFAULT_CORPUS_FOLDER, TestCase, TestCaseId};
+pub use mutation::InputStrategy;
 use mutation::{InputMutator, add_elements_from_input_map_to_vector_without_abi};
 use rayon::iter::ParallelIterator;
 use termcolor::{ColorChoice, StandardStream};
@@ -38,6 +41,7 @@ use types::{
     SuccessfulCaseOutcome,
 };
 
+use noirc_abi::InputMap;
 use noirc_artifacts::program::ProgramArtifact;
 use rand::prelude::*;
 use rand::{Rng, SeedableRng};
@@ -421,6 +425,12 @@ impl<
         }
     }
 
+    /// Restrict the values generated for some parameters to the given strategies, by parameter name
+    pub fn with_input_strategies(mut self, strategies: BTreeMap<String, InputStrategy>) -> Self {
+        self.mutator.set_strategies(strategies);
+        self
+    }
+
     /// Given the witness from ACIR execution and coverage from Brillig execution, check if they have any new coverage
     /// ACIR witness is optional, since we can skip ACIR execution or it could have failed, but we collected coverage from brillig
     /// We never expect brillig to have no coverage (at least for now)
@@ -669,7 +679,10 @@ impl<
                             )
                         } else {
                             // Or just get the input from the starting corpus if this is the first round
-                            corpus.get_testcase_by_id(fuzz_task.main_id()).clone()
+                            self.mutator.apply_strategies(
+                                corpus.get_testcase_by_id(fuzz_task.main_id()).clone(),
+                                &mut thread_prng,
+                            )
                         };
 
                         // Time mutations
@@ -1031,10 +1044,10 @@ impl<
                 exit_reason: status,
                 counterexample,
             }) => {
-                let reason = status.to_string();
+                let counterexample = self.shrink_counterexample(counterexample, &status);
                 FuzzTestResult::ProgramFailure(ProgramFailureResult {
-                    failure_reason: reason,
-                    counterexample: counterexample.clone(),
+                    failure_reason: status,
+                    counterexample,
                 })
             }
             HarnessExecutionOutcome::ForeignCallFailure(foreign_call_error_in_fuzzing) => {
@@ -1045,6 +1058,21 @@ impl<
         }
     }
 
+    /// Find a simpler counterexample which still makes the program fail with the same reason
+    fn shrink_counterexample(&self, counterexample: InputMap, exit_reason: &str) -> InputMap {
+        shrink::shrink_counterexample(
+            &self.acir_program.abi,
+            self.mutator.strategies(),
+            counterexample,
+            |candidate| match self.single_fuzz_acir_and_brillig(&TestCase::from(candidate)) {
+                HarnessExecutionOutcome::CounterExample(outcome) => {
+                    outcome.exit_reason == exit_reason
+                }
+                _ => false,
+            },
+        )
+    }
+
     /// Execute acir and brillig programs with the following Testcase
     pub fn single_fuzz_acir_and_brillig(&self, testcase: &TestCase) -> HarnessExecutionOutcome {
         let initial_witness = self.acir_program.abi.encode(testcase.value(), None).unwrap();
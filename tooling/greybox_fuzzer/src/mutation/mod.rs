@@ -23,15 +23,19 @@ mod configurations;
 mod dictionary;
 mod field;
 mod int;
+mod strategy;
 mod string;
 use num_traits::Zero;
 pub struct InputMutator {
     abi: Abi,
     weight_tree: NodeWeight,
     full_dictionary: FullDictionary,
+    /// Strategies restricting the values of some parameters, by parameter name
+    strategies: BTreeMap<String, InputStrategy>,
 }
 
 pub use dictionary::add_elements_from_input_map_to_vector_without_abi;
+pub use strategy::InputStrategy;
 const MUTATION_LOG_MIN: u32 = 0;
 const MUTATION_LOG_MAX: u32 = 5;
 /// NodeWeight determines the probability of mutating a particular object
@@ -71,7 +75,27 @@ impl InputMutator {
         let mut weight_tree = Self::count_all_input_weights(abi);
         weight_tree.calculate_offsets(0);
         let full_dictionary = FullDictionary::new(original_dictionary);
-        Self { abi: abi.clone(), weight_tree, full_dictionary }
+        Self { abi: abi.clone(), weight_tree, full_dictionary, strategies: BTreeMap::new() }
+    }
+
+    /// Restrict the values generated for some parameters to the given strategies
+    pub fn set_strategies(&mut self, strategies: BTreeMap<String, InputStrategy>) {
+        self.strategies = strategies;
+    }
+
+    /// The strategies restricting the values of some parameters, by parameter name
+    pub fn strategies(&self) -> &BTreeMap<String, InputStrategy> {
+        &self.strategies
+    }
+
+    /// Replace the values of the input which aren't allowed by the strategy of their parameter
+    pub fn apply_strategies(&self, mut input_map: InputMap, prng: &mut XorShiftRng) -> InputMap {
+        for (name, strategy) in &self.strategies {
+            if let Some(value) = input_map.get_mut(name) {
+                *value = strategy.apply(value, prng);
+            }
+        }
+        input_map
     }
 
     /// Fill the dictionary with values from an interesting input
@@ -677,7 +701,7 @@ impl InputMutator {
         for _ in 0..(1 << prng.gen_range(MUTATION_LOG_MIN..=MUTATION_LOG_MAX)) {
             starting_input_value = self.mutate_input_map_single(&starting_input_value, prng);
         }
-        starting_input_value
+        self.apply_strategies(starting_input_value, prng)
     }
 
     /// Generate the default input value for a given type
@@ -728,7 +752,14 @@ impl InputMutator {
         self.abi
             .parameters
             .iter()
-            .map(|param| (param.name.clone(), Self::generate_default_input_value(&param.typ)))
+            .map(|param| {
+                let value = Self::generate_default_input_value(&param.typ);
+                let value = match self.strategies.get(&param.name) {
+                    Some(strategy) => strategy.apply_simplest(&value),
+                    None => value,
+                };
+                (param.name.clone(), value)
+            })
             .collect()
     }
 }
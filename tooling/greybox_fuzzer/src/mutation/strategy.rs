@@ -0,0 +1,172 @@
+use acvm::{AcirField, FieldElement};
+use noirc_abi::input_parser::InputValue;
+use rand::{Rng, seq::SliceRandom};
+use rand_xorshift::XorShiftRng;
+
+/// A restriction on the values the fuzzer generates for a parameter, coming from a
+/// `#[range(min, max)]` or `#[one_of([values])]` attribute on it.
+/// A strategy on an array (or a nested array) applies to each of its elements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputStrategy {
+    /// Values between `min` and `max`, both included. Negative values are encoded in two's
+    /// complement on `signed_bit_size` bits for signed integers, and as negated field elements
+    /// otherwise.
+    Range { min: i128, max: i128, signed_bit_size: Option<u32> },
+    /// One of the given values, which must not be empty
+    OneOf(Vec<FieldElement>),
+}
+
+impl InputStrategy {
+    /// Check whether the strategy allows the given value
+    pub fn contains(&self, value: FieldElement) -> bool {
+        match self {
+            InputStrategy::Range { min, max, .. } => {
+                self.decode(value).is_some_and(|value| (*min..=*max).contains(&value))
+            }
+            InputStrategy::OneOf(values) => values.contains(&value),
+        }
+    }
+
+    /// Pick a random value allowed by the strategy
+    pub fn pick(&self, prng: &mut XorShiftRng) -> FieldElement {
+        match self {
+            InputStrategy::Range { min, max, .. } => self.encode(prng.gen_range(*min..=*max)),
+            InputStrategy::OneOf(values) => {
+                *values.choose(prng).expect("one_of strategies can't be empty")
+            }
+        }
+    }
+
+    /// The value the strategy considers the simplest: the value of a range closest to zero or
+    /// the first of the values of a `one_of`
+    pub fn simplest(&self) -> FieldElement {
+        match self {
+            InputStrategy::Range { min, max, .. } => self.encode(0i128.clamp(*min, *max)),
+            InputStrategy::OneOf(values) => values[0],
+        }
+    }
+
+    /// Encode a value of a range as a field element
+    pub(crate) fn encode(&self, value: i128) -> FieldElement {
+        match self {
+            InputStrategy::Range { signed_bit_size: Some(bit_size), .. } if value < 0 => {
+                FieldElement::from(value as u128 & (u128::MAX >> (u128::BITS - bit_size)))
+            }
+            _ if value < 0 => -FieldElement::from(value.unsigned_abs()),
+            _ => FieldElement::from(value.unsigned_abs()),
+        }
+    }
+
+    /// Decode a field element into a value of a range, if it is one
+    pub(crate) fn decode(&self, value: FieldElement) -> Option<i128> {
+        match self {
+            InputStrategy::Range { signed_bit_size: Some(bit_size), .. } => {
+                let value = value.try_into_u128()?;
+                if *bit_size < u128::BITS && value >> bit_size != 0 {
+                    return None;
+                }
+                // Sign-extend the two's complement value
+                let shift = u128::BITS - bit_size;
+                Some(((value << shift) as i128) >> shift)
+            }
+            _ => match value.try_into_u128() {
+                Some(value) => i128::try_from(value).ok(),
+                None => 0i128.checked_sub_unsigned((-value).try_into_u128()?),
+            },
+        }
+    }
+
+    /// Replace every value of the input which isn't allowed by the strategy with a random allowed one
+    pub fn apply(&self, input: &InputValue, prng: &mut XorShiftRng) -> InputValue {
+        self.restrict(input, &mut || self.pick(prng))
+    }
+
+    /// Replace every value of the input which isn't allowed by the strategy with the simplest one
+    pub fn apply_simplest(&self, input: &InputValue) -> InputValue {
+        self.restrict(input, &mut || self.simplest())
+    }
+
+    fn restrict(
+        &self,
+        input: &InputValue,
+        replacement: &mut dyn FnMut() -> FieldElement,
+    ) -> InputValue {
+        match input {
+            InputValue::Field(value) if self.contains(*value) => input.clone(),
+            InputValue::Field(_) => InputValue::Field(replacement()),
+            InputValue::Vec(elements) => InputValue::Vec(
+                elements.iter().map(|element| self.restrict(element, replacement)).collect(),
+            ),
+            InputValue::String(_) | InputValue::Struct(_) => input.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_apply_range_strategy() {
+        let mut prng = XorShiftRng::seed_from_u64(0);
+        let strategy = InputStrategy::Range { min: 10, max: 20, signed_bit_size: None };
+        let input = InputValue::Vec(vec![
+            InputValue::Field(FieldElement::from(15u128)),
+            InputValue::Field(FieldElement::from(0u128)),
+            InputValue::Field(-FieldElement::one()),
+        ]);
+
+        for _ in 0..10 {
+            let InputValue::Vec(elements) = strategy.apply(&input, &mut prng) else {
+                panic!("Expected the strategy to keep the array");
+            };
+            assert_eq!(elements[0], InputValue::Field(FieldElement::from(15u128)));
+            for element in elements {
+                let InputValue::Field(value) = element else {
+                    panic!("Expected a field element");
+                };
+                assert!(strategy.contains(value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_negative_range_strategy() {
+        let mut prng = XorShiftRng::seed_from_u64(0);
+        let strategy = InputStrategy::Range { min: -10, max: 10, signed_bit_size: Some(8) };
+        // -1 as an `i8`, and as a field element
+        assert!(strategy.contains(FieldElement::from(255u128)));
+        assert!(!strategy.contains(-FieldElement::one()));
+        assert!(!strategy.contains(FieldElement::from(200u128)));
+        assert_eq!(strategy.simplest(), FieldElement::zero());
+
+        for _ in 0..10 {
+            let input = InputValue::Field(FieldElement::from(100u128));
+            let InputValue::Field(value) = strategy.apply(&input, &mut prng) else {
+                panic!("Expected a field element");
+            };
+            assert!(strategy.contains(value));
+            assert!(value.to_u128() <= 10 || value.to_u128() >= 246);
+        }
+
+        let strategy = InputStrategy::Range { min: -10, max: -5, signed_bit_size: None };
+        assert!(strategy.contains(-FieldElement::from(7u128)));
+        assert_eq!(strategy.simplest(), -FieldElement::from(5u128));
+    }
+
+    #[test]
+    fn test_apply_one_of_strategy() {
+        let mut prng = XorShiftRng::seed_from_u64(0);
+        let values = vec![FieldElement::from(2u128), FieldElement::from(4u128)];
+        let strategy = InputStrategy::OneOf(values.clone());
+
+        for _ in 0..10 {
+            let input = InputValue::Field(FieldElement::from(3u128));
+            let InputValue::Field(value) = strategy.apply(&input, &mut prng) else {
+                panic!("Expected a field element");
+            };
+            assert!(values.contains(&value));
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! Shrinking of counterexamples
+//!
+//! Once the fuzzer finds an input that makes the program fail, it greedily tries simpler versions
+//! of it (changing one value at a time towards zero, or towards the simplest value allowed by
+//! its strategy) and keeps each one which still fails in the same way.
+use std::collections::BTreeMap;
+
+use acvm::{AcirField, FieldElement};
+use noirc_abi::{Abi, AbiType, InputMap, input_parser::InputValue};
+
+use crate::mutation::InputStrategy;
+
+/// Maximum number of executions of the program spent shrinking a single counterexample
+const MAX_SHRINKING_EXECUTIONS: usize = 1000;
+
+/// Shrink a counterexample, given a function checking whether an input still fails in the same way
+pub(crate) fn shrink_counterexample(
+    abi: &Abi,
+    strategies: &BTreeMap<String, InputStrategy>,
+    counterexample: InputMap,
+    mut still_fails: impl FnMut(&InputMap) -> bool,
+) -> InputMap {
+    let mut current = counterexample;
+    let mut executions = 0;
+    'shrinking: loop {
+        for candidate in simpler_input_maps(abi, strategies, &current) {
+            if executions == MAX_SHRINKING_EXECUTIONS {
+                break 'shrinking;
+            }
+            executions += 1;
+            if still_fails(&candidate) {
+                current = candidate;
+                continue 'shrinking;
+            }
+        }
+        // No candidate fails anymore, so the counterexample can't be shrunk further
+        break;
+    }
+    current
+}
+
+/// All the input maps obtained by simplifying a single value of the given one
+fn simpler_input_maps(
+    abi: &Abi,
+    strategies: &BTreeMap<String, InputStrategy>,
+    input_map: &InputMap,
+) -> Vec<InputMap> {
+    let mut candidates = Vec::new();
+    for param in &abi.parameters {
+        let Some(value) = input_map.get(&param.name) else {
+            continue;
+        };
+        let strategy = strategies.get(&param.name);
+        for simpler_value in simpler_input_values(&param.typ, value, strategy) {
+            let mut candidate = input_map.clone();
+            candidate.insert(param.name.clone(), simpler_value);
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// All the values obtained by simplifying a single element of the given one
+fn simpler_input_values(
+    abi_type: &AbiType,
+    value: &InputValue,
+    strategy: Option<&InputStrategy>,
+) -> Vec<InputValue> {
+    match (abi_type, value) {
+        (AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean, InputValue::Field(value)) => {
+            simpler_field_values(*value, strategy).into_iter().map(InputValue::Field).collect()
+        }
+        (AbiType::String { .. }, InputValue::String(string)) => {
+            // Replace the characters with zeros, starting with the whole string at once
+            let mut candidates = Vec::new();
+            if string.chars().filter(|char| *char != '\0').count() > 1 {
+                candidates.push(InputValue::String("\0".repeat(string.chars().count())));
+            }
+            for (index, char) in string.chars().enumerate() {
+                if char != '\0' {
+                    let simpler = string
+                        .chars()
+                        .enumerate()
+                        .map(|(other_index, char)| if other_index == index { '\0' } else { char })
+                        .collect();
+                    candidates.push(InputValue::String(simpler));
+                }
+            }
+            candidates
+        }
+        (AbiType::Array { typ, .. }, InputValue::Vec(elements)) => {
+            simpler_vectors(elements, |_| (typ.as_ref(), strategy))
+        }
+        (AbiType::Tuple { fields }, InputValue::Vec(elements)) => {
+            simpler_vectors(elements, |index| (&fields[index], None))
+        }
+        (AbiType::Struct { fields, .. }, InputValue::Struct(members)) => {
+            let mut candidates = Vec::new();
+            for (name, typ) in fields {
+                let Some(member) = members.get(name) else {
+                    continue;
+                };
+                for simpler_member in simpler_input_values(typ, member, None) {
+                    let mut candidate = members.clone();
+                    candidate.insert(name.clone(), simpler_member);
+                    candidates.push(InputValue::Struct(candidate));
+                }
+            }
+            candidates
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// All the vectors obtained by simplifying a single element of the given one
+fn simpler_vectors<'a>(
+    elements: &[InputValue],
+    element_type: impl Fn(usize) -> (&'a AbiType, Option<&'a InputStrategy>),
+) -> Vec<InputValue> {
+    let mut candidates = Vec::new();
+    for (index, element) in elements.iter().enumerate() {
+        let (typ, strategy) = element_type(index);
+        for simpler_element in simpler_input_values(typ, element, strategy) {
+            let mut candidate = elements.to_vec();
+            candidate[index] = simpler_element;
+            candidates.push(InputValue::Vec(candidate));
+        }
+    }
+    candidates
+}
+
+/// Values simpler than the given one, simplest first. Without a strategy these are values
+/// between zero and it, getting closer to it by halving the distance each time.
+/// With a range, they are values between the simplest value of the range and it.
+fn simpler_field_values(
+    value: FieldElement,
+    strategy: Option<&InputStrategy>,
+) -> Vec<FieldElement> {
+    let range = match strategy {
+        Some(InputStrategy::OneOf(values)) => {
+            return values.iter().take_while(|candidate| **candidate != value).copied().collect();
+        }
+        Some(range @ InputStrategy::Range { .. }) => range,
+        None => {
+            let Some(value) = value.try_into_u128() else {
+                return vec![FieldElement::zero()];
+            };
+            return halving_distances(value)
+                .map(|distance| FieldElement::from(value - distance))
+                .collect();
+        }
+    };
+
+    let simplest = range.simplest();
+    let (Some(value), Some(target)) = (range.decode(value), range.decode(simplest)) else {
+        return vec![simplest];
+    };
+    // The candidates are between the target and the value, so they can't overflow
+    halving_distances(value.abs_diff(target))
+        .map(|distance| {
+            let candidate = if value > target {
+                value.wrapping_sub_unsigned(distance)
+            } else {
+                value.wrapping_add_unsigned(distance)
+            };
+            range.encode(candidate)
+        })
+        .collect()
+}
+
+/// The given distance, then its half, and so on while it is not zero
+fn halving_distances(distance: u128) -> impl Iterator<Item = u128> {
+    std::iter::successors(Some(distance), |distance| Some(distance / 2))
+        .take_while(|distance| *distance > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use noirc_abi::{AbiParameter, AbiVisibility, Sign};
+
+    fn abi_with_parameters(parameters: Vec<(&str, AbiType)>) -> Abi {
+        let parameters = parameters
+            .into_iter()
+            .map(|(name, typ)| AbiParameter {
+                name: name.to_string(),
+                typ,
+                visibility: AbiVisibility::Private,
+            })
+            .collect();
+        Abi { parameters, return_type: None, error_types: BTreeMap::new() }
+    }
+
+    fn field(value: u128) -> InputValue {
+        InputValue::Field(FieldElement::from(value))
+    }
+
+    #[test]
+    fn test_shrinks_to_the_smallest_failing_value() {
+        let u32_type = AbiType::Integer { sign: Sign::Unsigned, width: 32 };
+        let abi = abi_with_parameters(vec![("x", u32_type.clone()), ("y", u32_type)]);
+        let counterexample =
+            InputMap::from([("x".to_string(), field(123_456)), ("y".to_string(), field(77))]);
+
+        // Fails whenever `x` is at least 1000, regardless of `y`
+        let shrunk = shrink_counterexample(&abi, &BTreeMap::new(), counterexample, |input| {
+            let InputValue::Field(x) = input["x"] else { unreachable!() };
+            x.try_into_u128().unwrap() >= 1000
+        });
+
+        assert_eq!(shrunk["x"], field(1000));
+        assert_eq!(shrunk["y"], field(0));
+    }
+
+    #[test]
+    fn test_simpler_field_values() {
+        let simpler = simpler_field_values(FieldElement::from(50u128), None);
+        let expected: Vec<FieldElement> = vec![
+            0u128.into(),
+            25u128.into(),
+            38u128.into(),
+            44u128.into(),
+            47u128.into(),
+            49u128.into(),
+        ];
+        assert_eq!(simpler, expected);
+
+        let strategy = InputStrategy::Range { min: 10, max: 100, signed_bit_size: None };
+        assert!(simpler_field_values(FieldElement::from(10u128), Some(&strategy)).is_empty());
+
+        // -8 as an `i8` shrinks towards zero
+        let strategy = InputStrategy::Range { min: -10, max: 10, signed_bit_size: Some(8) };
+        let simpler = simpler_field_values(FieldElement::from(248u128), Some(&strategy));
+        let expected: Vec<FieldElement> =
+            vec![0u128.into(), 252u128.into(), 254u128.into(), 255u128.into()];
+        assert_eq!(simpler, expected);
+
+        let strategy = InputStrategy::OneOf(vec![3u128.into(), 1u128.into(), 2u128.into()]);
+        let simpler = simpler_field_values(FieldElement::from(2u128), Some(&strategy));
+        assert_eq!(simpler, vec![3u128.into(), 1u128.into()]);
+    }
+
+    #[test]
+    fn test_shrinks_within_strategies() {
+        let array_type = AbiType::Array { length: 2, typ: Box::new(AbiType::Field) };
+        let abi = abi_with_parameters(vec![("xs", array_type)]);
+        let strategies = BTreeMap::from([(
+            "xs".to_string(),
+            InputStrategy::Range { min: 10, max: 100, signed_bit_size: None },
+        )]);
+        let counterexample =
+            InputMap::from([("xs".to_string(), InputValue::Vec(vec![field(90), field(50)]))]);
+
+        // Always fails, so every value shrinks to the minimum of the range
+        let shrunk = shrink_counterexample(&abi, &strategies, counterexample, |_| true);
+
+        assert_eq!(shrunk["xs"], InputValue::Vec(vec![field(10), field(10)]));
+    }
+}
@@ -2,7 +2,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies, unused_extern_crates))]
 
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     future::Future,
     ops::{self, ControlFlow},
     path::{Path, PathBuf},
@@ -27,7 +27,9 @@ use nargo::{
     parse_all,
     workspace::Workspace,
 };
-use nargo_toml::{PackageSelection, find_file_manifest, resolve_workspace_from_toml};
+use nargo_toml::{
+    PackageSelection, ResolveOptions, find_file_manifest, resolve_workspace_from_toml,
+};
 use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
 use noirc_frontend::{
     ParsedModule,
@@ -266,12 +268,20 @@ fn byte_span_to_range<'a, F: files::Files<'a> + ?Sized>(
 /// Create a workspace based on the source file location:
 /// * if there is a `Nargo.toml` file, use it to read the workspace
 /// * otherwise treat the parent directory as a dummy workspace
+///
+/// Every feature of the workspace members is enabled, so that the code under `cfg(feature = ...)` is checked.
+/// Code under `cfg(not(feature = ...))` is therefore not checked, which `nargo check --feature-powerset` covers.
 pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Workspace, LspError> {
     if let Some(toml_path) = find_file_manifest(file_path) {
         match resolve_workspace_from_toml(
             &toml_path,
             PackageSelection::All,
             Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+            &ResolveOptions {
+                include_dev_dependencies: true,
+                all_features: true,
+                ..Default::default()
+            },
         ) {
             Ok(workspace) => return Ok(workspace),
             Err(error) => {
@@ -307,6 +317,9 @@ pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Work
         entry_path: PathBuf::from(file_path),
         name: crate_name,
         dependencies: BTreeMap::new(),
+        dev_dependencies: BTreeMap::new(),
+        profile: None,
+        features: BTreeSet::new(),
         expression_width: None,
     };
     let workspace = Workspace {
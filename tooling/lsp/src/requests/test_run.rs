@@ -6,7 +6,9 @@ use nargo::{
     foreign_calls::DefaultForeignCallBuilder,
     ops::{TestStatus, run_test},
 };
-use nargo_toml::{PackageSelection, find_package_manifest, resolve_workspace_from_toml};
+use nargo_toml::{
+    PackageSelection, ResolveOptions, find_package_manifest, resolve_workspace_from_toml,
+};
 use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING, check_crate};
 use noirc_frontend::hir::FunctionNameMatch;
 
@@ -42,6 +44,11 @@ fn on_test_run_request_inner(
         &toml_path,
         PackageSelection::Selected(crate_name.clone()),
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        &ResolveOptions {
+            include_dev_dependencies: true,
+            all_features: true,
+            ..Default::default()
+        },
     )
     .map_err(|err| {
         // If we found a manifest, but the workspace is invalid, we raise an error about it
@@ -105,11 +112,13 @@ fn on_test_run_request_inner(
                     result: "pass".to_string(),
                     message: None,
                 },
-                TestStatus::Fail { message, .. } => NargoTestRunResult {
-                    id: params.id.clone(),
-                    result: "fail".to_string(),
-                    message: Some(message),
-                },
+                TestStatus::Fail { message, .. } | TestStatus::TimedOut { message } => {
+                    NargoTestRunResult {
+                        id: params.id.clone(),
+                        result: "fail".to_string(),
+                        message: Some(message),
+                    }
+                }
                 TestStatus::Skipped => NargoTestRunResult {
                     id: params.id.clone(),
                     result: "skipped".to_string(),
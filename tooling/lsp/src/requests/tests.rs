@@ -3,7 +3,9 @@ use std::future::{self, Future};
 use crate::insert_all_files_for_workspace_into_file_manager;
 use async_lsp::lsp_types::{LogMessageParams, MessageType};
 use async_lsp::{ErrorCode, LanguageClient, ResponseError};
-use nargo_toml::{PackageSelection, find_package_manifest, resolve_workspace_from_toml};
+use nargo_toml::{
+    PackageSelection, ResolveOptions, find_package_manifest, resolve_workspace_from_toml,
+};
 use noirc_driver::{NOIR_ARTIFACT_VERSION_STRING, check_crate};
 
 use crate::{
@@ -43,6 +45,11 @@ fn on_tests_request_inner(
         &toml_path,
         PackageSelection::All,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        &ResolveOptions {
+            include_dev_dependencies: true,
+            all_features: true,
+            ..Default::default()
+        },
     )
     .map_err(|err| {
         // If we found a manifest, but the workspace is invalid, we raise an error about it
@@ -18,7 +18,7 @@ use noirc_frontend::{
     },
     parser::{Item, ItemKind, ParsedSubModule},
     token::{
-        Attributes, FmtStrFragment, LocatedToken, MetaAttribute, MetaAttributeName,
+        Attributes, FmtStrFragment, FuzzStrategy, LocatedToken, MetaAttribute, MetaAttributeName,
         SecondaryAttribute, SecondaryAttributeKind, Token, Tokens,
     },
 };
@@ -398,6 +398,10 @@ fn param_with_file(param: Param, file: FileId) -> Param {
         visibility: param.visibility,
         pattern: pattern_with_file(param.pattern, file),
         typ: unresolved_type_with_file(param.typ, file),
+        fuzz_strategy: param.fuzz_strategy.map(|strategy| FuzzStrategy {
+            kind: strategy.kind,
+            location: location_with_file(strategy.location, file),
+        }),
         location: location_with_file(param.location, file),
     }
 }
@@ -585,11 +589,13 @@ fn secondary_attribute_with_file(
         | SecondaryAttributeKind::ContractLibraryMethod
         | SecondaryAttributeKind::Export
         | SecondaryAttributeKind::Field(_)
+        | SecondaryAttributeKind::Fixture
         | SecondaryAttributeKind::Tag(..)
         | SecondaryAttributeKind::Abi(_)
         | SecondaryAttributeKind::Varargs
         | SecondaryAttributeKind::UseCallersScope
-        | SecondaryAttributeKind::Allow(_) => secondary_attribute.kind,
+        | SecondaryAttributeKind::Allow(_)
+        | SecondaryAttributeKind::Cfg(_) => secondary_attribute.kind,
     };
     SecondaryAttribute { kind, location: location_with_file(secondary_attribute.location, file) }
 }
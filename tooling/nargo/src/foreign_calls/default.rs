@@ -6,6 +6,7 @@ use super::{
     layers::{self, Either, Layer, Layering},
     mocker::{DisabledMockForeignCallExecutor, MockForeignCallExecutor},
     print::PrintForeignCallExecutor,
+    transcript::LoggingForeignCallExecutor,
 };
 
 #[cfg(feature = "rpc")]
@@ -87,7 +88,43 @@ impl<W> DefaultForeignCallBuilder<W> {
         F: AcirField + Serialize + for<'de> Deserialize<'de>,
         B: ForeignCallExecutor<F>,
     {
-        let executor = {
+        let (top_layers, oracle_layers) = self.split_oracle_layers(base);
+        top_layers.add_to(oracle_layers)
+    }
+
+    /// Compose the executor layers with `base` as the default handler, logging the calls handled
+    /// by the oracle resolver or by `base` to `transcript`, if given.
+    ///
+    /// Prints and mocks are handled before calls are logged, so the transcript can be replayed
+    /// by using a [ReplayForeignCallExecutor][super::transcript::ReplayForeignCallExecutor]
+    /// as the base instead of an oracle resolver.
+    pub fn build_with_base_and_transcript<B, T, F>(
+        self,
+        base: B,
+        transcript: Option<T>,
+    ) -> TranscribedForeignCallLayers<W, B, T, F>
+    where
+        F: AcirField + Serialize + for<'de> Deserialize<'de>,
+        B: ForeignCallExecutor<F>,
+        T: std::io::Write,
+    {
+        let (top_layers, oracle_layers) = self.split_oracle_layers(base);
+        top_layers.add_to(match transcript {
+            Some(transcript) => {
+                Either::Left(LoggingForeignCallExecutor::new(oracle_layers, transcript))
+            }
+            None => Either::Right(oracle_layers),
+        })
+    }
+
+    /// Separate the layers handling calls to oracles, with `base` as the default handler,
+    /// from the ones handling prints and mocks on top of them.
+    fn split_oracle_layers<B, F>(self, base: B) -> (TopForeignCallLayers<W>, OracleLayers<B>)
+    where
+        F: AcirField + Serialize + for<'de> Deserialize<'de>,
+        B: ForeignCallExecutor<F>,
+    {
+        let oracle_layers = {
             #[cfg(feature = "rpc")]
             {
                 use rand::Rng;
@@ -108,6 +145,24 @@ impl<W> DefaultForeignCallBuilder<W> {
             }
         };
 
+        let top_layers =
+            TopForeignCallLayers { output: self.output, enable_mocks: self.enable_mocks };
+        (top_layers, oracle_layers)
+    }
+}
+
+/// The layers handling prints and mocks, which are added on top of the ones handling oracles.
+struct TopForeignCallLayers<W> {
+    output: W,
+    enable_mocks: bool,
+}
+
+impl<W> TopForeignCallLayers<W> {
+    fn add_to<E, F>(self, executor: E) -> Layer<PrintLayer<W>, Layer<MockLayer<F>, E>>
+    where
+        F: AcirField + Serialize + for<'de> Deserialize<'de>,
+        E: ForeignCallExecutor<F>,
+    {
         executor
             .add_layer(if self.enable_mocks {
                 Either::Left(MockForeignCallExecutor::default())
@@ -118,19 +173,23 @@ impl<W> DefaultForeignCallBuilder<W> {
     }
 }
 
-/// Facilitate static typing of layers on a base layer, so inner layers can be accessed.
+type PrintLayer<W> = PrintForeignCallExecutor<W>;
+type MockLayer<F> = Either<MockForeignCallExecutor<F>, DisabledMockForeignCallExecutor>;
+
+/// The layers handling oracles on top of a base layer.
 #[cfg(feature = "rpc")]
-pub type DefaultForeignCallLayers<W, B, F> = Layer<
-    PrintForeignCallExecutor<W>,
-    Layer<
-        Either<MockForeignCallExecutor<F>, DisabledMockForeignCallExecutor>,
-        Layer<Option<RPCForeignCallExecutor>, B>,
-    >,
->;
+pub type OracleLayers<B> = Layer<Option<RPCForeignCallExecutor>, B>;
 #[cfg(not(feature = "rpc"))]
-pub type DefaultForeignCallLayers<W, B, F> = Layer<
-    PrintForeignCallExecutor<W>,
-    Layer<Either<MockForeignCallExecutor<F>, DisabledMockForeignCallExecutor>, B>,
+pub type OracleLayers<B> = B;
+
+/// Facilitate static typing of layers on a base layer, so inner layers can be accessed.
+pub type DefaultForeignCallLayers<W, B, F> =
+    Layer<PrintLayer<W>, Layer<MockLayer<F>, OracleLayers<B>>>;
+
+/// The layers built by [DefaultForeignCallBuilder::build_with_base_and_transcript].
+pub type TranscribedForeignCallLayers<W, B, T, F> = Layer<
+    PrintLayer<W>,
+    Layer<MockLayer<F>, Either<LoggingForeignCallExecutor<T, OracleLayers<B>>, OracleLayers<B>>>,
 >;
 
 /// Convenience constructor for code that used to create the executor this way.
@@ -22,6 +22,16 @@ pub trait ForeignCallExecutor<F> {
     ) -> Result<ForeignCallResult<F>, ForeignCallError>;
 }
 
+/// An executor shared by the programs executed one after the other, e.g. a test and its fixtures.
+impl<F, E: ForeignCallExecutor<F>> ForeignCallExecutor<F> for &std::sync::Mutex<E> {
+    fn execute(
+        &mut self,
+        foreign_call: &ForeignCallWaitInfo<F>,
+    ) -> Result<ForeignCallResult<F>, ForeignCallError> {
+        self.lock().expect("foreign call executor should not be poisoned").execute(foreign_call)
+    }
+}
+
 /// This enumeration represents the Brillig foreign calls that are natively supported by nargo.
 /// After resolution of a foreign call, nargo will restart execution of the ACVM
 pub enum ForeignCall {
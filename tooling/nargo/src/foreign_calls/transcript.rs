@@ -1,6 +1,10 @@
 use std::{collections::VecDeque, path::Path};
 
-use acvm::{AcirField, acir::brillig::ForeignCallResult, pwg::ForeignCallWaitInfo};
+use acvm::{
+    AcirField,
+    acir::brillig::{ForeignCallParam, ForeignCallResult},
+    pwg::ForeignCallWaitInfo,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -59,8 +63,15 @@ where
 /// Replay an oracle transcript which was logged with [LoggingForeignCallExecutor].
 ///
 /// This is expected to be the last executor in the stack, e.g. prints can be handled above it.
+/// Programs executed one after the other, like the fixtures of a test and the test itself, can
+/// replay a single transcript by sharing the executor behind a [Mutex][std::sync::Mutex].
+#[derive(Clone)]
 pub struct ReplayForeignCallExecutor<F> {
     transcript: VecDeque<LogItem<F>>,
+    /// Index of the next call in the transcript
+    call_index: usize,
+    /// The name of what the transcript was recorded for, e.g. a test, used in error messages
+    name: Option<String>,
 }
 
 impl<F: for<'a> Deserialize<'a>> ReplayForeignCallExecutor<F> {
@@ -70,7 +81,36 @@ impl<F: for<'a> Deserialize<'a>> ReplayForeignCallExecutor<F> {
         let transcript =
             contents.lines().map(serde_json::from_str).collect::<Result<VecDeque<_>, _>>()?;
 
-        Ok(Self { transcript })
+        Ok(Self { transcript, call_index: 0, name: None })
+    }
+}
+
+impl<F> ReplayForeignCallExecutor<F> {
+    /// Set the name of what the transcript was recorded for, to be included in error messages.
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Check that every call of the transcript was replayed, failing with the first one which wasn't.
+    pub fn check_fully_replayed(&self) -> Result<(), ForeignCallError> {
+        match self.transcript.front() {
+            Some(next) => Err(ForeignCallError::TranscriptError(format!(
+                "{} recorded foreign calls were not made, starting with '{}' at {}",
+                self.transcript.len(),
+                next.call.function,
+                self.location()
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Describe where the replay failed, e.g. "call 2 of test `foo`"
+    fn location(&self) -> String {
+        match &self.name {
+            Some(name) => format!("call {} of {name}", self.call_index),
+            None => format!("call {}", self.call_index),
+        }
     }
 }
 
@@ -87,24 +127,133 @@ where
         if let Some(next) = self.transcript.front() {
             if next.call.function != foreign_call.function {
                 let msg = format!(
-                    "unexpected foreign call; expected '{}', got '{}'",
-                    next.call.function, foreign_call.function
+                    "unexpected foreign call at {}; expected '{}', got '{}'",
+                    self.location(),
+                    next.call.function,
+                    foreign_call.function
                 );
                 return error(msg);
             }
             if next.call.inputs != foreign_call.inputs {
                 let msg = format!(
-                    "unexpected foreign call inputs to '{}'; expected {:?}, got {:?}",
-                    next.call.function, next.call.inputs, foreign_call.inputs
+                    "unexpected foreign call inputs to '{}' at {}; {}",
+                    next.call.function,
+                    self.location(),
+                    describe_input_differences(&next.call.inputs, &foreign_call.inputs)
                 );
                 return error(msg);
             }
         }
         // Consume the next call.
         if let Some(next) = self.transcript.pop_front() {
+            self.call_index += 1;
             Ok(next.result)
         } else {
-            error("unexpected foreign call; no more calls in transcript".to_string())
+            error(format!(
+                "unexpected foreign call to '{}' at {}; no more calls in transcript",
+                foreign_call.function,
+                self.location()
+            ))
         }
     }
 }
+
+/// Describe how the inputs of a call differ from the expected ones, listing only the differing params.
+fn describe_input_differences<F: AcirField>(
+    expected: &[ForeignCallParam<F>],
+    actual: &[ForeignCallParam<F>],
+) -> String {
+    if expected.len() != actual.len() {
+        return format!("expected {} params, got {}", expected.len(), actual.len());
+    }
+    expected
+        .iter()
+        .zip(actual)
+        .enumerate()
+        .filter(|(_, (expected, actual))| expected != actual)
+        .map(|(index, (expected, actual))| {
+            format!("param {index}: expected {expected:?}, got {actual:?}")
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use acvm::{
+        FieldElement,
+        acir::brillig::{ForeignCallParam, ForeignCallResult},
+        pwg::ForeignCallWaitInfo,
+    };
+
+    use crate::foreign_calls::{ForeignCallError, ForeignCallExecutor, layers};
+
+    use super::{LoggingForeignCallExecutor, ReplayForeignCallExecutor};
+
+    fn call(function: &str, inputs: Vec<u128>) -> ForeignCallWaitInfo<FieldElement> {
+        let inputs = inputs
+            .into_iter()
+            .map(|input| ForeignCallParam::Single(FieldElement::from(input)))
+            .collect();
+        ForeignCallWaitInfo { function: function.to_string(), inputs }
+    }
+
+    /// Record a transcript of the given calls, each of them answered with an empty result.
+    fn record(calls: &[ForeignCallWaitInfo<FieldElement>]) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let mut executor = LoggingForeignCallExecutor::new(layers::Empty, file.as_file_mut());
+        for call in calls {
+            executor.execute(call).unwrap();
+        }
+        file
+    }
+
+    #[test]
+    fn replays_recorded_calls() {
+        let calls = vec![call("get_value", vec![1]), call("get_value", vec![2, 3])];
+        let file = record(&calls);
+
+        let mut replay = ReplayForeignCallExecutor::from_file(file.path()).unwrap();
+        for call in &calls {
+            assert_eq!(replay.execute(call).unwrap(), ForeignCallResult::default());
+        }
+        assert!(replay.execute(&calls[0]).is_err());
+    }
+
+    #[test]
+    fn shares_the_replay_between_executors() {
+        let calls = vec![call("get_value", vec![1]), call("get_value", vec![2])];
+        let file = record(&calls);
+
+        let replay = Mutex::new(ReplayForeignCallExecutor::from_file(file.path()).unwrap());
+        let mut fixture_executor = &replay;
+        fixture_executor.execute(&calls[0]).unwrap();
+        assert!(replay.lock().unwrap().check_fully_replayed().is_err());
+
+        let mut test_executor = &replay;
+        test_executor.execute(&calls[1]).unwrap();
+        assert!(replay.lock().unwrap().check_fully_replayed().is_ok());
+    }
+
+    #[test]
+    fn reports_differing_params() {
+        let file = record(&[call("get_value", vec![1]), call("get_value", vec![2, 3])]);
+
+        let mut replay: ReplayForeignCallExecutor<FieldElement> =
+            ReplayForeignCallExecutor::from_file(file.path())
+                .unwrap()
+                .with_name("test `foo`".to_string());
+        replay.execute(&call("get_value", vec![1])).unwrap();
+        let Err(ForeignCallError::TranscriptError(message)) =
+            replay.execute(&call("get_value", vec![2, 4]))
+        else {
+            panic!("Expected a transcript error");
+        };
+        assert!(message.starts_with(
+            "unexpected foreign call inputs to 'get_value' at call 1 of test `foo`; param 1:"
+        ));
+        assert!(!message.contains("param 0"));
+    }
+}
@@ -38,15 +38,40 @@ pub fn prepare_dependencies(
     dependencies: &BTreeMap<CrateName, Dependency>,
 ) {
     for (dep_name, dep) in dependencies.iter() {
+        link_dependency(context, parent_crate, dep_name, dep);
+    }
+}
+
+fn link_dependency(
+    context: &mut Context,
+    parent_crate: CrateId,
+    dep_name: &CrateName,
+    dep: &Dependency,
+) {
+    match dep {
+        Dependency::Remote { package, .. } | Dependency::Local { package } => {
+            let crate_id = prepare_dependency(context, &package.entry_path);
+            add_unstable_features(context, crate_id, package);
+            add_enabled_features(context, crate_id, package);
+            add_dep(context, parent_crate, crate_id, dep_name.clone());
+            prepare_dependencies(context, crate_id, &package.dependencies);
+        }
+    }
+}
+
+/// Link the dev-dependencies of a package which were resolved, and let the compiler know about
+/// the ones which weren't, so it can leave out the tests using them.
+fn prepare_dev_dependencies(context: &mut Context, crate_id: CrateId, package: &Package) {
+    let mut unlinked = Vec::new();
+    for (dep_name, dep) in &package.dev_dependencies {
         match dep {
-            Dependency::Remote { package } | Dependency::Local { package } => {
-                let crate_id = prepare_dependency(context, &package.entry_path);
-                add_unstable_features(context, crate_id, package);
-                add_dep(context, parent_crate, crate_id, dep_name.clone());
-                prepare_dependencies(context, crate_id, &package.dependencies);
-            }
+            Some(dep) => link_dependency(context, crate_id, dep_name, dep),
+            None => unlinked.push(dep_name.clone()),
         }
     }
+    if !unlinked.is_empty() {
+        context.unlinked_dev_dependencies.insert(crate_id, unlinked);
+    }
 }
 
 // We will pre-populate the file manager with all the files in the package
@@ -169,9 +194,10 @@ fn collect_all_files_in_packages_dependencies(
     seen_filenames: &mut HashSet<PathBuf>,
     processed_entry_paths: &mut HashSet<PathBuf>,
 ) {
-    for (_, dep) in package.dependencies.iter() {
+    let dev_dependencies = package.dev_dependencies.values().flatten();
+    for dep in package.dependencies.values().chain(dev_dependencies) {
         match dep {
-            Dependency::Local { package } | Dependency::Remote { package } => {
+            Dependency::Local { package } | Dependency::Remote { package, .. } => {
                 collect_all_files_in_package(
                     package,
                     filenames,
@@ -290,7 +316,9 @@ pub fn prepare_package<'file_manager, 'parsed_files>(
     let mut context = Context::from_ref_file_manager(file_manager, parsed_files);
     let crate_id = prepare_crate(&mut context, &package.entry_path);
     add_unstable_features(&mut context, crate_id, package);
+    add_enabled_features(&mut context, crate_id, package);
     prepare_dependencies(&mut context, crate_id, &package.dependencies);
+    prepare_dev_dependencies(&mut context, crate_id, package);
     (context, crate_id)
 }
 
@@ -300,3 +328,10 @@ fn add_unstable_features(context: &mut Context, crate_id: CrateId, package: &Pac
         .required_unstable_features
         .insert(crate_id, package.compiler_required_unstable_features.clone());
 }
+
+/// Add the package features enabled for the `Package` to the `Context`.
+///
+/// A package depended upon by several others is compiled once, with every feature any of them enables.
+fn add_enabled_features(context: &mut Context, crate_id: CrateId, package: &Package) {
+    context.enabled_features.entry(crate_id).or_default().extend(package.features.iter().cloned());
+}
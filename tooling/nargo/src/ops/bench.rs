@@ -0,0 +1,108 @@
+use std::time::{Duration, Instant};
+
+use acvm::{BlackBoxFunctionSolver, FieldElement, acir::native_types::WitnessMap};
+use noirc_driver::{CompileOptions, DEFAULT_EXPRESSION_WIDTH, compile_no_check};
+use noirc_errors::CustomDiagnostic;
+use noirc_frontend::hir::{Context, def_map::BenchFunction};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    NargoError, errors::try_to_diagnose_runtime_error, foreign_calls::ForeignCallExecutor,
+};
+
+use super::{execute_program, execute_program_with_profiling, transform_program};
+
+/// The measurements of a function marked with `#[bench]`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BenchMeasurements {
+    /// The number of ACIR opcodes of the compiled program
+    pub acir_opcodes: usize,
+    /// The number of Brillig opcodes of the compiled program
+    pub brillig_opcodes: usize,
+    /// The number of Brillig opcodes executed by a single run of the program
+    pub executed_brillig_opcodes: usize,
+    /// The mean wall time of a run of the program
+    pub mean_time: Duration,
+    /// The shortest wall time of a run of the program
+    pub min_time: Duration,
+}
+
+#[derive(Debug)]
+pub enum BenchStatus {
+    Pass(BenchMeasurements),
+    Fail { message: String, error_diagnostic: Option<CustomDiagnostic> },
+    CompileError(CustomDiagnostic),
+}
+
+/// Compiles a bench function and executes it `runs` times, measuring its program
+/// and how long its executions take.
+///
+/// The program is executed once more beforehand to count the Brillig opcodes it executes,
+/// since profiling slows down the execution.
+pub fn run_bench<B, F, E>(
+    blackbox_solver: &B,
+    context: &mut Context,
+    bench_function: &BenchFunction,
+    config: &CompileOptions,
+    runs: u32,
+    build_foreign_call_executor: F,
+) -> BenchStatus
+where
+    B: BlackBoxFunctionSolver<FieldElement>,
+    F: Fn() -> E,
+    E: ForeignCallExecutor<FieldElement>,
+{
+    let compiled_program = match compile_no_check(context, config, bench_function.id, None, false) {
+        Ok(compiled_program) => compiled_program,
+        Err(err) => return BenchStatus::CompileError(err.into()),
+    };
+
+    // Do the same optimizations as `compile_cmd`.
+    let target_width = config.expression_width.unwrap_or(DEFAULT_EXPRESSION_WIDTH);
+    let compiled_program = transform_program(compiled_program, target_width);
+    let program = &compiled_program.program;
+
+    let failed = |error: NargoError<FieldElement>| {
+        let error_diagnostic =
+            try_to_diagnose_runtime_error(&error, &compiled_program.abi, &compiled_program.debug);
+        BenchStatus::Fail { message: error.to_string(), error_diagnostic }
+    };
+
+    let executed_brillig_opcodes = match execute_program_with_profiling(
+        program,
+        WitnessMap::new(),
+        blackbox_solver,
+        &mut build_foreign_call_executor(),
+    ) {
+        Ok((_, profiling_samples)) => profiling_samples.len(),
+        Err(error) => return failed(error),
+    };
+
+    let mut times = Vec::new();
+    for _ in 0..runs {
+        let mut foreign_call_executor = build_foreign_call_executor();
+        let start = Instant::now();
+        let result = execute_program(
+            program,
+            WitnessMap::new(),
+            blackbox_solver,
+            &mut foreign_call_executor,
+        );
+        times.push(start.elapsed());
+        if let Err(error) = result {
+            return failed(error);
+        }
+    }
+
+    BenchStatus::Pass(BenchMeasurements {
+        acir_opcodes: program.functions.iter().map(|function| function.opcodes.len()).sum(),
+        brillig_opcodes: program
+            .unconstrained_functions
+            .iter()
+            .map(|function| function.bytecode.len())
+            .sum(),
+        executed_brillig_opcodes,
+        mean_time: times.iter().sum::<Duration>() / runs.max(1),
+        min_time: times.iter().min().copied().unwrap_or_default(),
+    })
+}
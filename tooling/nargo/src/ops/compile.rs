@@ -92,7 +92,8 @@ pub fn compile_program_with_debug_instrumenter(
     context.debug_instrumenter = debug_instrumenter;
     context.package_build_path = workspace.package_build_path(package);
 
-    noirc_driver::compile_main(&mut context, crate_id, compile_options, cached_program)
+    let compile_options = package.compile_options(compile_options);
+    noirc_driver::compile_main(&mut context, crate_id, &compile_options, cached_program)
 }
 
 #[tracing::instrument(level = "trace", skip_all, fields(package_name = package.name.to_string()))]
@@ -103,7 +104,8 @@ pub fn compile_contract(
     compile_options: &CompileOptions,
 ) -> CompilationResult<CompiledContract> {
     let (mut context, crate_id) = prepare_package(file_manager, parsed_files, package);
-    noirc_driver::compile_contract(&mut context, crate_id, compile_options)
+    let compile_options = package.compile_options(compile_options);
+    noirc_driver::compile_contract(&mut context, crate_id, &compile_options)
 }
 
 /// Constructs a single `CompilationResult` for a collection of `CompilationResult`s, merging the set of warnings/errors.
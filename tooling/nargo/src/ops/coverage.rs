@@ -0,0 +1,199 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    path::PathBuf,
+};
+
+use acvm::{
+    acir::circuit::{
+        AcirOpcodeLocation, BrilligOpcodeLocation, OpcodeLocation, brillig::BrilligFunctionId,
+    },
+    pwg::ProfilingSample,
+};
+use fm::{FileManager, codespan_files::Files};
+use noirc_errors::{call_stack::CallStackId, debug_info::DebugInfo};
+
+/// The opcodes executed by a program, from which its source coverage is computed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExecutedOpcodes {
+    /// ACIR opcodes solved under a true predicate, as (ACIR function index, opcode index).
+    pub acir: BTreeSet<(usize, usize)>,
+    /// Brillig opcodes which were executed, as (index of the calling ACIR function, Brillig function, opcode index).
+    pub brillig: BTreeSet<(usize, BrilligFunctionId, usize)>,
+}
+
+impl ExecutedOpcodes {
+    /// Records the opcodes solved by the ACVM of an ACIR function, along with the Brillig opcodes
+    /// found in its profiling samples.
+    pub(crate) fn record(
+        &mut self,
+        acir_function_index: usize,
+        solved_opcodes: Vec<usize>,
+        profiling_samples: &[ProfilingSample],
+    ) {
+        self.acir.extend(solved_opcodes.into_iter().map(|opcode| (acir_function_index, opcode)));
+        for sample in profiling_samples {
+            // The last location of a sample is the Brillig opcode being executed.
+            if let (
+                Some(brillig_function_id),
+                Some(OpcodeLocation::Brillig { brillig_index, .. }),
+            ) = (sample.brillig_function_id, sample.call_stack.last())
+            {
+                self.brillig.insert((acir_function_index, brillig_function_id, *brillig_index));
+            }
+        }
+    }
+}
+
+/// Line coverage of source files, merged across the programs added to it.
+///
+/// Each line holding compiled code is counted, along with the number of programs which executed it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+    files: BTreeMap<PathBuf, BTreeMap<usize, usize>>,
+}
+
+impl CoverageReport {
+    /// Adds the coverage of a program, given its debug info and the opcodes it executed.
+    ///
+    /// Files which are not on disk, like the ones of the standard library, are left out.
+    pub fn add_program(
+        &mut self,
+        debug: &[DebugInfo],
+        executed_opcodes: &ExecutedOpcodes,
+        file_manager: &FileManager,
+    ) {
+        let mut compiled_lines = BTreeSet::new();
+        let mut executed_lines = BTreeSet::new();
+        for (acir_function_index, debug_info) in debug.iter().enumerate() {
+            let brillig_call_stacks = debug_info
+                .brillig_locations
+                .values()
+                .flat_map(|locations| locations.values().copied());
+            let call_stacks =
+                debug_info.acir_locations.values().copied().chain(brillig_call_stacks);
+            compiled_lines.extend(source_lines(debug_info, call_stacks, file_manager));
+
+            let acir_call_stacks = executed_opcodes
+                .acir
+                .iter()
+                .filter(|(function_index, _)| *function_index == acir_function_index)
+                .filter_map(|(_, opcode)| {
+                    debug_info.acir_locations.get(&AcirOpcodeLocation::new(*opcode)).copied()
+                });
+            let brillig_call_stacks = executed_opcodes
+                .brillig
+                .iter()
+                .filter(|(function_index, ..)| *function_index == acir_function_index)
+                .filter_map(|(_, brillig_function_id, opcode)| {
+                    let locations = debug_info.brillig_locations.get(brillig_function_id)?;
+                    locations.get(&BrilligOpcodeLocation(*opcode)).copied()
+                });
+            let call_stacks = acir_call_stacks.chain(brillig_call_stacks);
+            executed_lines.extend(source_lines(debug_info, call_stacks, file_manager));
+        }
+        self.add_lines(compiled_lines, &executed_lines);
+    }
+
+    /// Records `lines` as holding compiled code, counting the `executed` ones as hit once more.
+    fn add_lines(
+        &mut self,
+        lines: impl IntoIterator<Item = (PathBuf, usize)>,
+        executed: &BTreeSet<(PathBuf, usize)>,
+    ) {
+        for (path, line) in lines {
+            let hits = self.files.entry(path.clone()).or_default().entry(line).or_default();
+            if executed.contains(&(path, line)) {
+                *hits += 1;
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: CoverageReport) {
+        for (path, lines) in other.files {
+            let file = self.files.entry(path).or_default();
+            for (line, hits) in lines {
+                *file.entry(line).or_default() += hits;
+            }
+        }
+    }
+
+    /// Renders the report in the LCOV tracefile format.
+    pub fn to_lcov(&self) -> String {
+        let mut lcov = String::new();
+        for (path, lines) in &self.files {
+            lcov.push_str("TN:\n");
+            lcov.push_str(&format!("SF:{}\n", path.display()));
+            for (line, hits) in lines {
+                lcov.push_str(&format!("DA:{line},{hits}\n"));
+            }
+            let lines_hit = lines.values().filter(|hits| **hits > 0).count();
+            lcov.push_str(&format!("LF:{}\nLH:{lines_hit}\n", lines.len()));
+            lcov.push_str("end_of_record\n");
+        }
+        lcov
+    }
+}
+
+/// The files and (1-based) lines of the locations in the given call stacks, including the call sites leading to them.
+fn source_lines(
+    debug_info: &DebugInfo,
+    call_stacks: impl IntoIterator<Item = CallStackId>,
+    file_manager: &FileManager,
+) -> BTreeSet<(PathBuf, usize)> {
+    let locations = &debug_info.location_tree.locations;
+    let mut visited = HashSet::new();
+    for mut call_stack in call_stacks {
+        // The root of the tree doesn't hold a location.
+        while let Some(parent) = locations[call_stack.index()].parent {
+            if !visited.insert(call_stack) {
+                break;
+            }
+            call_stack = parent;
+        }
+    }
+
+    let file_map = file_manager.as_file_map();
+    visited
+        .into_iter()
+        .filter_map(|call_stack| {
+            let location = locations[call_stack.index()].value;
+            let path = file_manager.path(location.file)?;
+            if !path.is_absolute() {
+                return None;
+            }
+            let line = file_map.line_index(location.file, location.span.start() as usize).ok()?;
+            Some((path.to_path_buf(), line + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, path::PathBuf};
+
+    use super::CoverageReport;
+
+    #[test]
+    fn merges_coverage_into_lcov() {
+        let main = PathBuf::from("/project/src/main.nr");
+        let lib = PathBuf::from("/project/src/lib.nr");
+
+        let mut first = CoverageReport::default();
+        first.add_lines(
+            [(main.clone(), 1), (main.clone(), 3), (lib, 2)],
+            &BTreeSet::from([(main.clone(), 1)]),
+        );
+        let mut second = CoverageReport::default();
+        second.add_lines(
+            [(main.clone(), 1), (main.clone(), 3)],
+            &BTreeSet::from([(main.clone(), 1), (main, 3)]),
+        );
+        first.merge(second);
+
+        assert_eq!(
+            first.to_lcov(),
+            "TN:\nSF:/project/src/lib.nr\nDA:2,0\nLF:1\nLH:0\nend_of_record\n\
+             TN:\nSF:/project/src/main.nr\nDA:1,2\nDA:3,1\nLF:2\nLH:2\nend_of_record\n"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use acvm::acir::circuit::brillig::BrilligBytecode;
 use acvm::acir::circuit::{OpcodeLocation, Program};
 use acvm::acir::native_types::WitnessStack;
-use acvm::brillig_vm::BranchToFeatureMap;
+use acvm::brillig_vm::{BranchToFeatureMap, ExecutionLimits};
 use acvm::pwg::{
     ACVM, ACVMStatus, ErrorLocation, OpcodeNotSolvable, OpcodeResolutionError, ProfilingSamples,
 };
@@ -11,6 +11,7 @@ type NargoErrorAndWitnessStack<F> = (NargoError<F>, WitnessStack<F>);
 type WitnessAndCoverage<F> = (WitnessStack<F>, Option<Vec<u32>>);
 use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
 
+use super::coverage::ExecutedOpcodes;
 use crate::NargoError;
 use crate::errors::{ExecutionError, ResolvedOpcodeLocation, execution_error_from};
 use crate::foreign_calls::ForeignCallExecutor;
@@ -54,6 +55,12 @@ struct ProgramExecutor<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: Foreig
 
     // Partial witness on failure
     failing_partial_witness: Option<WitnessMap<F>>,
+
+    // Opcodes executed so far, when tracking code coverage
+    executed_opcodes: Option<ExecutedOpcodes>,
+
+    // Limits on the Brillig opcodes executed by the program
+    execution_limits: Option<&'a ExecutionLimits>,
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
@@ -80,9 +87,39 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
             last_fuzzing_trace: None,
             return_witness_on_failure: false,
             failing_partial_witness: None,
+            executed_opcodes: None,
+            execution_limits: None,
         }
     }
 
+    fn with_coverage(&mut self) {
+        self.executed_opcodes = Some(ExecutedOpcodes::default());
+    }
+
+    /// Takes the profiling samples of `acvm`, recording the opcodes it executed if coverage is tracked.
+    ///
+    /// Coverage relies on profiling samples, which are only returned if profiling was requested.
+    fn take_profiling_samples(
+        &mut self,
+        acir_function_index: usize,
+        acvm: &mut ACVM<'_, F, B>,
+    ) -> ProfilingSamples {
+        let profiling_samples = acvm.take_profiling_samples();
+        let Some(executed_opcodes) = &mut self.executed_opcodes else {
+            return profiling_samples;
+        };
+        executed_opcodes.record(
+            acir_function_index,
+            acvm.take_solved_opcodes(),
+            &profiling_samples,
+        );
+        if self.profiling_active { profiling_samples } else { Vec::new() }
+    }
+
+    fn with_execution_limits(&mut self, execution_limits: Option<&'a ExecutionLimits>) {
+        self.execution_limits = execution_limits;
+    }
+
     fn with_brillig_fuzzing(
         &mut self,
         brillig_branch_to_feature_map: Option<&'a BranchToFeatureMap>,
@@ -112,8 +149,12 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
             self.unconstrained_functions,
             &circuit.assert_messages,
         );
-        acvm.with_profiler(self.profiling_active);
+        acvm.with_profiler(self.profiling_active || self.executed_opcodes.is_some());
+        acvm.with_coverage(self.executed_opcodes.is_some());
         acvm.with_brillig_fuzzing(self.brillig_branch_to_feature_map);
+        if let Some(execution_limits) = self.execution_limits {
+            acvm.with_execution_limits(execution_limits);
+        }
 
         loop {
             let solver_status = acvm.solve();
@@ -124,6 +165,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
                     unreachable!("Execution should not stop while in `InProgress` state.")
                 }
                 ACVMStatus::Failure(error) => {
+                    self.take_profiling_samples(self.current_function_index, &mut acvm);
                     self.last_fuzzing_trace = acvm.get_brillig_fuzzing_trace();
                     if self.return_witness_on_failure {
                         self.failing_partial_witness = Some(acvm.witness_map().clone());
@@ -164,7 +206,14 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
                     )));
                 }
                 ACVMStatus::RequiresForeignCall(foreign_call) => {
-                    let foreign_call_result = self.foreign_call_executor.execute(&foreign_call)?;
+                    let foreign_call_result =
+                        match self.foreign_call_executor.execute(&foreign_call) {
+                            Ok(foreign_call_result) => foreign_call_result,
+                            Err(error) => {
+                                self.take_profiling_samples(self.current_function_index, &mut acvm);
+                                return Err(error.into());
+                            }
+                        };
                     acvm.resolve_pending_foreign_call(foreign_call_result);
                 }
                 ACVMStatus::RequiresAcirCall(call_info) => {
@@ -182,7 +231,13 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
                     let acir_to_call = &self.functions[call_info.id.as_usize()];
                     let initial_witness = call_info.initial_witness;
                     // TODO: Profiling among multiple circuits is not supported
-                    let (call_solved_witness, _) = self.execute_circuit(initial_witness)?;
+                    let (call_solved_witness, _) = match self.execute_circuit(initial_witness) {
+                        Ok(call_result) => call_result,
+                        Err(error) => {
+                            self.take_profiling_samples(acir_function_caller, &mut acvm);
+                            return Err(error);
+                        }
+                    };
 
                     // Set tracking index back to the parent function after ACIR call execution
                     self.current_function_index = acir_function_caller;
@@ -211,7 +266,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
         // included in a failure case.
         self.call_stack.clear();
 
-        let profiling_samples = acvm.take_profiling_samples();
+        let profiling_samples = self.take_profiling_samples(self.current_function_index, &mut acvm);
         self.last_fuzzing_trace = acvm.get_brillig_fuzzing_trace();
         Ok((acvm.finalize(), profiling_samples))
     }
@@ -255,6 +310,65 @@ pub fn execute_program_with_profiling<
         profiling_active,
     )
 }
+
+/// Executes the program while tracking the opcodes it executes, for code coverage.
+///
+/// The executed opcodes are returned whether or not execution succeeded.
+pub fn execute_program_with_coverage<
+    F: AcirField,
+    B: BlackBoxFunctionSolver<F>,
+    E: ForeignCallExecutor<F>,
+>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+) -> (Result<WitnessStack<F>, NargoError<F>>, ExecutedOpcodes) {
+    let (result, executed_opcodes) = execute_test_program(
+        program,
+        initial_witness,
+        blackbox_solver,
+        foreign_call_executor,
+        true,
+        None,
+    );
+    (result, executed_opcodes.unwrap_or_default())
+}
+
+/// Executes the program of a test, optionally tracking the opcodes it executes for code coverage
+/// and stopping its unconstrained calls once `execution_limits` are exceeded.
+pub(crate) fn execute_test_program<
+    F: AcirField,
+    B: BlackBoxFunctionSolver<F>,
+    E: ForeignCallExecutor<F>,
+>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+    coverage: bool,
+    execution_limits: Option<&ExecutionLimits>,
+) -> (Result<WitnessStack<F>, NargoError<F>>, Option<ExecutedOpcodes>) {
+    let mut executor = ProgramExecutor::new(
+        &program.functions,
+        &program.unconstrained_functions,
+        blackbox_solver,
+        foreign_call_executor,
+        false,
+    );
+    if coverage {
+        executor.with_coverage();
+    }
+    executor.with_execution_limits(execution_limits);
+    let result = executor.execute_circuit(initial_witness);
+    let executed_opcodes = executor.executed_opcodes.take();
+    let result = result.map(|(main_witness, _)| {
+        executor.witness_stack.push(0, main_witness);
+        executor.finalize()
+    });
+    (result, executed_opcodes)
+}
+
 pub(crate) fn execute_program_with_brillig_fuzzing<
     F: AcirField,
     B: BlackBoxFunctionSolver<F>,
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use acvm::{
     BlackBoxFunctionSolver, FieldElement,
     acir::native_types::{WitnessMap, WitnessStack},
@@ -6,12 +8,15 @@ use acvm::{
 use noir_greybox_fuzzer::{
     AcirAndBrilligPrograms, ErrorAndCoverage, ErrorAndWitness, FuzzTestResult,
     FuzzedExecutorExecutionConfiguration, FuzzedExecutorFailureConfiguration,
-    FuzzedExecutorFolderConfiguration, WitnessAndCoverage,
+    FuzzedExecutorFolderConfiguration, InputStrategy, WitnessAndCoverage,
 };
-use noirc_abi::{Abi, InputMap};
+use noirc_abi::{Abi, AbiType, InputMap, Sign};
 use noirc_driver::{CompileOptions, compile_no_check};
 use noirc_errors::CustomDiagnostic;
-use noirc_frontend::hir::{Context, def_map::FuzzingHarness};
+use noirc_frontend::{
+    hir::{Context, def_map::FuzzingHarness},
+    token::FuzzStrategyKind,
+};
 
 use crate::foreign_calls::ForeignCallExecutor;
 use crate::{
@@ -217,7 +222,8 @@ where
                     corpus_dir: fuzz_folder_config.corpus_dir.clone(),
                     minimized_corpus_dir: fuzz_folder_config.minimized_corpus_dir.clone(),
                 },
-            );
+            )
+            .with_input_strategies(input_strategies(context, fuzzing_harness, &abi));
 
             let result = fuzzer.fuzz();
             match result {
@@ -337,3 +343,39 @@ where
 fn output(show_output: bool) -> Box<dyn std::io::Write> {
     if show_output { Box::new(std::io::stdout()) } else { Box::new(std::io::empty()) }
 }
+
+/// The `#[range(..)]` and `#[one_of(..)]` strategies of the parameters of a fuzzing harness
+fn input_strategies(
+    context: &Context,
+    fuzzing_harness: &FuzzingHarness,
+    abi: &Abi,
+) -> BTreeMap<String, InputStrategy> {
+    let fuzz_strategies = &context.def_interner.function_meta(&fuzzing_harness.id).fuzz_strategies;
+    fuzz_strategies
+        .iter()
+        .map(|(name, strategy)| {
+            let strategy = match strategy {
+                FuzzStrategyKind::Range { min, max } => InputStrategy::Range {
+                    min: *min,
+                    max: *max,
+                    signed_bit_size: signed_bit_size(abi, name),
+                },
+                FuzzStrategyKind::OneOf(values) => InputStrategy::OneOf(values.clone()),
+            };
+            (name.clone(), strategy)
+        })
+        .collect()
+}
+
+/// The bit size of the signed integers of a parameter, which can be in arrays
+fn signed_bit_size(abi: &Abi, name: &str) -> Option<u32> {
+    let param = abi.parameters.iter().find(|param| param.name == name)?;
+    let mut typ = &param.typ;
+    while let AbiType::Array { typ: element, .. } = typ {
+        typ = element.as_ref();
+    }
+    match typ {
+        AbiType::Integer { sign: Sign::Signed, width } => Some(*width),
+        _ => None,
+    }
+}
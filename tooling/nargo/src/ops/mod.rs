@@ -6,7 +6,11 @@ pub use self::compile::{
 pub use self::optimize::{optimize_contract, optimize_program};
 pub use self::transform::{transform_contract, transform_program};
 
-pub use self::execute::{execute_program, execute_program_with_profiling};
+pub use self::bench::{BenchMeasurements, BenchStatus, run_bench};
+pub use self::coverage::{CoverageReport, ExecutedOpcodes};
+pub use self::execute::{
+    execute_program, execute_program_with_coverage, execute_program_with_profiling,
+};
 pub use self::fuzz::{
     FuzzExecutionConfig, FuzzFolderConfig, FuzzingRunStatus, run_fuzzing_harness,
 };
@@ -15,8 +19,10 @@ pub use self::test::{
     test_status_program_compile_fail, test_status_program_compile_pass,
 };
 
+mod bench;
 mod check;
 mod compile;
+mod coverage;
 pub mod debug;
 mod execute;
 mod fuzz;
@@ -6,9 +6,11 @@ use acvm::{
         brillig::ForeignCallResult,
         native_types::{WitnessMap, WitnessStack},
     },
+    brillig_vm::ExecutionLimits,
     pwg::ForeignCallWaitInfo,
 };
-use noirc_abi::{Abi, input_parser::json::serialize_to_json};
+use fm::FileManager;
+use noirc_abi::{Abi, InputMap, input_parser::json::serialize_to_json};
 use noirc_driver::{
     CompileError, CompileOptions, CompiledProgram, DEFAULT_EXPRESSION_WIDTH, compile_no_check,
 };
@@ -30,15 +32,25 @@ use crate::{
 };
 
 use super::{
-    FuzzExecutionConfig, FuzzFolderConfig, FuzzingRunStatus, execute_program, run_fuzzing_harness,
+    CoverageReport, FuzzExecutionConfig, FuzzFolderConfig, FuzzingRunStatus,
+    execute::{execute_program, execute_test_program},
+    run_fuzzing_harness,
 };
 
 #[derive(Debug)]
 pub enum TestStatus {
     Pass,
-    Fail { message: String, error_diagnostic: Option<CustomDiagnostic> },
+    Fail {
+        message: String,
+        error_diagnostic: Option<CustomDiagnostic>,
+    },
     Skipped,
     CompileError(CustomDiagnostic),
+    /// The test exceeded its time limit or its limit of executed Brillig opcodes.
+    /// Such a test fails even if it should have failed.
+    TimedOut {
+        message: String,
+    },
 }
 
 impl TestStatus {
@@ -53,6 +65,11 @@ pub struct FuzzConfig {
 }
 
 /// Runs a test function. This will either run the test or fuzz it, depending on whether the function has arguments.
+///
+/// When `coverage` is given, the source lines executed by a test without arguments are added to it.
+/// Such a test also stops with [TestStatus::TimedOut] once `execution_limits` are exceeded,
+/// and fails without being executed if `check_program` rejects its compiled program.
+/// None of these apply to fuzz tests.
 #[allow(clippy::too_many_arguments)]
 pub fn run_or_fuzz_test<'a, W, B, F, E>(
     blackbox_solver: &B,
@@ -62,6 +79,9 @@ pub fn run_or_fuzz_test<'a, W, B, F, E>(
     package_name: String,
     config: &CompileOptions,
     fuzz_config: FuzzConfig,
+    coverage: Option<&mut CoverageReport>,
+    execution_limits: Option<&ExecutionLimits>,
+    check_program: Option<&dyn Fn(&CompiledProgram) -> Result<(), String>>,
     build_foreign_call_executor: F,
 ) -> TestStatus
 where
@@ -80,12 +100,15 @@ where
             build_foreign_call_executor,
         )
     } else {
-        run_test::<W, B, F, E>(
+        run_test_inner::<W, B, F, E>(
             blackbox_solver,
             context,
             test_function,
             output,
             config,
+            coverage,
+            execution_limits,
+            check_program,
             build_foreign_call_executor,
         )
     }
@@ -100,6 +123,37 @@ pub fn run_test<'a, W, B, F, E>(
     config: &CompileOptions,
     build_foreign_call_executor: F,
 ) -> TestStatus
+where
+    W: std::io::Write + 'a,
+    B: BlackBoxFunctionSolver<FieldElement>,
+    F: Fn(Box<dyn std::io::Write + 'a>, layers::Unhandled) -> E,
+    E: ForeignCallExecutor<FieldElement>,
+{
+    run_test_inner(
+        blackbox_solver,
+        context,
+        test_function,
+        output,
+        config,
+        None,
+        None,
+        None,
+        build_foreign_call_executor,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_test_inner<'a, W, B, F, E>(
+    blackbox_solver: &B,
+    context: &mut Context,
+    test_function: &TestFunction,
+    output: W,
+    config: &CompileOptions,
+    coverage: Option<&mut CoverageReport>,
+    execution_limits: Option<&ExecutionLimits>,
+    check_program: Option<&dyn Fn(&CompiledProgram) -> Result<(), String>>,
+    build_foreign_call_executor: F,
+) -> TestStatus
 where
     W: std::io::Write + 'a,
     B: BlackBoxFunctionSolver<FieldElement>,
@@ -107,24 +161,104 @@ where
     E: ForeignCallExecutor<FieldElement>,
 {
     match compile_no_check(context, config, test_function.id, None, false) {
-        Ok(compiled_program) => run_test_impl(
-            blackbox_solver,
-            compiled_program,
-            test_function,
-            output,
-            config,
-            build_foreign_call_executor,
-        ),
+        Ok(compiled_program) => {
+            let initial_witness = match evaluate_fixtures(
+                blackbox_solver,
+                context,
+                test_function,
+                config,
+                &compiled_program.abi,
+                &build_foreign_call_executor,
+            ) {
+                Ok(initial_witness) => initial_witness,
+                Err(status) => return status,
+            };
+            run_test_impl(
+                blackbox_solver,
+                compiled_program,
+                initial_witness,
+                test_function,
+                output,
+                config,
+                coverage.map(|coverage| (coverage, context.file_manager.as_ref())),
+                execution_limits,
+                check_program,
+                build_foreign_call_executor,
+            )
+        }
         Err(err) => test_status_program_compile_fail(err, test_function),
     }
 }
 
+/// Executes the fixtures of a test, returning the initial witness which gives the values
+/// they return to the parameters of the test.
+fn evaluate_fixtures<'a, B, F, E>(
+    blackbox_solver: &B,
+    context: &mut Context,
+    test_function: &TestFunction,
+    config: &CompileOptions,
+    abi: &Abi,
+    build_foreign_call_executor: &F,
+) -> Result<WitnessMap<FieldElement>, TestStatus>
+where
+    B: BlackBoxFunctionSolver<FieldElement>,
+    F: Fn(Box<dyn std::io::Write + 'a>, layers::Unhandled) -> E,
+    E: ForeignCallExecutor<FieldElement>,
+{
+    if test_function.fixtures.is_empty() {
+        return Ok(WitnessMap::new());
+    }
+
+    let target_width = config.expression_width.unwrap_or(DEFAULT_EXPRESSION_WIDTH);
+    let mut inputs = InputMap::new();
+    for fixture in &test_function.fixtures {
+        let fixture_failed = |message: String| TestStatus::Fail {
+            message: format!("Fixture `{}` failed: {message}", fixture.parameter),
+            error_diagnostic: None,
+        };
+
+        let program = compile_no_check(context, config, fixture.function, None, false)
+            .map_err(|err| TestStatus::CompileError(err.into()))?;
+        let program = crate::ops::transform_program(program, target_width);
+
+        // The output of fixtures isn't shown, only the one of the test itself
+        let foreign_call_executor =
+            build_foreign_call_executor(Box::new(std::io::sink()), layers::Unhandled);
+        let mut foreign_call_executor = TestForeignCallExecutor::new(foreign_call_executor);
+        let witness_stack = execute_program(
+            &program.program,
+            WitnessMap::new(),
+            blackbox_solver,
+            &mut foreign_call_executor,
+        )
+        .map_err(|err| fixture_failed(err.to_string()))?;
+
+        let main_witness =
+            &witness_stack.peek().expect("Should have at least one witness on the stack").witness;
+        let (_, return_value) =
+            program.abi.decode(main_witness).map_err(|err| fixture_failed(err.to_string()))?;
+        let return_value =
+            return_value.ok_or_else(|| fixture_failed("it didn't return a value".to_string()))?;
+        inputs.insert(fixture.parameter.clone(), return_value);
+    }
+
+    abi.encode(&inputs, None).map_err(|err| TestStatus::Fail {
+        message: format!("Could not give the values of fixtures to the test: {err}"),
+        error_diagnostic: None,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
 fn run_test_impl<'a, W, B, F, E>(
     blackbox_solver: &B,
     compiled_program: CompiledProgram,
+    initial_witness: WitnessMap<FieldElement>,
     test_function: &TestFunction,
     output: W,
     config: &CompileOptions,
+    coverage: Option<(&mut CoverageReport, &FileManager)>,
+    execution_limits: Option<&ExecutionLimits>,
+    check_program: Option<&dyn Fn(&CompiledProgram) -> Result<(), String>>,
     build_foreign_call_executor: F,
 ) -> TestStatus
 where
@@ -137,6 +271,12 @@ where
     let target_width = config.expression_width.unwrap_or(DEFAULT_EXPRESSION_WIDTH);
     let compiled_program = crate::ops::transform_program(compiled_program, target_width);
 
+    if let Some(check_program) = check_program {
+        if let Err(message) = check_program(&compiled_program) {
+            return TestStatus::Fail { message, error_diagnostic: None };
+        }
+    }
+
     let ignore_foreign_call_failures =
         std::env::var("NARGO_IGNORE_TEST_FAILURES_FROM_FOREIGN_CALLS")
             .is_ok_and(|var| &var == "true");
@@ -161,12 +301,23 @@ where
     let foreign_call_executor = TestForeignCallExecutor::new(foreign_call_executor);
     let mut foreign_call_executor = LoggingForeignCallExecutor::new(foreign_call_executor, writer);
 
-    let circuit_execution = execute_program(
+    let (circuit_execution, executed_opcodes) = execute_test_program(
         &compiled_program.program,
-        WitnessMap::new(),
+        initial_witness,
         blackbox_solver,
         &mut foreign_call_executor,
+        coverage.is_some(),
+        execution_limits,
     );
+    if let (Some((coverage, file_manager)), Some(executed_opcodes)) = (coverage, executed_opcodes) {
+        coverage.add_program(&compiled_program.debug, &executed_opcodes, file_manager);
+    }
+
+    if let (Err(_), Some(execution_limits)) = (&circuit_execution, execution_limits) {
+        if let Some(message) = exceeded_execution_limits_message(execution_limits) {
+            return TestStatus::TimedOut { message };
+        }
+    }
 
     let status = test_status_program_compile_pass(
         test_function,
@@ -188,6 +339,18 @@ where
     }
 }
 
+/// Describes the execution limits which were exceeded, if any.
+fn exceeded_execution_limits_message(execution_limits: &ExecutionLimits) -> Option<String> {
+    if execution_limits.is_interrupted() {
+        Some("Test execution was interrupted".to_string())
+    } else if execution_limits.max_opcodes_exceeded() {
+        let max_opcodes = execution_limits.max_opcodes()?;
+        Some(format!("Test exceeded the limit of {max_opcodes} executed Brillig opcodes"))
+    } else {
+        None
+    }
+}
+
 /// Runs the fuzzer on a test function. This assumes the function has arguments.
 pub fn fuzz_test<'a, B, F, E>(
     context: &mut Context,
@@ -1,8 +1,14 @@
-use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    path::PathBuf,
+};
 
 use acvm::acir::circuit::ExpressionWidth;
 pub use noirc_driver::CrateName;
+use noirc_driver::{CompileOptions, CompileProfile};
 use noirc_frontend::elaborator::UnstableFeature;
+use serde::{Deserialize, Serialize};
 
 use crate::constants::PROVER_INPUT_FILE;
 
@@ -23,22 +29,50 @@ impl Display for PackageType {
     }
 }
 
+/// The revision of a git repository a dependency refers to.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitReference {
+    Tag(String),
+    /// The tip of a branch, which `Nargo.lock` pins to the commit it pointed to when first resolved.
+    Branch(String),
+    /// A specific commit.
+    Rev(String),
+}
+
+impl Display for GitReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitReference::Tag(tag) => write!(f, "tag `{tag}`"),
+            GitReference::Branch(branch) => write!(f, "branch `{branch}`"),
+            GitReference::Rev(rev) => write!(f, "rev `{rev}`"),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum Dependency {
-    Local { package: Package },
-    Remote { package: Package },
+    Local {
+        package: Package,
+    },
+    /// A package fetched from the git repository at `git`.
+    Remote {
+        package: Package,
+        git: String,
+        reference: GitReference,
+    },
 }
 
 impl Dependency {
     pub fn is_binary(&self) -> bool {
         match self {
-            Self::Local { package } | Self::Remote { package } => package.is_binary(),
+            Self::Local { package } | Self::Remote { package, .. } => package.is_binary(),
         }
     }
 
     pub fn package_name(&self) -> &CrateName {
         match self {
-            Self::Local { package } | Self::Remote { package } => &package.name,
+            Self::Local { package } | Self::Remote { package, .. } => &package.name,
         }
     }
 }
@@ -54,7 +88,13 @@ pub struct Package {
     pub entry_path: PathBuf,
     pub name: CrateName,
     pub dependencies: BTreeMap<CrateName, Dependency>,
+    /// Dependencies only available to tests, which are `None` unless they were resolved for building tests.
+    pub dev_dependencies: BTreeMap<CrateName, Option<Dependency>>,
     pub expression_width: Option<ExpressionWidth>,
+    /// The compilation profile selected for the package, if it is a workspace member.
+    pub profile: Option<CompileProfile>,
+    /// Features enabled for the package, including the ones enabled by other features.
+    pub features: BTreeSet<String>,
 }
 
 impl Package {
@@ -75,4 +115,12 @@ impl Package {
     pub fn is_library(&self) -> bool {
         self.package_type == PackageType::Library
     }
+
+    /// The options to compile the package with, after applying its compilation profile.
+    pub fn compile_options(&self, compile_options: &CompileOptions) -> CompileOptions {
+        match &self.profile {
+            Some(profile) => profile.apply(compile_options),
+            None => compile_options.clone(),
+        }
+    }
 }
@@ -172,6 +172,9 @@ const IGNORED_NARGO_EXPAND_EXECUTION_TESTS: [&str; 7] = [
     "workspace_default_member",
 ];
 
+/// `noir_test_success` tests whose doc tests are run, with `nargo test --doc`.
+const DOC_TESTS: [&str; 1] = ["doc_tests"];
+
 /// Tests for which we don't check that stdout matches the expected output.
 const TESTS_WITHOUT_STDOUT_CHECK: [&str; 0] = [];
 
@@ -540,12 +543,20 @@ fn generate_noir_test_success_tests(test_file: &mut File, test_data_dir: &Path)
     for (test_name, test_dir) in test_cases {
         let test_dir = test_dir.display();
 
+        let test_content = if DOC_TESTS.contains(&test_name.as_str()) {
+            r#"
+                nargo.arg("--doc");
+                nargo.assert().success().stdout(predicate::str::contains("src/wrapper.nr:5"));
+            "#
+        } else {
+            "noir_test_success(nargo);"
+        };
         generate_test_cases(
             test_file,
             &test_name,
             &test_dir,
             "test",
-            "noir_test_success(nargo);",
+            test_content,
             &MatrixConfig::default(),
         );
     }
@@ -18,7 +18,8 @@ use nargo::{
     workspace::Workspace,
 };
 use nargo_toml::{
-    ManifestError, PackageSelection, get_package_manifest, resolve_workspace_from_toml,
+    ManifestError, PackageSelection, ResolveOptions, get_package_manifest,
+    resolve_workspace_from_toml,
 };
 use noirc_driver::{
     CompilationResult, CompileOptions, CrateName, NOIR_ARTIFACT_VERSION_STRING, check_crate,
@@ -84,6 +85,7 @@ fn read_workspace(
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+        &ResolveOptions::default(),
     )?;
 
     Ok(workspace)
@@ -118,7 +120,7 @@ fn main() {
         silence_warnings: true,
         skip_underconstrained_check: true,
         skip_brillig_constraints_check: true,
-        inliner_aggressiveness: opts.inliner_aggressiveness,
+        inliner_aggressiveness: Some(opts.inliner_aggressiveness),
         ..Default::default()
     };
 
@@ -0,0 +1,289 @@
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use bn254_blackbox_solver::Bn254BlackBoxSolver;
+use clap::Args;
+use nargo::{
+    foreign_calls::DefaultForeignCallBuilder,
+    insert_all_files_for_workspace_into_file_manager,
+    ops::{BenchMeasurements, BenchStatus, check_crate_and_report_errors},
+    package::Package,
+    parse_all, prepare_package,
+    workspace::Workspace,
+};
+use nargo_toml::PackageSelection;
+use noirc_driver::CompileOptions;
+use noirc_frontend::hir::FunctionNameMatch;
+
+use crate::errors::CliError;
+
+use super::{LockType, PackageOptions, WorkspaceCommand};
+
+/// Run the benchmarks of this program, marked with `#[bench]`, comparing them with the results
+/// of the previous run
+#[derive(Debug, Clone, Args)]
+pub(crate) struct BenchCommand {
+    /// If given, only benchmarks with names containing this string will be run
+    bench_names: Vec<String>,
+
+    /// Only run benchmarks that match exactly
+    #[clap(long)]
+    exact: bool,
+
+    /// Number of times each benchmark is executed to measure its execution time
+    #[clap(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+
+    #[clap(flatten)]
+    pub(super) package_options: PackageOptions,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+impl WorkspaceCommand for BenchCommand {
+    fn package_selection(&self) -> PackageSelection {
+        self.package_options.package_selection()
+    }
+
+    fn lock_type(&self) -> LockType {
+        // Writes the results of the benchmarks to the target directory.
+        LockType::Exclusive
+    }
+
+    fn include_dev_dependencies(&self) -> bool {
+        true
+    }
+}
+
+pub(crate) fn run(args: BenchCommand, workspace: Workspace) -> Result<(), CliError> {
+    let mut file_manager = workspace.new_file_manager();
+    insert_all_files_for_workspace_into_file_manager(&workspace, &mut file_manager);
+    let parsed_files = parse_all(&file_manager);
+
+    let pattern = if args.bench_names.is_empty() {
+        FunctionNameMatch::Anything
+    } else if args.exact {
+        FunctionNameMatch::Exact(args.bench_names.clone())
+    } else {
+        FunctionNameMatch::Contains(args.bench_names.clone())
+    };
+
+    let bench_dir = workspace.target_directory_path().join("bench");
+    let blackbox_solver = Bn254BlackBoxSolver(args.compile_options.pedantic_solving);
+
+    let mut found_bench = false;
+    let mut failed = false;
+    for package in &workspace {
+        let (mut context, crate_id) = prepare_package(&file_manager, &parsed_files, package);
+        let compile_options = package.compile_options(&args.compile_options);
+        check_crate_and_report_errors(&mut context, crate_id, &compile_options)?;
+
+        let bench_functions =
+            context.get_all_bench_functions_in_crate_matching(&crate_id, &pattern);
+        if bench_functions.is_empty() {
+            continue;
+        }
+        found_bench = true;
+
+        let mut baselines = BenchBaselines::load(&bench_dir, package)?;
+        for (bench_name, bench_function) in bench_functions {
+            let status = nargo::ops::run_bench(
+                &blackbox_solver,
+                &mut context,
+                &bench_function,
+                &compile_options,
+                args.runs,
+                || DefaultForeignCallBuilder::default().build(),
+            );
+            match status {
+                BenchStatus::Pass(measurements) => {
+                    let baseline = baselines.results.get(&bench_name);
+                    println!("[{}] {bench_name}", package.name);
+                    println!("{}", describe_measurements(&measurements, baseline));
+                    baselines.results.insert(bench_name, measurements);
+                }
+                BenchStatus::Fail { message, error_diagnostic } => {
+                    eprintln!("[{}] {bench_name} failed: {message}", package.name);
+                    if let Some(diagnostic) = error_diagnostic {
+                        noirc_errors::reporter::report_all(
+                            file_manager.as_file_map(),
+                            &[diagnostic],
+                            args.compile_options.deny_warnings,
+                            args.compile_options.silence_warnings,
+                        );
+                    }
+                    failed = true;
+                }
+                BenchStatus::CompileError(diagnostic) => {
+                    eprintln!("[{}] {bench_name} failed to compile", package.name);
+                    noirc_errors::reporter::report_all(
+                        file_manager.as_file_map(),
+                        &[diagnostic],
+                        args.compile_options.deny_warnings,
+                        args.compile_options.silence_warnings,
+                    );
+                    failed = true;
+                }
+            }
+        }
+        baselines.write()?;
+    }
+
+    if !found_bench {
+        let message = match &pattern {
+            FunctionNameMatch::Anything => "Found no benchmarks in this workspace".to_string(),
+            FunctionNameMatch::Exact(names) | FunctionNameMatch::Contains(names) => {
+                format!("Found no benchmarks matching {}", names.join(", "))
+            }
+        };
+        return Err(CliError::Generic(message));
+    }
+
+    if failed { Err(CliError::Generic(String::new())) } else { Ok(()) }
+}
+
+/// The results of the previous run of the benchmarks of a package, stored in
+/// `target/bench/<package>.json`. Each run replaces the results of the benchmarks it ran.
+struct BenchBaselines {
+    path: PathBuf,
+    results: BTreeMap<String, BenchMeasurements>,
+}
+
+impl BenchBaselines {
+    /// Loads the results of a package, which are empty if its benchmarks never ran.
+    fn load(bench_dir: &std::path::Path, package: &Package) -> Result<Self, CliError> {
+        let path = bench_dir.join(format!("{}.json", package.name));
+        let results = if path.exists() {
+            let contents = std::fs::read_to_string(&path).map_err(|error| {
+                CliError::Generic(format!("Could not read {}: {error}", path.display()))
+            })?;
+            // Results saved by an incompatible version of nargo are discarded.
+            serde_json::from_str(&contents).unwrap_or_default()
+        } else {
+            BTreeMap::new()
+        };
+        Ok(BenchBaselines { path, results })
+    }
+
+    fn write(&self) -> Result<(), CliError> {
+        let write = || {
+            if let Some(dir) = self.path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let json =
+                serde_json::to_string_pretty(&self.results).expect("Could not serialize results");
+            std::fs::write(&self.path, json + "\n")
+        };
+        write().map_err(|error| {
+            CliError::Generic(format!("Could not write {}: {error}", self.path.display()))
+        })
+    }
+}
+
+/// Describes the measurements of a benchmark, one per line, along with how they changed
+/// from the baseline if there is one.
+fn describe_measurements(
+    measurements: &BenchMeasurements,
+    baseline: Option<&BenchMeasurements>,
+) -> String {
+    let lines = [
+        count_change(
+            "ACIR opcodes",
+            measurements.acir_opcodes,
+            baseline.map(|baseline| baseline.acir_opcodes),
+        ),
+        count_change(
+            "Brillig opcodes",
+            measurements.brillig_opcodes,
+            baseline.map(|baseline| baseline.brillig_opcodes),
+        ),
+        count_change(
+            "Executed Brillig opcodes",
+            measurements.executed_brillig_opcodes,
+            baseline.map(|baseline| baseline.executed_brillig_opcodes),
+        ),
+        time_change(
+            "Mean execution time",
+            measurements.mean_time,
+            baseline.map(|baseline| baseline.mean_time),
+        ),
+        time_change(
+            "Min execution time",
+            measurements.min_time,
+            baseline.map(|baseline| baseline.min_time),
+        ),
+    ];
+    lines.join("\n")
+}
+
+fn count_change(name: &str, count: usize, baseline: Option<usize>) -> String {
+    match baseline {
+        Some(baseline) if baseline != count => {
+            let change = percent_change(baseline as f64, count as f64);
+            format!("  {name}: {baseline} -> {count}{change}")
+        }
+        _ => format!("  {name}: {count}"),
+    }
+}
+
+fn time_change(name: &str, time: Duration, baseline: Option<Duration>) -> String {
+    match baseline {
+        Some(baseline) => {
+            let change = percent_change(baseline.as_secs_f64(), time.as_secs_f64());
+            format!("  {name}: {baseline:.2?} -> {time:.2?}{change}")
+        }
+        None => format!("  {name}: {time:.2?}"),
+    }
+}
+
+fn percent_change(baseline: f64, value: f64) -> String {
+    if baseline == 0.0 {
+        String::new()
+    } else {
+        format!(" ({:+.1}%)", (value - baseline) * 100.0 / baseline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use nargo::ops::BenchMeasurements;
+
+    use super::describe_measurements;
+
+    #[test]
+    fn describes_changes_from_the_baseline() {
+        let baseline = BenchMeasurements {
+            acir_opcodes: 1000,
+            brillig_opcodes: 40,
+            executed_brillig_opcodes: 200,
+            mean_time: Duration::from_millis(10),
+            min_time: Duration::from_millis(8),
+        };
+        let measurements = BenchMeasurements {
+            acir_opcodes: 1200,
+            executed_brillig_opcodes: 100,
+            mean_time: Duration::from_millis(12),
+            ..baseline
+        };
+
+        assert_eq!(
+            describe_measurements(&measurements, Some(&baseline)),
+            "  ACIR opcodes: 1000 -> 1200 (+20.0%)
+  Brillig opcodes: 40
+  Executed Brillig opcodes: 200 -> 100 (-50.0%)
+  Mean execution time: 10.00ms -> 12.00ms (+20.0%)
+  Min execution time: 8.00ms -> 8.00ms (+0.0%)"
+        );
+
+        assert_eq!(
+            describe_measurements(&measurements, None),
+            "  ACIR opcodes: 1200
+  Brillig opcodes: 40
+  Executed Brillig opcodes: 100
+  Mean execution time: 12.00ms
+  Min execution time: 8.00ms"
+        );
+    }
+}
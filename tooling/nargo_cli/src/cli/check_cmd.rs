@@ -8,13 +8,17 @@ use nargo::{
     ops::check_crate_and_report_errors, package::Package, parse_all, prepare_package,
     workspace::Workspace,
 };
-use nargo_toml::PackageSelection;
+use nargo_toml::{Config, PackageSelection, ResolveOptions};
 use noir_artifact_cli::fs::artifact::write_to_file;
 use noirc_abi::{AbiParameter, AbiType, MAIN_RETURN_NAME};
 use noirc_driver::{CompileOptions, check_crate, compute_function_abi};
 use noirc_frontend::{hir::ParsedFiles, monomorphization::monomorphize};
 
-use super::{LockType, PackageOptions, WorkspaceCommand};
+use super::{LockType, NargoConfig, PackageOptions, WorkspaceCommand};
+
+/// The largest number of features whose combinations `--feature-powerset` checks, as their number
+/// doubles with each feature.
+const MAX_POWERSET_FEATURES: usize = 10;
 
 /// Check a local package and all of its dependencies for errors
 #[derive(Debug, Clone, Args)]
@@ -33,6 +37,11 @@ pub(crate) struct CheckCommand {
     /// Just show the hash of each packages, without actually performing the check.
     #[clap(long, hide = true)]
     show_program_hash: bool,
+
+    /// Check every package with each combination of its features, instead of the ones selected
+    /// with `--features`, `--all-features` and `--no-default-features`.
+    #[clap(long)]
+    pub(super) feature_powerset: bool,
 }
 
 impl WorkspaceCommand for CheckCommand {
@@ -52,10 +61,11 @@ pub(crate) fn run(args: CheckCommand, workspace: Workspace) -> Result<(), CliErr
     let parsed_files = parse_all(&workspace_file_manager);
 
     for package in &workspace {
+        let compile_options = package.compile_options(&args.compile_options);
         if args.show_program_hash {
             let (mut context, crate_id) =
                 prepare_package(&workspace_file_manager, &parsed_files, package);
-            check_crate(&mut context, crate_id, &args.compile_options).unwrap();
+            check_crate(&mut context, crate_id, &compile_options).unwrap();
             let Some(main) = context.get_main_function(&crate_id) else {
                 continue;
             };
@@ -69,13 +79,77 @@ pub(crate) fn run(args: CheckCommand, workspace: Workspace) -> Result<(), CliErr
             &workspace_file_manager,
             &parsed_files,
             package,
-            &args.compile_options,
+            &compile_options,
             args.allow_overwrite,
         )?;
     }
     Ok(())
 }
 
+/// Checks each selected package with every combination of the features it declares, so that code
+/// under `cfg(not(feature = ...))` is checked as well as code under `cfg(feature = ...)`.
+pub(crate) fn run_feature_powerset(
+    args: CheckCommand,
+    config: NargoConfig,
+) -> Result<(), CliError> {
+    let base_options = config.resolve_options();
+    super::with_workspace(args.clone(), config, |args, workspace| {
+        for package in &workspace {
+            let features = declared_features(package)?;
+            if features.len() > MAX_POWERSET_FEATURES {
+                return Err(CliError::Generic(format!(
+                    "Package `{}` declares {} features, but at most {MAX_POWERSET_FEATURES} can be combined with --feature-powerset",
+                    package.name,
+                    features.len()
+                )));
+            }
+            for combination in feature_powerset(&features) {
+                let description = if combination.is_empty() {
+                    "none".to_string()
+                } else {
+                    combination.join(", ")
+                };
+                println!("[{}] Checking with features: {description}", package.name);
+                let options = ResolveOptions {
+                    features: combination
+                        .iter()
+                        .map(|feature| format!("{}/{feature}", package.name))
+                        .collect(),
+                    all_features: false,
+                    no_default_features: true,
+                    ..base_options.clone()
+                };
+                let selection = PackageSelection::Selected(package.name.clone());
+                let workspace = super::read_workspace(&workspace.root_dir, selection, &options)?;
+                run(args.clone(), workspace)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// The features declared in the `[features]` table of `package`, other than `default`, which only
+/// enables some of the others.
+fn declared_features(package: &Package) -> Result<Vec<String>, CliError> {
+    let nargo_toml = nargo_toml::read_toml(&package.root_dir.join("Nargo.toml"))?;
+    let Config::Package { package_config } = nargo_toml.config else {
+        return Ok(Vec::new());
+    };
+    let features = package_config.features.into_keys();
+    Ok(features.filter(|feature| feature != "default").collect())
+}
+
+/// Every combination of `features`, starting with none of them.
+fn feature_powerset(features: &[String]) -> Vec<Vec<String>> {
+    (0..1usize << features.len())
+        .map(|mask| {
+            let included =
+                features.iter().enumerate().filter(|(index, _)| (mask >> index) & 1 == 1);
+            included.map(|(_, feature)| feature.clone()).collect()
+        })
+        .collect()
+}
+
 /// Evaluates the necessity to create or update Prover.toml and Verifier.toml based on the allow_overwrite flag and files' existence.
 /// Returns `true` if any file was generated or updated, `false` otherwise.
 fn check_package(
@@ -152,7 +226,22 @@ fn create_input_toml_template(
 mod tests {
     use noirc_abi::{AbiParameter, AbiType, AbiVisibility, Sign};
 
-    use super::create_input_toml_template;
+    use super::{create_input_toml_template, feature_powerset};
+
+    #[test]
+    fn feature_powerset_includes_each_feature_on_and_off() {
+        let features = vec![String::from("fast"), String::from("std")];
+        let combinations = feature_powerset(&features);
+        assert_eq!(
+            combinations,
+            vec![
+                Vec::<String>::new(),
+                vec![String::from("fast")],
+                vec![String::from("std")],
+                vec![String::from("fast"), String::from("std")],
+            ]
+        );
+    }
 
     #[test]
     fn valid_toml_template() {
@@ -231,8 +231,8 @@ fn compile_programs(
         )?;
 
         // Choose the target width for the final, backend specific transformation.
-        let target_width =
-            get_target_width(package.expression_width, compile_options.expression_width);
+        let expression_width = package.compile_options(compile_options).expression_width;
+        let target_width = get_target_width(package.expression_width, expression_width);
 
         // If the compiled program is the same as the cached one, we don't apply transformations again, unless the target width has changed.
         // The transformations might not be idempotent, which would risk creating witnesses that don't work with earlier versions,
@@ -282,8 +282,8 @@ fn compile_contracts(
         .map(|package| {
             let (contract, warnings) =
                 compile_contract(file_manager, parsed_files, package, compile_options)?;
-            let target_width =
-                get_target_width(package.expression_width, compile_options.expression_width);
+            let expression_width = package.compile_options(compile_options).expression_width;
+            let target_width = get_target_width(package.expression_width, expression_width);
             let contract = nargo::ops::transform_contract(contract, target_width);
             save_contract(contract, package, target_dir, compile_options.show_artifact_paths);
             Ok(((), warnings))
@@ -333,7 +333,7 @@ mod tests {
 
     use clap::Parser;
     use nargo::ops::compile_program;
-    use nargo_toml::PackageSelection;
+    use nargo_toml::{PackageSelection, ResolveOptions};
     use noirc_driver::{CompileOptions, CrateName};
     use noirc_frontend::elaborator::UnstableFeature;
 
@@ -403,7 +403,7 @@ mod tests {
         let verbose = matches!(sel, PackageSelection::Selected(_));
 
         let test_workspaces = read_test_program_dirs(&test_programs_dir(), "execution_success")
-            .filter_map(|dir| read_workspace(&dir, sel.clone()).ok())
+            .filter_map(|dir| read_workspace(&dir, sel.clone(), &ResolveOptions::default()).ok())
             .collect::<Vec<_>>();
 
         assert!(!test_workspaces.is_empty(), "should find some test workspaces");
@@ -15,7 +15,9 @@ use nargo::ops::debug::{
 use nargo::ops::{TestStatus, check_crate_and_report_errors, test_status_program_compile_pass};
 use nargo::package::Package;
 use nargo::workspace::Workspace;
-use nargo_toml::{PackageSelection, get_package_manifest, resolve_workspace_from_toml};
+use nargo_toml::{
+    PackageSelection, ResolveOptions, get_package_manifest, resolve_workspace_from_toml,
+};
 use noir_artifact_cli::fs::inputs::read_inputs_from_file;
 use noir_debugger::{DebugExecutionResult, DebugProject, RunParams};
 use noirc_abi::Abi;
@@ -89,6 +91,7 @@ fn find_workspace(project_folder: &str, package: Option<&str>) -> Option<Workspa
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        &ResolveOptions::default(),
     ) {
         Ok(workspace) => Some(workspace),
         Err(err) => {
@@ -152,6 +155,12 @@ fn load_and_compile_project(
         .find(|p| p.is_binary() || p.is_contract())
         .ok_or(LoadError::Generic("No matching binary or contract packages found in workspace. Only these packages can be debugged.".into()))?;
 
+    // Whether the program runs in Brillig is decided by the debugger rather than the profile
+    let compile_options = CompileOptions {
+        force_brillig: compile_options.force_brillig,
+        ..package.compile_options(&compile_options)
+    };
+
     let (compiled_program, test_def) = match test_name {
         None => {
             let program = compile_main(&workspace, package, &compile_options)?;
@@ -197,6 +206,12 @@ fn loop_uninitialized_dap<R: Read, W: Write>(
                     supports_disassemble_request: Some(true),
                     supports_instruction_breakpoints: Some(true),
                     supports_stepping_granularity: Some(true),
+                    supports_conditional_breakpoints: Some(true),
+                    supports_hit_conditional_breakpoints: Some(true),
+                    supports_data_breakpoints: Some(true),
+                    supports_step_back: Some(true),
+                    supports_evaluate_for_hovers: Some(true),
+                    supports_set_variable: Some(true),
                     ..Default::default()
                 }));
                 server.respond(rsp)?;
@@ -323,6 +338,7 @@ fn analyze_test_result<R: Read, W: Write>(
         }
         TestStatus::CompileError(diagnostic) => format!("x Test failed.\n{diagnostic:#?}"),
         TestStatus::Skipped => "* Test skipped".into(),
+        TestStatus::TimedOut { message } => format!("x Test timed out: {message}"),
     };
 
     server.send_event(dap::events::Event::Output(OutputEventBody {
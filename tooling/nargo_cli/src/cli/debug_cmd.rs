@@ -100,11 +100,6 @@ pub(crate) fn run(args: DebugCommand, workspace: Workspace) -> Result<(), CliErr
         witness_name: args.witness_name,
         target_dir: &workspace.target_directory_path(),
     };
-    let run_params = RunParams {
-        pedantic_solving: args.compile_options.pedantic_solving,
-        raw_source_printing: args.raw_source_printing,
-        oracle_resolver_url: args.oracle_resolver,
-    };
     let workspace_clone = workspace.clone();
 
     let Some(package) = workspace_clone.into_iter().find(|p| p.is_binary() || p.is_contract())
@@ -115,8 +110,17 @@ pub(crate) fn run(args: DebugCommand, workspace: Workspace) -> Result<(), CliErr
         return Ok(());
     };
 
-    let compile_options =
-        compile_options_for_debugging(acir_mode, skip_instrumentation, None, args.compile_options);
+    let compile_options = compile_options_for_debugging(
+        acir_mode,
+        skip_instrumentation,
+        None,
+        package.compile_options(&args.compile_options),
+    );
+    let run_params = RunParams {
+        pedantic_solving: compile_options.pedantic_solving,
+        raw_source_printing: args.raw_source_printing,
+        oracle_resolver_url: args.oracle_resolver,
+    };
 
     if let Some(test_name) = args.test_name {
         debug_test(test_name, package, workspace, compile_options, run_params, package_params)
@@ -41,7 +41,8 @@ pub(crate) fn run(args: ExpandCommand, workspace: Workspace) -> Result<(), CliEr
     let parsed_files = parse_all(&workspace_file_manager);
 
     for package in &workspace {
-        expand_package(&workspace_file_manager, &parsed_files, package, &args.compile_options)?;
+        let compile_options = package.compile_options(&args.compile_options);
+        expand_package(&workspace_file_manager, &parsed_files, package, &compile_options)?;
     }
 
     Ok(())
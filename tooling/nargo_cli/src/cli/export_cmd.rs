@@ -58,7 +58,7 @@ pub(crate) fn run(args: ExportCommand, workspace: Workspace) -> Result<(), CliEr
                 &parsed_files,
                 &workspace,
                 package,
-                &args.compile_options,
+                &package.compile_options(&args.compile_options),
             )
         })
         .collect()
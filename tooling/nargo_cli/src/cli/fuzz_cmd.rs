@@ -85,6 +85,9 @@ impl WorkspaceCommand for FuzzCommand {
         // Reads the code to compile fuzzing harnesses in memory, but doesn't save artifacts.
         LockType::None
     }
+    fn include_dev_dependencies(&self) -> bool {
+        true
+    }
 }
 
 /// List the fuzzing harnesses for this program
@@ -104,7 +107,7 @@ fn list_harnesses_command(
                     parsed_files,
                     package,
                     pattern,
-                    &args.compile_options,
+                    &package.compile_options(&args.compile_options),
                 );
                 match harnesses {
                     Ok(harness_names) => Ok((package.name.clone(), harness_names)),
@@ -175,7 +178,7 @@ pub(crate) fn run(args: FuzzCommand, workspace: Workspace) -> Result<(), CliErro
                 args.oracle_resolver.as_deref(),
                 Some(workspace.root_dir.clone()),
                 package.name.to_string(),
-                &args.compile_options,
+                &package.compile_options(&args.compile_options),
                 &fuzz_folder_config,
                 &fuzz_execution_config,
             )
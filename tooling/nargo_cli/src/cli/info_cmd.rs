@@ -96,10 +96,9 @@ pub(crate) fn run(mut args: InfoCommand, workspace: Workspace) -> Result<(), Cli
             .into_iter()
             .par_bridge()
             .map(|(package, program)| {
-                let target_width = get_target_width(
-                    package.expression_width,
-                    args.compile_options.expression_width,
-                );
+                let expression_width =
+                    package.compile_options(&args.compile_options).expression_width;
+                let target_width = get_target_width(package.expression_width, expression_width);
                 let package_name = package.name.to_string();
                 count_opcodes_and_gates_in_program(program, package_name, Some(target_width))
             })
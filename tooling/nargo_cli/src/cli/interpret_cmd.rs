@@ -74,8 +74,8 @@ pub(crate) fn run(args: InterpretCommand, workspace: Workspace) -> Result<(), Cl
     let ssa_passes = primary_passes(&opts);
 
     for package in binary_packages {
-        let ssa_options =
-            &args.compile_options.as_ssa_options(workspace.package_build_path(package));
+        let compile_options = package.compile_options(&args.compile_options);
+        let ssa_options = &compile_options.as_ssa_options(workspace.package_build_path(package));
 
         // Compile into monomorphized AST
         let program_result = compile_into_program(
@@ -83,7 +83,7 @@ pub(crate) fn run(args: InterpretCommand, workspace: Workspace) -> Result<(), Cl
             &parsed_files,
             &workspace,
             package,
-            &args.compile_options,
+            &compile_options,
         );
 
         // Report warnings and get the AST, or exit if the compilation failed.
@@ -218,7 +218,7 @@ fn compile_into_program(
     Ok(((program, abi), warnings))
 }
 
-fn msg_matches(patterns: &[String], msg: &str) -> bool {
+pub(super) fn msg_matches(patterns: &[String], msg: &str) -> bool {
     let msg = msg.to_lowercase();
     patterns.iter().any(|p| msg.contains(&p.to_lowercase()))
 }
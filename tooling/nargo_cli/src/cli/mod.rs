@@ -2,8 +2,9 @@ use clap::{Args, Parser, Subcommand};
 use const_format::formatcp;
 use nargo::workspace::Workspace;
 use nargo_toml::{
-    ManifestError, NargoToml, PackageConfig, PackageMetadata, PackageSelection,
-    get_package_manifest, resolve_workspace_from_fixed_toml, resolve_workspace_from_toml,
+    LockfileMode, ManifestError, NargoToml, PackageConfig, PackageMetadata, PackageSelection,
+    ResolveOptions, get_package_manifest, resolve_workspace_from_fixed_toml,
+    resolve_workspace_from_toml,
 };
 use noirc_driver::{CrateName, NOIR_ARTIFACT_VERSION_STRING};
 use std::{
@@ -17,6 +18,7 @@ use color_eyre::eyre;
 
 use crate::errors::CliError;
 
+mod bench_cmd;
 mod check_cmd;
 pub mod compile_cmd;
 mod dap_cmd;
@@ -32,7 +34,11 @@ mod init_cmd;
 mod interpret_cmd;
 mod lsp_cmd;
 mod new_cmd;
+mod outdated_cmd;
 mod test_cmd;
+mod tree_cmd;
+mod update_cmd;
+mod vendor_cmd;
 
 const GIT_HASH: &str = env!("GIT_COMMIT");
 const IS_DIRTY: &str = env!("GIT_DIRTY");
@@ -66,6 +72,53 @@ pub struct NargoConfig {
     /// Override the default target directory.
     #[arg(long, hide = true, global = true, value_parser = parse_path)]
     target_dir: Option<PathBuf>,
+
+    /// Require Nargo.lock to be up to date, failing instead of updating it.
+    #[arg(long, global = true)]
+    locked: bool,
+
+    /// Require Nargo.lock to be up to date and all git dependencies to be cached locally,
+    /// failing instead of accessing the network.
+    #[arg(long, global = true)]
+    frozen: bool,
+
+    /// Compile workspace members with the settings of this profile from Nargo.toml (defaults to `dev`).
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Features to enable on the selected workspace members, separated by commas.
+    /// Use `member/feature` to enable a feature of a single member.
+    #[arg(long, global = true, value_delimiter = ',')]
+    features: Vec<String>,
+
+    /// Enable every feature of the selected workspace members.
+    #[arg(long, global = true)]
+    all_features: bool,
+
+    /// Don't enable the `default` feature of the selected workspace members.
+    #[arg(long, global = true)]
+    no_default_features: bool,
+}
+
+impl NargoConfig {
+    /// Options for resolving the dependencies of the workspace.
+    fn resolve_options(&self) -> ResolveOptions {
+        let lockfile_mode = if self.frozen {
+            LockfileMode::Frozen
+        } else if self.locked {
+            LockfileMode::Locked
+        } else {
+            LockfileMode::Update
+        };
+        ResolveOptions {
+            lockfile_mode,
+            include_dev_dependencies: false,
+            profile: self.profile.clone(),
+            features: self.features.clone(),
+            all_features: self.all_features,
+            no_default_features: self.no_default_features,
+        }
+    }
 }
 
 /// Options for commands that work on either workspace or package scope.
@@ -110,11 +163,16 @@ enum NargoCommand {
     Debug(debug_cmd::DebugCommand),
     Test(test_cmd::TestCommand),
     Fuzz(fuzz_cmd::FuzzCommand),
+    Bench(bench_cmd::BenchCommand),
     Info(info_cmd::InfoCommand),
     Lsp(lsp_cmd::LspCommand),
     #[command(hide = true)]
     Dap(dap_cmd::DapCommand),
     Expand(expand_cmd::ExpandCommand),
+    Vendor(vendor_cmd::VendorCommand),
+    Update(update_cmd::UpdateCommand),
+    Tree(tree_cmd::TreeCommand),
+    Outdated(outdated_cmd::OutdatedCommand),
     GenerateCompletionScript(generate_completion_script_cmd::GenerateCompletionScriptCommand),
 }
 
@@ -124,6 +182,10 @@ trait WorkspaceCommand {
     fn package_selection(&self) -> PackageSelection;
     /// The kind of lock the command needs to take out on the selected packages.
     fn lock_type(&self) -> LockType;
+    /// Whether the command builds tests, which need the `[dev-dependencies]` of the workspace members.
+    fn include_dev_dependencies(&self) -> bool {
+        false
+    }
 }
 
 /// What kind of lock to take out on the (selected) workspace members.
@@ -146,6 +208,9 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
     match command {
         NargoCommand::New(args) => new_cmd::run(args, config),
         NargoCommand::Init(args) => init_cmd::run(args, config),
+        NargoCommand::Check(args) if args.feature_powerset => {
+            check_cmd::run_feature_powerset(args, config)
+        }
         NargoCommand::Check(args) => with_workspace(args, config, check_cmd::run),
         NargoCommand::Compile(args) => compile_with_maybe_dummy_workspace(args, config),
         NargoCommand::Interpret(args) => with_workspace(args, config, interpret_cmd::run),
@@ -154,11 +219,21 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::Export(args) => with_workspace(args, config, export_cmd::run),
         NargoCommand::Test(args) => with_workspace(args, config, test_cmd::run),
         NargoCommand::Fuzz(args) => with_workspace(args, config, fuzz_cmd::run),
+        NargoCommand::Bench(args) => with_workspace(args, config, bench_cmd::run),
         NargoCommand::Info(args) => with_workspace(args, config, info_cmd::run),
         NargoCommand::Lsp(_) => lsp_cmd::run(),
         NargoCommand::Dap(args) => dap_cmd::run(args),
         NargoCommand::Fmt(args) => with_workspace(args, config, fmt_cmd::run),
         NargoCommand::Expand(args) => with_workspace(args, config, expand_cmd::run),
+        NargoCommand::Vendor(args) => vendor_cmd::run(args, config),
+        NargoCommand::Update(args) => update_cmd::run(args, config),
+        NargoCommand::Tree(args) => with_workspace(args, config, tree_cmd::run),
+        NargoCommand::Outdated(args) => {
+            let offline = !config.resolve_options().lockfile_mode.allows_network();
+            with_workspace(args, config, |args, workspace| {
+                outdated_cmd::run(args, workspace, offline)
+            })
+        }
         NargoCommand::GenerateCompletionScript(args) => generate_completion_script_cmd::run(args),
     }?;
 
@@ -176,6 +251,7 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
 fn read_workspace(
     program_dir: &Path,
     selection: PackageSelection,
+    options: &ResolveOptions,
 ) -> Result<Workspace, ManifestError> {
     let toml_path = get_package_manifest(program_dir)?;
 
@@ -183,6 +259,7 @@ fn read_workspace(
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+        options,
     )?;
 
     Ok(workspace)
@@ -196,6 +273,7 @@ fn compile_with_maybe_dummy_workspace(
 ) -> Result<(), CliError> {
     if cmd.compile_options.debug_compile_stdin {
         let package_name = "debug_compile_stdin".to_string();
+        let resolve_options = config.resolve_options();
 
         // dummy root dir
         let root_dir = PathBuf::new();
@@ -205,7 +283,11 @@ fn compile_with_maybe_dummy_workspace(
         package.name = Some(package_name.clone());
         package.package_type = Some("bin".into());
         let dependencies = BTreeMap::new();
-        let package_config = PackageConfig { package, dependencies };
+        let dev_dependencies = BTreeMap::new();
+        let profile = BTreeMap::new();
+        let features = BTreeMap::new();
+        let package_config =
+            PackageConfig { package, dependencies, dev_dependencies, profile, features };
         let config = nargo_toml::Config::Package { package_config };
         let nargo_toml = NargoToml { root_dir, config };
         let package_name =
@@ -216,6 +298,7 @@ fn compile_with_maybe_dummy_workspace(
             nargo_toml,
             selection,
             Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+            &resolve_options,
         )?;
         compile_cmd::run(cmd, workspace)
     } else {
@@ -232,18 +315,23 @@ where
     // All commands need to run on the workspace level, because that's where the `target` directory is.
     let workspace_dir = nargo_toml::find_root(&config.program_dir, true)?;
     let package_dir = nargo_toml::find_root(&config.program_dir, false)?;
+    let resolve_options = ResolveOptions {
+        include_dev_dependencies: cmd.include_dev_dependencies(),
+        ..config.resolve_options()
+    };
     // Check if we're running inside the directory of a package, without having selected the entire workspace
     // or a specific package; if that's the case then parse the package name to select it in the workspace.
     let selection = match cmd.package_selection() {
         PackageSelection::DefaultOrAll if workspace_dir != package_dir => {
-            let package = read_workspace(&package_dir, PackageSelection::DefaultOrAll)?;
+            let package =
+                read_workspace(&package_dir, PackageSelection::DefaultOrAll, &resolve_options)?;
             let package = package.into_iter().next().expect("there should be exactly 1 package");
             PackageSelection::Selected(package.name.clone())
         }
         other => other,
     };
     // Parse the top level workspace with the member selected.
-    let mut workspace = read_workspace(&workspace_dir, selection)?;
+    let mut workspace = read_workspace(&workspace_dir, selection, &resolve_options)?;
     // Optionally override the target directory. It's only done here because most commands like the LSP and DAP
     // don't read or write artifacts, so they don't use the target directory.
     workspace.target_dir = config.target_dir.clone();
@@ -311,6 +399,7 @@ fn parse_path(path: &str) -> Result<PathBuf, String> {
 mod tests {
     use super::NargoCli;
     use clap::Parser;
+    use nargo_toml::LockfileMode;
 
     #[test]
     fn test_parse_invalid_expression_width() {
@@ -337,4 +426,59 @@ mod tests {
         let cli = NargoCli::try_parse_from(cmd.split_ascii_whitespace()).expect("should parse");
         assert!(cli.config.target_dir.is_none());
     }
+
+    #[test]
+    fn test_parse_lockfile_mode() {
+        let cmd = "nargo --program-dir . execute";
+        let cli = NargoCli::try_parse_from(cmd.split_ascii_whitespace()).expect("should parse");
+        assert_eq!(cli.config.resolve_options().lockfile_mode, LockfileMode::Update);
+
+        let cmd = "nargo --program-dir . execute --locked";
+        let cli = NargoCli::try_parse_from(cmd.split_ascii_whitespace()).expect("should parse");
+        assert_eq!(cli.config.resolve_options().lockfile_mode, LockfileMode::Locked);
+
+        let cmd = "nargo --program-dir . test --locked --frozen";
+        let cli = NargoCli::try_parse_from(cmd.split_ascii_whitespace()).expect("should parse");
+        assert_eq!(cli.config.resolve_options().lockfile_mode, LockfileMode::Frozen);
+    }
+
+    #[test]
+    fn test_parse_test_backend() {
+        let cmd = "nargo --program-dir . test --backend ssa-interpreter --ssa-pass mem2reg";
+        assert!(NargoCli::try_parse_from(cmd.split_ascii_whitespace()).is_ok());
+
+        let cmd = "nargo --program-dir . test --differential --ssa-pass mem2reg";
+        assert!(NargoCli::try_parse_from(cmd.split_ascii_whitespace()).is_ok());
+
+        let cmd = "nargo --program-dir . test --ssa-pass mem2reg";
+        let err = NargoCli::try_parse_from(cmd.split_ascii_whitespace())
+            .expect_err("should fail because only the SSA interpreter uses `--ssa-pass`");
+        assert!(err.to_string().contains("--differential"));
+
+        let cmd = "nargo --program-dir . test --backend ssa-interpreter --differential";
+        let err = NargoCli::try_parse_from(cmd.split_ascii_whitespace())
+            .expect_err("should fail because `--differential` always uses both backends");
+        assert!(err.to_string().contains("--differential"));
+    }
+
+    #[test]
+    fn test_parse_profile_flag() {
+        let cmd = "nargo --program-dir . compile --profile release";
+        let cli = NargoCli::try_parse_from(cmd.split_ascii_whitespace()).expect("should parse");
+        assert_eq!(cli.config.resolve_options().profile, Some("release".to_string()));
+    }
+
+    #[test]
+    fn test_parse_feature_flags() {
+        let cmd = "nargo --program-dir . check --features poseidon,hash/fast --no-default-features";
+        let cli = NargoCli::try_parse_from(cmd.split_ascii_whitespace()).expect("should parse");
+        let options = cli.config.resolve_options();
+        assert_eq!(options.features, vec!["poseidon".to_string(), "hash/fast".to_string()]);
+        assert!(options.no_default_features);
+        assert!(!options.all_features);
+
+        let cmd = "nargo --program-dir . check --all-features";
+        let cli = NargoCli::try_parse_from(cmd.split_ascii_whitespace()).expect("should parse");
+        assert!(cli.config.resolve_options().all_features);
+    }
 }
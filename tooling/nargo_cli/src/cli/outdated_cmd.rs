@@ -0,0 +1,62 @@
+use clap::Args;
+use nargo::workspace::Workspace;
+use nargo_toml::PackageSelection;
+
+use crate::errors::CliError;
+
+use super::{LockType, PackageOptions, WorkspaceCommand};
+
+/// List the git dependencies pinned to a tag for which the repository has a newer tag
+///
+/// Only tags which are semver versions are compared. With `--frozen`, only the tags in the local cache are considered.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct OutdatedCommand {
+    #[clap(flatten)]
+    pub(super) package_options: PackageOptions,
+
+    /// Output the outdated dependencies as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+impl WorkspaceCommand for OutdatedCommand {
+    fn package_selection(&self) -> PackageSelection {
+        self.package_options.package_selection()
+    }
+
+    fn lock_type(&self) -> LockType {
+        // Only reads the manifests.
+        LockType::None
+    }
+
+    fn include_dev_dependencies(&self) -> bool {
+        true
+    }
+}
+
+pub(crate) fn run(
+    args: OutdatedCommand,
+    workspace: Workspace,
+    offline: bool,
+) -> Result<(), CliError> {
+    let outdated = nargo_toml::find_outdated_dependencies(&workspace, offline)?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&outdated).expect("report should serialize"));
+        return Ok(());
+    }
+
+    if outdated.is_empty() {
+        println!("All git dependencies are up to date");
+        return Ok(());
+    }
+    for dependency in &outdated {
+        let compatible = dependency.compatible.as_deref().unwrap_or("-");
+        let latest = dependency.latest.as_deref().unwrap_or("-");
+        println!(
+            "{} ({}): {} -> compatible {compatible}, latest {latest}",
+            dependency.name, dependency.git, dependency.tag
+        );
+    }
+    Ok(())
+}
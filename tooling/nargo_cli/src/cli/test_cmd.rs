@@ -3,42 +3,49 @@ use std::{
     collections::{BTreeMap, HashMap},
     fmt::Display,
     panic::{UnwindSafe, catch_unwind},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
         Mutex,
-        mpsc::{self, Sender},
+        mpsc::{self, RecvTimeoutError, Sender},
     },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use acvm::{BlackBoxFunctionSolver, FieldElement};
+use acvm::{BlackBoxFunctionSolver, FieldElement, brillig_vm::ExecutionLimits};
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
-use clap::Args;
+use clap::{ArgGroup, Args};
+use doc_tests::PackageDocTests;
 use fm::FileManager;
-use formatters::{Formatter, JsonFormatter, PrettyFormatter, TerseFormatter};
+use formatters::{Formatter, JsonFormatter, JunitFormatter, PrettyFormatter, TerseFormatter};
 use nargo::{
     FuzzExecutionConfig, FuzzFolderConfig,
-    foreign_calls::DefaultForeignCallBuilder,
+    foreign_calls::{DefaultForeignCallBuilder, layers::Either},
     insert_all_files_for_workspace_into_file_manager,
-    ops::{FuzzConfig, TestStatus, check_crate_and_report_errors},
+    ops::{CoverageReport, FuzzConfig, TestStatus, report_errors},
     package::Package,
     parse_all, prepare_package,
     workspace::Workspace,
 };
 use nargo_toml::PackageSelection;
-use noirc_driver::{CompileOptions, check_crate};
+use noirc_driver::{CompileOptions, CompiledProgram, check_crate};
 use noirc_frontend::hir::{FunctionNameMatch, ParsedFiles, def_map::TestFunction};
+use sizes::{SizeBaselines, TestSizes};
 
 use crate::errors::CliError;
 
 use super::{LockType, PackageOptions, WorkspaceCommand};
 
+mod doc_tests;
 pub(crate) mod formatters;
+mod interpreter;
+mod oracles;
+mod sizes;
 
 /// Run the tests for this program
 #[derive(Debug, Clone, Args)]
 #[clap(visible_alias = "t")]
+#[clap(group(ArgGroup::new("interpreter").args(["backend", "differential"])))]
 pub(crate) struct TestCommand {
     /// If given, only tests with names containing this string will be run
     test_names: Vec<String>,
@@ -55,6 +62,11 @@ pub(crate) struct TestCommand {
     #[clap(long, hide = true)]
     list_tests: bool,
 
+    /// Run the `noir` code blocks of doc comments as tests, instead of the test functions.
+    /// Test names are then matched against the locations of the code blocks, e.g. `src/lib.nr:12`
+    #[clap(long)]
+    doc: bool,
+
     #[clap(flatten)]
     pub(super) package_options: PackageOptions,
 
@@ -65,6 +77,16 @@ pub(crate) struct TestCommand {
     #[clap(long)]
     oracle_resolver: Option<String>,
 
+    /// Record the oracle calls made by each test to a transcript in this directory,
+    /// so that they can be replayed with `--replay-oracles`
+    #[clap(long, value_name = "DIR", conflicts_with = "replay_oracles")]
+    record_oracles: Option<PathBuf>,
+
+    /// Replay the oracle calls made by each test from the transcripts recorded in this directory
+    /// with `--record-oracles`, instead of calling an oracle resolver
+    #[clap(long, value_name = "DIR", conflicts_with = "oracle_resolver")]
+    replay_oracles: Option<PathBuf>,
+
     /// Number of threads used for running tests in parallel
     #[clap(long, default_value_t = rayon::current_num_threads())]
     test_threads: usize,
@@ -73,10 +95,65 @@ pub(crate) struct TestCommand {
     #[clap(long)]
     format: Option<Format>,
 
+    /// Write the report of `--format junit` to this file instead of stdout
+    #[clap(long)]
+    report_path: Option<PathBuf>,
+
+    /// Record the source lines executed by tests and write them as an LCOV report
+    #[clap(long)]
+    coverage: bool,
+
+    /// Write the coverage report to this file instead of `lcov.info` in the target directory
+    #[clap(long, requires = "coverage")]
+    coverage_path: Option<PathBuf>,
+
+    /// Fail tests which run for longer than this number of seconds.
+    /// A test can override it with `#[test(timeout = N)]`. Fuzz tests are not affected.
+    #[clap(long, value_name = "SECS")]
+    test_timeout: Option<u64>,
+
+    /// Stop evaluating the comptime code of a package after this number of seconds,
+    /// reporting its tests as timed out.
+    #[clap(long, value_name = "SECS", default_value_t = 600)]
+    comptime_timeout: u64,
+
+    /// Fail tests which execute more than this number of Brillig opcodes
+    #[clap(long)]
+    max_brillig_opcodes: Option<usize>,
+
+    /// Check the circuit sizes of tests against the baselines stored in `test_sizes.json`
+    /// in the root of each package
+    #[clap(long)]
+    check_sizes: bool,
+
+    /// Record the circuit sizes of tests as their new baselines instead of checking them
+    #[clap(long, requires = "check_sizes")]
+    bless: bool,
+
+    /// Percentage by which circuit sizes may grow over their baselines
+    #[clap(long, default_value_t = 0.0)]
+    size_tolerance: f64,
+
     /// Display one character per test instead of one line
     #[clap(short = 'q', long = "quiet")]
     quiet: bool,
 
+    /// The backend used to execute tests
+    #[clap(long, default_value_t = Backend::Acvm)]
+    backend: Backend,
+
+    /// Execute each test with both the ACVM and the SSA interpreter, failing it if their results
+    /// or printed output differ
+    #[clap(long, conflicts_with = "backend")]
+    differential: bool,
+
+    /// The names of the SSA passes after which the SSA interpreter runs tests,
+    /// with `--backend ssa-interpreter` or `--differential`.
+    ///
+    /// When nothing is specified, tests are interpreted after all passes.
+    #[clap(long, requires = "interpreter")]
+    ssa_pass: Vec<String>,
+
     /// Do not run fuzz tests (tests that have arguments)
     #[clap(long, conflicts_with("only_fuzz"))]
     no_fuzz: bool,
@@ -118,6 +195,9 @@ impl WorkspaceCommand for TestCommand {
         // Reads the code to compile tests in memory, but doesn't save artifacts.
         LockType::None
     }
+    fn include_dev_dependencies(&self) -> bool {
+        true
+    }
 }
 
 #[derive(Debug, Copy, Clone, clap::ValueEnum)]
@@ -128,14 +208,17 @@ enum Format {
     Terse,
     /// Output a JSON Lines document
     Json,
+    /// Output a JUnit XML report
+    Junit,
 }
 
 impl Format {
-    fn formatter(&self) -> Box<dyn Formatter> {
+    fn formatter(&self, report_path: Option<PathBuf>) -> Box<dyn Formatter> {
         match self {
             Format::Pretty => Box::new(PrettyFormatter),
             Format::Terse => Box::new(TerseFormatter),
             Format::Json => Box::new(JsonFormatter),
+            Format::Junit => Box::new(JunitFormatter::new(report_path)),
         }
     }
 }
@@ -146,6 +229,25 @@ impl Display for Format {
             Format::Pretty => write!(f, "pretty"),
             Format::Terse => write!(f, "terse"),
             Format::Json => write!(f, "json"),
+            Format::Junit => write!(f, "junit"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+enum Backend {
+    /// Compile tests to ACIR and Brillig and execute them with the ACVM
+    Acvm,
+    /// Execute the SSA of tests with the SSA interpreter.
+    /// Fuzz tests and tests using fixtures are skipped.
+    SsaInterpreter,
+}
+
+impl Display for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Backend::Acvm => write!(f, "acvm"),
+            Backend::SsaInterpreter => write!(f, "ssa-interpreter"),
         }
     }
 }
@@ -182,7 +284,15 @@ const STACK_SIZE: usize = 4 * 1024 * 1024;
 pub(crate) fn run(args: TestCommand, workspace: Workspace) -> Result<(), CliError> {
     let mut file_manager = workspace.new_file_manager();
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut file_manager);
-    let parsed_files = parse_all(&file_manager);
+    let doc_tests = if args.doc {
+        doc_tests::add_doc_tests(&workspace, &mut file_manager)
+    } else {
+        BTreeMap::new()
+    };
+    let mut parsed_files = parse_all(&file_manager);
+    for package_doc_tests in doc_tests.values() {
+        package_doc_tests.declare_module(&mut parsed_files);
+    }
 
     let pattern = if args.test_names.is_empty() {
         FunctionNameMatch::Anything
@@ -192,34 +302,92 @@ pub(crate) fn run(args: TestCommand, workspace: Workspace) -> Result<(), CliErro
         FunctionNameMatch::Contains(args.test_names.clone())
     };
 
+    if args.report_path.is_some() && !matches!(args.format, Some(Format::Junit)) {
+        return Err(CliError::Generic("`--report-path` requires `--format junit`".to_string()));
+    }
+
     let formatter: Box<dyn Formatter> = if let Some(format) = args.format {
-        format.formatter()
+        format.formatter(args.report_path.clone())
     } else if args.quiet {
         Box::new(TerseFormatter)
     } else {
         Box::new(PrettyFormatter)
     };
 
+    let coverage_path = args.coverage.then(|| {
+        args.coverage_path
+            .clone()
+            .unwrap_or_else(|| workspace.target_directory_path().join("lcov.info"))
+    });
+
+    let size_baselines = if args.check_sizes {
+        let mut size_baselines = BTreeMap::new();
+        for package in &workspace {
+            size_baselines.insert(package.name.to_string(), SizeBaselines::load(package)?);
+        }
+        Some(Mutex::new(size_baselines))
+    } else {
+        None
+    };
+
     let runner = TestRunner {
         file_manager: &file_manager,
         parsed_files: &parsed_files,
+        doc_tests: &doc_tests,
         workspace,
         args: &args,
         pattern,
         num_threads: args.test_threads,
         formatter,
+        coverage: args.coverage.then(|| Mutex::new(CoverageReport::default())),
+        size_baselines,
     };
-    runner.run()
+    let result = runner.run();
+
+    if let Some(size_baselines) = runner.size_baselines {
+        for baselines in size_baselines.into_inner().unwrap().values() {
+            baselines.write()?;
+        }
+    }
+
+    if let (Some(coverage), Some(coverage_path)) = (runner.coverage, coverage_path) {
+        write_coverage_report(&coverage.into_inner().unwrap(), &coverage_path)?;
+    }
+    result
+}
+
+/// Writes the coverage merged across all tests as an LCOV tracefile.
+fn write_coverage_report(coverage: &CoverageReport, path: &Path) -> Result<(), CliError> {
+    let write = || -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, coverage.to_lcov())
+    };
+    write().map_err(|error| {
+        CliError::Generic(format!("Could not write coverage report to {}: {error}", path.display()))
+    })
+}
+
+/// Whether compiling with these options stopped evaluating comptime code because it took too long.
+fn comptime_deadline_reached(options: &CompileOptions) -> bool {
+    options.comptime_deadline.is_some_and(|deadline| Instant::now() >= deadline)
 }
 
 struct TestRunner<'a> {
     file_manager: &'a FileManager,
     parsed_files: &'a ParsedFiles,
+    /// The doc tests of each package, when `--doc` is set.
+    doc_tests: &'a BTreeMap<String, PackageDocTests>,
     workspace: Workspace,
     args: &'a TestCommand,
     pattern: FunctionNameMatch,
     num_threads: usize,
     formatter: Box<dyn Formatter>,
+    /// Source lines executed by the tests so far, when `--coverage` is set.
+    coverage: Option<Mutex<CoverageReport>>,
+    /// The circuit size baselines of each package, when `--check-sizes` is set.
+    size_baselines: Option<Mutex<BTreeMap<String, SizeBaselines>>>,
 }
 
 impl<'a> TestRunner<'a> {
@@ -248,6 +416,9 @@ impl<'a> TestRunner<'a> {
         // Now run all tests in parallel, but show output for each package sequentially
         let tests_count = tests.len();
         let all_passed = self.run_all_tests(tests, &test_count_per_package);
+        self.formatter.finish().map_err(|error| {
+            CliError::Generic(format!("Could not write the test report: {error}"))
+        })?;
 
         if tests_count == 0 {
             match &self.pattern {
@@ -550,24 +721,41 @@ impl<'a> TestRunner<'a> {
         root_path: Option<PathBuf>,
         package_name: String,
     ) -> Result<Vec<Test<'a>>, CliError> {
-        let test_functions = self.get_tests_in_package(package)?;
+        let Some(test_functions) = self.get_tests_in_package(package)? else {
+            // The names of the tests aren't known, so the package reports a single timed out test
+            let message =
+                format!("Comptime code timed out after {} seconds", self.args.comptime_timeout);
+            return Ok(vec![Test {
+                name: "<comptime>".to_string(),
+                package_name,
+                runner: Box::new(move || (TestStatus::TimedOut { message }, String::new())),
+                has_arguments: false,
+            }]);
+        };
+        let doc_tests = self.doc_tests.get(&package_name);
 
-        let tests: Vec<Test> = test_functions
+        let mut tests: Vec<Test> = test_functions
             .into_iter()
             .map(|(test_name, test_function)| {
-                let test_name_copy = test_name.clone();
+                let test_name_copy = doc_tests
+                    .and_then(|doc_tests| doc_tests.display_name(&test_name))
+                    .map_or_else(|| test_name.clone(), |display_name| display_name.to_string());
                 let root_path = root_path.clone();
                 let package_name_clone = package_name.clone();
                 let package_name_clone2 = package_name.clone();
                 let runner = Box::new(move || {
-                    self.run_test::<S>(
+                    let (status, output) = self.run_test::<S>(
                         package,
                         &test_name,
                         test_function.has_arguments,
                         foreign_call_resolver_url,
                         root_path,
                         package_name_clone.clone(),
-                    )
+                    );
+                    match doc_tests {
+                        Some(doc_tests) => (doc_tests.remap_status(status), output),
+                        None => (status, output),
+                    }
                 });
                 Test {
                     name: test_name_copy,
@@ -578,19 +766,60 @@ impl<'a> TestRunner<'a> {
             })
             .collect();
 
+        // Ignored doc tests aren't compiled, but are still reported
+        if let Some(doc_tests) = doc_tests {
+            for name in doc_tests.ignored_tests(&self.pattern) {
+                tests.push(Test {
+                    name,
+                    package_name: package_name.clone(),
+                    runner: Box::new(|| (TestStatus::Skipped, String::new())),
+                    has_arguments: false,
+                });
+            }
+        }
+
         Ok(tests)
     }
 
-    /// Compiles a single package and returns all of its test names
+    /// Compiles a single package and returns all of its test names,
+    /// or `None` if evaluating its comptime code timed out.
     fn get_tests_in_package(
         &'a self,
         package: &'a Package,
-    ) -> Result<Vec<(String, TestFunction)>, CliError> {
+    ) -> Result<Option<Vec<(String, TestFunction)>>, CliError> {
         let (mut context, crate_id) =
             prepare_package(self.file_manager, self.parsed_files, package);
-        check_crate_and_report_errors(&mut context, crate_id, &self.args.compile_options)?;
+        let options = self.compile_options_with_comptime_deadline(package);
+        let result = check_crate(&mut context, crate_id, &options);
+        if result.is_err() && comptime_deadline_reached(&options) {
+            return Ok(None);
+        }
+        let package_doc_tests = self.doc_tests.get(&package.name.to_string());
+        let result = match package_doc_tests {
+            Some(package_doc_tests) => package_doc_tests.remap_result(result),
+            None => result,
+        };
+        report_errors(result, self.file_manager, options.deny_warnings, options.silence_warnings)?;
+
+        if !self.args.doc {
+            let test_functions =
+                context.get_all_test_functions_in_crate_matching(&crate_id, &self.pattern);
+            return Ok(Some(test_functions));
+        }
 
-        Ok(context.get_all_test_functions_in_crate_matching(&crate_id, &self.pattern))
+        // Doc tests are matched against their display names rather than their function names
+        let test_functions = context
+            .get_all_test_functions_in_crate_matching(&crate_id, &FunctionNameMatch::Anything);
+        Ok(Some(doc_tests::filter_doc_tests(package_doc_tests, test_functions, &self.pattern)))
+    }
+
+    /// The compile options of a package, which stop evaluating its comptime code
+    /// once `--comptime-timeout` is exceeded.
+    fn compile_options_with_comptime_deadline(&self, package: &Package) -> CompileOptions {
+        let mut options = package.compile_options(&self.args.compile_options);
+        let timeout = Duration::from_secs(self.args.comptime_timeout);
+        options.comptime_deadline = Instant::now().checked_add(timeout);
+        options
     }
 
     /// Runs a single test and returns its status together with whatever was printed to stdout
@@ -613,13 +842,36 @@ impl<'a> TestRunner<'a> {
 
         let (mut context, crate_id) =
             prepare_package(self.file_manager, self.parsed_files, package);
-        check_crate(&mut context, crate_id, &self.args.compile_options)
-            .expect("Any errors should have occurred when collecting test functions");
+        let compile_options = self.compile_options_with_comptime_deadline(package);
+        if check_crate(&mut context, crate_id, &compile_options).is_err() {
+            // Comptime code can take longer to evaluate than it did when collecting test functions
+            assert!(
+                comptime_deadline_reached(&compile_options),
+                "Any errors should have occurred when collecting test functions"
+            );
+            let message =
+                format!("Comptime code timed out after {} seconds", self.args.comptime_timeout);
+            return (TestStatus::TimedOut { message }, String::new());
+        }
 
         let pattern = FunctionNameMatch::Exact(vec![fn_name.to_string()]);
         let test_functions = context.get_all_test_functions_in_crate_matching(&crate_id, &pattern);
         let (_, test_function) = test_functions.first().expect("Test function should exist");
 
+        // The SSA interpreter can only run tests which don't need any input
+        let can_interpret = !has_arguments && test_function.fixtures.is_empty();
+        if self.args.backend == Backend::SsaInterpreter {
+            if !can_interpret {
+                return (TestStatus::Skipped, String::new());
+            }
+            return interpreter::run_test_with_interpreter(
+                &mut context,
+                test_function,
+                &compile_options,
+                &self.args.ssa_pass,
+            );
+        }
+
         let blackbox_solver = S::default();
         let mut output_buffer = Vec::new();
 
@@ -637,32 +889,184 @@ impl<'a> TestRunner<'a> {
             },
         };
 
-        let test_status = nargo::ops::run_or_fuzz_test(
-            &blackbox_solver,
-            &mut context,
-            test_function,
-            &mut output_buffer,
-            package_name.clone(),
-            &self.args.compile_options,
-            fuzz_config,
-            |output, base| {
-                DefaultForeignCallBuilder {
-                    output,
-                    enable_mocks: true,
-                    resolver_url: foreign_call_resolver_url.map(|s| s.to_string()),
-                    root_path: root_path.clone(),
-                    package_name: Some(package_name.clone()),
+        // Fuzz tests have their own `--fuzz-timeout`
+        let timeout = test_function.timeout.or(self.args.test_timeout).filter(|_| !has_arguments);
+        let execution_limits = (timeout.is_some() || self.args.max_brillig_opcodes.is_some())
+            .then(|| ExecutionLimits::new(self.args.max_brillig_opcodes));
+
+        let check_sizes = |program: &CompiledProgram| {
+            self.check_test_sizes(&package_name, fn_name, test_function.max_opcodes, program)
+        };
+        let check_sizes = (test_function.max_opcodes.is_some() || self.size_baselines.is_some())
+            .then_some(&check_sizes as &dyn Fn(&CompiledProgram) -> Result<(), String>);
+
+        // Fuzz tests run many times, so their oracle calls are neither recorded nor replayed
+        let recorded_transcript = match &self.args.record_oracles {
+            Some(dir) if !has_arguments => {
+                match oracles::create_transcript(dir, &package_name, fn_name) {
+                    Ok(transcript) => Some(transcript),
+                    Err(message) => {
+                        return (
+                            TestStatus::Fail { message, error_diagnostic: None },
+                            String::new(),
+                        );
+                    }
                 }
-                .build_with_base(base)
-            },
-        );
+            }
+            _ => None,
+        };
+        // The fixtures of a test replay the start of its transcript, then the test replays the rest
+        let replayed_transcript = match &self.args.replay_oracles {
+            Some(dir) if !has_arguments => {
+                match oracles::load_transcript(dir, &package_name, fn_name) {
+                    Ok(transcript) => Some(Mutex::new(transcript)),
+                    Err(message) => {
+                        return (
+                            TestStatus::Fail { message, error_diagnostic: None },
+                            String::new(),
+                        );
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        let mut test_coverage = self.coverage.is_some().then(CoverageReport::default);
+        let (execution_started_sender, execution_started_receiver) = mpsc::channel::<()>();
+        let (test_finished_sender, test_finished_receiver) = mpsc::channel::<()>();
+        let test_status = thread::scope(|scope| {
+            if let (Some(timeout), Some(execution_limits)) = (timeout, &execution_limits) {
+                // The timer only starts once the test is compiled and starts executing.
+                // The senders are dropped once the test finishes, which stops the timer early.
+                scope.spawn(move || {
+                    if execution_started_receiver.recv().is_err() {
+                        return;
+                    }
+                    let timeout = Duration::from_secs(timeout);
+                    if let Err(RecvTimeoutError::Timeout) =
+                        test_finished_receiver.recv_timeout(timeout)
+                    {
+                        execution_limits.interrupt();
+                    }
+                });
+            }
+
+            let test_status = nargo::ops::run_or_fuzz_test(
+                &blackbox_solver,
+                &mut context,
+                test_function,
+                &mut output_buffer,
+                package_name.clone(),
+                &compile_options,
+                fuzz_config,
+                test_coverage.as_mut(),
+                execution_limits.as_ref(),
+                check_sizes,
+                |output, base| {
+                    // Foreign call executors are only built once the test is compiled
+                    let _ = execution_started_sender.send(());
+                    // Replayed calls are handled instead of the unhandled ones, by the base layer
+                    let base = match &replayed_transcript {
+                        Some(transcript) => Either::Left(transcript),
+                        None => Either::Right(base),
+                    };
+                    DefaultForeignCallBuilder {
+                        output,
+                        enable_mocks: true,
+                        resolver_url: foreign_call_resolver_url.map(|s| s.to_string()),
+                        root_path: root_path.clone(),
+                        package_name: Some(package_name.clone()),
+                    }
+                    .build_with_base_and_transcript(base, recorded_transcript.as_ref())
+                },
+            );
+            drop(execution_started_sender);
+            drop(test_finished_sender);
+            test_status
+        });
+
+        // Only a test stopped by the timer timed out: one which completed keeps its result,
+        // even if the timer went off after its last Brillig opcode.
+        let test_status = match (timeout, &execution_limits, test_status) {
+            (Some(timeout), Some(execution_limits), TestStatus::TimedOut { .. })
+                if execution_limits.is_interrupted() =>
+            {
+                let message = format!("Test timed out after {timeout} seconds");
+                TestStatus::TimedOut { message }
+            }
+            (_, _, test_status) => test_status,
+        };
+
+        // A test which passed must also have made every call of its transcript
+        let test_status = match (test_status, replayed_transcript) {
+            (TestStatus::Pass, Some(transcript)) => {
+                match transcript.into_inner().unwrap().check_fully_replayed() {
+                    Ok(()) => TestStatus::Pass,
+                    Err(error) => {
+                        TestStatus::Fail { message: error.to_string(), error_diagnostic: None }
+                    }
+                }
+            }
+            (test_status, _) => test_status,
+        };
+
+        if let (Some(coverage), Some(test_coverage)) = (&self.coverage, test_coverage) {
+            coverage.lock().unwrap().merge(test_coverage);
+        }
 
         let output_string =
             String::from_utf8(output_buffer).expect("output buffer should contain valid utf8");
 
+        let test_status = if self.args.differential && can_interpret {
+            interpreter::compare_with_interpreter(
+                &mut context,
+                test_function,
+                &compile_options,
+                &self.args.ssa_pass,
+                test_status,
+                &output_string,
+            )
+        } else {
+            test_status
+        };
+
         (test_status, output_string)
     }
 
+    /// Checks the circuit sizes of a test against its `max_opcodes` and, with `--check-sizes`,
+    /// against its baseline, which is replaced instead with `--bless`.
+    fn check_test_sizes(
+        &self,
+        package_name: &str,
+        test_name: &str,
+        max_opcodes: Option<usize>,
+        program: &CompiledProgram,
+    ) -> Result<(), String> {
+        let sizes = TestSizes::of_program(program, package_name);
+        if let Some(max_opcodes) = max_opcodes {
+            if sizes.acir_opcodes > max_opcodes {
+                return Err(format!(
+                    "Test program has {} ACIR opcodes, more than its `max_opcodes` of {max_opcodes}",
+                    sizes.acir_opcodes
+                ));
+            }
+        }
+
+        let Some(size_baselines) = &self.size_baselines else {
+            return Ok(());
+        };
+        let mut size_baselines = size_baselines.lock().unwrap();
+        let baselines = size_baselines
+            .get_mut(package_name)
+            .expect("Baselines should be loaded for every package");
+        if self.args.bless {
+            baselines.bless(test_name, sizes);
+            Ok(())
+        } else {
+            baselines.check(test_name, &sizes, self.args.size_tolerance)
+        }
+    }
+
     /// Display the status of a single test
     fn display_test_result(
         &'a self,
@@ -0,0 +1,736 @@
+//! Running the fenced `noir` code blocks of doc comments as tests, with `nargo test --doc`.
+//!
+//! The code blocks of a package are turned into test functions of a generated module,
+//! `__doc_tests`, which is declared in the root module of the package. Every code line keeps
+//! its length, with its doc comment prefix replaced by spaces, so that the locations of
+//! diagnostics in the generated module can be mapped back to the doc comments.
+
+use std::{collections::BTreeMap, ops::Range};
+
+use fm::{FileId, FileManager};
+use nargo::{ops::TestStatus, workspace::Workspace};
+use noirc_driver::CompilationResult;
+use noirc_errors::{CustomDiagnostic, Location, Span};
+use noirc_frontend::{
+    ast::{Ident, ItemVisibility, ModuleDeclaration},
+    hir::{FunctionNameMatch, ParsedFiles},
+    parser::{Item, ItemKind},
+};
+
+/// The name of the module generated in the root of a package to hold its doc tests.
+const DOC_TESTS_MODULE: &str = "__doc_tests";
+
+/// A fenced `noir` code block found in a doc comment.
+#[derive(Debug, PartialEq, Eq)]
+struct CodeBlock {
+    /// The 1-based line of the opening fence.
+    line: usize,
+    /// The span of the opening fence, where errors outside of the code are reported.
+    fence_span: Span,
+    ignore: bool,
+    should_fail: bool,
+    lines: Vec<CodeLine>,
+}
+
+/// A line of a code block, with its doc comment prefix replaced by spaces.
+#[derive(Debug, PartialEq, Eq)]
+struct CodeLine {
+    /// Where the line starts in the source file.
+    offset: u32,
+    /// The line, including its line terminator.
+    text: String,
+}
+
+impl CodeLine {
+    /// Returns the item declaration the line starts, without its visibility and modifiers,
+    /// if it starts one.
+    fn item_start(&self) -> Option<&str> {
+        let mut text = self.text.trim_start();
+        for prefix in ["pub(crate) ", "pub ", "comptime ", "unconstrained "] {
+            text = text.strip_prefix(prefix).unwrap_or(text);
+        }
+        const ITEM_KEYWORDS: [&str; 11] = [
+            "fn ", "struct ", "impl ", "impl<", "trait ", "global ", "enum ", "mod ", "type ",
+            "use ", "#[",
+        ];
+        ITEM_KEYWORDS.iter().any(|keyword| text.starts_with(keyword)).then_some(text)
+    }
+
+    /// Returns how much the line changes the nesting of brackets, outside of strings and comments.
+    fn depth_change(&self) -> i32 {
+        let mut change = 0;
+        let mut in_string = false;
+        let mut chars = self.text.chars().peekable();
+        while let Some(char) = chars.next() {
+            match char {
+                '\\' if in_string => {
+                    chars.next();
+                }
+                '"' => in_string = !in_string,
+                '/' if !in_string && chars.peek() == Some(&'/') => break,
+                '{' | '(' | '[' if !in_string => change += 1,
+                '}' | ')' | ']' if !in_string => change -= 1,
+                _ => (),
+            }
+        }
+        change
+    }
+}
+
+/// The doc tests of a package.
+pub(super) struct PackageDocTests {
+    /// The generated file holding the doc tests.
+    file_id: FileId,
+    /// The root file of the package, where the module of the doc tests is declared.
+    root_file_id: FileId,
+    tests: Vec<DocTest>,
+}
+
+/// A test generated from a code block.
+struct DocTest {
+    /// The fully qualified name of the test function, which is `None` if the code block
+    /// is ignored and was not compiled.
+    function_name: Option<String>,
+    /// Where the code block is, e.g. `src/lib.nr:12`.
+    display_name: String,
+    source_file: FileId,
+    fence_span: Span,
+    /// The part of the generated file taken by the test.
+    range: Range<u32>,
+    /// The code lines of the test in the generated file.
+    segments: Vec<Segment>,
+}
+
+/// A code line copied to the generated file.
+struct Segment {
+    start: u32,
+    len: u32,
+    source_start: u32,
+}
+
+/// Extracts the code blocks of the doc comments of all packages of the workspace, and adds
+/// the files holding their doc tests to the file manager.
+///
+/// The returned doc tests, by package name, must be declared with [`PackageDocTests::declare_module`]
+/// once the files are parsed.
+pub(super) fn add_doc_tests(
+    workspace: &Workspace,
+    file_manager: &mut FileManager,
+) -> BTreeMap<String, PackageDocTests> {
+    let mut doc_tests = BTreeMap::new();
+    for package in workspace {
+        let Some(src_dir) = package.entry_path.parent() else {
+            continue;
+        };
+        let Some(root_file_id) = file_manager.name_to_id(package.entry_path.clone()) else {
+            continue;
+        };
+
+        let mut source_files: Vec<_> = file_manager
+            .as_file_map()
+            .all_file_ids()
+            .filter_map(|file_id| {
+                let path = file_manager.path(*file_id)?;
+                path.starts_with(src_dir).then(|| (path.to_path_buf(), *file_id))
+            })
+            .collect();
+        source_files.sort();
+
+        let mut code_blocks = Vec::new();
+        for (path, file_id) in source_files {
+            let source = file_manager.fetch_file(file_id).expect("File should exist");
+            let path = path.strip_prefix(&package.root_dir).unwrap_or(&path);
+            for code_block in extract_code_blocks(source) {
+                let display_name = format!("{}:{}", path.display(), code_block.line);
+                code_blocks.push((file_id, display_name, code_block));
+            }
+        }
+        if code_blocks.is_empty() {
+            continue;
+        }
+
+        let (source, tests) = generate_module(code_blocks);
+        let path = src_dir.join(format!("{DOC_TESTS_MODULE}.{}", fm::FILE_EXTENSION));
+        let file_id = file_manager
+            .add_file_with_source(&path, source)
+            .expect("Doc tests file should be added");
+        let package_doc_tests = PackageDocTests { file_id, root_file_id, tests };
+        doc_tests.insert(package.name.to_string(), package_doc_tests);
+    }
+    doc_tests
+}
+
+impl PackageDocTests {
+    /// Declares the module holding the doc tests in the root module of the package.
+    pub(super) fn declare_module(&self, parsed_files: &mut ParsedFiles) {
+        let (root_module, _) =
+            parsed_files.get_mut(&self.root_file_id).expect("Root file should be parsed");
+        let location = Location::new(Span::empty(0), self.root_file_id);
+        let module_declaration = ModuleDeclaration {
+            visibility: ItemVisibility::Private,
+            ident: Ident::new(DOC_TESTS_MODULE.to_string(), location),
+            outer_attributes: Vec::new(),
+            has_semicolon: true,
+        };
+        root_module.items.push(Item {
+            kind: ItemKind::ModuleDecl(module_declaration),
+            location,
+            doc_comments: Vec::new(),
+        });
+    }
+
+    /// Returns the display name of the doc test with the given function name, if any.
+    pub(super) fn display_name(&self, function_name: &str) -> Option<&str> {
+        self.tests
+            .iter()
+            .find(|test| test.function_name.as_deref() == Some(function_name))
+            .map(|test| test.display_name.as_str())
+    }
+
+    /// Returns the display names of the ignored doc tests matching the pattern.
+    pub(super) fn ignored_tests(&self, pattern: &FunctionNameMatch) -> Vec<String> {
+        self.tests
+            .iter()
+            .filter(|test| test.function_name.is_none() && matches(pattern, &test.display_name))
+            .map(|test| test.display_name.clone())
+            .collect()
+    }
+
+    /// Moves the locations of the diagnostics of a compilation from the generated file
+    /// to the doc comments.
+    pub(super) fn remap_result<T>(&self, result: CompilationResult<T>) -> CompilationResult<T> {
+        let remap_all = |diagnostics: Vec<CustomDiagnostic>| {
+            diagnostics.into_iter().map(|diagnostic| self.remap_diagnostic(diagnostic)).collect()
+        };
+        match result {
+            Ok((value, warnings)) => Ok((value, remap_all(warnings))),
+            Err(errors) => Err(remap_all(errors)),
+        }
+    }
+
+    /// Moves the locations of the diagnostic of a test from the generated file to the doc comments.
+    pub(super) fn remap_status(&self, status: TestStatus) -> TestStatus {
+        match status {
+            TestStatus::Fail { message, error_diagnostic } => TestStatus::Fail {
+                message,
+                error_diagnostic: error_diagnostic
+                    .map(|diagnostic| self.remap_diagnostic(diagnostic)),
+            },
+            TestStatus::CompileError(diagnostic) => {
+                TestStatus::CompileError(self.remap_diagnostic(diagnostic))
+            }
+            status => status,
+        }
+    }
+
+    fn remap_diagnostic(&self, mut diagnostic: CustomDiagnostic) -> CustomDiagnostic {
+        for secondary in &mut diagnostic.secondaries {
+            secondary.location = self.remap_location(secondary.location);
+        }
+        for location in &mut diagnostic.call_stack {
+            *location = self.remap_location(*location);
+        }
+        if diagnostic.file == self.file_id {
+            // The diagnostic is reported in the file of the doc test containing its location
+            let location = diagnostic
+                .secondaries
+                .first()
+                .map(|secondary| secondary.location)
+                .or_else(|| diagnostic.call_stack.last().copied());
+            diagnostic.file = location
+                .map(|location| location.file)
+                .filter(|file| *file != self.file_id)
+                .unwrap_or(self.root_file_id);
+        }
+        diagnostic
+    }
+
+    fn remap_location(&self, location: Location) -> Location {
+        if location.file != self.file_id {
+            return location;
+        }
+        let start = location.span.start();
+        let Some(test) = self.tests.iter().find(|test| test.range.contains(&start)) else {
+            return location;
+        };
+        let span = match (test.source_offset(start), test.source_offset(location.span.end())) {
+            (Some(start), Some(end)) if start <= end => Span::from(start..end),
+            (Some(start), _) => Span::empty(start),
+            // Errors in the generated code around the code block are reported at its fence
+            (None, _) => test.fence_span,
+        };
+        Location::new(span, test.source_file)
+    }
+}
+
+impl DocTest {
+    /// Returns the offset in the source file of an offset in a code line of the generated file.
+    fn source_offset(&self, offset: u32) -> Option<u32> {
+        self.segments
+            .iter()
+            .find(|segment| segment.start <= offset && offset <= segment.start + segment.len)
+            .map(|segment| segment.source_start + offset - segment.start)
+    }
+}
+
+fn matches(pattern: &FunctionNameMatch, name: &str) -> bool {
+    match pattern {
+        FunctionNameMatch::Anything => true,
+        FunctionNameMatch::Exact(patterns) => patterns.iter().any(|pattern| name == pattern),
+        FunctionNameMatch::Contains(patterns) => {
+            patterns.iter().any(|pattern| name.contains(pattern))
+        }
+    }
+}
+
+/// Filters the tests of a package compiled with its doc tests down to the doc tests
+/// matching the pattern, which is matched against their display names.
+pub(super) fn filter_doc_tests<T>(
+    doc_tests: Option<&PackageDocTests>,
+    tests: Vec<(String, T)>,
+    pattern: &FunctionNameMatch,
+) -> Vec<(String, T)> {
+    let Some(doc_tests) = doc_tests else {
+        return Vec::new();
+    };
+    tests
+        .into_iter()
+        .filter(|(name, _)| {
+            doc_tests.display_name(name).is_some_and(|display_name| matches(pattern, display_name))
+        })
+        .collect()
+}
+
+/// Returns the doc comment prefix of a line, if it is a `///` or `//!` doc comment,
+/// including its indentation and the space following it.
+fn doc_comment_prefix_len(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start();
+    let rest = match trimmed.strip_prefix("///") {
+        Some(rest) if !rest.starts_with('/') => rest,
+        _ => trimmed.strip_prefix("//!")?,
+    };
+    Some(line.len() - rest.len() + usize::from(rest.starts_with(' ')))
+}
+
+/// Extracts the fenced code blocks of a source file whose info string contains `noir`.
+///
+/// The info string can also contain `ignore` and `should_fail`, separated by commas or spaces.
+fn extract_code_blocks(source: &str) -> Vec<CodeBlock> {
+    let mut code_blocks = Vec::new();
+    let mut code_block: Option<CodeBlock> = None;
+    let mut in_other_code_block = false;
+
+    let mut offset = 0;
+    for (index, line) in source.split_inclusive('\n').enumerate() {
+        let line_start = offset;
+        offset += line.len();
+
+        let text = line.trim_end_matches(['\n', '\r']);
+        let Some(prefix_len) = doc_comment_prefix_len(text) else {
+            // Code blocks which are not closed by the end of their doc comment are dropped
+            code_block = None;
+            in_other_code_block = false;
+            continue;
+        };
+        let content = text[prefix_len..].trim_start();
+        let is_fence = content.starts_with("```");
+
+        if code_block.is_some() {
+            if is_fence {
+                code_blocks.extend(code_block.take());
+            } else if let Some(code_block) = &mut code_block {
+                let code = &line[prefix_len..];
+                let text = if code.trim().is_empty() {
+                    code.trim_start_matches([' ', '\t']).to_string()
+                } else {
+                    " ".repeat(prefix_len) + code
+                };
+                code_block.lines.push(CodeLine { offset: line_start as u32, text });
+            }
+        } else if in_other_code_block {
+            in_other_code_block = !is_fence;
+        } else if is_fence {
+            let info = content.trim_start_matches('`');
+            let flags: Vec<&str> = info
+                .split(|char: char| char == ',' || char.is_whitespace())
+                .filter(|flag| !flag.is_empty())
+                .collect();
+            if flags.contains(&"noir") {
+                code_block = Some(CodeBlock {
+                    line: index + 1,
+                    fence_span: Span::from(line_start as u32..(line_start + text.len()) as u32),
+                    ignore: flags.contains(&"ignore"),
+                    should_fail: flags.contains(&"should_fail"),
+                    lines: Vec::new(),
+                });
+            } else {
+                in_other_code_block = true;
+            }
+        }
+    }
+
+    code_blocks
+}
+
+/// Splits the lines of a code block into the items it declares and the statements of its test,
+/// and tells whether it declares a `main` function.
+///
+/// An item starts at a line at the top level of the code block starting with an item keyword
+/// or an attribute, and ends at the first line ending with `}` or `;` at the top level.
+/// Empty lines go along with the line before them.
+fn split_items(lines: &[CodeLine]) -> (Vec<&CodeLine>, Vec<&CodeLine>, bool) {
+    let mut items = Vec::new();
+    let mut statements = Vec::new();
+    let mut has_main = false;
+    let mut depth = 0;
+    let mut in_item = false;
+    let mut after_item = false;
+    for line in lines {
+        let text = line.text.trim();
+        if text.is_empty() {
+            if in_item || after_item {
+                items.push(line)
+            } else {
+                statements.push(line)
+            }
+            continue;
+        }
+
+        if depth == 0 && !in_item {
+            match line.item_start() {
+                Some(item) => {
+                    in_item = true;
+                    has_main |= item.starts_with("fn main()");
+                }
+                None => after_item = false,
+            }
+        }
+        depth += line.depth_change();
+        if in_item {
+            items.push(line);
+            if depth <= 0 && (text.ends_with('}') || text.ends_with(';')) {
+                in_item = false;
+                after_item = true;
+            }
+        } else {
+            statements.push(line);
+        }
+    }
+    (items, statements, has_main)
+}
+
+/// Generates the source of the module holding the doc tests of a package, from its code blocks
+/// along with the files they are in and their display names.
+///
+/// Each code block that isn't ignored becomes a `test` function in its own module, with the
+/// items it declares, such as imports, functions and structs, placed before the function.
+/// A code block with no statements outside of its items calls its `main` function, if any.
+fn generate_module(code_blocks: Vec<(FileId, String, CodeBlock)>) -> (String, Vec<DocTest>) {
+    let mut source = String::new();
+    let mut tests = Vec::new();
+    for (index, (source_file, display_name, code_block)) in code_blocks.into_iter().enumerate() {
+        if code_block.ignore {
+            tests.push(DocTest {
+                function_name: None,
+                display_name,
+                source_file,
+                fence_span: code_block.fence_span,
+                range: 0..0,
+                segments: Vec::new(),
+            });
+            continue;
+        }
+
+        let module_name = format!("doc_test_{index}");
+        let start = source.len() as u32;
+        let mut segments = Vec::new();
+        let mut push_line = |source: &mut String, line: &CodeLine| {
+            let start = source.len() as u32;
+            source.push_str(&line.text);
+            let len = line.text.trim_end_matches(['\n', '\r']).len() as u32;
+            segments.push(Segment { start, len, source_start: line.offset });
+            if !line.text.ends_with('\n') {
+                source.push('\n');
+            }
+        };
+
+        let (items, body, has_main) = split_items(&code_block.lines);
+
+        source.push_str(&format!("mod {module_name} {{\n"));
+        for line in items {
+            push_line(&mut source, line);
+        }
+        if code_block.should_fail {
+            source.push_str("#[test(should_fail)]\n");
+        } else {
+            source.push_str("#[test]\n");
+        }
+        source.push_str("fn test() {\n");
+        // Like rustdoc, a code block which only declares items runs its `main` function
+        if has_main && body.iter().all(|line| line.text.trim().is_empty()) {
+            source.push_str("main();\n");
+        }
+        for line in body {
+            push_line(&mut source, line);
+        }
+        source.push_str("}\n}\n");
+
+        tests.push(DocTest {
+            function_name: Some(format!("{DOC_TESTS_MODULE}::{module_name}::test")),
+            display_name,
+            source_file,
+            fence_span: code_block.fence_span,
+            range: start..source.len() as u32,
+            segments,
+        });
+    }
+    (source, tests)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use fm::{FileId, FileManager};
+    use noirc_errors::{CustomDiagnostic, Location, Span};
+
+    use super::{CodeLine, PackageDocTests, extract_code_blocks, generate_module};
+
+    const SOURCE: &str = "//! A library.
+//!
+//! ```noir
+//! assert(crate::double(2) == 4);
+//! ```
+
+/// Doubles a number.
+///
+/// ```noir,should_fail
+/// use crate::double;
+///
+/// assert(double(2) == 5);
+/// ```
+///
+/// ```
+/// not noir
+/// ```
+///
+/// ```noir ignore
+/// let x = ;
+/// ```
+pub fn double(x: u32) -> u32 {
+    x * 2
+}
+";
+
+    #[test]
+    fn extracts_noir_code_blocks() {
+        let code_blocks = extract_code_blocks(SOURCE);
+        assert_eq!(code_blocks.len(), 3);
+
+        let code_block = &code_blocks[0];
+        assert_eq!(code_block.line, 3);
+        assert!(!code_block.ignore && !code_block.should_fail);
+        let offset = SOURCE.find("//! assert").unwrap() as u32;
+        assert_eq!(
+            code_block.lines,
+            vec![CodeLine { offset, text: "    assert(crate::double(2) == 4);\n".to_string() }]
+        );
+
+        let code_block = &code_blocks[1];
+        assert_eq!(code_block.line, 9);
+        assert!(!code_block.ignore && code_block.should_fail);
+        let texts: Vec<_> = code_block.lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(texts, vec!["    use crate::double;\n", "\n", "    assert(double(2) == 5);\n"]);
+
+        let code_block = &code_blocks[2];
+        assert_eq!(code_block.line, 19);
+        assert!(code_block.ignore && !code_block.should_fail);
+    }
+
+    #[test]
+    fn drops_code_blocks_not_closed_in_their_doc_comment() {
+        let source = "/// ```noir\n/// let x = 1;\nfn foo() {}\n/// ```\n";
+        assert!(extract_code_blocks(source).is_empty());
+    }
+
+    #[test]
+    fn generates_a_test_for_each_code_block() {
+        let file_id = FileId::dummy();
+        let code_blocks = extract_code_blocks(SOURCE)
+            .into_iter()
+            .map(|code_block| (file_id, format!("src/lib.nr:{}", code_block.line), code_block))
+            .collect();
+        let (source, tests) = generate_module(code_blocks);
+
+        assert_eq!(
+            source,
+            "mod doc_test_0 {
+#[test]
+fn test() {
+    assert(crate::double(2) == 4);
+}
+}
+mod doc_test_1 {
+    use crate::double;
+
+#[test(should_fail)]
+fn test() {
+    assert(double(2) == 5);
+}
+}
+"
+        );
+
+        let names: Vec<_> = tests.iter().map(|test| test.function_name.as_deref()).collect();
+        assert_eq!(
+            names,
+            vec![
+                Some("__doc_tests::doc_test_0::test"),
+                Some("__doc_tests::doc_test_1::test"),
+                None
+            ]
+        );
+    }
+
+    fn generate_single_test(source: &str) -> String {
+        let code_blocks = extract_code_blocks(source)
+            .into_iter()
+            .map(|code_block| (FileId::dummy(), String::new(), code_block))
+            .collect();
+        generate_module(code_blocks).0
+    }
+
+    #[test]
+    fn places_items_outside_of_the_test_function() {
+        let source = "/// ```noir
+/// struct Point { x: Field }
+///
+/// #[inline_always]
+/// fn origin() -> Point {
+///     Point { x: 0 }
+/// }
+///
+/// let point = origin();
+/// let check = |p: Point| { p.x == 0 };
+/// assert(check(point));
+/// ```
+fn foo() {}
+";
+        assert_eq!(
+            generate_single_test(source),
+            "mod doc_test_0 {
+    struct Point { x: Field }
+
+    #[inline_always]
+    fn origin() -> Point {
+        Point { x: 0 }
+    }
+
+#[test]
+fn test() {
+    let point = origin();
+    let check = |p: Point| { p.x == 0 };
+    assert(check(point));
+}
+}
+"
+        );
+    }
+
+    #[test]
+    fn calls_the_main_function_of_code_blocks_without_statements() {
+        let source = "/// ```noir
+/// fn main() {
+///     assert(1 + 1 == 2);
+/// }
+/// ```
+fn foo() {}
+";
+        assert_eq!(
+            generate_single_test(source),
+            "mod doc_test_0 {
+    fn main() {
+        assert(1 + 1 == 2);
+    }
+#[test]
+fn test() {
+main();
+}
+}
+"
+        );
+    }
+
+    #[test]
+    fn reports_diagnostics_in_the_file_of_their_doc_test() {
+        let mut file_manager = FileManager::new(Path::new(""));
+        let root_file = file_manager
+            .add_file_with_source(Path::new("src/lib.nr"), "mod foo;\n".to_string())
+            .unwrap();
+        let foo_source = "/// ```noir\n/// assert(false);\n/// ```\nfn foo() {}\n";
+        let foo_file = file_manager
+            .add_file_with_source(Path::new("src/foo.nr"), foo_source.to_string())
+            .unwrap();
+        let code_blocks = extract_code_blocks(foo_source)
+            .into_iter()
+            .map(|code_block| (foo_file, "src/foo.nr:1".to_string(), code_block))
+            .collect();
+        let (generated_source, tests) = generate_module(code_blocks);
+        let file_id = file_manager
+            .add_file_with_source(Path::new("src/__doc_tests.nr"), generated_source.clone())
+            .unwrap();
+        let doc_tests = PackageDocTests { file_id, root_file_id: root_file, tests };
+
+        // An execution failure only has a call stack
+        let start = generated_source.find("assert").unwrap() as u32;
+        let mut diagnostic = CustomDiagnostic::from_message("failed", file_id);
+        diagnostic.call_stack = vec![Location::new(Span::from(start..start + 6), file_id)];
+        let diagnostic = doc_tests.remap_diagnostic(diagnostic);
+        assert_eq!(diagnostic.file, foo_file);
+
+        // A diagnostic without a location is reported in the root file
+        let diagnostic = CustomDiagnostic::from_message("failed", file_id);
+        assert_eq!(doc_tests.remap_diagnostic(diagnostic).file, root_file);
+    }
+
+    #[test]
+    fn remaps_diagnostics_to_the_doc_comments() {
+        let mut file_manager = FileManager::new(Path::new(""));
+        let source_file =
+            file_manager.add_file_with_source(Path::new("src/lib.nr"), SOURCE.to_string()).unwrap();
+        let code_blocks = extract_code_blocks(SOURCE)
+            .into_iter()
+            .map(|code_block| (source_file, format!("src/lib.nr:{}", code_block.line), code_block))
+            .collect();
+        let (generated_source, tests) = generate_module(code_blocks);
+        let file_id = file_manager
+            .add_file_with_source(Path::new("src/__doc_tests.nr"), generated_source.clone())
+            .unwrap();
+        let doc_tests = PackageDocTests { file_id, root_file_id: source_file, tests };
+
+        // An error at `double(2) == 5` is reported at the same code in the doc comment
+        let code = "double(2) == 5";
+        let generated_start = generated_source.find(code).unwrap() as u32;
+        let location = Location::new(
+            Span::from(generated_start..generated_start + code.len() as u32),
+            file_id,
+        );
+        let diagnostic = doc_tests.remap_diagnostic(CustomDiagnostic::simple_error(
+            String::new(),
+            String::new(),
+            location,
+        ));
+
+        let source_start = SOURCE.find(code).unwrap() as u32;
+        assert_eq!(diagnostic.file, source_file);
+        assert_eq!(
+            diagnostic.secondaries[0].location,
+            Location::new(Span::from(source_start..source_start + code.len() as u32), source_file)
+        );
+
+        // An error in the generated function is reported at the fence of the code block
+        let generated_start = generated_source.find("fn test").unwrap() as u32;
+        let location = Location::new(Span::from(generated_start..generated_start + 2), file_id);
+        assert_eq!(doc_tests.remap_location(location).span, doc_tests.tests[0].fence_span);
+    }
+}
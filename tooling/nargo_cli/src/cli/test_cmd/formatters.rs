@@ -1,4 +1,4 @@
-use std::{io::Write, panic::RefUnwindSafe, time::Duration};
+use std::{io::Write, panic::RefUnwindSafe, path::PathBuf, sync::Mutex, time::Duration};
 
 use fm::FileManager;
 use nargo::ops::TestStatus;
@@ -19,6 +19,7 @@ use super::TestResult;
 ///     1. A `package_start_sync` event
 ///     2. One `test_end` event for each test
 ///     3. A `package_end` event
+/// 5. A `finish` event once all packages are done
 ///
 /// The reason we have some `sync` and `async` events is that formatters that show output
 /// to humans rely on the `sync` events to show a more predictable output (package by package),
@@ -62,6 +63,11 @@ pub(crate) trait Formatter: Send + Sync + RefUnwindSafe {
         deny_warnings: bool,
         silence_warnings: bool,
     ) -> std::io::Result<()>;
+
+    /// Called once all tests have run, for formatters which write a single report at the end.
+    fn finish(&self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 pub(crate) struct PrettyFormatter;
@@ -138,6 +144,13 @@ impl Formatter for PrettyFormatter {
                     );
                 }
             }
+            TestStatus::TimedOut { message } => {
+                writer.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+                write!(writer, "TIMEOUT\n{message}\n")?;
+                writer.reset()?;
+                show_time(&mut writer)?;
+                writeln!(writer)?;
+            }
             TestStatus::Skipped => {
                 writer.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
                 write!(writer, "skipped")?;
@@ -271,6 +284,11 @@ impl Formatter for TerseFormatter {
                 write!(writer, "F")?;
                 writer.reset()?;
             }
+            TestStatus::TimedOut { .. } => {
+                writer.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+                write!(writer, "T")?;
+                writer.reset()?;
+            }
             TestStatus::Skipped => {
                 writer.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
                 write!(writer, "s")?;
@@ -336,6 +354,11 @@ impl Formatter for TerseFormatter {
                             silence_warnings,
                         );
                     }
+                    TestStatus::TimedOut { message } => {
+                        writer.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+                        writeln!(writer, "{message}")?;
+                        writer.reset()?;
+                    }
                 }
 
                 let name_len = test_result.name.len();
@@ -447,6 +470,15 @@ impl Formatter for JsonFormatter {
             TestStatus::Skipped => {
                 json.insert("event".to_string(), json!("ignored"));
             }
+            TestStatus::TimedOut { message } => {
+                json.insert("event".to_string(), json!("failed"));
+                json.insert("timed_out".to_string(), json!(true));
+
+                if !stdout.is_empty() {
+                    stdout.push('\n');
+                }
+                stdout.push_str(message.trim());
+            }
             TestStatus::CompileError(diagnostic) => {
                 json.insert("event".to_string(), json!("failed"));
 
@@ -497,7 +529,9 @@ impl Formatter for JsonFormatter {
         for test_result in test_results {
             match &test_result.status {
                 TestStatus::Pass => passed += 1,
-                TestStatus::Fail { .. } | TestStatus::CompileError(..) => failed += 1,
+                TestStatus::Fail { .. }
+                | TestStatus::CompileError(..)
+                | TestStatus::TimedOut { .. } => failed += 1,
                 TestStatus::Skipped => ignored += 1,
             }
         }
@@ -508,6 +542,222 @@ impl Formatter for JsonFormatter {
     }
 }
 
+/// Writes a JUnit XML report, with one `<testsuite>` per package, to stdout or to `report_path`.
+pub(super) struct JunitFormatter {
+    report_path: Option<PathBuf>,
+    /// The `<testsuite>` elements of the packages which are done.
+    suites: Mutex<Vec<JunitSuite>>,
+}
+
+struct JunitSuite {
+    xml: String,
+    tests: usize,
+    failures: usize,
+    errors: usize,
+    skipped: usize,
+    time: Duration,
+}
+
+impl JunitFormatter {
+    pub(super) fn new(report_path: Option<PathBuf>) -> Self {
+        Self { report_path, suites: Mutex::new(Vec::new()) }
+    }
+}
+
+impl Formatter for JunitFormatter {
+    fn package_start_async(&self, _package_name: &str, _test_count: usize) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn package_start_sync(&self, _package_name: &str, _test_count: usize) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn test_start_async(&self, _name: &str, _package_name: &str) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn test_end_async(
+        &self,
+        _test_result: &TestResult,
+        _file_manager: &FileManager,
+        _show_output: bool,
+        _deny_warnings: bool,
+        _silence_warnings: bool,
+    ) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn test_end_sync(
+        &self,
+        _test_result: &TestResult,
+        _current_test_count: usize,
+        _total_test_count: usize,
+        _file_manager: &FileManager,
+        _show_output: bool,
+        _deny_warnings: bool,
+        _silence_warnings: bool,
+    ) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn package_end(
+        &self,
+        package_name: &str,
+        test_results: &[TestResult],
+        file_manager: &FileManager,
+        show_output: bool,
+        _deny_warnings: bool,
+        silence_warnings: bool,
+    ) -> std::io::Result<()> {
+        let suite =
+            junit_suite(package_name, test_results, file_manager, show_output, silence_warnings);
+        self.suites.lock().unwrap().push(suite);
+        Ok(())
+    }
+
+    fn finish(&self) -> std::io::Result<()> {
+        let suites = self.suites.lock().unwrap();
+        let total = |count: fn(&JunitSuite) -> usize| suites.iter().map(count).sum::<usize>();
+        let time: Duration = suites.iter().map(|suite| suite.time).sum();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"nargo test\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+            total(|suite| suite.tests),
+            total(|suite| suite.failures),
+            total(|suite| suite.errors),
+            total(|suite| suite.skipped),
+            time.as_secs_f64(),
+        ));
+        for suite in suites.iter() {
+            xml.push_str(&suite.xml);
+        }
+        xml.push_str("</testsuites>\n");
+
+        match &self.report_path {
+            Some(report_path) => {
+                if let Some(dir) = report_path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(report_path, xml)
+            }
+            None => {
+                print!("{xml}");
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Renders the `<testsuite>` element of a package.
+fn junit_suite(
+    package_name: &str,
+    test_results: &[TestResult],
+    file_manager: &FileManager,
+    show_output: bool,
+    silence_warnings: bool,
+) -> JunitSuite {
+    let mut suite = JunitSuite {
+        xml: String::new(),
+        tests: test_results.len(),
+        failures: 0,
+        errors: 0,
+        skipped: 0,
+        time: test_results.iter().map(|test_result| test_result.time_to_run).sum(),
+    };
+
+    let mut test_cases = String::new();
+    for test_result in test_results {
+        test_cases.push_str(&format!(
+            "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+            escape_xml(&test_result.name),
+            escape_xml(package_name),
+            test_result.time_to_run.as_secs_f64(),
+        ));
+
+        let mut body = String::new();
+        match &test_result.status {
+            TestStatus::Pass => (),
+            TestStatus::Fail { message, error_diagnostic } => {
+                suite.failures += 1;
+                let mut details = message.trim().to_string();
+                if let Some(diagnostic) = error_diagnostic {
+                    if !(diagnostic.is_warning() && silence_warnings) {
+                        details.push('\n');
+                        details.push_str(&diagnostic_to_string(diagnostic, file_manager));
+                    }
+                }
+                body.push_str(&format!(
+                    "      <failure message=\"{}\">{}</failure>\n",
+                    escape_xml(message.trim()),
+                    escape_xml(&details),
+                ));
+            }
+            TestStatus::CompileError(diagnostic) => {
+                suite.errors += 1;
+                body.push_str(&format!(
+                    "      <error message=\"{}\">{}</error>\n",
+                    escape_xml(diagnostic.message.trim()),
+                    escape_xml(&diagnostic_to_string(diagnostic, file_manager)),
+                ));
+            }
+            TestStatus::Skipped => {
+                suite.skipped += 1;
+                body.push_str("      <skipped/>\n");
+            }
+            TestStatus::TimedOut { message } => {
+                suite.failures += 1;
+                body.push_str(&format!(
+                    "      <failure type=\"timeout\" message=\"{}\"/>\n",
+                    escape_xml(message.trim()),
+                ));
+            }
+        }
+        if show_output && !test_result.output.is_empty() {
+            body.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                escape_xml(&test_result.output)
+            ));
+        }
+
+        if body.is_empty() {
+            test_cases.push_str("/>\n");
+        } else {
+            test_cases.push_str(&format!(">\n{body}    </testcase>\n"));
+        }
+    }
+
+    suite.xml = format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n{test_cases}  </testsuite>\n",
+        escape_xml(package_name),
+        suite.tests,
+        suite.failures,
+        suite.errors,
+        suite.skipped,
+        suite.time.as_secs_f64(),
+    );
+    suite
+}
+
+/// Escapes text for use in XML content or attributes, dropping the control characters XML doesn't allow.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' | '\r' | '\t' => escaped.push(char),
+            char if char.is_control() => (),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
 fn package_start(package_name: &str, test_count: usize) -> std::io::Result<()> {
     let plural = if test_count == 1 { "" } else { "s" };
     println!("[{package_name}] Running {test_count} test function{plural}");
@@ -544,3 +794,55 @@ pub(crate) fn diagnostic_to_string(
 fn stdout() -> StandardStream {
     StandardStream::stdout(ColorChoice::Always)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, time::Duration};
+
+    use fm::FileManager;
+    use nargo::ops::TestStatus;
+
+    use super::{TestResult, junit_suite};
+
+    #[test]
+    fn renders_junit_suite() {
+        let file_manager = FileManager::new(Path::new(""));
+        let test_result = |name: &str, status: TestStatus| {
+            TestResult::new(
+                name.to_string(),
+                "my_pkg".to_string(),
+                status,
+                "printed <output>".to_string(),
+                Duration::from_millis(1500),
+            )
+        };
+        let test_results = [
+            test_result("passes", TestStatus::Pass),
+            test_result(
+                "fails",
+                TestStatus::Fail { message: "x != \"y\"".to_string(), error_diagnostic: None },
+            ),
+            test_result("is_skipped", TestStatus::Skipped),
+            test_result(
+                "times_out",
+                TestStatus::TimedOut { message: "Test timed out after 1 seconds".to_string() },
+            ),
+        ];
+
+        let suite = junit_suite("my_pkg", &test_results, &file_manager, true, false);
+        assert_eq!((suite.tests, suite.failures, suite.errors, suite.skipped), (4, 2, 0, 1));
+        assert!(suite.xml.starts_with(
+            "  <testsuite name=\"my_pkg\" tests=\"4\" failures=\"2\" errors=\"0\" skipped=\"1\" time=\"6.000\">"
+        ));
+        assert!(
+            suite
+                .xml
+                .contains("<failure type=\"timeout\" message=\"Test timed out after 1 seconds\"/>")
+        );
+        assert!(suite.xml.contains(
+            "<testcase name=\"fails\" classname=\"my_pkg\" time=\"1.500\">\n      <failure message=\"x != &quot;y&quot;\">x != &quot;y&quot;</failure>"
+        ));
+        assert!(suite.xml.contains("<skipped/>"));
+        assert!(suite.xml.contains("<system-out>printed &lt;output&gt;</system-out>"));
+    }
+}
@@ -0,0 +1,309 @@
+//! Running tests with the SSA interpreter, either instead of the ACVM or alongside it to find
+//! differences between the two.
+
+use std::path::PathBuf;
+
+use nargo::ops::{TestStatus, check_expected_failure_message, test_status_program_compile_fail};
+use noirc_driver::CompileOptions;
+use noirc_errors::CustomDiagnostic;
+use noirc_evaluator::ssa::{
+    interpreter::{InterpreterOptions, errors::InterpreterError},
+    primary_passes,
+    ssa_gen::{Ssa, generate_ssa},
+};
+use noirc_frontend::{
+    hir::{Context, def_map::TestFunction},
+    monomorphization::monomorphize,
+};
+
+use crate::cli::interpret_cmd::msg_matches;
+
+/// The name of the stage after all the SSA passes ran, used when no stage is chosen.
+const FINAL_STAGE: &str = "Final SSA";
+
+/// The outcome of interpreting the SSA of a test at one stage of the SSA pipeline.
+pub(super) struct InterpretedStage {
+    /// The stage, e.g. `Initial SSA` or `Mem2Reg (step 2)`
+    pub(super) name: String,
+    pub(super) status: TestStatus,
+    /// What the test printed while being interpreted
+    pub(super) output: String,
+}
+
+/// Generates the SSA of a test and interprets it after the passes whose names contain one of
+/// `stages` (`Initial SSA` being the stage before any pass), or only after all the passes
+/// if `stages` is empty.
+///
+/// Returns the status of the test directly if its SSA can't be generated.
+pub(super) fn interpret_test(
+    context: &mut Context,
+    test_function: &TestFunction,
+    options: &CompileOptions,
+    stages: &[String],
+) -> Result<Vec<InterpretedStage>, TestStatus> {
+    let force_unconstrained = options.force_brillig || options.minimal_ssa;
+    let program = monomorphize(test_function.id, &mut context.def_interner, force_unconstrained)
+        .map_err(|error| TestStatus::CompileError(CustomDiagnostic::from(error)))?;
+    let mut ssa = generate_ssa(program)
+        .map_err(|error| test_status_program_compile_fail(error.into(), test_function))?;
+
+    let mut interpreted_stages = Vec::new();
+    let initial_stage = "Initial SSA";
+    if msg_matches(stages, initial_stage) {
+        interpreted_stages.push(interpret_stage(&ssa, initial_stage.to_string(), test_function));
+    }
+
+    let ssa_options = options.as_ssa_options(PathBuf::new());
+    for (i, ssa_pass) in primary_passes(&ssa_options).iter().enumerate() {
+        let msg = format!("{} (step {})", ssa_pass.msg(), i + 1);
+        if msg_matches(&options.skip_ssa_pass, &msg) {
+            continue;
+        }
+
+        ssa = ssa_pass
+            .run(ssa)
+            .map_err(|error| test_status_program_compile_fail(error.into(), test_function))?;
+        if msg_matches(stages, &msg) {
+            interpreted_stages.push(interpret_stage(&ssa, msg, test_function));
+        }
+    }
+
+    if stages.is_empty() {
+        interpreted_stages.push(interpret_stage(&ssa, FINAL_STAGE.to_string(), test_function));
+    } else if interpreted_stages.is_empty() {
+        let message = format!("No SSA pass matches any of {}", stages.join(", "));
+        return Err(TestStatus::Fail { message, error_diagnostic: None });
+    }
+    Ok(interpreted_stages)
+}
+
+/// Runs a test with the SSA interpreter only. The test fails at the first stage it fails at,
+/// or is skipped if it calls an oracle, and its output is the one printed at the last stage.
+pub(super) fn run_test_with_interpreter(
+    context: &mut Context,
+    test_function: &TestFunction,
+    options: &CompileOptions,
+    stages: &[String],
+) -> (TestStatus, String) {
+    let interpreted_stages = match interpret_test(context, test_function, options, stages) {
+        Ok(interpreted_stages) => interpreted_stages,
+        Err(status) => return (status, String::new()),
+    };
+
+    let output = interpreted_stages.last().map(|stage| stage.output.clone()).unwrap_or_default();
+    let is_skipped =
+        interpreted_stages.iter().any(|stage| matches!(stage.status, TestStatus::Skipped));
+    let failed_stage = interpreted_stages.into_iter().find(|stage| stage.status.failed());
+    let status = match failed_stage {
+        Some(InterpretedStage { name, status: TestStatus::Fail { message, .. }, .. })
+            if name != FINAL_STAGE =>
+        {
+            let message = format!("After {name}: {message}");
+            TestStatus::Fail { message, error_diagnostic: None }
+        }
+        Some(stage) => stage.status,
+        None if is_skipped => TestStatus::Skipped,
+        None => TestStatus::Pass,
+    };
+    (status, output)
+}
+
+/// Interprets the SSA of a test again at each of the given stages and compares the results and
+/// the printed output with those of the ACVM, failing the test at the first difference.
+/// The ACVM status of the test is kept if there is none.
+pub(super) fn compare_with_interpreter(
+    context: &mut Context,
+    test_function: &TestFunction,
+    options: &CompileOptions,
+    stages: &[String],
+    acvm_status: TestStatus,
+    acvm_output: &str,
+) -> TestStatus {
+    // Tests which didn't run to completion with the ACVM can't be compared
+    if !matches!(acvm_status, TestStatus::Pass | TestStatus::Fail { .. }) {
+        return acvm_status;
+    }
+
+    let interpreted_stages = match interpret_test(context, test_function, options, stages) {
+        Ok(interpreted_stages) => interpreted_stages,
+        Err(status) => {
+            let message = format!("Could not interpret the test: {}", status_message(&status));
+            return TestStatus::Fail { message, error_diagnostic: None };
+        }
+    };
+
+    for stage in interpreted_stages {
+        // The interpreter can't run tests calling oracles other than the ones printing
+        if matches!(stage.status, TestStatus::Skipped) {
+            continue;
+        }
+
+        let difference = if acvm_status.failed() != stage.status.failed() {
+            format!(
+                "the test {} with the ACVM but {} with the SSA interpreter",
+                status_message(&acvm_status),
+                status_message(&stage.status),
+            )
+        } else if acvm_output != stage.output {
+            format!(
+                "the printed output differs.\nACVM output:\n{acvm_output}\nSSA interpreter output:\n{}",
+                stage.output
+            )
+        } else {
+            continue;
+        };
+
+        let message = format!(
+            "The SSA interpreter diverged from the ACVM after {}: {difference}",
+            stage.name
+        );
+        return TestStatus::Fail { message, error_diagnostic: None };
+    }
+    acvm_status
+}
+
+/// Interprets the SSA of a test, checking its result like the ACVM one is checked.
+fn interpret_stage(ssa: &Ssa, name: String, test_function: &TestFunction) -> InterpretedStage {
+    let mut output = Vec::new();
+    let result = ssa.interpret_with_options(Vec::new(), InterpreterOptions::default(), &mut output);
+    let output = String::from_utf8(output).expect("output buffer should contain valid utf8");
+
+    let status = match result {
+        Ok(_) if test_function.should_fail() => TestStatus::Fail {
+            message: "error: Test passed when it should have failed".to_string(),
+            error_diagnostic: None,
+        },
+        Ok(_) => TestStatus::Pass,
+        Err(InterpreterError::UnknownForeignFunctionCall { .. }) => TestStatus::Skipped,
+        Err(error) if test_function.should_fail() => {
+            check_expected_failure_message(test_function, Some(failure_message(error)), None)
+        }
+        Err(error) => TestStatus::Fail { message: error.to_string(), error_diagnostic: None },
+    };
+    InterpretedStage { name, status, output }
+}
+
+/// The message of the failing assertion if there is one, or the whole error otherwise.
+fn failure_message(error: InterpreterError) -> String {
+    match error {
+        InterpreterError::ConstrainEqFailed { msg: Some(msg), .. }
+        | InterpreterError::ConstrainNeFailed { msg: Some(msg), .. }
+        | InterpreterError::RangeCheckFailed { msg: Some(msg), .. } => msg,
+        error => error.to_string(),
+    }
+}
+
+/// A short description of a status, used when reporting differences.
+fn status_message(status: &TestStatus) -> String {
+    match status {
+        TestStatus::Pass => "passed".to_string(),
+        TestStatus::Fail { message, .. } => format!("failed ({})", message.trim()),
+        TestStatus::Skipped => "was skipped".to_string(),
+        TestStatus::CompileError(diagnostic) => {
+            format!("failed to compile ({})", diagnostic.message)
+        }
+        TestStatus::TimedOut { message } => format!("timed out ({message})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use bn254_blackbox_solver::Bn254BlackBoxSolver;
+    use nargo::{foreign_calls::DefaultForeignCallBuilder, ops::TestStatus, parse_all};
+    use noirc_driver::{CompileOptions, check_crate, file_manager_with_stdlib, prepare_crate};
+    use noirc_frontend::hir::{Context, FunctionNameMatch, def_map::TestFunction};
+
+    use super::{compare_with_interpreter, run_test_with_interpreter};
+
+    /// Checks a code snippet and returns its only test function.
+    fn prepare_test(source: &str) -> (Context<'static, 'static>, TestFunction) {
+        let root = Path::new("");
+        let file_name = Path::new("main.nr");
+        let mut file_manager = file_manager_with_stdlib(root);
+        file_manager.add_file_with_source(file_name, source.to_string()).expect(
+            "Adding source buffer to file manager should never fail when file manager is empty",
+        );
+        let parsed_files = parse_all(&file_manager);
+
+        let mut context = Context::new(file_manager, parsed_files);
+        let crate_id = prepare_crate(&mut context, file_name);
+        check_crate(&mut context, crate_id, &CompileOptions::default()).expect("should check");
+
+        let mut test_functions = context
+            .get_all_test_functions_in_crate_matching(&crate_id, &FunctionNameMatch::Anything);
+        assert_eq!(test_functions.len(), 1);
+        let (_, test_function) = test_functions.remove(0);
+        (context, test_function)
+    }
+
+    /// Runs a test with the ACVM, returning its status and printed output.
+    fn run_with_acvm(context: &mut Context, test_function: &TestFunction) -> (TestStatus, String) {
+        let mut output = Vec::new();
+        let status = nargo::ops::run_test(
+            &Bn254BlackBoxSolver(false),
+            context,
+            test_function,
+            &mut output,
+            &CompileOptions::default(),
+            |output, base| {
+                DefaultForeignCallBuilder::default().with_output(output).build_with_base(base)
+            },
+        );
+        (status, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn agrees_with_the_acvm() {
+        let src = "
+        #[test]
+        fn test_sum() {
+            let x = 1 + 2;
+            println(x);
+            assert_eq(x, 3);
+        }
+        ";
+        let (mut context, test_function) = prepare_test(src);
+        let (acvm_status, acvm_output) = run_with_acvm(&mut context, &test_function);
+        assert!(matches!(acvm_status, TestStatus::Pass));
+
+        let stages = vec!["Initial SSA".to_string(), "Mem2Reg".to_string()];
+        let status = compare_with_interpreter(
+            &mut context,
+            &test_function,
+            &CompileOptions::default(),
+            &stages,
+            acvm_status,
+            &acvm_output,
+        );
+        assert!(matches!(status, TestStatus::Pass));
+    }
+
+    #[test]
+    fn skips_tests_calling_oracles() {
+        let src = "
+        #[oracle(get_number)]
+        unconstrained fn get_number_oracle() -> Field {}
+
+        unconstrained fn get_number() -> Field {
+            get_number_oracle()
+        }
+
+        #[test]
+        fn test_oracle() {
+            // Safety: the oracle is only called to be skipped
+            let x = unsafe { get_number() };
+            assert(x != 0);
+        }
+        ";
+        let (mut context, test_function) = prepare_test(src);
+        let (status, _) = run_test_with_interpreter(
+            &mut context,
+            &test_function,
+            &CompileOptions::default(),
+            &[],
+        );
+        assert!(matches!(status, TestStatus::Skipped));
+    }
+}
@@ -0,0 +1,64 @@
+//! Recording the oracle calls made by tests to transcripts, and replaying them instead of
+//! calling an oracle resolver.
+
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use acvm::FieldElement;
+use nargo::foreign_calls::transcript::ReplayForeignCallExecutor;
+
+/// The path of the transcript of a test within a directory of transcripts,
+/// e.g. `<dir>/<package>/foo.bar.test_baz.jsonl` for the test `foo::bar::test_baz`.
+fn transcript_path(dir: &Path, package_name: &str, test_name: &str) -> PathBuf {
+    dir.join(package_name).join(format!("{}.jsonl", test_name.replace("::", ".")))
+}
+
+/// Creates the file the oracle calls of a test are recorded to, replacing any previous one.
+pub(super) fn create_transcript(
+    dir: &Path,
+    package_name: &str,
+    test_name: &str,
+) -> Result<File, String> {
+    let path = transcript_path(dir, package_name, test_name);
+    let create = || {
+        std::fs::create_dir_all(dir.join(package_name))?;
+        File::create(&path)
+    };
+    create().map_err(|error| {
+        format!("Could not create the oracle transcript {}: {error}", path.display())
+    })
+}
+
+/// Loads the oracle calls recorded for a test, to be replayed.
+pub(super) fn load_transcript(
+    dir: &Path,
+    package_name: &str,
+    test_name: &str,
+) -> Result<ReplayForeignCallExecutor<FieldElement>, String> {
+    let path = transcript_path(dir, package_name, test_name);
+    if !path.exists() {
+        return Err(format!(
+            "No oracle transcript was recorded for this test at {}. Record one with `--record-oracles`",
+            path.display()
+        ));
+    }
+    let transcript = ReplayForeignCallExecutor::from_file(&path).map_err(|error| {
+        format!("Could not read the oracle transcript {}: {error}", path.display())
+    })?;
+    Ok(transcript.with_name(format!("test `{test_name}`")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::transcript_path;
+
+    #[test]
+    fn transcript_paths_of_nested_tests() {
+        let path = transcript_path(Path::new("oracles"), "my_package", "foo::bar::test_baz");
+        assert_eq!(path, Path::new("oracles/my_package/foo.bar.test_baz.jsonl"));
+    }
+}
@@ -0,0 +1,148 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use nargo::package::Package;
+use noirc_artifacts::program::ProgramArtifact;
+use noirc_artifacts_info::count_opcodes_and_gates_in_program;
+use noirc_driver::CompiledProgram;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::CliError;
+
+/// The name of the file, in the root of a package, holding the size baselines of its tests.
+pub(super) const SIZE_BASELINES_FILE: &str = "test_sizes.json";
+
+/// The sizes of the program compiled for a test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct TestSizes {
+    pub(super) acir_opcodes: usize,
+    pub(super) brillig_opcodes: usize,
+}
+
+impl TestSizes {
+    /// Counts the opcodes of a program the same way `nargo info` does.
+    pub(super) fn of_program(program: &CompiledProgram, package_name: &str) -> Self {
+        let artifact = ProgramArtifact::from(program.clone());
+        let info = count_opcodes_and_gates_in_program(artifact, package_name.to_string(), None);
+        TestSizes {
+            acir_opcodes: info.functions.iter().map(|function| function.opcodes).sum(),
+            brillig_opcodes: info.unconstrained_functions_opcodes,
+        }
+    }
+
+    /// Describes how these sizes grew from `baseline`, if either of them grew by more than
+    /// `tolerance` percent.
+    pub(super) fn regression(&self, baseline: &TestSizes, tolerance: f64) -> Option<String> {
+        let regressed = |size: usize, baseline: usize| {
+            size as f64 > baseline as f64 * (1.0 + tolerance / 100.0)
+        };
+        if !regressed(self.acir_opcodes, baseline.acir_opcodes)
+            && !regressed(self.brillig_opcodes, baseline.brillig_opcodes)
+        {
+            return None;
+        }
+
+        Some(format!(
+            "Circuit size regressed beyond a tolerance of {tolerance}%:\n{}\n{}",
+            size_change("ACIR opcodes", baseline.acir_opcodes, self.acir_opcodes),
+            size_change("Brillig opcodes", baseline.brillig_opcodes, self.brillig_opcodes),
+        ))
+    }
+}
+
+fn size_change(name: &str, baseline: usize, size: usize) -> String {
+    if size == baseline {
+        return format!("  {name}: {size}");
+    }
+    let change = if baseline == 0 {
+        String::new()
+    } else {
+        format!(" ({:+.1}%)", (size as f64 - baseline as f64) * 100.0 / baseline as f64)
+    };
+    format!("  {name}: {baseline} -> {size}{change}")
+}
+
+/// The size baselines of the tests of a package, stored in its `test_sizes.json` file.
+pub(super) struct SizeBaselines {
+    path: PathBuf,
+    sizes: BTreeMap<String, TestSizes>,
+    /// Whether sizes were blessed since the baselines were loaded.
+    changed: bool,
+}
+
+impl SizeBaselines {
+    /// Loads the baselines of a package, which are empty if it doesn't have any yet.
+    pub(super) fn load(package: &Package) -> Result<Self, CliError> {
+        let path = package.root_dir.join(SIZE_BASELINES_FILE);
+        let sizes = if path.exists() {
+            let contents = std::fs::read_to_string(&path).map_err(|error| {
+                CliError::Generic(format!("Could not read {}: {error}", path.display()))
+            })?;
+            serde_json::from_str(&contents).map_err(|error| {
+                CliError::Generic(format!("Could not parse {}: {error}", path.display()))
+            })?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(SizeBaselines { path, sizes, changed: false })
+    }
+
+    /// Checks the sizes of a test against its baseline.
+    pub(super) fn check(
+        &self,
+        test_name: &str,
+        sizes: &TestSizes,
+        tolerance: f64,
+    ) -> Result<(), String> {
+        let Some(baseline) = self.sizes.get(test_name) else {
+            return Err(format!(
+                "No size baseline for this test in {}, run `nargo test --check-sizes --bless` to record it",
+                self.path.display()
+            ));
+        };
+        match sizes.regression(baseline, tolerance) {
+            Some(regression) => Err(regression),
+            None => Ok(()),
+        }
+    }
+
+    /// Makes `sizes` the new baseline of a test.
+    pub(super) fn bless(&mut self, test_name: &str, sizes: TestSizes) {
+        if self.sizes.get(test_name) != Some(&sizes) {
+            self.sizes.insert(test_name.to_string(), sizes);
+            self.changed = true;
+        }
+    }
+
+    /// Writes the baselines back to their file, if any of them were blessed.
+    pub(super) fn write(&self) -> Result<(), CliError> {
+        if !self.changed {
+            return Ok(());
+        }
+        let json = serde_json::to_string_pretty(&self.sizes).expect("Could not serialize sizes");
+        std::fs::write(&self.path, json + "\n").map_err(|error| {
+            CliError::Generic(format!("Could not write {}: {error}", self.path.display()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TestSizes;
+
+    #[test]
+    fn reports_size_regressions_beyond_tolerance() {
+        let baseline = TestSizes { acir_opcodes: 1000, brillig_opcodes: 40 };
+
+        let within_tolerance = TestSizes { acir_opcodes: 1040, brillig_opcodes: 40 };
+        assert_eq!(within_tolerance.regression(&baseline, 5.0), None);
+
+        let smaller = TestSizes { acir_opcodes: 500, brillig_opcodes: 20 };
+        assert_eq!(smaller.regression(&baseline, 0.0), None);
+
+        let regressed = TestSizes { acir_opcodes: 1200, brillig_opcodes: 40 };
+        assert_eq!(
+            regressed.regression(&baseline, 5.0).unwrap(),
+            "Circuit size regressed beyond a tolerance of 5%:\n  ACIR opcodes: 1000 -> 1200 (+20.0%)\n  Brillig opcodes: 40"
+        );
+    }
+}
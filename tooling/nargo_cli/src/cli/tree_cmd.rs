@@ -0,0 +1,351 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+use nargo::{
+    package::{CrateName, Dependency, GitReference, Package},
+    workspace::Workspace,
+};
+use nargo_toml::PackageSelection;
+use serde::Serialize;
+
+use crate::errors::CliError;
+
+use super::{LockType, PackageOptions, WorkspaceCommand};
+
+/// Display the dependency tree of a package or the workspace
+///
+/// Dependencies used by several packages are only expanded the first time, and marked with `(*)` afterwards.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct TreeCommand {
+    #[clap(flatten)]
+    pub(super) package_options: PackageOptions,
+
+    /// Include the dev-dependencies of workspace members.
+    #[clap(long)]
+    dev: bool,
+
+    /// Output the dependency graph as JSON, listing every package once along with the packages depending on it.
+    #[clap(long)]
+    json: bool,
+}
+
+impl WorkspaceCommand for TreeCommand {
+    fn package_selection(&self) -> PackageSelection {
+        self.package_options.package_selection()
+    }
+
+    fn lock_type(&self) -> LockType {
+        // Only reads the manifests.
+        LockType::None
+    }
+
+    fn include_dev_dependencies(&self) -> bool {
+        self.dev
+    }
+}
+
+pub(crate) fn run(args: TreeCommand, workspace: Workspace) -> Result<(), CliError> {
+    if args.json {
+        let report = dependency_graph(&workspace);
+        println!("{}", serde_json::to_string_pretty(&report).expect("graph should serialize"));
+        return Ok(());
+    }
+
+    for line in render_tree(&workspace, &workspace.root_dir) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// The lines of the dependency trees of `packages`, separated by an empty line.
+fn render_tree<'a>(
+    packages: impl IntoIterator<Item = &'a Package>,
+    root_dir: &Path,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut expanded = BTreeSet::new();
+    for (index, package) in packages.into_iter().enumerate() {
+        if index > 0 {
+            lines.push(String::new());
+        }
+        let source = local_source(package, root_dir);
+        lines.push(describe_package(package, None, &source));
+        expanded.insert(package.root_dir.clone());
+        render_dependencies(package, "", root_dir, &mut expanded, &mut lines);
+    }
+    lines
+}
+
+/// The dependencies of a package, including its dev-dependencies if they were resolved.
+fn dependencies(package: &Package) -> Vec<(&CrateName, &Dependency, bool)> {
+    let dependencies = package.dependencies.iter().map(|(name, dep)| (name, dep, false));
+    let dev_dependencies = package
+        .dev_dependencies
+        .iter()
+        .filter_map(|(name, dep)| dep.as_ref().map(|dep| (name, dep, true)));
+    dependencies.chain(dev_dependencies).collect()
+}
+
+fn dependency_package(dependency: &Dependency) -> &Package {
+    match dependency {
+        Dependency::Local { package } | Dependency::Remote { package, .. } => package,
+    }
+}
+
+fn render_dependencies(
+    package: &Package,
+    prefix: &str,
+    root_dir: &Path,
+    expanded: &mut BTreeSet<PathBuf>,
+    lines: &mut Vec<String>,
+) {
+    let dependencies = dependencies(package);
+    for (index, &(crate_name, dependency, is_dev)) in dependencies.iter().enumerate() {
+        let (branch, indent) = if index + 1 == dependencies.len() {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        let package = dependency_package(dependency);
+        let source = match dependency {
+            Dependency::Local { package } => local_source(package, root_dir),
+            Dependency::Remote { git, reference, .. } => {
+                format!("{git}, {}", describe_reference(reference))
+            }
+        };
+        let is_repeated =
+            !expanded.insert(package.root_dir.clone()) && !package.dependencies.is_empty();
+
+        let mut line =
+            format!("{prefix}{branch}{}", describe_package(package, Some(crate_name), &source));
+        if is_dev {
+            line.push_str(" [dev]");
+        }
+        if is_repeated {
+            line.push_str(" (*)");
+        }
+        lines.push(line);
+
+        if !is_repeated {
+            let prefix = format!("{prefix}{indent}");
+            render_dependencies(package, &prefix, root_dir, expanded, lines);
+        }
+    }
+}
+
+/// Describes a package as `name v1.0.0 (source)`, mentioning the name it is imported under if it differs.
+fn describe_package(package: &Package, crate_name: Option<&CrateName>, source: &str) -> String {
+    let mut description = package.name.to_string();
+    if let Some(crate_name) = crate_name.filter(|crate_name| *crate_name != &package.name) {
+        description.push_str(&format!(" as {crate_name}"));
+    }
+    if let Some(version) = &package.version {
+        description.push_str(&format!(" v{version}"));
+    }
+    description.push_str(&format!(" ({source})"));
+    description
+}
+
+/// The directory of a local package, relative to the workspace if it is inside it.
+fn local_source(package: &Package, root_dir: &Path) -> String {
+    match package.root_dir.strip_prefix(root_dir) {
+        Ok(path) if path.as_os_str().is_empty() => ".".to_string(),
+        Ok(path) => path.display().to_string(),
+        Err(_) => package.root_dir.display().to_string(),
+    }
+}
+
+fn describe_reference(reference: &GitReference) -> String {
+    match reference {
+        GitReference::Tag(tag) => format!("tag {tag}"),
+        GitReference::Branch(branch) => format!("branch {branch}"),
+        GitReference::Rev(rev) => format!("rev {rev}"),
+    }
+}
+
+/// The dependency graph of the selected workspace members, in the `--json` output.
+#[derive(Debug, Serialize)]
+struct DependencyGraph {
+    /// Identifiers of the selected workspace members.
+    members: Vec<String>,
+    packages: Vec<PackageNode>,
+}
+
+#[derive(Debug, Serialize)]
+struct PackageNode {
+    /// The directory of the package, which identifies it in the graph.
+    id: String,
+    name: String,
+    version: Option<String>,
+    #[serde(rename = "type")]
+    package_type: String,
+    source: PackageSource,
+    dependencies: Vec<DependencyEdge>,
+    /// Identifiers of the packages depending on this one.
+    dependents: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum PackageSource {
+    Local,
+    Remote { git: String, reference: GitReference },
+}
+
+#[derive(Debug, Serialize)]
+struct DependencyEdge {
+    /// The name the dependency is imported under.
+    name: String,
+    id: String,
+    dev: bool,
+}
+
+fn dependency_graph(workspace: &Workspace) -> DependencyGraph {
+    let mut nodes = BTreeMap::new();
+    let mut members = Vec::new();
+    for package in workspace {
+        members.push(package_id(package));
+        add_package_node(package, PackageSource::Local, &mut nodes);
+    }
+
+    let edges: Vec<(String, String)> = nodes
+        .values()
+        .flat_map(|node: &PackageNode| {
+            node.dependencies.iter().map(|edge| (edge.id.clone(), node.id.clone()))
+        })
+        .collect();
+    for (dependency_id, dependent_id) in edges {
+        let dependents = &mut nodes.get_mut(&dependency_id).expect("node was added").dependents;
+        if !dependents.contains(&dependent_id) {
+            dependents.push(dependent_id);
+        }
+    }
+
+    DependencyGraph { members, packages: nodes.into_values().collect() }
+}
+
+fn package_id(package: &Package) -> String {
+    package.root_dir.display().to_string()
+}
+
+fn add_package_node(
+    package: &Package,
+    source: PackageSource,
+    nodes: &mut BTreeMap<String, PackageNode>,
+) {
+    let id = package_id(package);
+    if nodes.contains_key(&id) {
+        return;
+    }
+
+    let dependencies = dependencies(package);
+    nodes.insert(
+        id.clone(),
+        PackageNode {
+            id,
+            name: package.name.to_string(),
+            version: package.version.clone(),
+            package_type: package.package_type.to_string(),
+            source,
+            dependencies: dependencies
+                .iter()
+                .map(|(crate_name, dependency, is_dev)| DependencyEdge {
+                    name: crate_name.to_string(),
+                    id: package_id(dependency_package(dependency)),
+                    dev: *is_dev,
+                })
+                .collect(),
+            dependents: Vec::new(),
+        },
+    );
+
+    for (_, dependency, _) in dependencies {
+        let source = match dependency {
+            Dependency::Local { .. } => PackageSource::Local,
+            Dependency::Remote { git, reference, .. } => {
+                PackageSource::Remote { git: git.clone(), reference: reference.clone() }
+            }
+        };
+        add_package_node(dependency_package(dependency), source, nodes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        path::{Path, PathBuf},
+        str::FromStr,
+    };
+
+    use nargo::package::{CrateName, Dependency, GitReference, Package, PackageType};
+
+    use super::render_tree;
+
+    fn package(name: &str, root_dir: PathBuf, dependencies: Vec<(&str, Dependency)>) -> Package {
+        Package {
+            version: Some("0.1.0".to_string()),
+            compiler_required_version: None,
+            compiler_required_unstable_features: Vec::new(),
+            entry_path: root_dir.join("src").join("lib.nr"),
+            root_dir,
+            package_type: PackageType::Library,
+            name: CrateName::from_str(name).unwrap(),
+            dependencies: dependencies
+                .into_iter()
+                .map(|(name, dependency)| (CrateName::from_str(name).unwrap(), dependency))
+                .collect(),
+            dev_dependencies: BTreeMap::new(),
+            expression_width: None,
+            profile: None,
+            features: BTreeSet::new(),
+        }
+    }
+
+    #[test]
+    fn renders_tree_marking_repeated_dependencies() {
+        let root_dir = Path::new("/workspace");
+        let remote = package("field_ops", PathBuf::from("/cache/field_ops"), Vec::new());
+        let hash = package(
+            "hash",
+            root_dir.join("libs").join("hash"),
+            vec![(
+                "ops",
+                Dependency::Remote {
+                    package: remote,
+                    git: "https://github.com/noir-lang/field_ops".to_string(),
+                    reference: GitReference::Tag("v0.2.0".to_string()),
+                },
+            )],
+        );
+        let a = package(
+            "a",
+            root_dir.join("a"),
+            vec![("hash", Dependency::Local { package: hash.clone() })],
+        );
+        let mut b = package(
+            "b",
+            root_dir.join("b"),
+            vec![("hash", Dependency::Local { package: hash.clone() })],
+        );
+        b.dev_dependencies.insert(
+            CrateName::from_str("hash").unwrap(),
+            Some(Dependency::Local { package: hash }),
+        );
+
+        let lines = render_tree([&a, &b], root_dir);
+        let expected = [
+            "a v0.1.0 (a)",
+            "└── hash v0.1.0 (libs/hash)",
+            "    └── field_ops as ops v0.1.0 (https://github.com/noir-lang/field_ops, tag v0.2.0)",
+            "",
+            "b v0.1.0 (b)",
+            "├── hash v0.1.0 (libs/hash) (*)",
+            "└── hash v0.1.0 (libs/hash) [dev] (*)",
+        ];
+        assert_eq!(lines, expected);
+    }
+}
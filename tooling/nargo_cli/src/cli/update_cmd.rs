@@ -0,0 +1,17 @@
+use crate::errors::CliError;
+
+use super::NargoConfig;
+use clap::Args;
+
+/// Update the commits that git dependencies following a branch are pinned to in Nargo.lock.
+///
+/// Branches are fetched again from their repository, even if the workspace is vendored.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct UpdateCommand;
+
+pub(crate) fn run(_args: UpdateCommand, config: NargoConfig) -> Result<(), CliError> {
+    let workspace_dir = nargo_toml::find_root(&config.program_dir, true)?;
+    nargo_toml::update_workspace(&workspace_dir, &config.resolve_options())?;
+    println!("Updated {}", workspace_dir.join(nargo_toml::LOCKFILE_NAME).display());
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::errors::CliError;
+
+use super::NargoConfig;
+use clap::Args;
+
+/// Copy all git dependencies into the `vendor` directory of the workspace.
+///
+/// Set `vendored = true` in the `[package]` or `[workspace]` section of Nargo.toml
+/// to resolve dependencies from there without accessing the network.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct VendorCommand;
+
+pub(crate) fn run(_args: VendorCommand, config: NargoConfig) -> Result<(), CliError> {
+    let workspace_dir = nargo_toml::find_root(&config.program_dir, true)?;
+    let packages = nargo_toml::vendor_workspace(&workspace_dir, &config.resolve_options())?;
+
+    for package in &packages {
+        println!("Vendored {} at {}", package.git, package.reference);
+    }
+    println!(
+        "Vendored {} git dependencies into {}",
+        packages.len(),
+        workspace_dir.join(nargo_toml::VENDOR_DIR).display()
+    );
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! Replaying the oracle calls of a test and of its fixtures from a transcript.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+
+const SOURCE: &str = "
+#[oracle(get_number)]
+unconstrained fn get_number_oracle(x: Field) -> Field {}
+
+unconstrained fn get_number(x: Field) -> Field {
+    get_number_oracle(x)
+}
+
+#[fixture]
+fn number() -> Field {
+    // Safety: the value is checked by the test
+    unsafe { get_number(1) }
+}
+
+#[test(fixtures(number))]
+fn test_number(number: Field) {
+    // Safety: the value is checked right away
+    let other = unsafe { get_number(2) };
+    assert_eq(number + other, 5);
+}
+";
+
+/// A call to `get_number` with input `x` answered with `result`, as recorded by `--record-oracles`.
+fn transcript_line(x: u32, result: u32) -> String {
+    format!(
+        r#"{{"call":{{"function":"get_number","inputs":["{x:02x}"]}},"result":{{"values":["{result:02x}"]}}}}"#
+    )
+}
+
+fn replay(transcript: &[String]) -> assert_cmd::assert::Assert {
+    let project_dir = assert_fs::TempDir::new().unwrap();
+    project_dir
+        .child("Nargo.toml")
+        .write_str("[package]\nname = \"oracles\"\ntype = \"lib\"\n")
+        .unwrap();
+    project_dir.child("src").child("lib.nr").write_str(SOURCE).unwrap();
+    project_dir
+        .child("transcripts")
+        .child("oracles")
+        .child("test_number.jsonl")
+        .write_str(&transcript.join("\n"))
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&project_dir).arg("test").arg("--replay-oracles").arg("transcripts");
+    cmd.assert()
+}
+
+#[test]
+fn replays_fixture_and_test_calls_from_one_transcript() {
+    replay(&[transcript_line(1, 2), transcript_line(2, 3)]).success();
+}
+
+#[test]
+fn fails_when_recorded_calls_are_not_replayed() {
+    let transcript = [transcript_line(1, 2), transcript_line(2, 3), transcript_line(3, 4)];
+    replay(&transcript).failure().stdout(predicate::str::contains(
+        "1 recorded foreign calls were not made, starting with 'get_number' at call 2 of test `test_number`",
+    ));
+}
@@ -6,7 +6,10 @@ use nargo::foreign_calls::DefaultForeignCallBuilder;
 use noirc_driver::{CompileOptions, check_crate, file_manager_with_stdlib};
 use noirc_frontend::hir::FunctionNameMatch;
 use std::io::Write;
-use std::{collections::BTreeMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
 
 use nargo::{
     ops::{TestStatus, report_errors, run_test},
@@ -64,6 +67,9 @@ fn run_stdlib_tests(force_brillig: bool, inliner_aggressiveness: i64) {
         entry_path: PathBuf::from("main.nr"),
         name: "stdlib".parse().unwrap(),
         dependencies: BTreeMap::new(),
+        dev_dependencies: BTreeMap::new(),
+        profile: None,
+        features: BTreeSet::new(),
         expression_width: None,
     };
 
@@ -97,7 +103,11 @@ fn run_stdlib_tests(force_brillig: bool, inliner_aggressiveness: i64) {
                     &mut context,
                     &test_function,
                     std::io::stdout(),
-                    &CompileOptions { force_brillig, inliner_aggressiveness, ..Default::default() },
+                    &CompileOptions {
+                        force_brillig,
+                        inliner_aggressiveness: Some(inliner_aggressiveness),
+                        ..Default::default()
+                    },
                     |output, base| {
                         DefaultForeignCallBuilder::default()
                             .with_output(output)
@@ -172,6 +182,12 @@ fn display_test_report(
                     compile_options.silence_warnings,
                 );
             }
+            TestStatus::TimedOut { message } => {
+                writer
+                    .set_color(ColorSpec::new().set_fg(Some(Color::Red)))
+                    .expect("Failed to set color");
+                writeln!(writer, "TIMEOUT\n{message}\n").expect("Failed to write to stderr");
+            }
         }
         writer.reset().expect("Failed to reset writer");
     }
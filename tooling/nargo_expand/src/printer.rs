@@ -605,6 +605,7 @@ impl<'context, 'string> ItemPrinter<'context, 'string> {
                     }
                     FunctionAttributeKind::Test(..)
                     | FunctionAttributeKind::FuzzingHarness(..)
+                    | FunctionAttributeKind::Bench
                     | FunctionAttributeKind::Fold
                     | FunctionAttributeKind::NoPredicates
                     | FunctionAttributeKind::InlineAlways => {
@@ -1,6 +1,7 @@
 use noirc_frontend::token::{
-    Attribute, Attributes, FunctionAttribute, FunctionAttributeKind, FuzzingScope, MetaAttribute,
-    MetaAttributeName, SecondaryAttribute, SecondaryAttributeKind, TestScope, Token,
+    Attribute, Attributes, CfgPredicate, FunctionAttribute, FunctionAttributeKind,
+    FuzzStrategyKind, FuzzingScope, MetaAttribute, MetaAttributeName, SecondaryAttribute,
+    SecondaryAttributeKind, Token,
 };
 
 use crate::chunks::ChunkGroup;
@@ -59,13 +60,14 @@ impl Formatter<'_> {
             FunctionAttributeKind::Foreign(_)
             | FunctionAttributeKind::Builtin(_)
             | FunctionAttributeKind::Oracle(_) => self.format_one_arg_attribute(),
-            FunctionAttributeKind::Test(test_scope) => self.format_test_attribute(test_scope),
+            FunctionAttributeKind::Test(..) => self.format_test_attribute(),
             FunctionAttributeKind::FuzzingHarness(fuzz_scope) => {
                 self.format_fuzz_attribute(fuzz_scope);
             }
             FunctionAttributeKind::Fold
             | FunctionAttributeKind::NoPredicates
-            | FunctionAttributeKind::InlineAlways => {
+            | FunctionAttributeKind::InlineAlways
+            | FunctionAttributeKind::Bench => {
                 self.format_no_args_attribute();
             }
         }
@@ -87,6 +89,7 @@ impl Formatter<'_> {
             }
             SecondaryAttributeKind::ContractLibraryMethod
             | SecondaryAttributeKind::Export
+            | SecondaryAttributeKind::Fixture
             | SecondaryAttributeKind::Varargs
             | SecondaryAttributeKind::UseCallersScope => {
                 self.format_no_args_attribute();
@@ -99,6 +102,9 @@ impl Formatter<'_> {
             SecondaryAttributeKind::Tag(_) => {
                 self.write_and_skip_span_without_formatting(attribute.location.span);
             }
+            SecondaryAttributeKind::Cfg(predicate) => {
+                self.format_cfg_attribute(&predicate);
+            }
             SecondaryAttributeKind::Meta(meta_attribute) => {
                 self.format_meta_attribute(meta_attribute);
             }
@@ -122,35 +128,62 @@ impl Formatter<'_> {
         self.write_right_bracket(); // ]
     }
 
-    fn format_test_attribute(&mut self, test_scope: TestScope) {
+    fn format_test_attribute(&mut self) {
         self.write_current_token_and_bump(); // #[
         self.skip_comments_and_whitespace();
         self.write_current_token_and_bump(); // test
+        self.skip_comments_and_whitespace();
 
-        match test_scope {
-            TestScope::None => (),
-            TestScope::ShouldFailWith { reason: None } => {
-                self.write_left_paren(); // (
-                self.skip_comments_and_whitespace();
-                self.write_current_token_and_bump(); // should_fail
-                self.write_right_paren(); // )
-            }
-            TestScope::ShouldFailWith { reason: Some(..) } | TestScope::OnlyFailWith { .. } => {
-                self.write_left_paren(); // (
-                self.skip_comments_and_whitespace();
-                self.write_current_token_and_bump(); // should_fail_with | only_fail_with
-                self.write_space();
-                self.write_token(Token::Assign);
-                self.write_space();
+        if self.is_at(Token::LeftParen) {
+            self.write_left_paren(); // (
+            self.skip_comments_and_whitespace();
+            while !self.is_at(Token::RightParen) {
+                self.write_current_token_and_bump(); // should_fail, timeout, etc.
                 self.skip_comments_and_whitespace();
-                self.write_current_token_and_bump(); // "reason"
-                self.write_right_paren(); // )
+                if self.is_at(Token::Assign) {
+                    self.write_space();
+                    self.write_token(Token::Assign);
+                    self.write_space();
+                    self.skip_comments_and_whitespace();
+                    self.write_current_token_and_bump(); // "reason" | number
+                    self.skip_comments_and_whitespace();
+                }
+                if self.is_at(Token::LeftParen) {
+                    self.format_test_attribute_names(); // (a, b)
+                }
+                if self.is_at(Token::Comma) {
+                    self.bump();
+                    self.skip_comments_and_whitespace();
+                    if !self.is_at(Token::RightParen) {
+                        self.write(", ");
+                    }
+                }
             }
+            self.write_right_paren(); // )
         }
 
         self.write_right_bracket(); // ]
     }
 
+    /// Formats a parenthesized list of names given to a test option, like `fixtures(a, b)`.
+    fn format_test_attribute_names(&mut self) {
+        self.write_left_paren(); // (
+        self.skip_comments_and_whitespace();
+        while !self.is_at(Token::RightParen) {
+            self.write_current_token_and_bump(); // name
+            self.skip_comments_and_whitespace();
+            if self.is_at(Token::Comma) {
+                self.bump();
+                self.skip_comments_and_whitespace();
+                if !self.is_at(Token::RightParen) {
+                    self.write(", ");
+                }
+            }
+        }
+        self.write_right_paren(); // )
+        self.skip_comments_and_whitespace();
+    }
+
     fn format_fuzz_attribute(&mut self, fuzz_scope: FuzzingScope) {
         self.write_current_token_and_bump(); // #[
         self.skip_comments_and_whitespace();
@@ -191,6 +224,39 @@ impl Formatter<'_> {
         self.write_right_bracket(); // ]
     }
 
+    fn format_cfg_attribute(&mut self, predicate: &CfgPredicate) {
+        self.write_current_token_and_bump(); // #[
+        self.skip_comments_and_whitespace();
+        self.write_current_token_and_bump(); // cfg
+        self.write_left_paren(); // (
+        self.format_cfg_predicate(predicate);
+        self.write_right_paren(); // )
+        self.write_right_bracket(); // ]
+    }
+
+    fn format_cfg_predicate(&mut self, predicate: &CfgPredicate) {
+        self.skip_comments_and_whitespace();
+        match predicate {
+            CfgPredicate::Feature(_) => {
+                self.write_current_token_and_bump(); // feature
+                self.write_space();
+                self.write_token(Token::Assign);
+                self.write_space();
+                self.skip_comments_and_whitespace();
+                self.write_current_token_and_bump(); // "name"
+            }
+            CfgPredicate::Test => {
+                self.write_current_token_and_bump(); // test
+            }
+            CfgPredicate::Not(predicate) => {
+                self.write_current_token_and_bump(); // not
+                self.write_left_paren(); // (
+                self.format_cfg_predicate(predicate);
+                self.write_right_paren(); // )
+            }
+        }
+    }
+
     fn format_meta_attribute(&mut self, meta_attribute: MetaAttribute) {
         self.write_current_token_and_bump(); // #[
         self.skip_comments_and_whitespace();
@@ -248,6 +314,52 @@ impl Formatter<'_> {
         }
         self.write_right_bracket(); // ]
     }
+
+    /// Formats the fuzz strategy attribute of a function parameter, keeping its integers
+    /// as they are written in the source.
+    pub(super) fn format_fuzz_strategy(&mut self, kind: FuzzStrategyKind) {
+        self.skip_comments_and_whitespace();
+        self.write_current_token_and_bump(); // #[
+        self.skip_comments_and_whitespace();
+        self.write_current_token_and_bump(); // range or one_of
+        self.write_left_paren(); // (
+        match kind {
+            FuzzStrategyKind::Range { .. } => {
+                self.format_fuzz_strategy_integer(); // min
+                self.write_comma();
+                self.write_space();
+                self.format_fuzz_strategy_integer(); // max
+            }
+            FuzzStrategyKind::OneOf(values) => {
+                self.write_left_bracket(); // [
+                for index in 0..values.len() {
+                    if index > 0 {
+                        self.write_comma();
+                        self.write_space();
+                    }
+                    self.format_fuzz_strategy_integer();
+                }
+                // Remove the trailing comma
+                self.skip_comments_and_whitespace();
+                if self.is_at(Token::Comma) {
+                    self.bump();
+                }
+                self.write_right_bracket(); // ]
+            }
+        }
+        self.write_right_paren(); // )
+        self.write_right_bracket(); // ]
+    }
+
+    fn format_fuzz_strategy_integer(&mut self) {
+        self.skip_comments_and_whitespace();
+        if self.is_at(Token::Minus) {
+            self.write_current_token_and_bump(); // -
+            self.skip_comments_and_whitespace();
+        }
+        self.write_current_token_as_in_source();
+        self.bump();
+    }
 }
 
 #[cfg(test)]
@@ -281,6 +393,27 @@ mod tests {
         assert_format_attribute(src, expected);
     }
 
+    #[test]
+    fn format_cfg_attribute() {
+        let src = "  #[ cfg ( feature  =  \"poseidon\" ) ] ";
+        let expected = "#[cfg(feature = \"poseidon\")]";
+        assert_format_attribute(src, expected);
+    }
+
+    #[test]
+    fn format_cfg_not_attribute() {
+        let src = "  #[ cfg ( not ( feature  =  \"poseidon\" ) ) ] ";
+        let expected = "#[cfg(not(feature = \"poseidon\"))]";
+        assert_format_attribute(src, expected);
+    }
+
+    #[test]
+    fn format_cfg_test_attribute() {
+        let src = "  #[ cfg ( not ( test ) ) ] ";
+        let expected = "#[cfg(not(test))]";
+        assert_format_attribute(src, expected);
+    }
+
     #[test]
     fn format_contract_library_method() {
         let src = "  #[ contract_library_method ] ";
@@ -295,6 +428,13 @@ mod tests {
         assert_format_attribute(src, expected);
     }
 
+    #[test]
+    fn format_fixture() {
+        let src = "  #[ fixture ] ";
+        let expected = "#[fixture]";
+        assert_format_attribute(src, expected);
+    }
+
     #[test]
     fn format_varargs() {
         let src = "  #[ varargs ] ";
@@ -400,6 +540,20 @@ mod tests {
         assert_format_attribute(src, expected);
     }
 
+    #[test]
+    fn format_test_should_fail_with_timeout_attribute() {
+        let src = "  #[ test ( should_fail_with=\"reason\" ,timeout=10, )] ";
+        let expected = "#[test(should_fail_with = \"reason\", timeout = 10)]";
+        assert_format_attribute(src, expected);
+    }
+
+    #[test]
+    fn format_test_fixtures_attribute() {
+        let src = "  #[ test ( fixtures ( a ,b, ) ,timeout=10 )] ";
+        let expected = "#[test(fixtures(a, b), timeout = 10)]";
+        assert_format_attribute(src, expected);
+    }
+
     #[test]
     fn format_fuzz_attribute() {
         let src = "  #[ fuzz ] ";
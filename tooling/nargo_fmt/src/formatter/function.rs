@@ -153,6 +153,11 @@ impl Formatter<'_> {
     }
 
     fn format_function_param(&mut self, param: Param) {
+        if let Some(strategy) = param.fuzz_strategy {
+            self.format_fuzz_strategy(strategy.kind);
+            self.write_space();
+        }
+
         let group = self.format_pattern(param.pattern);
         self.format_chunk_group(group);
         self.skip_comments_and_whitespace();
@@ -388,6 +393,29 @@ mod tests {
         assert_format(src, expected);
     }
 
+    #[test]
+    fn format_function_parameter_fuzz_strategies() {
+        let src = "fn  foo( #[ range ( 0 , 100 ) ]  x : u32, #[one_of([ 1,2 ,3, ])] y:Field ) {  }";
+        let expected = "fn foo(#[range(0, 100)] x: u32, #[one_of([1, 2, 3])] y: Field) {}\n";
+        assert_format(src, expected);
+
+        let src = "fn foo(#[range(0x0,0xff)] x: u8, #[one_of([0xFF, 1_000])] y: Field) {}";
+        let expected = "fn foo(#[range(0x0, 0xff)] x: u8, #[one_of([0xFF, 1_000])] y: Field) {}\n";
+        assert_format(src, expected);
+
+        let src = "fn foo(#[one_of([0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000])] y: Field) {}";
+        let expected = "fn foo(#[one_of([0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000])] y: Field) {}\n";
+        assert_format(src, expected);
+
+        let src = "fn foo(#[range(1 /* min */, 2)] x: u8) {}";
+        let expected = "fn foo(#[range(1 /* min */, 2)] x: u8) {}\n";
+        assert_format(src, expected);
+
+        let src = "fn foo(#[range( - 10 ,-0x5)] x: i8) {}";
+        let expected = "fn foo(#[range(-10, -0x5)] x: i8) {}\n";
+        assert_format(src, expected);
+    }
+
     #[test]
     fn format_function_return_visibility() {
         let src = "fn  foo( )  ->  pub   Field  {  }";
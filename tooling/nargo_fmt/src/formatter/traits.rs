@@ -93,6 +93,7 @@ impl Formatter<'_> {
                         visibility: Visibility::Private,
                         pattern: Pattern::Identifier(name),
                         typ,
+                        fuzz_strategy: None,
                         location: Location::dummy(), // Doesn't matter
                     })
                     .collect();
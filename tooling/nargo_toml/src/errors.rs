@@ -77,6 +77,111 @@ pub enum ManifestError {
 
     #[error("Failed to parse expression width with the following error: {0}")]
     ParseExpressionWidth(String),
+
+    #[error(
+        "Git dependency `{git}` in {toml} must specify exactly one of `tag`, `version`, `branch` or `rev`"
+    )]
+    InvalidGitDependency { toml: PathBuf, git: String },
+
+    #[error("Invalid version requirement `{version}` in {toml}: {error}")]
+    InvalidDependencyVersion { toml: PathBuf, version: String, error: String },
+
+    #[error(
+        "No tag of `{git}` satisfies all the version requirements placed on it:\n{requirements}"
+    )]
+    UnsatisfiableGitRequirements { git: String, requirements: String },
+
+    #[error("{path} is badly formed, could not parse.\n\n {error}")]
+    MalformedLockfile { path: PathBuf, error: String },
+
+    #[error("Cannot write lockfile {0}")]
+    LockfileWriteFailed(PathBuf),
+
+    #[error("{path} needs to be updated but --locked or --frozen was passed: {reason}")]
+    LockfileOutdated { path: PathBuf, reason: String },
+
+    #[error(
+        "Git dependency `{git}` at {reference} resolved to commit {found} but Nargo.lock pins commit {expected}. The reference may have been moved; remove its entry from Nargo.lock if the change is expected"
+    )]
+    LockfileCommitMismatch { git: String, reference: String, expected: String, found: String },
+
+    #[error(
+        "Contents of git dependency `{git}` at {reference} do not match the checksum in Nargo.lock. The checkout in {checkout} may have been modified"
+    )]
+    LockfileChecksumMismatch { git: String, reference: String, checkout: PathBuf },
+
+    #[error(
+        "Dependencies are vendored but {0} does not exist. Run `nargo vendor` to create it, or remove `vendored = true` from Nargo.toml"
+    )]
+    MissingVendorManifest(PathBuf),
+
+    #[error("{path} is badly formed, could not parse.\n\n {error}")]
+    MalformedVendorManifest { path: PathBuf, error: String },
+
+    #[error(
+        "Git dependency `{git}` at {reference} is not vendored in {vendor_dir}. Run `nargo vendor` to update it"
+    )]
+    MissingVendoredDependency { git: String, reference: String, vendor_dir: PathBuf },
+
+    #[error(
+        "Contents of vendored git dependency `{git}` at {reference} in {path} have been modified. Run `nargo vendor` to restore them"
+    )]
+    VendoredDependencyModified { git: String, reference: String, path: PathBuf },
+
+    #[error(
+        "{0} already exists but was not created by `nargo vendor`. Move it out of the way before vendoring"
+    )]
+    UnexpectedVendorDirectory(PathBuf),
+
+    #[error("Failed to vendor dependencies: {0}")]
+    VendorFailed(String),
+
+    #[error(
+        "Profile `{name}` is not declared in {toml} or its workspace. Declare it in a `[profile.{name}]` table"
+    )]
+    UnknownProfile { toml: PathBuf, name: String },
+
+    #[error("Cyclic profile inheritance in {toml}: {cycle}")]
+    CyclicProfileInheritance { toml: PathBuf, cycle: String },
+
+    #[error("Invalid profile `{name}` in {toml}: {error}")]
+    InvalidProfile { toml: PathBuf, name: String, error: String },
+
+    #[error("Package in {toml} does not have feature `{feature}`")]
+    UnknownFeature { toml: PathBuf, feature: String },
+
+    #[error("None of the selected packages has feature `{feature}`")]
+    UnknownSelectedFeature { feature: String },
+
+    #[error(
+        "Feature `{feature}` in {toml} enables `{value}`, which is neither a feature of the package nor `dependency/feature` for one of its dependencies"
+    )]
+    InvalidFeatureValue { toml: PathBuf, feature: String, value: String },
+
+    #[error(
+        "Dependency `{name}` in {toml} is inherited from the workspace, but the package is not a member of a workspace"
+    )]
+    NotInWorkspace { toml: PathBuf, name: String },
+
+    #[error(
+        "Dependency `{name}` in {toml} is inherited from the workspace, but {workspace_toml} does not declare it in `[workspace.dependencies]`"
+    )]
+    MissingWorkspaceDependency { toml: PathBuf, name: String, workspace_toml: PathBuf },
+
+    #[error(
+        "Invalid dependency `{name}` in {toml}: only members can use `workspace = true`, and only to inherit a dependency"
+    )]
+    InvalidWorkspaceDependency { toml: PathBuf, name: String },
+
+    #[error(
+        "Package `{package_name}` requires the unstable features {features}, which compiler version {compiler_version} does not know.\n Remove them from `compiler_unstable_features` in {toml} or compile this project with a version that supports them"
+    )]
+    UnknownUnstableFeatures {
+        toml: PathBuf,
+        package_name: CrateName,
+        features: String,
+        compiler_version: String,
+    },
 }
 
 #[allow(clippy::enum_variant_names)]
@@ -87,7 +192,7 @@ pub enum SemverError {
     )]
     InvalidCompilerVersionRequirement { package_name: CrateName, required_compiler_version: String },
     #[error(
-        "Incompatible compiler version in package {package_name}. Required compiler version is {required_compiler_version} but the compiler version is {compiler_version_found}.\n Update the compiler_version field in Nargo.toml to >={required_compiler_version} or compile this project with version {required_compiler_version}"
+        "Incompatible compiler version in package {package_name}. Required compiler version is {required_compiler_version} but the compiler version is {compiler_version_found}.\n Update the compiler_version field in the Nargo.toml of {package_name} or compile this project with a version matching {required_compiler_version}"
     )]
     IncompatibleVersion {
         package_name: CrateName,
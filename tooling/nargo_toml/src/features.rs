@@ -0,0 +1,203 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use crate::ManifestError;
+
+/// Name of the feature enabled unless default features are turned off.
+const DEFAULT_FEATURE_NAME: &str = "default";
+
+/// Features requested for a package, either on the command line or by a package depending on it.
+#[derive(Debug, Clone)]
+pub(crate) struct FeatureRequest {
+    pub(crate) features: Vec<String>,
+    /// Whether to enable the `default` feature, if the package declares one.
+    pub(crate) default_features: bool,
+    /// Whether to enable every feature the package declares.
+    pub(crate) all_features: bool,
+}
+
+impl Default for FeatureRequest {
+    fn default() -> Self {
+        Self { features: Vec::new(), default_features: true, all_features: false }
+    }
+}
+
+impl FeatureRequest {
+    /// The part of the command line request `self` that applies to the selected workspace member `member`,
+    /// which declares the features `declared`, along with the requested features it uses.
+    ///
+    /// A feature written as `member/feature` only applies to that member. Any other feature applies to the
+    /// members declaring it, or having the dependency it names as `dependency/feature`.
+    pub(crate) fn for_member<'a>(
+        &'a self,
+        member: &str,
+        declared: &BTreeMap<String, Vec<String>>,
+        is_dependency: impl Fn(&str) -> bool,
+    ) -> (FeatureRequest, Vec<&'a str>) {
+        let mut features = Vec::new();
+        let mut used = Vec::new();
+        for requested in &self.features {
+            let feature = match requested.split_once('/') {
+                Some((package, feature)) if package == member => feature,
+                Some((dependency, _)) if is_dependency(dependency) => requested.as_str(),
+                None if declared.contains_key(requested) => requested.as_str(),
+                _ => continue,
+            };
+            features.push(feature.to_string());
+            used.push(requested.as_str());
+        }
+        let request = FeatureRequest { features, ..self.clone() };
+        (request, used)
+    }
+}
+
+/// The features enabled for a package, and the ones they enable on its dependencies.
+#[derive(Debug, Default)]
+pub(crate) struct EnabledFeatures {
+    pub(crate) features: BTreeSet<String>,
+    pub(crate) dependency_features: BTreeMap<String, Vec<String>>,
+}
+
+/// Resolves the features of the package with the manifest `toml` and the `[features]` table `declared`,
+/// following the features enabled by the requested ones.
+///
+/// A feature can enable other features of the package, or a feature of a dependency as `dependency/feature`.
+pub(crate) fn resolve_features(
+    declared: &BTreeMap<String, Vec<String>>,
+    request: &FeatureRequest,
+    is_dependency: impl Fn(&str) -> bool,
+    toml: &Path,
+) -> Result<EnabledFeatures, ManifestError> {
+    for (feature, values) in declared {
+        for value in values {
+            let is_valid = match value.split_once('/') {
+                Some((dependency, feature)) => is_dependency(dependency) && !feature.is_empty(),
+                None => declared.contains_key(value),
+            };
+            if !is_valid {
+                return Err(ManifestError::InvalidFeatureValue {
+                    toml: toml.to_path_buf(),
+                    feature: feature.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+    }
+
+    let mut pending = Vec::new();
+    for feature in &request.features {
+        let is_known = match feature.split_once('/') {
+            Some((dependency, _)) => is_dependency(dependency),
+            None => declared.contains_key(feature),
+        };
+        if !is_known {
+            return Err(ManifestError::UnknownFeature {
+                toml: toml.to_path_buf(),
+                feature: feature.clone(),
+            });
+        }
+        pending.push(feature.clone());
+    }
+    if request.default_features && declared.contains_key(DEFAULT_FEATURE_NAME) {
+        pending.push(DEFAULT_FEATURE_NAME.to_string());
+    }
+    if request.all_features {
+        pending.extend(declared.keys().cloned());
+    }
+
+    let mut enabled = EnabledFeatures::default();
+    while let Some(feature) = pending.pop() {
+        if let Some((dependency, feature)) = feature.split_once('/') {
+            let features = enabled.dependency_features.entry(dependency.to_string()).or_default();
+            if !features.iter().any(|enabled| enabled == feature) {
+                features.push(feature.to_string());
+            }
+        } else if enabled.features.insert(feature.clone()) {
+            pending.extend(declared[&feature].iter().cloned());
+        }
+    }
+    Ok(enabled)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, path::Path};
+
+    use super::{FeatureRequest, resolve_features};
+    use crate::ManifestError;
+
+    fn declared(features: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+        features
+            .iter()
+            .map(|(name, values)| {
+                (name.to_string(), values.iter().map(|value| value.to_string()).collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn follows_enabled_features() {
+        let declared = declared(&[
+            ("default", &["poseidon"]),
+            ("poseidon", &[]),
+            ("fast", &["poseidon", "hash/fast"]),
+            ("unused", &[]),
+        ]);
+        let is_dependency = |name: &str| name == "hash";
+        let toml = Path::new("Nargo.toml");
+
+        let request = FeatureRequest::default();
+        let enabled = resolve_features(&declared, &request, is_dependency, toml).unwrap();
+        assert_eq!(enabled.features.into_iter().collect::<Vec<_>>(), vec!["default", "poseidon"]);
+        assert!(enabled.dependency_features.is_empty());
+
+        let request = FeatureRequest {
+            features: vec!["fast".to_string()],
+            default_features: false,
+            all_features: false,
+        };
+        let enabled = resolve_features(&declared, &request, is_dependency, toml).unwrap();
+        assert_eq!(enabled.features.into_iter().collect::<Vec<_>>(), vec!["fast", "poseidon"]);
+        assert_eq!(enabled.dependency_features["hash"], vec!["fast".to_string()]);
+
+        let request = FeatureRequest { all_features: true, ..Default::default() };
+        let enabled = resolve_features(&declared, &request, is_dependency, toml).unwrap();
+        assert_eq!(enabled.features.len(), 4);
+    }
+
+    #[test]
+    fn splits_command_line_features_between_members() {
+        let request = FeatureRequest {
+            features: vec!["fast".to_string(), "a/slow".to_string(), "hash/fast".to_string()],
+            default_features: false,
+            all_features: false,
+        };
+
+        let declared_a = declared(&[("fast", &[]), ("slow", &[])]);
+        let (request_a, used) = request.for_member("a", &declared_a, |_| false);
+        assert_eq!(request_a.features, vec!["fast".to_string(), "slow".to_string()]);
+        assert!(!request_a.default_features);
+        assert_eq!(used, vec!["fast", "a/slow"]);
+
+        let declared_b = declared(&[("slow", &[])]);
+        let (request_b, used) = request.for_member("b", &declared_b, |name| name == "hash");
+        assert_eq!(request_b.features, vec!["hash/fast".to_string()]);
+        assert_eq!(used, vec!["hash/fast"]);
+    }
+
+    #[test]
+    fn rejects_unknown_features() {
+        let toml = Path::new("Nargo.toml");
+        let is_dependency = |name: &str| name == "hash";
+
+        let request = FeatureRequest { features: vec!["fast".to_string()], ..Default::default() };
+        let result = resolve_features(&declared(&[]), &request, is_dependency, toml);
+        assert!(matches!(result, Err(ManifestError::UnknownFeature { .. })));
+
+        let declared = declared(&[("fast", &["other/fast"])]);
+        let result = resolve_features(&declared, &FeatureRequest::default(), is_dependency, toml);
+        assert!(matches!(result, Err(ManifestError::InvalidFeatureValue { .. })));
+    }
+}
@@ -1,15 +1,53 @@
-use std::path::PathBuf;
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+pub use nargo::package::GitReference;
 
 use crate::flock::FileLock;
 
-/// Creates a unique folder name for a GitHub repo
-/// by using its URL and tag
-fn resolve_folder_name(base: &url::Url, tag: &str) -> String {
+/// Name of the folder holding the checkout of `reference`, within the folder of the repository.
+/// Branches and revisions are kept apart from tags using `~`, which can't appear in a git ref name.
+fn folder_name(reference: &GitReference) -> String {
+    match reference {
+        GitReference::Tag(tag) => tag.clone(),
+        GitReference::Branch(branch) => format!("~branch/{branch}"),
+        GitReference::Rev(rev) => format!("~rev/{rev}"),
+    }
+}
+
+/// Parses the URL of a git repository. Besides URLs such as `https://`, `ssh://` and `file://` ones,
+/// this accepts the scp-like syntax of SSH, e.g. `git@example.com:org/repo.git`.
+pub(crate) fn parse_git_url(url: &str) -> Result<url::Url, String> {
+    if !url.contains("://") {
+        if let Some((host, path)) = url.split_once(':') {
+            if !host.is_empty() && !host.contains('/') {
+                let ssh_url = format!("ssh://{host}/{}", path.trim_start_matches('/'));
+                return url::Url::parse(&ssh_url).map_err(|err| err.to_string());
+            }
+        }
+    }
+    url::Url::parse(url).map_err(|err| err.to_string())
+}
+
+/// Creates a unique folder name for a git repo
+/// by using its URL and the referenced revision
+pub(crate) fn resolve_folder_name(base: &url::Url, reference: &GitReference) -> String {
+    let mut folder = repository_folder(base);
+    folder.push(folder_name(reference));
+    folder.to_string_lossy().into_owned()
+}
+
+/// Folder holding the checkouts of a repository, e.g. `github.com/noir-lang/noir-bignum`.
+/// Local repositories given by a `file://` URL are kept under `file`.
+fn repository_folder(base: &url::Url) -> PathBuf {
+    let host = base.host_str().unwrap_or(base.scheme());
     let mut folder = PathBuf::from("");
-    for part in [base.domain().unwrap(), base.path(), tag] {
+    for part in [host, &base.path().replace(':', "_")] {
         folder.push(part.trim_start_matches('/'));
     }
-    folder.to_string_lossy().into_owned()
+    folder
 }
 
 /// Path to the `nargo` directory under `$HOME`.
@@ -19,8 +57,8 @@ fn nargo_crates() -> PathBuf {
 
 /// Target directory to download dependencies into, e.g.
 /// `$HOME/nargo/github.com/noir-lang/noir-bignum/v0.1.2`
-fn git_dep_location(base: &url::Url, tag: &str) -> PathBuf {
-    let folder_name = resolve_folder_name(base, tag);
+fn git_dep_location(base: &url::Url, reference: &GitReference) -> PathBuf {
+    let folder_name = resolve_folder_name(base, reference);
 
     nargo_crates().join(folder_name)
 }
@@ -34,48 +72,178 @@ pub(crate) fn lock_git_deps() -> std::io::Result<FileLock> {
 /// tag
 /// github-rs looks promising, however it seems to require an API token
 ///
-/// One advantage of using "git clone" is that there is effectively no rate limit
-pub(crate) fn clone_git_repo(url: &str, tag: &str) -> Result<PathBuf, String> {
-    use std::process::Command;
-
-    let base = match url::Url::parse(url) {
-        Ok(base) => base,
-        Err(err) => return Err(err.to_string()),
-    };
+/// One advantage of using "git clone" is that there is effectively no rate limit.
+/// It also means the user's git configuration applies, including credential helpers
+/// for private repositories over HTTPS and SSH keys.
+///
+/// If `offline` is set, only the local cache is consulted.
+pub(crate) fn clone_git_repo(
+    url: &str,
+    reference: &GitReference,
+    offline: bool,
+) -> Result<PathBuf, String> {
+    let base = parse_git_url(url)?;
 
-    let loc = git_dep_location(&base, tag);
+    let loc = git_dep_location(&base, reference);
     if loc.exists() {
         return Ok(loc);
     }
+    if offline {
+        return Err(format!(
+            "Git dependency `{url}` at {reference} is not in the local cache and network access is disabled"
+        ));
+    }
+
+    let result = match reference {
+        GitReference::Tag(name) | GitReference::Branch(name) => run_git(
+            Command::new("git")
+                .arg("-c")
+                .arg("advice.detachedHead=false")
+                .arg("clone")
+                .arg("--depth")
+                .arg("1")
+                .arg("--branch")
+                .arg(name)
+                .arg(url)
+                .arg(&loc),
+        ),
+        // A commit can't be cloned directly, and fetching one by its hash isn't supported by every server.
+        GitReference::Rev(rev) => {
+            run_git(Command::new("git").arg("clone").arg("--no-checkout").arg(url).arg(&loc))
+                .and_then(|()| {
+                    run_git(
+                        Command::new("git")
+                            .arg("-C")
+                            .arg(&loc)
+                            .arg("-c")
+                            .arg("advice.detachedHead=false")
+                            .arg("checkout")
+                            .arg(rev),
+                    )
+                })
+        }
+    };
 
-    Command::new("git")
-        .arg("-c")
-        .arg("advice.detachedHead=false")
-        .arg("clone")
-        .arg("--depth")
-        .arg("1")
-        .arg("--branch")
-        .arg(tag)
-        .arg(base.as_str())
-        .arg(&loc)
-        .status()
-        .expect("git clone command failed to start");
+    if let Err(err) = result {
+        // Don't leave a partial checkout behind, as it would be mistaken for a cached one.
+        let _ = std::fs::remove_dir_all(&loc);
+        return Err(format!("Failed to fetch git dependency `{url}` at {reference}: {err}"));
+    }
 
     Ok(loc)
 }
 
+/// Fetches the latest commit of `branch` into its checkout in the local cache, cloning it if it isn't
+/// there yet.
+///
+/// If `offline` is set, the cached checkout is used as it is.
+pub(crate) fn update_git_branch(url: &str, branch: &str, offline: bool) -> Result<PathBuf, String> {
+    let reference = GitReference::Branch(branch.to_string());
+    let loc = git_dep_location(&parse_git_url(url)?, &reference);
+    if offline || !loc.exists() {
+        return clone_git_repo(url, &reference, offline);
+    }
+
+    run_git(
+        Command::new("git").arg("-C").arg(&loc).args(["fetch", "--depth", "1", "origin", branch]),
+    )
+    .and_then(|()| {
+        run_git(Command::new("git").arg("-C").arg(&loc).args(["reset", "--hard", "FETCH_HEAD"]))
+    })
+    .map_err(|err| format!("Failed to update git dependency `{url}` at {reference}: {err}"))?;
+    Ok(loc)
+}
+
+/// Runs a git command, letting it interact with the terminal, e.g. to ask for credentials.
+fn run_git(command: &mut Command) -> Result<(), String> {
+    let status = command.status().map_err(|err| format!("git command failed to start: {err}"))?;
+    if status.success() { Ok(()) } else { Err(format!("git exited with {status}")) }
+}
+
+/// Lists the tags of a git repository.
+///
+/// If `offline` is set, only the tags already in the local cache are listed.
+pub(crate) fn list_git_tags(url: &str, offline: bool) -> Result<Vec<String>, String> {
+    let base = parse_git_url(url)?;
+
+    if offline {
+        let repo_dir = nargo_crates().join(repository_folder(&base));
+        let Ok(entries) = std::fs::read_dir(repo_dir) else {
+            return Ok(Vec::new());
+        };
+        let tags = entries
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str().map(String::from))
+            .filter(|name| !name.starts_with('~'))
+            .collect();
+        return Ok(tags);
+    }
+
+    let output = std::process::Command::new("git")
+        .arg("ls-remote")
+        .arg("--tags")
+        .arg("--refs")
+        .arg(url)
+        .output()
+        .map_err(|err| format!("git ls-remote command failed to start: {err}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to list the tags of {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let tags = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once("refs/tags/"))
+        .map(|(_, tag)| tag.trim().to_string())
+        .collect();
+    Ok(tags)
+}
+
+/// Returns the hash of the commit checked out in `dir`.
+pub(crate) fn git_head_commit(dir: &Path) -> Result<String, String> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .arg("rev-parse")
+        .arg("HEAD")
+        .output()
+        .map_err(|err| format!("git rev-parse command failed to start: {err}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to read the commit checked out in {}: {}",
+            dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
     use url::Url;
 
-    use super::resolve_folder_name;
+    use super::{GitReference, parse_git_url, resolve_folder_name};
 
     #[test_case("https://github.com/noir-lang/noir-bignum/"; "with slash")]
     #[test_case("https://github.com/noir-lang/noir-bignum"; "without slash")]
     fn test_resolve_folder_name(url: &str) {
-        let tag = "v0.4.2";
-        let dir = resolve_folder_name(&Url::parse(url).unwrap(), tag);
+        let tag = GitReference::Tag("v0.4.2".to_string());
+        let dir = resolve_folder_name(&Url::parse(url).unwrap(), &tag);
         assert_eq!(dir, "github.com/noir-lang/noir-bignum/v0.4.2");
     }
+
+    #[test_case("git@git.example.com:libs/utils.git", "git.example.com/libs/utils.git/~branch/main"; "scp-like ssh")]
+    #[test_case("ssh://git@git.example.com/libs/utils.git", "git.example.com/libs/utils.git/~branch/main"; "ssh")]
+    #[test_case("file:///srv/git/utils", "file/srv/git/utils/~branch/main"; "file")]
+    fn test_resolve_folder_name_for_other_urls(url: &str, expected: &str) {
+        let branch = GitReference::Branch("main".to_string());
+        let dir = resolve_folder_name(&parse_git_url(url).unwrap(), &branch);
+        assert_eq!(dir, expected);
+    }
 }
@@ -2,28 +2,46 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies, unused_extern_crates))]
 
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     path::{Component, Path, PathBuf},
+    rc::Rc,
     str::FromStr,
 };
 
+use ::semver::Version;
 use errors::SemverError;
 use fm::{FILE_EXTENSION, NormalizePath};
 use nargo::{
     package::{Dependency, Package, PackageType},
     workspace::Workspace,
 };
-use noirc_driver::parse_expression_width;
+use noirc_driver::{DEFAULT_PROFILE_NAME, parse_expression_width};
 use noirc_frontend::{elaborator::UnstableFeature, graph::CrateName};
 use serde::Deserialize;
 
 mod errors;
+mod features;
 mod flock;
 mod git;
+mod lockfile;
+mod outdated;
+mod profile;
 mod semver;
+mod vendor;
+mod workspace;
 
 pub use errors::ManifestError;
-use git::{clone_git_repo, lock_git_deps};
+use features::FeatureRequest;
+pub use git::GitReference;
+use git::{clone_git_repo, git_head_commit, list_git_tags, lock_git_deps, update_git_branch};
+use lockfile::LockfileResolver;
+pub use lockfile::{LOCKFILE_NAME, LockedPackage, Lockfile, LockfileMode};
+pub use outdated::{OutdatedDependency, find_outdated_dependencies};
+pub use profile::ProfileConfig;
+use vendor::VendoredSources;
+pub use vendor::{VENDOR_DIR, VendoredPackage};
+use workspace::WorkspaceInheritance;
+pub use workspace::WorkspacePackageConfig;
 
 /// Searches for a `Nargo.toml` file in the current directory and all parent directories.
 /// For example, if the current directory is `/workspace/package/src`, then this function
@@ -149,16 +167,32 @@ pub struct PackageConfig {
     pub package: PackageMetadata,
     #[serde(default)]
     pub dependencies: BTreeMap<String, DependencyConfig>,
+    #[serde(default, rename = "dev-dependencies")]
+    pub dev_dependencies: BTreeMap<String, DependencyConfig>,
+    #[serde(default)]
+    pub profile: BTreeMap<String, ProfileConfig>,
+    /// Features of the package, each with the features it enables in turn.
+    #[serde(default)]
+    pub features: BTreeMap<String, Vec<String>>,
 }
 
 impl PackageConfig {
     fn resolve_to_package(
         &self,
         root_dir: &Path,
-        processed: &mut Vec<String>,
+        state: &mut ResolutionState,
         assume_default_entry: bool, // assume that the 'default_entry_path' exists, e.g. src/main.nr
+        is_member: bool,
+        features: &FeatureRequest,
     ) -> Result<Package, ManifestError> {
-        let name: CrateName = if let Some(name) = &self.package.name {
+        // Members of a workspace inherit the settings they don't set from it.
+        let workspace = state.workspace_of(root_dir);
+        let metadata = match &workspace {
+            Some(workspace) => self.package.inherit(&workspace.package),
+            None => self.package.clone(),
+        };
+
+        let name: CrateName = if let Some(name) = &metadata.name {
             name.parse().map_err(|_| ManifestError::InvalidPackageName {
                 toml: root_dir.join("Nargo.toml"),
                 name: name.into(),
@@ -167,18 +201,91 @@ impl PackageConfig {
             return Err(ManifestError::MissingNameField { toml: root_dir.join("Nargo.toml") });
         };
 
+        // Check the package can be compiled before resolving anything else about it, since a mismatch
+        // tends to show up as confusing errors later on.
+        if let Some(compiler_version) = &state.compiler_version {
+            if let Some(required_version) = &metadata.compiler_version {
+                semver::check_compiler_version(&name, required_version, compiler_version)
+                    .map_err(ManifestError::SemverError)?;
+            }
+            let unknown_features: Vec<_> = metadata
+                .compiler_unstable_features
+                .iter()
+                .flatten()
+                .filter(|feature| UnstableFeature::from_str(feature).is_err())
+                .map(|feature| format!("`{feature}`"))
+                .collect();
+            if !unknown_features.is_empty() {
+                return Err(ManifestError::UnknownUnstableFeatures {
+                    toml: root_dir.join("Nargo.toml"),
+                    package_name: name,
+                    features: unknown_features.join(", "),
+                    compiler_version: semver::strip_build_meta_data(compiler_version),
+                });
+            }
+        }
+
+        let is_dependency = |name: &str| {
+            self.dependencies.contains_key(name) || self.dev_dependencies.contains_key(name)
+        };
+        // Only part of the features requested on the command line may apply to a workspace member.
+        let member_features;
+        let features = if is_member {
+            let (request, used) =
+                features.for_member(&name.to_string(), &self.features, is_dependency);
+            state.used_member_features.extend(used.into_iter().map(String::from));
+            member_features = request;
+            &member_features
+        } else {
+            features
+        };
+        let enabled_features = features::resolve_features(
+            &self.features,
+            features,
+            is_dependency,
+            &root_dir.join("Nargo.toml"),
+        )?;
+
         let mut dependencies: BTreeMap<CrateName, Dependency> = BTreeMap::new();
         for (name, dep_config) in self.dependencies.iter() {
+            let (dep_config, dep_root) =
+                dep_config.inherit_from_workspace(name, root_dir, workspace.as_deref())?;
+            let dep_features = dep_config.feature_request(&enabled_features, name);
             let name = name.parse().map_err(|_| ManifestError::InvalidDependencyName {
                 toml: root_dir.join("Nargo.toml"),
                 name: name.into(),
             })?;
-            let resolved_dep = dep_config.resolve_to_dependency(root_dir, processed)?;
+            let resolved_dep = dep_config.resolve_to_dependency(&dep_root, state, &dep_features)?;
 
             dependencies.insert(name, resolved_dep);
         }
 
-        let package_type = match self.package.package_type.as_deref() {
+        // Dev-dependencies are only resolved when building the tests of workspace members,
+        // otherwise we only keep their names so the compiler knows which tests to leave out.
+        let mut dev_dependencies: BTreeMap<CrateName, Option<Dependency>> = BTreeMap::new();
+        for (name, dep_config) in self.dev_dependencies.iter() {
+            let (dep_config, dep_root) =
+                dep_config.inherit_from_workspace(name, root_dir, workspace.as_deref())?;
+            let dep_features = dep_config.feature_request(&enabled_features, name);
+            let name: CrateName =
+                name.parse().map_err(|_| ManifestError::InvalidDependencyName {
+                    toml: root_dir.join("Nargo.toml"),
+                    name: name.into(),
+                })?;
+            if dependencies.contains_key(&name) {
+                // Already available to the tests as a regular dependency.
+                continue;
+            }
+            let resolved_dep = if is_member && state.include_dev_dependencies {
+                Some(dep_config.resolve_to_dependency(&dep_root, state, &dep_features)?)
+            } else {
+                None
+            };
+
+            dev_dependencies.insert(name, resolved_dep);
+        }
+
+        let package_type = match metadata.package_type.as_deref() {
             Some("lib") => PackageType::Library,
             Some("bin") => PackageType::Binary,
             Some("contract") => PackageType::Contract,
@@ -191,7 +298,7 @@ impl PackageConfig {
             None => return Err(ManifestError::MissingPackageType(root_dir.join("Nargo.toml"))),
         };
 
-        let entry_path = if let Some(entry_path) = &self.package.entry {
+        let entry_path = if let Some(entry_path) = &metadata.entry {
             let custom_entry_path = root_dir.join(entry_path);
             if custom_entry_path.exists() {
                 custom_entry_path
@@ -223,7 +330,7 @@ impl PackageConfig {
         };
 
         // If there is a package version, ensure that it is semver compatible
-        if let Some(version) = &self.package.version {
+        if let Some(version) = &metadata.version {
             semver::parse_semver_compatible_version(version).map_err(|err| {
                 ManifestError::SemverError(SemverError::CouldNotParsePackageVersion {
                     package_name: name.to_string(),
@@ -232,8 +339,7 @@ impl PackageConfig {
             })?;
         }
 
-        let expression_width = self
-            .package
+        let expression_width = metadata
             .expression_width
             .as_ref()
             .map(|expression_width| {
@@ -242,23 +348,47 @@ impl PackageConfig {
             })
             .map_or(Ok(None), |res| res.map(Some))?;
 
+        // Only workspace members are compiled with a profile; dependencies are compiled as part of them.
+        let profile = if is_member {
+            let no_profiles = BTreeMap::new();
+            let workspace_profiles = workspace.as_ref().map_or(&no_profiles, |ws| &ws.profiles);
+            Some(profile::resolve_profile(
+                &state.profile,
+                &self.profile,
+                workspace_profiles,
+                &root_dir.join("Nargo.toml"),
+            )?)
+        } else {
+            None
+        };
+        let expression_width = match profile.as_ref().and_then(|profile| profile.expression_width) {
+            Some(width) => Some(
+                parse_expression_width(&width.to_string())
+                    .map_err(|err| ManifestError::ParseExpressionWidth(err.to_string()))?,
+            ),
+            None => expression_width,
+        };
+
         // Collect any unstable features the package needs to compile.
-        // Ignore the ones that we don't recognize: maybe they are no longer unstable, but a dependency hasn't been updated.
+        // Unknown ones were rejected above if the compiler version is checked, otherwise they are ignored.
         let compiler_required_unstable_features =
-            self.package.compiler_unstable_features.as_ref().map_or(Vec::new(), |feats| {
+            metadata.compiler_unstable_features.as_ref().map_or(Vec::new(), |feats| {
                 feats.iter().flat_map(|feat| UnstableFeature::from_str(feat).ok()).collect()
             });
 
         Ok(Package {
-            version: self.package.version.clone(),
-            compiler_required_version: self.package.compiler_version.clone(),
+            version: metadata.version.clone(),
+            compiler_required_version: metadata.compiler_version.clone(),
             compiler_required_unstable_features,
             root_dir: root_dir.to_path_buf(),
             entry_path,
             package_type,
             name,
             dependencies,
+            dev_dependencies,
             expression_width,
+            profile,
+            features: enabled_features.features,
         })
     }
 }
@@ -277,6 +407,8 @@ pub enum Config {
     Workspace {
         #[serde(alias = "workspace")]
         workspace_config: WorkspaceConfig,
+        #[serde(default)]
+        profile: BTreeMap<String, ProfileConfig>,
     },
 }
 
@@ -305,10 +437,22 @@ pub struct NargoToml {
 #[derive(Default, Debug, Deserialize, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct WorkspaceConfig {
-    /// List of members in this workspace.
+    /// List of members in this workspace, which may contain `*` and `?` wildcards.
     pub members: Vec<PathBuf>,
+    /// Paths to leave out of the members matched by wildcards.
+    #[serde(default)]
+    pub exclude: Vec<PathBuf>,
     /// Specifies the default crate to interact with in the context (similarly to how we have nargo as the default crate in this repository).
     pub default_member: Option<PathBuf>,
+    /// Resolve git dependencies from the `vendor` directory instead of the network.
+    #[serde(default)]
+    pub vendored: bool,
+    /// Package settings inherited by members which don't set them.
+    #[serde(default)]
+    pub package: WorkspacePackageConfig,
+    /// Dependencies which members can inherit with `{ workspace = true }`.
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, DependencyConfig>,
 }
 
 #[allow(dead_code)]
@@ -332,6 +476,9 @@ pub struct PackageMetadata {
     pub compiler_unstable_features: Option<Vec<String>>,
     pub license: Option<String>,
     pub expression_width: Option<String>,
+    /// Resolve git dependencies from the `vendor` directory instead of the network.
+    #[serde(default)]
+    pub vendored: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -339,19 +486,120 @@ pub struct PackageMetadata {
 /// Enum representing the different types of ways to
 /// supply a source for the dependency
 pub enum DependencyConfig {
-    Github { git: String, tag: String, directory: Option<String> },
-    Path { path: String },
+    /// A package in a git repository, at either an exact `tag`, the highest tag satisfying
+    /// a semver `version` requirement, the tip of a `branch` or a specific commit `rev`.
+    Github {
+        git: String,
+        tag: Option<String>,
+        version: Option<String>,
+        branch: Option<String>,
+        rev: Option<String>,
+        directory: Option<String>,
+        #[serde(default)]
+        features: Vec<String>,
+        #[serde(default = "enabled_by_default", rename = "default-features")]
+        default_features: bool,
+    },
+    Path {
+        path: String,
+        #[serde(default)]
+        features: Vec<String>,
+        #[serde(default = "enabled_by_default", rename = "default-features")]
+        default_features: bool,
+    },
+    /// The dependency of the same name in `[workspace.dependencies]`, with some additional `features`.
+    Workspace {
+        workspace: bool,
+        #[serde(default)]
+        features: Vec<String>,
+    },
+}
+
+fn enabled_by_default() -> bool {
+    true
 }
 
 impl DependencyConfig {
+    /// The features to enable on the dependency called `name`, given the features enabled
+    /// on the package depending on it.
+    fn feature_request(&self, enabled: &features::EnabledFeatures, name: &str) -> FeatureRequest {
+        let (features, default_features) = match self {
+            Self::Github { features, default_features, .. }
+            | Self::Path { features, default_features, .. } => (features, *default_features),
+            Self::Workspace { features, .. } => (features, true),
+        };
+        let mut features = features.clone();
+        features.extend(enabled.dependency_features.get(name).into_iter().flatten().cloned());
+        FeatureRequest { features, default_features, all_features: false }
+    }
+
+    /// Replaces a `{ workspace = true }` dependency called `name` with its entry in `[workspace.dependencies]`,
+    /// returning it along with the directory its paths are relative to.
+    fn inherit_from_workspace(
+        &self,
+        name: &str,
+        pkg_root: &Path,
+        workspace: Option<&WorkspaceInheritance>,
+    ) -> Result<(DependencyConfig, PathBuf), ManifestError> {
+        let Self::Workspace { workspace: inherit, features } = self else {
+            return Ok((self.clone(), pkg_root.to_path_buf()));
+        };
+        let toml = pkg_root.join("Nargo.toml");
+        if !inherit {
+            return Err(ManifestError::InvalidWorkspaceDependency { toml, name: name.into() });
+        }
+        let Some(workspace) = workspace else {
+            return Err(ManifestError::NotInWorkspace { toml, name: name.into() });
+        };
+
+        let mut inherited = match workspace.dependencies.get(name) {
+            Some(Self::Workspace { .. }) => {
+                return Err(ManifestError::InvalidWorkspaceDependency {
+                    toml: workspace.toml_path(),
+                    name: name.into(),
+                });
+            }
+            Some(inherited) => inherited.clone(),
+            None => {
+                return Err(ManifestError::MissingWorkspaceDependency {
+                    toml,
+                    name: name.into(),
+                    workspace_toml: workspace.toml_path(),
+                });
+            }
+        };
+        if let Self::Github { features: inherited_features, .. }
+        | Self::Path { features: inherited_features, .. } = &mut inherited
+        {
+            inherited_features.extend(features.iter().cloned());
+        }
+        Ok((inherited, workspace.root_dir.clone()))
+    }
+
     fn resolve_to_dependency(
         &self,
         pkg_root: &Path,
-        processed: &mut Vec<String>,
+        state: &mut ResolutionState,
+        features: &FeatureRequest,
     ) -> Result<Dependency, ManifestError> {
         let dep = match self {
-            Self::Github { git, tag, directory } => {
-                let dir_path = clone_git_repo(git, tag).map_err(ManifestError::GitError)?;
+            Self::Github { git, tag, version, branch, rev, directory, .. } => {
+                let toml = pkg_root.join("Nargo.toml");
+                let reference = match (tag, version, branch, rev) {
+                    (Some(tag), None, None, None) => {
+                        GitReference::Tag(state.select_pinned_git_tag(git, tag, &toml)?)
+                    }
+                    (None, Some(version), None, None) => {
+                        GitReference::Tag(state.select_versioned_git_tag(git, version, &toml)?)
+                    }
+                    (None, None, Some(branch), None) => GitReference::Branch(branch.clone()),
+                    (None, None, None, Some(rev)) => GitReference::Rev(rev.clone()),
+                    _ => {
+                        return Err(ManifestError::InvalidGitDependency { toml, git: git.clone() });
+                    }
+                };
+                let (dir_path, commit) =
+                    state.checkout_git_dependency(git, &reference, directory.as_deref())?;
                 let project_path = if let Some(directory) = directory {
                     let internal_path = dir_path.join(directory).normalize();
                     if !internal_path.starts_with(&dir_path) {
@@ -362,18 +610,31 @@ impl DependencyConfig {
                     }
                     internal_path
                 } else {
-                    dir_path
+                    dir_path.clone()
                 };
                 let toml_path = project_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path, processed)?;
-                Dependency::Remote { package }
+                let package = resolve_package_from_toml(&toml_path, state, false, features)?;
+                if let Some(lockfile) = &mut state.lockfile {
+                    lockfile.check_git_dependency(
+                        &package.name.to_string(),
+                        git,
+                        &reference,
+                        directory.as_deref(),
+                        &dir_path,
+                        commit,
+                    )?;
+                }
+                Dependency::Remote { package, git: git.clone(), reference }
             }
-            Self::Path { path } => {
+            Self::Path { path, .. } => {
                 let dir_path = pkg_root.join(path);
                 let toml_path = dir_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path, processed)?;
+                let package = resolve_package_from_toml(&toml_path, state, false, features)?;
                 Dependency::Local { package }
             }
+            Self::Workspace { .. } => {
+                unreachable!("workspace dependencies are inherited before being resolved")
+            }
         };
 
         // Cannot depend on a binary
@@ -386,20 +647,358 @@ impl DependencyConfig {
     }
 }
 
+/// State shared while resolving the packages of a workspace and their dependencies.
+struct ResolutionState {
+    /// Manifests currently being resolved, used to detect cyclic dependencies.
+    processed: Vec<String>,
+    /// Whether git dependencies may only come from the local cache.
+    offline: bool,
+    /// Pins of git dependencies, or `None` if the manifest doesn't exist on disk.
+    lockfile: Option<LockfileResolver>,
+    /// Version requirements on each git repository, keyed by URL.
+    git_requirements: BTreeMap<String, semver::GitRequirements>,
+    /// Set when the tag selected for a git repository changed after the previous one was used.
+    restart: bool,
+    /// Whether the dev-dependencies of workspace members should be resolved.
+    include_dev_dependencies: bool,
+    /// Vendored sources of git dependencies, if the workspace uses them instead of the network.
+    vendor: Option<VendoredSources>,
+    /// Directories of the git dependencies used, keyed by repository and revision.
+    git_checkouts: BTreeMap<(String, GitReference), PathBuf>,
+    /// Name of the compilation profile selected for workspace members.
+    profile: String,
+    /// Settings of the workspace being resolved, inherited by its members.
+    workspace: Option<Rc<WorkspaceInheritance>>,
+    /// Features requested on the command line, for the selected workspace members.
+    member_features: FeatureRequest,
+    /// Features requested on the command line which applied to at least one selected member.
+    used_member_features: BTreeSet<String>,
+    /// Version of the running compiler, which every package must be compatible with, if it is checked.
+    compiler_version: Option<Version>,
+}
+
+impl ResolutionState {
+    /// Creates the state for resolving the package or workspace in `root_dir`. Unless `use_vendor` is false,
+    /// git dependencies are taken from the vendor directory if the workspace is configured to do so.
+    fn new(
+        root_dir: &Path,
+        options: &ResolveOptions,
+        use_vendor: bool,
+    ) -> Result<Self, ManifestError> {
+        let (lockfile, vendor) = if root_dir.join("Nargo.toml").exists() {
+            let (lockfile_dir, owned) = lockfile_root(root_dir);
+            let complete = owned && options.include_dev_dependencies;
+            let lockfile = LockfileResolver::new(&lockfile_dir, options.lockfile_mode, complete)?;
+            let vendor = if use_vendor && is_vendored(&lockfile_dir) {
+                Some(VendoredSources::load(&lockfile_dir)?)
+            } else {
+                None
+            };
+            (Some(lockfile), vendor)
+        } else {
+            (None, None)
+        };
+        let offline = !options.lockfile_mode.allows_network();
+        Ok(Self {
+            processed: Vec::new(),
+            offline,
+            lockfile,
+            git_requirements: BTreeMap::new(),
+            restart: false,
+            include_dev_dependencies: options.include_dev_dependencies,
+            vendor,
+            git_checkouts: BTreeMap::new(),
+            profile: options.profile.clone().unwrap_or_else(|| DEFAULT_PROFILE_NAME.to_string()),
+            workspace: None,
+            member_features: FeatureRequest {
+                features: options.features.clone(),
+                default_features: !options.no_default_features,
+                all_features: options.all_features,
+            },
+            used_member_features: BTreeSet::new(),
+            compiler_version: None,
+        })
+    }
+
+    /// The settings inherited by the package in `root_dir` from its workspace, if it is a member of one.
+    fn workspace_of(&self, root_dir: &Path) -> Option<Rc<WorkspaceInheritance>> {
+        let root_dir = root_dir.normalize();
+        match &self.workspace {
+            Some(workspace) if workspace.members.contains(&root_dir) => Some(workspace.clone()),
+            _ => workspace::enclosing_workspace(&root_dir).map(Rc::new),
+        }
+    }
+
+    /// Writes the lockfile once resolution is done.
+    fn finish(self) -> Result<(), ManifestError> {
+        match self.lockfile {
+            Some(lockfile) => lockfile.finish(),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the directory holding `git` at `reference`, along with its commit if it is already known.
+    ///
+    /// The directory is in the vendor directory if dependencies are vendored, otherwise the repository
+    /// is cloned into the local cache. A branch pinned in the lockfile is checked out at the pinned commit,
+    /// while other branches are fetched again to get their latest commit.
+    fn checkout_git_dependency(
+        &mut self,
+        git: &str,
+        reference: &GitReference,
+        directory: Option<&str>,
+    ) -> Result<(PathBuf, Option<String>), ManifestError> {
+        let key = (git_repository_key(git), reference.clone());
+        let (dir, commit) = match &mut self.vendor {
+            Some(vendor) => {
+                let (dir, commit) = vendor.checkout(git, reference)?;
+                (dir, Some(commit))
+            }
+            None => {
+                let dir = match reference {
+                    GitReference::Branch(branch) => {
+                        self.checkout_git_branch(git, branch, directory)
+                    }
+                    GitReference::Tag(_) | GitReference::Rev(_) => {
+                        clone_git_repo(git, reference, self.offline)
+                    }
+                };
+                (dir.map_err(ManifestError::GitError)?, None)
+            }
+        };
+        self.git_checkouts.insert(key, dir.clone());
+        Ok((dir, commit))
+    }
+
+    /// Returns the directory holding `branch` of `git` at the commit pinned in the lockfile, or at its
+    /// latest commit if it isn't pinned.
+    fn checkout_git_branch(
+        &self,
+        git: &str,
+        branch: &str,
+        directory: Option<&str>,
+    ) -> Result<PathBuf, String> {
+        let reference = GitReference::Branch(branch.to_string());
+        let locked_commit = self
+            .lockfile
+            .as_ref()
+            .and_then(|lockfile| lockfile.locked_commit(git, &reference, directory));
+        match locked_commit {
+            Some(commit) => {
+                // The cached checkout of the branch can be used while it is still at the pinned commit
+                let cached = clone_git_repo(git, &reference, true)
+                    .ok()
+                    .filter(|dir| git_head_commit(dir).is_ok_and(|head| head == commit));
+                match cached {
+                    Some(dir) => Ok(dir),
+                    None => clone_git_repo(git, &GitReference::Rev(commit), self.offline),
+                }
+            }
+            // Only fetch the branch once while resolving the workspace
+            None => match self.git_checkouts.get(&(git_repository_key(git), reference)) {
+                Some(dir) => Ok(dir.clone()),
+                None => update_git_branch(git, branch, self.offline),
+            },
+        }
+    }
+
+    /// Runs `resolve` again until the tags selected for git dependencies no longer change,
+    /// so that every package ends up depending on the same tag of a repository.
+    fn resolve_until_stable<T>(
+        &mut self,
+        mut resolve: impl FnMut(&mut Self) -> Result<T, ManifestError>,
+    ) -> Result<T, ManifestError> {
+        loop {
+            self.restart = false;
+            self.git_checkouts.clear();
+            if let Some(lockfile) = &mut self.lockfile {
+                lockfile.reset();
+            }
+            let resolved = resolve(self)?;
+            if !self.restart {
+                return Ok(resolved);
+            }
+        }
+    }
+
+    /// Picks the tag of `git` to use for the package with the manifest `toml`, which depends on
+    /// the exact `tag`.
+    ///
+    /// A tag which is a semver version is unified with the tags and requirements of the other packages
+    /// depending on the same repository, as if it was a caret requirement, so that `v0.4.1` and `v0.4.2`
+    /// both resolve to `v0.4.2`. Other tags are used as they are.
+    fn select_pinned_git_tag(
+        &mut self,
+        git: &str,
+        tag: &str,
+        toml: &Path,
+    ) -> Result<String, ManifestError> {
+        let Some(version) = semver::parse_tag_version(tag) else {
+            return Ok(tag.to_string());
+        };
+        let requirements = self.git_requirements.entry(git_repository_key(git)).or_default();
+        requirements.add_pinned(tag, &version, toml.display().to_string());
+        self.select_git_tag(git)
+    }
+
+    /// Picks the tag of `git` to use for the package with the manifest `toml`, which requires `version`.
+    fn select_versioned_git_tag(
+        &mut self,
+        git: &str,
+        version: &str,
+        toml: &Path,
+    ) -> Result<String, ManifestError> {
+        semver::check_version_requirement(version).map_err(|err| {
+            ManifestError::InvalidDependencyVersion {
+                toml: toml.to_path_buf(),
+                version: version.to_string(),
+                error: err.to_string(),
+            }
+        })?;
+
+        let requirements = self.git_requirements.entry(git_repository_key(git)).or_default();
+        requirements.add(version.to_string(), toml.display().to_string());
+        requirements.has_version_requirement = true;
+        self.select_git_tag(git)
+    }
+
+    /// Picks the highest tag of `git` satisfying the requirements of every package depending on it.
+    ///
+    /// Tags pinned in the lockfile or by the packages themselves are preferred over the tags of the
+    /// repository, which are only listed if a package gave a version requirement.
+    fn select_git_tag(&mut self, git: &str) -> Result<String, ManifestError> {
+        let requirements = self.git_requirements.entry(git_repository_key(git)).or_default();
+        let locked_tags = self.lockfile.as_ref().map(|lockfile| lockfile.locked_tags(git));
+        let known_tags =
+            locked_tags.unwrap_or_default().into_iter().chain(requirements.pinned.clone());
+        let tag = match requirements.select(&known_tags.collect::<Vec<_>>()) {
+            Some(tag) => tag,
+            None => match &requirements.selected {
+                Some(selected) if requirements.matches(selected) => selected.clone(),
+                _ if requirements.has_version_requirement => {
+                    let tags = match &self.vendor {
+                        Some(vendor) => vendor.tags(git),
+                        None => {
+                            list_git_tags(git, self.offline).map_err(ManifestError::GitError)?
+                        }
+                    };
+                    requirements.select(&tags).ok_or_else(|| {
+                        ManifestError::UnsatisfiableGitRequirements {
+                            git: git.to_string(),
+                            requirements: requirements.describe(),
+                        }
+                    })?
+                }
+                _ => {
+                    return Err(ManifestError::UnsatisfiableGitRequirements {
+                        git: git.to_string(),
+                        requirements: requirements.describe(),
+                    });
+                }
+            },
+        };
+
+        if requirements.selected.as_ref().is_some_and(|selected| *selected != tag) {
+            self.restart = true;
+        }
+        requirements.selected = Some(tag.clone());
+        Ok(tag)
+    }
+}
+
+/// Identifies a git repository regardless of a trailing slash or `.git` suffix in its URL.
+fn git_repository_key(git: &str) -> String {
+    let git = git.trim_end_matches('/');
+    git.strip_suffix(".git").unwrap_or(git).to_string()
+}
+
+/// Options controlling how the dependencies of a workspace are resolved.
+#[derive(Debug, Default, Clone)]
+pub struct ResolveOptions {
+    pub lockfile_mode: LockfileMode,
+    /// Resolve the `[dev-dependencies]` of workspace members, which are needed to build their tests.
+    pub include_dev_dependencies: bool,
+    /// The compilation profile to select for workspace members, instead of the default one.
+    pub profile: Option<String>,
+    /// Features to enable on the selected workspace members, in addition to their default features,
+    /// either by name or as `member/feature`.
+    pub features: Vec<String>,
+    /// Enable every feature of selected workspace members.
+    pub all_features: bool,
+    /// Don't enable the `default` feature of selected workspace members.
+    pub no_default_features: bool,
+}
+
+/// Returns the directory whose `Nargo.lock` applies to the package or workspace in `root_dir`,
+/// and whether that lockfile belongs to it, as opposed to an enclosing workspace.
+fn lockfile_root(root_dir: &Path) -> (PathBuf, bool) {
+    match workspace::enclosing_workspace(root_dir) {
+        Some(workspace) => (workspace.root_dir, false),
+        None => (root_dir.normalize(), true),
+    }
+}
+
+/// Whether the manifest in `root_dir` asks for git dependencies to be resolved from the vendor directory.
+fn is_vendored(root_dir: &Path) -> bool {
+    match read_toml(&root_dir.join("Nargo.toml")) {
+        Ok(NargoToml { config: Config::Package { package_config }, .. }) => {
+            package_config.package.vendored
+        }
+        Ok(NargoToml { config: Config::Workspace { workspace_config, .. }, .. }) => {
+            workspace_config.vendored
+        }
+        Err(_) => false,
+    }
+}
+
+/// The name of the package with the manifest `toml_path`, if it can be read.
+fn package_name(toml_path: &Path) -> Option<String> {
+    match read_toml(toml_path) {
+        Ok(NargoToml { config: Config::Package { package_config }, .. }) => {
+            package_config.package.name
+        }
+        _ => None,
+    }
+}
+
 fn toml_to_workspace(
     nargo_toml: NargoToml,
     package_selection: PackageSelection,
     assume_default_entry: bool, // assume that the 'default_entry_path' exists, e.g. src/main.nr
+    current_compiler_version: Option<String>,
+    options: &ResolveOptions,
 ) -> Result<Workspace, ManifestError> {
-    let mut resolved = Vec::new();
+    let mut state = ResolutionState::new(&nargo_toml.root_dir, options, true)?;
+    state.compiler_version = current_compiler_version.map(|version| {
+        semver::parse_semver_compatible_version(&version)
+            .expect("The compiler version is not a valid semver version")
+    });
     let _lock = lock_git_deps().expect("Failed to lock git dependencies cache");
+    let workspace =
+        resolve_workspace(nargo_toml, package_selection, assume_default_entry, &mut state)?;
+    state.finish()?;
+    Ok(workspace)
+}
+
+fn resolve_workspace(
+    nargo_toml: NargoToml,
+    package_selection: PackageSelection,
+    assume_default_entry: bool, // assume that the 'default_entry_path' exists, e.g. src/main.nr
+    state: &mut ResolutionState,
+) -> Result<Workspace, ManifestError> {
     let workspace = match nargo_toml.config {
         Config::Package { package_config } => {
-            let member = package_config.resolve_to_package(
-                &nargo_toml.root_dir,
-                &mut resolved,
-                assume_default_entry,
-            )?;
+            let member = state.resolve_until_stable(|state| {
+                let is_member = true;
+                let features = state.member_features.clone();
+                package_config.resolve_to_package(
+                    &nargo_toml.root_dir,
+                    state,
+                    assume_default_entry,
+                    is_member,
+                    &features,
+                )
+            })?;
             match &package_selection {
                 PackageSelection::Selected(selected_name) if selected_name != &member.name => {
                     return Err(ManifestError::MissingSelectedPackage(member.name));
@@ -413,14 +1012,44 @@ fn toml_to_workspace(
                 },
             }
         }
-        Config::Workspace { workspace_config } => {
+        Config::Workspace { workspace_config, profile } => {
+            let member_paths = workspace::expand_members(&nargo_toml.root_dir, &workspace_config);
+            state.workspace = Some(Rc::new(WorkspaceInheritance::new(
+                nargo_toml.root_dir.clone(),
+                &workspace_config,
+                profile,
+            )));
+            let resolved_members = state.resolve_until_stable(|state| {
+                member_paths
+                    .iter()
+                    .map(|member_path| {
+                        let package_root_dir = nargo_toml.root_dir.join(member_path);
+                        let package_toml_path = package_root_dir.join("Nargo.toml");
+                        let is_member = true;
+                        let is_selected = match &package_selection {
+                            PackageSelection::Selected(selected_name) => {
+                                package_name(&package_toml_path) == Some(selected_name.to_string())
+                            }
+                            PackageSelection::DefaultOrAll => workspace_config
+                                .default_member
+                                .as_ref()
+                                .is_none_or(|default_member| default_member == member_path),
+                            PackageSelection::All => true,
+                        };
+                        let features = if is_selected {
+                            state.member_features.clone()
+                        } else {
+                            FeatureRequest::default()
+                        };
+                        resolve_package_from_toml(&package_toml_path, state, is_member, &features)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })?;
+
             let mut members = Vec::new();
             let mut selected_package_index = None;
-            for (index, member_path) in workspace_config.members.into_iter().enumerate() {
-                let package_root_dir = nargo_toml.root_dir.join(&member_path);
-                let package_toml_path = package_root_dir.join("Nargo.toml");
-                let member = resolve_package_from_toml(&package_toml_path, &mut resolved)?;
-
+            let member_paths = member_paths.into_iter().zip(resolved_members);
+            for (index, (member_path, member)) in member_paths.enumerate() {
                 match &package_selection {
                     PackageSelection::Selected(selected_name) => {
                         if &member.name == selected_name {
@@ -467,6 +1096,15 @@ fn toml_to_workspace(
         }
     };
 
+    let unused_feature = state
+        .member_features
+        .features
+        .iter()
+        .find(|feature| !state.used_member_features.contains(*feature));
+    if let Some(feature) = unused_feature {
+        return Err(ManifestError::UnknownSelectedFeature { feature: feature.clone() });
+    }
+
     Ok(workspace)
 }
 
@@ -494,14 +1132,16 @@ pub fn read_toml(toml_path: &Path) -> Result<NargoToml, ManifestError> {
 /// Resolves a Nargo.toml file into a `Package` struct as defined by our `nargo` core.
 fn resolve_package_from_toml(
     toml_path: &Path,
-    processed: &mut Vec<String>,
+    state: &mut ResolutionState,
+    is_member: bool,
+    features: &FeatureRequest,
 ) -> Result<Package, ManifestError> {
     // Checks for cyclic dependencies
     let str_path = toml_path.to_str().expect("ICE - path is empty");
-    if processed.contains(&str_path.to_string()) {
+    if state.processed.contains(&str_path.to_string()) {
         let mut cycle = false;
         let mut message = String::new();
-        for toml in processed {
+        for toml in &state.processed {
             cycle = cycle || toml == str_path;
             if cycle {
                 message += &format!("{toml} referencing ");
@@ -512,7 +1152,7 @@ fn resolve_package_from_toml(
     }
     // Adds the package to the set of resolved packages
     if let Some(str) = toml_path.to_str() {
-        processed.push(str.to_string());
+        state.processed.push(str.to_string());
     }
 
     let nargo_toml = read_toml(toml_path)?;
@@ -520,15 +1160,24 @@ fn resolve_package_from_toml(
     let result = match nargo_toml.config {
         Config::Package { package_config } => {
             let assume_default_entry = false;
-            package_config.resolve_to_package(&nargo_toml.root_dir, processed, assume_default_entry)
+            package_config.resolve_to_package(
+                &nargo_toml.root_dir,
+                state,
+                assume_default_entry,
+                is_member,
+                features,
+            )
         }
         Config::Workspace { .. } => {
             Err(ManifestError::UnexpectedWorkspace(toml_path.to_path_buf()))
         }
     };
-    let pos =
-        processed.iter().position(|toml| toml == str_path).expect("added package must be here");
-    processed.remove(pos);
+    let pos = state
+        .processed
+        .iter()
+        .position(|toml| toml == str_path)
+        .expect("added package must be here");
+    state.processed.remove(pos);
     result
 }
 
@@ -546,9 +1195,52 @@ pub fn resolve_workspace_from_toml(
     toml_path: &Path,
     package_selection: PackageSelection,
     current_compiler_version: Option<String>,
+    options: &ResolveOptions,
 ) -> Result<Workspace, ManifestError> {
     let nargo_toml = read_toml(toml_path)?;
-    resolve_workspace_from_fixed_toml(nargo_toml, package_selection, current_compiler_version)
+    resolve_workspace_from_fixed_toml(
+        nargo_toml,
+        package_selection,
+        current_compiler_version,
+        options,
+    )
+}
+
+/// Copies every git dependency of the workspace in `root_dir`, including dev-dependencies, into the
+/// vendor directory of the workspace, so it can later be resolved with `vendored = true`.
+///
+/// The dependencies are fetched as usual, even if the workspace is already vendored.
+pub fn vendor_workspace(
+    root_dir: &Path,
+    options: &ResolveOptions,
+) -> Result<Vec<VendoredPackage>, ManifestError> {
+    let nargo_toml = read_toml(&get_package_manifest(root_dir)?)?;
+    let vendor_root = lockfile_root(&nargo_toml.root_dir).0;
+    let options = ResolveOptions { include_dev_dependencies: true, ..options.clone() };
+    let mut state = ResolutionState::new(&nargo_toml.root_dir, &options, false)?;
+    let _lock = lock_git_deps().expect("Failed to lock git dependencies cache");
+    let assume_default_entry = true;
+    resolve_workspace(nargo_toml, PackageSelection::All, assume_default_entry, &mut state)?;
+    let git_checkouts = std::mem::take(&mut state.git_checkouts);
+    state.finish()?;
+    vendor::vendor_git_checkouts(&vendor_root, &git_checkouts)
+}
+
+/// Resolves the workspace in `root_dir` again, pinning the branches its git dependencies follow to their
+/// latest commit in `Nargo.lock`.
+///
+/// The branches are fetched from the network, even if the workspace is vendored.
+pub fn update_workspace(root_dir: &Path, options: &ResolveOptions) -> Result<(), ManifestError> {
+    let nargo_toml = read_toml(&get_package_manifest(root_dir)?)?;
+    let options = ResolveOptions { include_dev_dependencies: true, ..options.clone() };
+    let mut state = ResolutionState::new(&nargo_toml.root_dir, &options, false)?;
+    if let Some(lockfile) = &mut state.lockfile {
+        lockfile.update_branches();
+    }
+    let _lock = lock_git_deps().expect("Failed to lock git dependencies cache");
+    let assume_default_entry = true;
+    resolve_workspace(nargo_toml, PackageSelection::All, assume_default_entry, &mut state)?;
+    state.finish()
 }
 
 /// Resolves a Nargo.toml _ into a `Workspace` struct as defined by our `nargo` core.
@@ -558,9 +1250,16 @@ pub fn resolve_workspace_from_fixed_toml(
     nargo_toml: NargoToml,
     package_selection: PackageSelection,
     current_compiler_version: Option<String>,
+    options: &ResolveOptions,
 ) -> Result<Workspace, ManifestError> {
     let assume_default_entry = true;
-    let workspace = toml_to_workspace(nargo_toml, package_selection, assume_default_entry)?;
+    let workspace = toml_to_workspace(
+        nargo_toml,
+        package_selection,
+        assume_default_entry,
+        current_compiler_version.clone(),
+        options,
+    )?;
     if let Some(current_compiler_version) = current_compiler_version {
         semver::semver_check_workspace(&workspace, current_compiler_version)?;
     }
@@ -576,7 +1275,13 @@ mod tests {
 
     use test_case::test_matrix;
 
-    use crate::{Config, ManifestError, find_root};
+    use nargo::workspace::Workspace;
+    use noirc_frontend::graph::CrateName;
+
+    use crate::{
+        Config, DependencyConfig, ManifestError, PackageSelection, ResolutionState, ResolveOptions,
+        SemverError, find_root, resolve_workspace_from_toml,
+    };
 
     #[test]
     fn parse_standard_toml() {
@@ -610,6 +1315,73 @@ mod tests {
         assert!(Config::try_from(src).is_ok());
     }
 
+    #[test]
+    fn parse_git_version_dependency_toml() {
+        let src = r#"
+        [package]
+        name = "test"
+        type = "bin"
+
+        [dependencies]
+        bignum = { version = "^0.4", git = "https://github.com/noir-lang/noir-bignum" }
+    "#;
+
+        let Ok(Config::Package { package_config }) = Config::try_from(src) else {
+            panic!("expected a package config");
+        };
+        let dependency = &package_config.dependencies["bignum"];
+        assert!(matches!(
+            dependency,
+            DependencyConfig::Github { tag: None, version: Some(version), .. } if version == "^0.4"
+        ));
+    }
+
+    #[test]
+    fn parse_git_branch_and_rev_dependency_toml() {
+        let src = r#"
+        [package]
+        name = "test"
+        type = "bin"
+
+        [dependencies]
+        utils = { branch = "main", git = "git@git.example.com:libs/utils.git" }
+        math = { rev = "4f2a9c1", git = "file:///srv/git/math" }
+    "#;
+
+        let Ok(Config::Package { package_config }) = Config::try_from(src) else {
+            panic!("expected a package config");
+        };
+        assert!(matches!(
+            &package_config.dependencies["utils"],
+            DependencyConfig::Github { branch: Some(branch), tag: None, .. } if branch == "main"
+        ));
+        assert!(matches!(
+            &package_config.dependencies["math"],
+            DependencyConfig::Github { rev: Some(rev), tag: None, .. } if rev == "4f2a9c1"
+        ));
+    }
+
+    #[test]
+    fn parse_dev_dependencies_toml() {
+        let src = r#"
+        [package]
+        name = "test"
+        type = "lib"
+
+        [dev-dependencies]
+        test_utils = { path = "../test_utils" }
+    "#;
+
+        let Ok(Config::Package { package_config }) = Config::try_from(src) else {
+            panic!("expected a package config");
+        };
+        assert!(package_config.dependencies.is_empty());
+        assert!(matches!(
+            &package_config.dev_dependencies["test_utils"],
+            DependencyConfig::Path { path, .. } if path == "../test_utils"
+        ));
+    }
+
     #[test]
     fn parse_workspace_toml() {
         let src = r#"
@@ -648,6 +1420,118 @@ mod tests {
         assert!(Config::try_from(src).is_ok());
     }
 
+    #[test]
+    fn parse_features_toml() {
+        let src = r#"
+    [package]
+    name = "test"
+    type = "lib"
+
+    [features]
+    default = ["poseidon"]
+    poseidon = ["hash/poseidon"]
+
+    [dependencies]
+    hash = { path = "../hash", features = ["fast"], default-features = false }
+    ecdsa = { path = "../ecdsa" }
+    "#;
+
+        let Ok(Config::Package { package_config }) = Config::try_from(src) else {
+            panic!("should parse as a package");
+        };
+        assert_eq!(package_config.features["default"], vec!["poseidon".to_string()]);
+        assert!(matches!(
+            &package_config.dependencies["hash"],
+            DependencyConfig::Path { features, default_features: false, .. } if features == &["fast"]
+        ));
+        assert!(matches!(
+            &package_config.dependencies["ecdsa"],
+            DependencyConfig::Path { default_features: true, .. }
+        ));
+    }
+
+    #[test]
+    fn parse_profile_toml() {
+        let src = r#"
+    [package]
+    name = "test"
+    type = "bin"
+
+    [profile.release]
+    inliner_aggressiveness = 0
+
+    [profile.ci]
+    inherits = "release"
+    force_brillig = true
+    "#;
+
+        let Ok(Config::Package { package_config }) = Config::try_from(src) else {
+            panic!("should parse as a package");
+        };
+        assert_eq!(package_config.profile["ci"].inherits.as_deref(), Some("release"));
+        assert_eq!(package_config.profile["release"].inliner_aggressiveness, Some(0));
+
+        let src = r#"
+    [workspace]
+    members = ["a"]
+
+    [profile.release]
+    skip_underconstrained_check = true
+    "#;
+        let Ok(Config::Workspace { profile, .. }) = Config::try_from(src) else {
+            panic!("should parse as a workspace");
+        };
+        assert_eq!(profile["release"].skip_underconstrained_check, Some(true));
+
+        let src = r#"
+    [package]
+    name = "test"
+    type = "bin"
+
+    [profile.release]
+    unknown_setting = true
+    "#;
+        assert!(Config::try_from(src).is_err());
+    }
+
+    #[test]
+    fn parse_workspace_inheritance_toml() {
+        let src = r#"
+    [workspace]
+    members = ["circuits/*"]
+    exclude = ["circuits/old"]
+
+    [workspace.package]
+    version = "0.1.0"
+    compiler_version = ">=0.30.0"
+
+    [workspace.dependencies]
+    hash = { path = "libs/hash", features = ["poseidon"] }
+    "#;
+        let Ok(Config::Workspace { workspace_config, .. }) = Config::try_from(src) else {
+            panic!("should parse as a workspace");
+        };
+        assert_eq!(workspace_config.exclude, vec![PathBuf::from("circuits/old")]);
+        assert_eq!(workspace_config.package.version.as_deref(), Some("0.1.0"));
+        assert!(matches!(workspace_config.dependencies["hash"], DependencyConfig::Path { .. }));
+
+        let src = r#"
+    [package]
+    name = "test"
+    type = "bin"
+
+    [dependencies]
+    hash = { workspace = true, features = ["fast"] }
+    "#;
+        let Ok(Config::Package { package_config }) = Config::try_from(src) else {
+            panic!("should parse as a package");
+        };
+        assert!(matches!(
+            &package_config.dependencies["hash"],
+            DependencyConfig::Workspace { workspace: true, features } if features == &["fast"]
+        ));
+    }
+
     /// Test that `find_root` handles all kinds of prefixes.
     /// (It dispatches based on `workspace` to methods which handle paths differently).
     #[test_matrix(
@@ -765,4 +1649,126 @@ mod tests {
         assert_ok("project/examples/baz/src", true, "project/examples/baz");
         assert_ok("project/examples/baz/src", false, "project/examples/baz");
     }
+
+    #[test]
+    fn unifies_compatible_exact_git_tags() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut state =
+            ResolutionState::new(tmp.path(), &ResolveOptions::default(), false).unwrap();
+        let git = "https://github.com/noir-lang/noir-bignum";
+        let a = tmp.path().join("a").join("Nargo.toml");
+        let b = tmp.path().join("b").join("Nargo.toml");
+        let c = tmp.path().join("c").join("Nargo.toml");
+
+        assert_eq!(state.select_pinned_git_tag(git, "v0.4.1", &a).unwrap(), "v0.4.1");
+        assert!(!state.restart);
+
+        // The higher compatible tag is used by both packages once it is known
+        let tag = state.select_pinned_git_tag(&format!("{git}.git"), "v0.4.2", &b).unwrap();
+        assert_eq!(tag, "v0.4.2");
+        assert!(state.restart);
+        assert_eq!(
+            state.select_pinned_git_tag(&format!("{git}/"), "v0.4.1", &a).unwrap(),
+            "v0.4.2"
+        );
+
+        // Tags which are not versions are used as they are
+        assert_eq!(state.select_pinned_git_tag(git, "nightly", &c).unwrap(), "nightly");
+
+        let result = state.select_pinned_git_tag(git, "v0.5.0", &c);
+        let Err(ManifestError::UnsatisfiableGitRequirements { requirements, .. }) = result else {
+            panic!("incompatible tags of the same repository should conflict");
+        };
+        assert!(requirements.contains(&format!("^0.4.1 (required by {})", a.display())));
+        assert!(requirements.contains(&format!("^0.4.2 (required by {})", b.display())));
+        assert!(requirements.contains(&format!("^0.5.0 (required by {})", c.display())));
+    }
+
+    #[test]
+    fn applies_command_line_features_to_selected_members() {
+        let tmp = tempfile::tempdir().unwrap();
+        for (name, features) in [("a", "fast = []\nslow = []\n"), ("b", "fast = []\n")] {
+            let dir = tmp.path().join(name);
+            std::fs::create_dir_all(dir.join("src")).unwrap();
+            std::fs::write(dir.join("src").join("lib.nr"), "").unwrap();
+            let toml =
+                format!("[package]\nname = \"{name}\"\ntype = \"lib\"\n[features]\n{features}");
+            std::fs::write(dir.join("Nargo.toml"), toml).unwrap();
+        }
+        let toml_path = tmp.path().join("Nargo.toml");
+        std::fs::write(&toml_path, "[workspace]\nmembers = [\"a\", \"b\"]\n").unwrap();
+
+        let resolve = |package_selection: PackageSelection, features: &[&str]| {
+            let features = features.iter().map(|feature| feature.to_string()).collect();
+            let options = ResolveOptions { features, ..Default::default() };
+            resolve_workspace_from_toml(&toml_path, package_selection, None, &options)
+        };
+        let member_features = |workspace: &Workspace| {
+            workspace
+                .members
+                .iter()
+                .map(|member| member.features.iter().cloned().collect::<Vec<_>>().join(","))
+                .collect::<Vec<_>>()
+        };
+
+        let workspace = resolve(PackageSelection::All, &["a/slow", "fast"]).unwrap();
+        assert_eq!(member_features(&workspace), vec!["fast,slow", "fast"]);
+
+        let a = CrateName::from_str("a").unwrap();
+        let workspace = resolve(PackageSelection::Selected(a), &["fast"]).unwrap();
+        assert_eq!(member_features(&workspace), vec!["fast", ""]);
+
+        let b = CrateName::from_str("b").unwrap();
+        let result = resolve(PackageSelection::Selected(b), &["slow"]);
+        assert!(matches!(
+            result,
+            Err(ManifestError::UnknownSelectedFeature { feature }) if feature == "slow"
+        ));
+    }
+
+    #[test]
+    fn checks_compiler_version_of_dependencies() {
+        let tmp = tempfile::tempdir().unwrap();
+        let write_package = |name: &str, extra: &str| {
+            let dir = tmp.path().join(name);
+            std::fs::create_dir_all(dir.join("src")).unwrap();
+            std::fs::write(dir.join("src").join("lib.nr"), "").unwrap();
+            let toml = format!("[package]\nname = \"{name}\"\ntype = \"lib\"\n{extra}");
+            std::fs::write(dir.join("Nargo.toml"), toml).unwrap();
+            dir.join("Nargo.toml")
+        };
+        let resolve = |toml_path: &Path| {
+            resolve_workspace_from_toml(
+                toml_path,
+                PackageSelection::All,
+                Some("0.1.0".to_string()),
+                &ResolveOptions::default(),
+            )
+        };
+
+        write_package("bad_dependency", "compiler_version = \"0.2.0\"\n");
+        let toml_path = write_package(
+            "test",
+            "[dependencies]\nbad_dependency = { path = \"../bad_dependency\" }\n",
+        );
+        let Err(ManifestError::SemverError(error)) = resolve(&toml_path) else {
+            panic!("the compiler version of the dependency should be checked");
+        };
+        assert_eq!(
+            error,
+            SemverError::IncompatibleVersion {
+                package_name: CrateName::from_str("bad_dependency").unwrap(),
+                required_compiler_version: "0.2.0".to_string(),
+                compiler_version_found: "0.1.0".to_string(),
+            }
+        );
+
+        let toml_path =
+            write_package("unstable", "compiler_unstable_features = [\"enums\", \"teleport\"]\n");
+        let result = resolve(&toml_path);
+        assert!(matches!(
+            result,
+            Err(ManifestError::UnknownUnstableFeatures { features, .. }) if features == "`teleport`"
+        ));
+    }
 }
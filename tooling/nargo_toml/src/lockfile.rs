@@ -0,0 +1,394 @@
+//! `Nargo.lock` pins every git dependency of a workspace to the commit its tag pointed to
+//! when it was first resolved, along with a checksum of the checked out sources.
+//!
+//! Dependencies specified by a version requirement keep using the locked tag as long as it
+//! satisfies the requirements, rather than moving to the newest matching tag. Likewise, branches
+//! keep using their locked commit until they are updated with `nargo update`.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    ManifestError,
+    git::{GitReference, git_head_commit},
+    git_repository_key,
+};
+
+/// Name of the lockfile written next to the workspace `Nargo.toml`.
+pub const LOCKFILE_NAME: &str = "Nargo.lock";
+
+const LOCKFILE_VERSION: u32 = 1;
+
+const LOCKFILE_HEADER: &str =
+    "# This file is automatically generated by Nargo.\n# It is not intended for manual editing.\n";
+
+/// How the resolver treats `Nargo.lock`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LockfileMode {
+    /// Add entries for new dependencies and remove the ones no longer used.
+    #[default]
+    Update,
+    /// Fail if `Nargo.lock` is missing an entry or would otherwise need to change.
+    Locked,
+    /// Same as `Locked`, but also fail if a dependency is not already in the local cache.
+    Frozen,
+}
+
+impl LockfileMode {
+    /// Whether dependencies may be fetched from the network.
+    pub fn allows_network(self) -> bool {
+        self != LockfileMode::Frozen
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub version: u32,
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockedPackage>,
+}
+
+/// A git dependency pinned to a specific commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub git: String,
+    /// The `tag`, `branch` or `rev` the dependency refers to.
+    #[serde(flatten)]
+    pub reference: GitReference,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory: Option<String>,
+    pub commit: String,
+    pub checksum: String,
+}
+
+impl LockedPackage {
+    fn key(&self) -> LockKey {
+        (self.git.clone(), self.reference.clone(), self.directory.clone())
+    }
+}
+
+/// Git dependencies are identified by their URL, revision and sub-directory.
+type LockKey = (String, GitReference, Option<String>);
+
+impl Lockfile {
+    /// Reads the lockfile at `path`, returning `None` if it doesn't exist.
+    pub fn read(path: &Path) -> Result<Option<Lockfile>, ManifestError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|_| ManifestError::ReadFailed(path.to_path_buf()))?;
+        let lockfile = toml::from_str(&contents).map_err(|err| {
+            ManifestError::MalformedLockfile { path: path.to_path_buf(), error: err.to_string() }
+        })?;
+        Ok(Some(lockfile))
+    }
+
+    fn write(&self, path: &Path) -> Result<(), ManifestError> {
+        let contents = toml::to_string(self).expect("lockfile should always be serializable");
+        std::fs::write(path, format!("{LOCKFILE_HEADER}{contents}"))
+            .map_err(|_| ManifestError::LockfileWriteFailed(path.to_path_buf()))
+    }
+}
+
+/// Checks resolved git dependencies against `Nargo.lock` and collects the new pins.
+pub(crate) struct LockfileResolver {
+    path: PathBuf,
+    mode: LockfileMode,
+    /// Whether all the dependencies pinned by the lockfile are resolved. If it belongs to an enclosing
+    /// workspace, or dev-dependencies are skipped, we can't tell which entries are unused.
+    complete: bool,
+    previous: Option<Lockfile>,
+    resolved: BTreeMap<LockKey, LockedPackage>,
+    /// Whether branches are resolved to their latest commit instead of the one they are pinned to.
+    update_branches: bool,
+}
+
+impl LockfileResolver {
+    pub(crate) fn new(
+        root_dir: &Path,
+        mode: LockfileMode,
+        complete: bool,
+    ) -> Result<Self, ManifestError> {
+        let path = root_dir.join(LOCKFILE_NAME);
+        let previous = Lockfile::read(&path)?;
+        let resolved = BTreeMap::new();
+        Ok(Self { path, mode, complete, previous, resolved, update_branches: false })
+    }
+
+    fn locked(&self, key: &LockKey) -> Option<&LockedPackage> {
+        if self.update_branches && matches!(key.1, GitReference::Branch(_)) {
+            return None;
+        }
+        self.previous.as_ref()?.packages.iter().find(|package| &package.key() == key)
+    }
+
+    /// Ignores the commits branches are pinned to, so that they are pinned to their latest commit instead.
+    pub(crate) fn update_branches(&mut self) {
+        self.update_branches = true;
+    }
+
+    /// The commit a git dependency is pinned to, if any.
+    pub(crate) fn locked_commit(
+        &self,
+        git: &str,
+        reference: &GitReference,
+        directory: Option<&str>,
+    ) -> Option<String> {
+        let key = (git.to_string(), reference.clone(), directory.map(String::from));
+        self.locked(&key).map(|package| package.commit.clone())
+    }
+
+    /// Forgets the dependencies checked so far, before resolving the workspace again.
+    pub(crate) fn reset(&mut self) {
+        self.resolved.clear();
+    }
+
+    /// Tags of the git repository at `git` pinned in the lockfile.
+    pub(crate) fn locked_tags(&self, git: &str) -> Vec<String> {
+        let Some(previous) = &self.previous else {
+            return Vec::new();
+        };
+        previous
+            .packages
+            .iter()
+            .filter(|package| git_repository_key(&package.git) == git_repository_key(git))
+            .filter_map(|package| match &package.reference {
+                GitReference::Tag(tag) => Some(tag.clone()),
+                GitReference::Branch(_) | GitReference::Rev(_) => None,
+            })
+            .collect()
+    }
+
+    /// Checks the checkout of a git dependency in `checkout_dir` against its pinned commit and checksum,
+    /// or pins it if it hasn't been seen before.
+    ///
+    /// The `commit` is read from the git metadata of the checkout unless it is already known,
+    /// e.g. because the dependency was vendored.
+    pub(crate) fn check_git_dependency(
+        &mut self,
+        name: &str,
+        git: &str,
+        reference: &GitReference,
+        directory: Option<&str>,
+        checkout_dir: &Path,
+        commit: Option<String>,
+    ) -> Result<(), ManifestError> {
+        let key = (git.to_string(), reference.clone(), directory.map(String::from));
+        if self.resolved.contains_key(&key) {
+            return Ok(());
+        }
+
+        let commit = match commit {
+            Some(commit) => commit,
+            None => git_head_commit(checkout_dir).map_err(ManifestError::GitError)?,
+        };
+        let checksum = checksum_directory(checkout_dir).map_err(ManifestError::GitError)?;
+
+        match self.locked(&key) {
+            Some(locked) if locked.commit != commit => {
+                return Err(ManifestError::LockfileCommitMismatch {
+                    git: git.to_string(),
+                    reference: reference.to_string(),
+                    expected: locked.commit.clone(),
+                    found: commit,
+                });
+            }
+            Some(locked) if locked.checksum != checksum => {
+                return Err(ManifestError::LockfileChecksumMismatch {
+                    git: git.to_string(),
+                    reference: reference.to_string(),
+                    checkout: checkout_dir.to_path_buf(),
+                });
+            }
+            Some(_) => (),
+            None if self.mode != LockfileMode::Update => {
+                return Err(ManifestError::LockfileOutdated {
+                    path: self.path.clone(),
+                    reason: format!("`{git}` at {reference} is not pinned"),
+                });
+            }
+            None => (),
+        }
+
+        let package = LockedPackage {
+            name: name.to_string(),
+            git: git.to_string(),
+            reference: reference.clone(),
+            directory: directory.map(String::from),
+            commit,
+            checksum,
+        };
+        self.resolved.insert(key, package);
+        Ok(())
+    }
+
+    /// Writes the updated lockfile if anything changed, or fails if it isn't allowed to.
+    pub(crate) fn finish(self) -> Result<(), ManifestError> {
+        let mut packages: Vec<LockedPackage> = self.resolved.into_values().collect();
+
+        if !self.complete {
+            // Keep the entries of the dependencies we didn't resolve.
+            if let Some(previous) = &self.previous {
+                for package in &previous.packages {
+                    if !packages.iter().any(|resolved| resolved.key() == package.key()) {
+                        packages.push(package.clone());
+                    }
+                }
+            }
+            packages.sort_by_key(LockedPackage::key);
+        }
+
+        let lockfile = Lockfile { version: LOCKFILE_VERSION, packages };
+
+        let unchanged = match &self.previous {
+            Some(previous) => previous == &lockfile,
+            // Don't create a lockfile for packages without any git dependencies.
+            None => lockfile.packages.is_empty(),
+        };
+        if unchanged {
+            return Ok(());
+        }
+
+        if self.mode != LockfileMode::Update {
+            let reason = if self.previous.is_none() {
+                "the file does not exist".to_string()
+            } else {
+                "some entries are no longer used".to_string()
+            };
+            return Err(ManifestError::LockfileOutdated { path: self.path, reason });
+        }
+
+        lockfile.write(&self.path)
+    }
+}
+
+/// Computes a SHA-256 over the relative paths and contents of every file under `dir`,
+/// skipping the `.git` directory, in a platform independent order.
+pub(crate) fn checksum_directory(dir: &Path) -> Result<String, String> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files).map_err(|err| format!("{}: {err}", dir.display()))?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for (relative_path, path) in files {
+        let contents = std::fs::read(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+        hasher.update(relative_path.as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(contents);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<(String, PathBuf)>,
+) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != ".git" {
+                collect_files(root, &path, files)?;
+            }
+        } else {
+            let relative_path = path.strip_prefix(root).expect("path should be under root");
+            let relative_path = relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((relative_path, path));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LockedPackage, Lockfile, LockfileMode, LockfileResolver, checksum_directory};
+    use crate::git::GitReference;
+
+    #[test]
+    fn checksum_ignores_git_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+        std::fs::write(tmp.path().join("src").join("lib.nr"), "fn foo() {}").unwrap();
+        let before = checksum_directory(tmp.path()).unwrap();
+
+        std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
+        std::fs::write(tmp.path().join(".git").join("HEAD"), "ref: refs/heads/main").unwrap();
+        assert_eq!(checksum_directory(tmp.path()).unwrap(), before);
+
+        std::fs::write(tmp.path().join("src").join("lib.nr"), "fn bar() {}").unwrap();
+        assert_ne!(checksum_directory(tmp.path()).unwrap(), before);
+    }
+
+    #[test]
+    fn lockfile_round_trips_git_references() {
+        let package = |reference| LockedPackage {
+            name: "utils".to_string(),
+            git: "git@git.example.com:libs/utils.git".to_string(),
+            reference,
+            directory: None,
+            commit: "4f2a9c1".to_string(),
+            checksum: "abc".to_string(),
+        };
+        let lockfile = Lockfile {
+            version: 1,
+            packages: vec![
+                package(GitReference::Tag("v0.1.0".to_string())),
+                package(GitReference::Branch("main".to_string())),
+                package(GitReference::Rev("4f2a9c1".to_string())),
+            ],
+        };
+
+        let contents = toml::to_string(&lockfile).unwrap();
+        assert!(contents.contains("tag = \"v0.1.0\""));
+        assert!(contents.contains("branch = \"main\""));
+        assert!(contents.contains("rev = \"4f2a9c1\""));
+        assert_eq!(toml::from_str::<Lockfile>(&contents).unwrap(), lockfile);
+    }
+
+    #[test]
+    fn updating_branches_ignores_their_pinned_commits() {
+        let tmp = tempfile::tempdir().unwrap();
+        let git = "https://git.example.com/libs/utils";
+        let package = |reference| LockedPackage {
+            name: "utils".to_string(),
+            git: git.to_string(),
+            reference,
+            directory: None,
+            commit: "4f2a9c1".to_string(),
+            checksum: "abc".to_string(),
+        };
+        let tag = GitReference::Tag("v0.1.0".to_string());
+        let branch = GitReference::Branch("main".to_string());
+        let lockfile =
+            Lockfile { version: 1, packages: vec![package(tag.clone()), package(branch.clone())] };
+        lockfile.write(&tmp.path().join(super::LOCKFILE_NAME)).unwrap();
+
+        let mut resolver = LockfileResolver::new(tmp.path(), LockfileMode::Update, true).unwrap();
+        assert_eq!(resolver.locked_commit(git, &branch, None).as_deref(), Some("4f2a9c1"));
+
+        resolver.update_branches();
+        assert_eq!(resolver.locked_commit(git, &branch, None), None);
+        assert_eq!(resolver.locked_commit(git, &tag, None).as_deref(), Some("4f2a9c1"));
+    }
+
+    #[test]
+    fn does_not_create_lockfile_without_git_dependencies() {
+        let tmp = tempfile::tempdir().unwrap();
+        let resolver = LockfileResolver::new(tmp.path(), LockfileMode::Locked, true).unwrap();
+        resolver.finish().expect("nothing to lock");
+        assert!(!tmp.path().join(super::LOCKFILE_NAME).exists());
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use nargo::{
+    package::{Dependency, GitReference, Package},
+    workspace::Workspace,
+};
+use semver::{Version, VersionReq};
+use serde::Serialize;
+
+use crate::{ManifestError, git::list_git_tags, semver::parse_tag_version};
+
+/// A git dependency pinned to a tag, along with the newer tags of its repository.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutdatedDependency {
+    /// Name of the package in the repository.
+    pub name: String,
+    pub git: String,
+    /// The tag the dependency is pinned to.
+    pub tag: String,
+    /// The highest tag which is semver compatible with `tag`, if it is newer.
+    pub compatible: Option<String>,
+    /// The highest tag of the repository, if it is newer.
+    pub latest: Option<String>,
+}
+
+/// Compares the tags git dependencies of the workspace are pinned to with the tags of their repositories,
+/// returning the dependencies for which a newer tag exists.
+///
+/// Tags which aren't semver versions, as well as dependencies on branches and revisions, are ignored.
+/// When `offline` is set, only the tags already in the local cache are considered.
+pub fn find_outdated_dependencies(
+    workspace: &Workspace,
+    offline: bool,
+) -> Result<Vec<OutdatedDependency>, ManifestError> {
+    let mut pinned = BTreeSet::new();
+    for package in workspace {
+        collect_pinned_tags(package, &mut pinned);
+    }
+
+    let mut repository_tags: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut outdated = Vec::new();
+    for (git, tag, name) in pinned {
+        if !repository_tags.contains_key(&git) {
+            let tags = list_git_tags(&git, offline).map_err(ManifestError::GitError)?;
+            repository_tags.insert(git.clone(), tags);
+        }
+        let (compatible, latest) = newer_tags(&tag, &repository_tags[&git]);
+        if latest.is_some() {
+            outdated.push(OutdatedDependency { name, git, tag, compatible, latest });
+        }
+    }
+    Ok(outdated)
+}
+
+/// Returns the highest of `tags` which is semver compatible with `tag`, and the highest overall,
+/// if they are newer than `tag`. Pre-releases are left out.
+fn newer_tags(tag: &str, tags: &[String]) -> (Option<String>, Option<String>) {
+    let Some(version) = parse_tag_version(tag) else {
+        return (None, None);
+    };
+    let requirement =
+        VersionReq::parse(&format!("^{version}")).expect("a version is a valid requirement");
+    let newer: Vec<(Version, &String)> = tags
+        .iter()
+        .filter_map(|tag| Some((parse_tag_version(tag)?, tag)))
+        .filter(|(candidate, _)| candidate.pre.is_empty() && candidate > &version)
+        .collect();
+    let highest = |compatible_only: bool| {
+        newer
+            .iter()
+            .filter(|(candidate, _)| !compatible_only || requirement.matches(candidate))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, tag)| tag.to_string())
+    };
+    (highest(true), highest(false))
+}
+
+/// Collects the repository, tag and package name of every git dependency pinned to a tag.
+fn collect_pinned_tags(package: &Package, pinned: &mut BTreeSet<(String, String, String)>) {
+    let dev_dependencies = package.dev_dependencies.values().flatten();
+    for dependency in package.dependencies.values().chain(dev_dependencies) {
+        match dependency {
+            Dependency::Remote { package, git, reference } => {
+                if let GitReference::Tag(tag) = reference {
+                    pinned.insert((git.clone(), tag.clone(), package.name.to_string()));
+                }
+                collect_pinned_tags(package, pinned);
+            }
+            Dependency::Local { package } => collect_pinned_tags(package, pinned),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::newer_tags;
+
+    #[test]
+    fn finds_newer_tags() {
+        let tags: Vec<String> =
+            ["v0.3.9", "v0.4.1", "v0.4.2", "v0.5.0-beta.1", "v1.0.0", "nightly"]
+                .into_iter()
+                .map(String::from)
+                .collect();
+
+        let (compatible, latest) = newer_tags("v0.4.1", &tags);
+        assert_eq!(compatible.as_deref(), Some("v0.4.2"));
+        assert_eq!(latest.as_deref(), Some("v1.0.0"));
+
+        let (compatible, latest) = newer_tags("v1.0.0", &tags);
+        assert_eq!((compatible, latest), (None, None));
+
+        assert_eq!(newer_tags("nightly", &tags), (None, None));
+    }
+}
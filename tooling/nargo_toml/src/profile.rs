@@ -0,0 +1,180 @@
+use std::{collections::BTreeMap, path::Path};
+
+use noirc_driver::{BUILTIN_PROFILE_NAMES, CompileProfile};
+use serde::Deserialize;
+
+use crate::ManifestError;
+
+/// A `[profile.<name>]` table, in either a package or a workspace `Nargo.toml`.
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileConfig {
+    /// Name of the profile to take the settings which aren't set here from.
+    pub inherits: Option<String>,
+    pub expression_width: Option<usize>,
+    pub bounded_codegen: Option<bool>,
+    pub inliner_aggressiveness: Option<i64>,
+    pub max_bytecode_increase_percent: Option<i32>,
+    pub skip_underconstrained_check: Option<bool>,
+    pub skip_brillig_constraints_check: Option<bool>,
+    pub enable_brillig_constraints_check_lookback: Option<bool>,
+    pub enable_brillig_debug_assertions: Option<bool>,
+    pub count_array_copies: Option<bool>,
+    pub force_brillig: Option<bool>,
+    pub pedantic_solving: Option<bool>,
+}
+
+impl ProfileConfig {
+    fn to_profile(&self, name: &str) -> CompileProfile {
+        CompileProfile {
+            name: name.to_string(),
+            expression_width: self.expression_width,
+            bounded_codegen: self.bounded_codegen,
+            inliner_aggressiveness: self.inliner_aggressiveness,
+            max_bytecode_increase_percent: self.max_bytecode_increase_percent,
+            skip_underconstrained_check: self.skip_underconstrained_check,
+            skip_brillig_constraints_check: self.skip_brillig_constraints_check,
+            enable_brillig_constraints_check_lookback: self
+                .enable_brillig_constraints_check_lookback,
+            enable_brillig_debug_assertions: self.enable_brillig_debug_assertions,
+            count_array_copies: self.count_array_copies,
+            force_brillig: self.force_brillig,
+            pedantic_solving: self.pedantic_solving,
+        }
+    }
+}
+
+/// Resolves the profile called `name` for the package with the manifest `toml`.
+///
+/// Settings of the package take precedence over the ones of the workspace for the same profile,
+/// which in turn take precedence over the settings of the profile it inherits from.
+pub(crate) fn resolve_profile(
+    name: &str,
+    package_profiles: &BTreeMap<String, ProfileConfig>,
+    workspace_profiles: &BTreeMap<String, ProfileConfig>,
+    toml: &Path,
+) -> Result<CompileProfile, ManifestError> {
+    let mut chain = vec![name.to_string()];
+    let mut profile: Option<CompileProfile> = None;
+    let mut current = name.to_string();
+    loop {
+        let Some((settings, inherits)) =
+            lookup_profile(&current, package_profiles, workspace_profiles)
+        else {
+            return Err(ManifestError::UnknownProfile { toml: toml.to_path_buf(), name: current });
+        };
+        profile = Some(match profile {
+            Some(profile) => profile.inherit(&settings),
+            None => settings,
+        });
+
+        let Some(parent) = inherits else {
+            break;
+        };
+        let is_cycle = chain.contains(&parent);
+        chain.push(parent.clone());
+        if is_cycle {
+            return Err(ManifestError::CyclicProfileInheritance {
+                toml: toml.to_path_buf(),
+                cycle: chain.join(" -> "),
+            });
+        }
+        current = parent;
+    }
+
+    let profile = profile.expect("at least one profile was looked up");
+    profile.validate().map_err(|error| ManifestError::InvalidProfile {
+        toml: toml.to_path_buf(),
+        name: name.to_string(),
+        error,
+    })?;
+    Ok(profile)
+}
+
+/// Merges the package and workspace settings of a profile, returning them with the profile it inherits from.
+fn lookup_profile(
+    name: &str,
+    package_profiles: &BTreeMap<String, ProfileConfig>,
+    workspace_profiles: &BTreeMap<String, ProfileConfig>,
+) -> Option<(CompileProfile, Option<String>)> {
+    let package = package_profiles.get(name);
+    let workspace = workspace_profiles.get(name);
+    if package.is_none() && workspace.is_none() && !BUILTIN_PROFILE_NAMES.contains(&name) {
+        return None;
+    }
+
+    let default = ProfileConfig::default();
+    let package = package.unwrap_or(&default);
+    let workspace = workspace.unwrap_or(&default);
+    let settings = package.to_profile(name).inherit(&workspace.to_profile(name));
+    let inherits = package.inherits.clone().or_else(|| workspace.inherits.clone());
+    Some((settings, inherits))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, path::Path};
+
+    use super::{ProfileConfig, resolve_profile};
+    use crate::ManifestError;
+
+    #[test]
+    fn resolves_inherited_and_workspace_profiles() {
+        let workspace = BTreeMap::from([(
+            "release".to_string(),
+            ProfileConfig {
+                skip_underconstrained_check: Some(false),
+                inliner_aggressiveness: Some(0),
+                ..Default::default()
+            },
+        )]);
+        let package = BTreeMap::from([
+            (
+                "ci".to_string(),
+                ProfileConfig {
+                    inherits: Some("release".to_string()),
+                    force_brillig: Some(true),
+                    ..Default::default()
+                },
+            ),
+            (
+                "release".to_string(),
+                ProfileConfig { inliner_aggressiveness: Some(3), ..Default::default() },
+            ),
+        ]);
+        let toml = Path::new("Nargo.toml");
+
+        let profile = resolve_profile("ci", &package, &workspace, toml).unwrap();
+        assert_eq!(profile.name, "ci");
+        assert_eq!(profile.force_brillig, Some(true));
+        assert_eq!(profile.inliner_aggressiveness, Some(3));
+        assert_eq!(profile.skip_underconstrained_check, Some(false));
+
+        let profile = resolve_profile("dev", &package, &workspace, toml).unwrap();
+        assert_eq!(profile.inliner_aggressiveness, None);
+
+        assert!(matches!(
+            resolve_profile("bench", &package, &workspace, toml),
+            Err(ManifestError::UnknownProfile { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_cyclic_inheritance() {
+        let package = BTreeMap::from([
+            (
+                "a".to_string(),
+                ProfileConfig { inherits: Some("b".to_string()), ..Default::default() },
+            ),
+            (
+                "b".to_string(),
+                ProfileConfig { inherits: Some("a".to_string()), ..Default::default() },
+            ),
+        ]);
+        let result = resolve_profile("a", &package, &BTreeMap::new(), Path::new("Nargo.toml"));
+        assert!(matches!(
+            result,
+            Err(ManifestError::CyclicProfileInheritance { cycle, .. }) if cycle == "a -> b -> a"
+        ));
+    }
+}
@@ -5,6 +5,7 @@ use nargo::{
 };
 use noirc_driver::CrateName;
 use semver::{Error, Prerelease, Version, VersionReq};
+use std::collections::BTreeSet;
 
 // Parse a semver compatible version string
 pub(crate) fn parse_semver_compatible_version(version: &str) -> Result<Version, Error> {
@@ -13,6 +14,11 @@ pub(crate) fn parse_semver_compatible_version(version: &str) -> Result<Version,
     Ok(version)
 }
 
+// Check that a version requirement on a dependency is valid
+pub(crate) fn check_version_requirement(version: &str) -> Result<(), Error> {
+    VersionReq::parse(version).map(|_| ())
+}
+
 // Check that all of the packages in the workspace are compatible with the current compiler version
 pub(crate) fn semver_check_workspace(
     workspace: &Workspace,
@@ -31,31 +37,14 @@ pub(crate) fn semver_check_workspace(
 fn semver_check_package(package: &Package, compiler_version: &Version) -> Result<(), SemverError> {
     // Check that this package's compiler version requirements are satisfied
     if let Some(version) = &package.compiler_required_version {
-        let version_req = match VersionReq::parse(version) {
-            Ok(version_req) => version_req,
-            Err(err) => {
-                return Err(SemverError::CouldNotParseRequiredVersion {
-                    package_name: package.name.clone().into(),
-                    error: err.to_string(),
-                });
-            }
-        };
-
-        validate_compiler_version_requirement(&package.name, &version_req)?;
-
-        if !version_req.matches(compiler_version) {
-            return Err(SemverError::IncompatibleVersion {
-                package_name: package.name.clone(),
-                required_compiler_version: version.clone(),
-                compiler_version_found: strip_build_meta_data(compiler_version),
-            });
-        };
+        check_compiler_version(&package.name, version, compiler_version)?;
     }
 
     // Check that all of this package's dependencies' compiler version requirements are satisfied
-    for dep in package.dependencies.values() {
+    let dev_dependencies = package.dev_dependencies.values().flatten();
+    for dep in package.dependencies.values().chain(dev_dependencies) {
         match dep {
-            Dependency::Local { package } | Dependency::Remote { package } => {
+            Dependency::Local { package } | Dependency::Remote { package, .. } => {
                 semver_check_package(package, compiler_version)?;
             }
         }
@@ -64,6 +53,35 @@ fn semver_check_package(package: &Package, compiler_version: &Version) -> Result
     Ok(())
 }
 
+// Check that the `compiler_version` requirement of a package is satisfied by the current compiler version
+pub(crate) fn check_compiler_version(
+    package_name: &CrateName,
+    required_version: &str,
+    compiler_version: &Version,
+) -> Result<(), SemverError> {
+    let version_req = match VersionReq::parse(required_version) {
+        Ok(version_req) => version_req,
+        Err(err) => {
+            return Err(SemverError::CouldNotParseRequiredVersion {
+                package_name: package_name.clone().into(),
+                error: err.to_string(),
+            });
+        }
+    };
+
+    validate_compiler_version_requirement(package_name, &version_req)?;
+
+    if !version_req.matches(compiler_version) {
+        return Err(SemverError::IncompatibleVersion {
+            package_name: package_name.clone(),
+            required_compiler_version: required_version.to_string(),
+            compiler_version_found: strip_build_meta_data(compiler_version),
+        });
+    };
+
+    Ok(())
+}
+
 fn validate_compiler_version_requirement(
     package_name: &CrateName,
     required_compiler_version: &VersionReq,
@@ -79,12 +97,71 @@ fn validate_compiler_version_requirement(
 }
 
 // Strip the build meta data from the version string since it is ignored by semver.
-fn strip_build_meta_data(version: &Version) -> String {
+pub(crate) fn strip_build_meta_data(version: &Version) -> String {
     let version_string = version.to_string();
     let mut split = version_string.split('+');
     split.next().expect("split was called on an empty string").to_string()
 }
 
+/// Parses a git tag such as `v0.4.2` or `0.4.2` into a version.
+pub(crate) fn parse_tag_version(tag: &str) -> Option<Version> {
+    Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()
+}
+
+/// The version requirements placed on a git repository by all the packages depending on it,
+/// and the tag currently selected to satisfy them.
+#[derive(Debug, Default)]
+pub(crate) struct GitRequirements {
+    /// Each requirement along with the manifest it comes from.
+    requirements: BTreeSet<(String, String)>,
+    /// The exact tags that packages depend on, which are the candidates for a tag satisfying
+    /// the requirements before the tags of the repository are listed.
+    pub(crate) pinned: BTreeSet<String>,
+    /// Whether a package gave a `version` requirement rather than an exact tag.
+    pub(crate) has_version_requirement: bool,
+    pub(crate) selected: Option<String>,
+}
+
+impl GitRequirements {
+    pub(crate) fn add(&mut self, requirement: String, source: String) {
+        self.requirements.insert((requirement, source));
+    }
+
+    /// Adds the requirement of a package depending on the exact `tag`, which is compatible with
+    /// any tag of a version that `cargo` would consider compatible with it.
+    pub(crate) fn add_pinned(&mut self, tag: &str, version: &Version, source: String) {
+        self.add(format!("^{version}"), source);
+        self.pinned.insert(tag.to_string());
+    }
+
+    /// Whether the version of `tag` satisfies every requirement.
+    pub(crate) fn matches(&self, tag: &str) -> bool {
+        let Some(version) = parse_tag_version(tag) else {
+            return false;
+        };
+        self.requirements.iter().all(|(requirement, _)| {
+            VersionReq::parse(requirement).is_ok_and(|req| req.matches(&version))
+        })
+    }
+
+    /// Picks the tag with the highest version satisfying every requirement.
+    pub(crate) fn select<'a>(&self, tags: impl IntoIterator<Item = &'a String>) -> Option<String> {
+        tags.into_iter()
+            .filter(|tag| self.matches(tag))
+            .max_by_key(|tag| parse_tag_version(tag))
+            .cloned()
+    }
+
+    /// Lists the requirements, one per line, for error messages.
+    pub(crate) fn describe(&self) -> String {
+        let lines = self
+            .requirements
+            .iter()
+            .map(|(requirement, source)| format!("  {requirement} (required by {source})"));
+        lines.collect::<Vec<_>>().join("\n")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
@@ -106,6 +183,9 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            dev_dependencies: BTreeMap::new(),
+            profile: None,
+            features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -143,6 +223,9 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            dev_dependencies: BTreeMap::new(),
+            profile: None,
+            features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -200,6 +283,9 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            dev_dependencies: BTreeMap::new(),
+            profile: None,
+            features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -223,6 +309,9 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            dev_dependencies: BTreeMap::new(),
+            profile: None,
+            features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -232,6 +321,24 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_git_requirements_select_highest_compatible_tag() {
+        let tags: Vec<String> = ["v0.3.9", "v0.4.1", "v0.4.2", "0.5.0", "nightly"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let mut requirements = GitRequirements::default();
+        requirements.add("^0.4".to_string(), "a/Nargo.toml".to_string());
+        assert_eq!(requirements.select(&tags), Some("v0.4.2".to_string()));
+
+        requirements.add("=0.4.1".to_string(), "b/Nargo.toml".to_string());
+        assert_eq!(requirements.select(&tags), Some("v0.4.1".to_string()));
+
+        requirements.add(">=0.5".to_string(), "c/Nargo.toml".to_string());
+        assert_eq!(requirements.select(&tags), None);
+    }
+
     #[test]
     fn test_semver_build_data() {
         let compiler_version = Version::parse("0.1.0+this-is-ignored-by-semver").unwrap();
@@ -244,6 +351,9 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            dev_dependencies: BTreeMap::new(),
+            profile: None,
+            features: BTreeSet::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
         };
@@ -254,4 +364,23 @@ mod tests {
             )
         };
     }
+
+    #[test]
+    fn test_check_compiler_version() {
+        let compiler_version = Version::parse("0.2.0+build-data").unwrap();
+        let package_name = CrateName::from_str("test").unwrap();
+
+        assert!(check_compiler_version(&package_name, ">=0.1.0", &compiler_version).is_ok());
+
+        let got_err = check_compiler_version(&package_name, "0.3.0", &compiler_version);
+        let expected_version_error = SemverError::IncompatibleVersion {
+            package_name: package_name.clone(),
+            required_compiler_version: "0.3.0".to_string(),
+            compiler_version_found: "0.2.0".to_string(),
+        };
+        assert_eq!(got_err, Err(expected_version_error));
+
+        let got_err = check_compiler_version(&package_name, "not a version", &compiler_version);
+        assert!(matches!(got_err, Err(SemverError::CouldNotParseRequiredVersion { .. })));
+    }
 }
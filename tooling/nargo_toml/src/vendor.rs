@@ -0,0 +1,252 @@
+//! Vendored git dependencies are copied into a `vendor` directory next to the workspace `Nargo.toml`,
+//! so that a workspace with `vendored = true` can be resolved without accessing the network.
+//!
+//! The directory contains a `vendor.toml` manifest recording where each dependency came from,
+//! along with a checksum of its sources to detect local modifications.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ManifestError,
+    git::{GitReference, git_head_commit, parse_git_url, resolve_folder_name},
+    git_repository_key,
+    lockfile::checksum_directory,
+};
+
+/// Name of the directory holding the vendored dependencies, next to the workspace `Nargo.toml`.
+pub const VENDOR_DIR: &str = "vendor";
+
+/// Name of the manifest written into the vendor directory.
+const VENDOR_MANIFEST_NAME: &str = "vendor.toml";
+
+const VENDOR_MANIFEST_VERSION: u32 = 1;
+
+const VENDOR_MANIFEST_HEADER: &str = "# This file is automatically generated by `nargo vendor`.\n# It is not intended for manual editing.\n";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct VendorManifest {
+    version: u32,
+    #[serde(default, rename = "package")]
+    packages: Vec<VendoredPackage>,
+}
+
+/// A git dependency copied into the vendor directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VendoredPackage {
+    pub git: String,
+    /// The `tag`, `branch` or `rev` the dependency refers to.
+    #[serde(flatten)]
+    pub reference: GitReference,
+    /// Location of the sources, relative to the vendor directory.
+    pub path: String,
+    pub commit: String,
+    pub checksum: String,
+}
+
+/// Git dependencies resolved from the vendor directory instead of the network.
+pub(crate) struct VendoredSources {
+    dir: PathBuf,
+    packages: Vec<VendoredPackage>,
+    /// Dependencies whose checksum has already been verified.
+    verified: BTreeSet<(String, GitReference)>,
+}
+
+impl VendoredSources {
+    /// Loads the vendor manifest of the workspace in `root_dir`.
+    pub(crate) fn load(root_dir: &Path) -> Result<Self, ManifestError> {
+        let dir = root_dir.join(VENDOR_DIR);
+        let manifest_path = dir.join(VENDOR_MANIFEST_NAME);
+        let Some(manifest) = read_manifest(&manifest_path)? else {
+            return Err(ManifestError::MissingVendorManifest(manifest_path));
+        };
+        Ok(Self { dir, packages: manifest.packages, verified: BTreeSet::new() })
+    }
+
+    fn find(&self, git: &str, reference: &GitReference) -> Option<&VendoredPackage> {
+        let git = git_repository_key(git);
+        self.packages.iter().find(|package| {
+            git_repository_key(&package.git) == git && &package.reference == reference
+        })
+    }
+
+    /// Tags of the git repository at `git` which have been vendored.
+    pub(crate) fn tags(&self, git: &str) -> Vec<String> {
+        let git = git_repository_key(git);
+        self.packages
+            .iter()
+            .filter(|package| git_repository_key(&package.git) == git)
+            .filter_map(|package| match &package.reference {
+                GitReference::Tag(tag) => Some(tag.clone()),
+                GitReference::Branch(_) | GitReference::Rev(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the directory holding `git` at `reference`, along with the commit it was vendored from,
+    /// after checking that its contents haven't been modified.
+    pub(crate) fn checkout(
+        &mut self,
+        git: &str,
+        reference: &GitReference,
+    ) -> Result<(PathBuf, String), ManifestError> {
+        let Some(package) = self.find(git, reference) else {
+            return Err(ManifestError::MissingVendoredDependency {
+                git: git.to_string(),
+                reference: reference.to_string(),
+                vendor_dir: self.dir.clone(),
+            });
+        };
+        let path = self.dir.join(&package.path);
+        let commit = package.commit.clone();
+
+        let key = (git_repository_key(git), reference.clone());
+        if !self.verified.contains(&key) {
+            let modified = match checksum_directory(&path) {
+                Ok(checksum) => checksum != package.checksum,
+                Err(_) => true,
+            };
+            if modified {
+                return Err(ManifestError::VendoredDependencyModified {
+                    git: git.to_string(),
+                    reference: reference.to_string(),
+                    path,
+                });
+            }
+            self.verified.insert(key);
+        }
+        Ok((path, commit))
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<Option<VendorManifest>, ManifestError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents =
+        std::fs::read_to_string(path).map_err(|_| ManifestError::ReadFailed(path.to_path_buf()))?;
+    let manifest = toml::from_str(&contents).map_err(|err| {
+        ManifestError::MalformedVendorManifest { path: path.to_path_buf(), error: err.to_string() }
+    })?;
+    Ok(Some(manifest))
+}
+
+/// Copies the checkouts of git dependencies, keyed by URL and revision, into the vendor directory
+/// of the workspace in `root_dir`, replacing anything vendored before.
+pub(crate) fn vendor_git_checkouts(
+    root_dir: &Path,
+    checkouts: &BTreeMap<(String, GitReference), PathBuf>,
+) -> Result<Vec<VendoredPackage>, ManifestError> {
+    let dir = root_dir.join(VENDOR_DIR);
+    let manifest_path = dir.join(VENDOR_MANIFEST_NAME);
+    if dir.exists() {
+        // Don't delete a directory we haven't created.
+        if read_manifest(&manifest_path)?.is_none() {
+            return Err(ManifestError::UnexpectedVendorDirectory(dir));
+        }
+        std::fs::remove_dir_all(&dir)
+            .map_err(|err| ManifestError::VendorFailed(format!("{}: {err}", dir.display())))?;
+    }
+
+    std::fs::create_dir_all(&dir)
+        .map_err(|err| ManifestError::VendorFailed(format!("{}: {err}", dir.display())))?;
+
+    let mut packages = Vec::new();
+    for ((git, reference), checkout_dir) in checkouts {
+        let base = parse_git_url(git).map_err(ManifestError::GitError)?;
+        let path = resolve_folder_name(&base, reference).replace('\\', "/");
+        let vendored_dir = dir.join(&path);
+        copy_directory(checkout_dir, &vendored_dir).map_err(|err| {
+            ManifestError::VendorFailed(format!("{}: {err}", checkout_dir.display()))
+        })?;
+
+        let commit = git_head_commit(checkout_dir).map_err(ManifestError::GitError)?;
+        let checksum = checksum_directory(&vendored_dir).map_err(ManifestError::VendorFailed)?;
+        packages.push(VendoredPackage {
+            git: git.clone(),
+            reference: reference.clone(),
+            path,
+            commit,
+            checksum,
+        });
+    }
+
+    let manifest = VendorManifest { version: VENDOR_MANIFEST_VERSION, packages };
+    let contents =
+        toml::to_string(&manifest).expect("vendor manifest should always be serializable");
+    std::fs::write(&manifest_path, format!("{VENDOR_MANIFEST_HEADER}{contents}")).map_err(
+        |err| ManifestError::VendorFailed(format!("{}: {err}", manifest_path.display())),
+    )?;
+
+    Ok(manifest.packages)
+}
+
+/// Recursively copies `from` into `to`, skipping the `.git` directory.
+fn copy_directory(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != ".git" {
+                copy_directory(&entry.path(), &target)?;
+            }
+        } else {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{VENDOR_DIR, VendoredPackage, VendoredSources, copy_directory};
+    use crate::{ManifestError, git::GitReference, lockfile::checksum_directory};
+
+    #[test]
+    fn detects_modified_vendored_dependency() {
+        let tmp = tempfile::tempdir().unwrap();
+        let checkout = tmp.path().join("checkout");
+        std::fs::create_dir_all(checkout.join("src")).unwrap();
+        std::fs::create_dir_all(checkout.join(".git")).unwrap();
+        std::fs::write(checkout.join("src").join("lib.nr"), "fn foo() {}").unwrap();
+
+        let dir = tmp.path().join(VENDOR_DIR);
+        let path = "github.com/noir-lang/foo/v0.1.0".to_string();
+        copy_directory(&checkout, &dir.join(&path)).unwrap();
+        assert!(!dir.join(&path).join(".git").exists());
+
+        let git = "https://github.com/noir-lang/foo".to_string();
+        let tag = GitReference::Tag("v0.1.0".to_string());
+        let package = VendoredPackage {
+            git: git.clone(),
+            reference: tag.clone(),
+            path: path.clone(),
+            commit: "abc".to_string(),
+            checksum: checksum_directory(&checkout).unwrap(),
+        };
+        let mut sources = VendoredSources {
+            dir: dir.clone(),
+            packages: vec![package],
+            verified: BTreeSet::new(),
+        };
+        assert!(sources.checkout(&git, &tag).is_ok());
+        assert!(matches!(
+            sources.checkout(&git, &GitReference::Branch("main".to_string())),
+            Err(ManifestError::MissingVendoredDependency { .. })
+        ));
+
+        std::fs::write(dir.join(&path).join("src").join("lib.nr"), "fn bar() {}").unwrap();
+        sources.verified.clear();
+        assert!(matches!(
+            sources.checkout(&git, &tag),
+            Err(ManifestError::VendoredDependencyModified { .. })
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+//! Settings shared by the members of a workspace, and the expansion of its `members` patterns.
+
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+};
+
+use fm::NormalizePath;
+use serde::Deserialize;
+
+use crate::{
+    Config, DependencyConfig, NargoToml, PackageMetadata, ProfileConfig, WorkspaceConfig,
+    get_package_manifest, read_toml,
+};
+
+/// The `[workspace.package]` table, holding package settings inherited by members which don't set them.
+#[derive(Default, Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspacePackageConfig {
+    pub version: Option<String>,
+    pub authors: Option<Vec<String>>,
+    pub license: Option<String>,
+    pub compiler_version: Option<String>,
+    pub compiler_unstable_features: Option<Vec<String>>,
+    pub expression_width: Option<String>,
+}
+
+impl PackageMetadata {
+    /// Fills in the settings which are not set with the ones of the workspace.
+    pub(crate) fn inherit(&self, workspace: &WorkspacePackageConfig) -> PackageMetadata {
+        let mut metadata = self.clone();
+        metadata.version = metadata.version.or_else(|| workspace.version.clone());
+        metadata.authors = metadata.authors.or_else(|| workspace.authors.clone());
+        metadata.license = metadata.license.or_else(|| workspace.license.clone());
+        metadata.compiler_version =
+            metadata.compiler_version.or_else(|| workspace.compiler_version.clone());
+        metadata.compiler_unstable_features = metadata
+            .compiler_unstable_features
+            .or_else(|| workspace.compiler_unstable_features.clone());
+        metadata.expression_width =
+            metadata.expression_width.or_else(|| workspace.expression_width.clone());
+        metadata
+    }
+}
+
+/// Everything the members of a workspace inherit from it.
+#[derive(Debug)]
+pub(crate) struct WorkspaceInheritance {
+    pub(crate) root_dir: PathBuf,
+    /// Normalized directories of the members.
+    pub(crate) members: Vec<PathBuf>,
+    pub(crate) package: WorkspacePackageConfig,
+    pub(crate) dependencies: BTreeMap<String, DependencyConfig>,
+    pub(crate) profiles: BTreeMap<String, ProfileConfig>,
+}
+
+impl WorkspaceInheritance {
+    pub(crate) fn new(
+        root_dir: PathBuf,
+        config: &WorkspaceConfig,
+        profiles: BTreeMap<String, ProfileConfig>,
+    ) -> Self {
+        let members = expand_members(&root_dir, config)
+            .iter()
+            .map(|member| root_dir.join(member).normalize())
+            .collect();
+        Self {
+            root_dir,
+            members,
+            package: config.package.clone(),
+            dependencies: config.dependencies.clone(),
+            profiles,
+        }
+    }
+
+    pub(crate) fn toml_path(&self) -> PathBuf {
+        self.root_dir.join("Nargo.toml")
+    }
+}
+
+/// Looks for a workspace in the ancestors of `root_dir` which has the package in `root_dir` as a member.
+pub(crate) fn enclosing_workspace(root_dir: &Path) -> Option<WorkspaceInheritance> {
+    let root_dir = root_dir.normalize();
+    for dir in root_dir.ancestors().skip(1) {
+        let Ok(toml_path) = get_package_manifest(dir) else {
+            continue;
+        };
+        let Ok(NargoToml {
+            root_dir: workspace_dir,
+            config: Config::Workspace { workspace_config, profile },
+        }) = read_toml(&toml_path)
+        else {
+            continue;
+        };
+        let workspace = WorkspaceInheritance::new(workspace_dir, &workspace_config, profile);
+        if workspace.members.contains(&root_dir) {
+            return Some(workspace);
+        }
+    }
+    None
+}
+
+/// Expands the `members` of the workspace in `root_dir` into the paths of its members, relative to `root_dir`.
+///
+/// Members may contain `*` and `?` wildcards, which only match directories containing a `Nargo.toml`.
+/// Members matching an `exclude` entry, or inside a directory matching one, are left out.
+pub(crate) fn expand_members(root_dir: &Path, config: &WorkspaceConfig) -> Vec<PathBuf> {
+    let mut members: Vec<PathBuf> = Vec::new();
+    for pattern in &config.members {
+        let expanded = if has_wildcard(pattern) {
+            expand_pattern(root_dir, pattern)
+                .into_iter()
+                .filter(|member| root_dir.join(member).join("Nargo.toml").is_file())
+                .collect()
+        } else {
+            vec![pattern.clone()]
+        };
+        for member in expanded {
+            let is_excluded = config.exclude.iter().any(|exclude| is_within(&member, exclude));
+            if !is_excluded && !members.contains(&member) {
+                members.push(member);
+            }
+        }
+    }
+    members
+}
+
+fn has_wildcard(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?'])
+}
+
+/// The components of a relative path, without any `.`.
+fn components(path: &Path) -> Vec<String> {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect()
+}
+
+/// Lists the directories under `root_dir` matching `pattern`, sorted by name.
+fn expand_pattern(root_dir: &Path, pattern: &Path) -> Vec<PathBuf> {
+    let mut matches = vec![PathBuf::new()];
+    for component in components(pattern) {
+        if !component.contains(['*', '?']) {
+            matches.iter_mut().for_each(|path| path.push(&component));
+            continue;
+        }
+
+        let mut next = Vec::new();
+        for path in matches {
+            let Ok(entries) = std::fs::read_dir(root_dir.join(&path)) else {
+                continue;
+            };
+            let mut names: Vec<String> = entries
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| !name.starts_with('.') || component.starts_with('.'))
+                .filter(|name| wildcard_matches(&component, name))
+                .collect();
+            names.sort();
+            next.extend(names.into_iter().map(|name| path.join(name)));
+        }
+        matches = next;
+    }
+    matches
+}
+
+/// Whether `path` is `pattern`, or inside a directory matching it.
+fn is_within(path: &Path, pattern: &Path) -> bool {
+    let path = components(path);
+    let pattern = components(pattern);
+    pattern.len() <= path.len()
+        && pattern.iter().zip(&path).all(|(pattern, name)| wildcard_matches(pattern, name))
+}
+
+/// Matches a single path component against a pattern where `*` stands for any number of characters
+/// and `?` for exactly one.
+fn wildcard_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern, and of the name when we reached it.
+    let mut backtrack = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // Let the last `*` match one more character.
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|char| *char == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{expand_members, is_within, wildcard_matches};
+    use crate::WorkspaceConfig;
+
+    #[test]
+    fn matches_wildcards() {
+        assert!(wildcard_matches("*", "circuit"));
+        assert!(wildcard_matches("circuit_*", "circuit_a"));
+        assert!(wildcard_matches("c*t_?", "circuit_a"));
+        assert!(!wildcard_matches("c*t_?", "circuit_ab"));
+        assert!(!wildcard_matches("lib*", "circuit"));
+
+        assert!(is_within(Path::new("circuits/old/a"), Path::new("./circuits/old")));
+        assert!(is_within(Path::new("circuits/a_test"), Path::new("circuits/*_test")));
+        assert!(!is_within(Path::new("circuits/a"), Path::new("circuits/a/b")));
+    }
+
+    #[test]
+    fn expands_glob_members() {
+        let tmp = tempfile::tempdir().unwrap();
+        for package in ["circuits/a", "circuits/b", "circuits/old", "libs/c"] {
+            std::fs::create_dir_all(tmp.path().join(package)).unwrap();
+            std::fs::write(tmp.path().join(package).join("Nargo.toml"), "").unwrap();
+        }
+        // Directories without a manifest aren't members.
+        std::fs::create_dir_all(tmp.path().join("circuits").join("docs")).unwrap();
+
+        let config = WorkspaceConfig {
+            members: vec![PathBuf::from("circuits/*"), PathBuf::from("libs/c")],
+            exclude: vec![PathBuf::from("circuits/old")],
+            ..Default::default()
+        };
+        let members = expand_members(tmp.path(), &config);
+        assert_eq!(
+            members,
+            vec![PathBuf::from("circuits/a"), PathBuf::from("circuits/b"), PathBuf::from("libs/c")]
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use acvm::{FieldElement, acir::circuit::Program};
 use noirc_abi::{Abi, AbiType, AbiValue};
-use noirc_driver::{CompiledContract, CompiledContractOutputs, CompiledProgram, ContractFunction};
+use noirc_driver::{
+    CompileProfile, CompiledContract, CompiledContractOutputs, CompiledProgram, ContractFunction,
+};
 use serde::{Deserialize, Serialize};
 
 use noirc_driver::DebugFile;
@@ -35,6 +37,9 @@ pub struct ContractArtifact {
     pub outputs: ContractOutputsArtifact,
     /// Map of file Id to the source code so locations in debug info can be mapped to source code they point to.
     pub file_map: BTreeMap<FileId, DebugFile>,
+    /// The compilation profile the contract was compiled with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<CompileProfile>,
 }
 
 impl From<CompiledContract> for ContractArtifact {
@@ -45,6 +50,7 @@ impl From<CompiledContract> for ContractArtifact {
             functions: contract.functions.into_iter().map(ContractFunctionArtifact::from).collect(),
             outputs: contract.outputs.into(),
             file_map: contract.file_map,
+            profile: contract.profile,
         }
     }
 }
@@ -108,6 +114,7 @@ impl ContractFunctionArtifact {
             warnings: Vec::new(),
             names: self.names,
             brillig_names: self.brillig_names,
+            profile: None,
         }
     }
 }
@@ -34,6 +34,29 @@ impl<F: AcirField> DebugVars<F> {
         self.frames.last().map(|(fn_id, frame)| self.build_stack_frame(fn_id, frame))
     }
 
+    /// Returns the id of the variable with the given name in the current stack frame, along
+    /// with the depth of the frame. The latest declared variable wins if several are named alike.
+    pub fn find_var(&self, name: &str) -> Option<(DebugVarId, usize)> {
+        let (_, frame) = self.frames.last()?;
+        let var_id = frame
+            .keys()
+            .filter(|var_id| {
+                self.lookup_var(**var_id).is_some_and(|(var_name, _)| var_name == name)
+            })
+            .max()?;
+        Some((*var_id, self.frames.len() - 1))
+    }
+
+    /// Returns the value and type of a variable of the stack frame at the given depth
+    pub fn get_var(
+        &self,
+        var_id: DebugVarId,
+        frame: usize,
+    ) -> Option<(&PrintableValue<F>, &PrintableType)> {
+        let value = self.frames.get(frame)?.1.get(&var_id)?;
+        Some((value, self.get_type(var_id)?))
+    }
+
     fn lookup_var(&self, var_id: DebugVarId) -> Option<(&str, &PrintableType)> {
         self.variables.get(&var_id).and_then(|debug_var| {
             let printable_type = self.types.get(&debug_var.debug_type_id)?;
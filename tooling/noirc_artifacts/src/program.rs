@@ -4,6 +4,7 @@ use acvm::FieldElement;
 use acvm::acir::circuit::Program;
 use fm::FileId;
 use noirc_abi::Abi;
+use noirc_driver::CompileProfile;
 use noirc_driver::CompiledProgram;
 use noirc_driver::DebugFile;
 use noirc_errors::debug_info::ProgramDebugInfo;
@@ -41,6 +42,10 @@ pub struct ProgramArtifact {
     pub names: Vec<String>,
     /// Names of the unconstrained functions in the program.
     pub brillig_names: Vec<String>,
+
+    /// The compilation profile the program was compiled with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<CompileProfile>,
 }
 
 impl From<CompiledProgram> for ProgramArtifact {
@@ -54,6 +59,7 @@ impl From<CompiledProgram> for ProgramArtifact {
             file_map: compiled_program.file_map,
             names: compiled_program.names,
             brillig_names: compiled_program.brillig_names,
+            profile: compiled_program.profile,
         }
     }
 }
@@ -70,6 +76,7 @@ impl From<ProgramArtifact> for CompiledProgram {
             warnings: vec![],
             names: program.names,
             brillig_names: program.brillig_names,
+            profile: program.profile,
         }
     }
 }
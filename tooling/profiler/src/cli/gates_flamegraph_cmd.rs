@@ -190,6 +190,7 @@ mod tests {
             file_map: BTreeMap::default(),
             names: vec!["main".to_string()],
             brillig_names: Vec::new(),
+            profile: None,
         };
 
         // Write the artifact to a file
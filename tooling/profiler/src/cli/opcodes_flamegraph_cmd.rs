@@ -205,6 +205,7 @@ mod tests {
             file_map: BTreeMap::default(),
             names: vec!["main".to_string()],
             brillig_names: Vec::new(),
+            profile: None,
         };
 
         // Write the artifact to a file
@@ -264,6 +265,7 @@ mod tests {
             file_map: BTreeMap::default(),
             names: vec!["main".to_string()],
             brillig_names: vec!["main".to_string(), "main".to_string(), "main_1".to_string()],
+            profile: None,
         };
 
         // Write the artifact to a file
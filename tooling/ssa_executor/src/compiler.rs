@@ -95,6 +95,7 @@ pub fn compile_from_artifacts(artifacts: ArtifactsAndWarnings) -> CompiledProgra
         warnings,
         names,
         brillig_names,
+        profile: None,
     }
 }
 